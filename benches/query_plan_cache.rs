@@ -0,0 +1,119 @@
+//! Compares building SQL for every request with reusing the cached query plan.
+//!
+//! Run with `cargo bench --bench query_plan_cache`.
+
+use std::time::{Duration, Instant};
+
+use bson::{doc, Document};
+use subgraph::{
+    cli_args::CliArgs,
+    configuration::subgraph::{data_sources::ServiceDataSourceConfig, SubGraphConfig},
+    data_sources::{sql::SqlDataSource, DataSource},
+    resolver_type::ResolverType,
+};
+
+const ITERATIONS: u32 = 10_000;
+
+fn bench_config() -> SubGraphConfig {
+    toml::from_str::<SubGraphConfig>(
+        r#"
+        [service]
+        name = "query_plan_bench"
+
+        [[service.data_sources]]
+        [service.data_sources.SQL]
+        name = "bench_data_source"
+        uri = "sqlite::memory:"
+        dialect = "SQLITE"
+
+        [[service.entities]]
+        name = "order"
+        data_source = { from = "bench_data_source", table = "orders" }
+        fields = [
+          { name = "id", scalar = "Int", required = true },
+          { name = "customer", scalar = "String", required = true },
+          { name = "status", scalar = "String", required = true },
+          { name = "total", scalar = "Int", required = true },
+          { name = "paid", scalar = "Boolean", required = true },
+        ]
+        "#,
+    )
+    .unwrap()
+}
+
+fn bench_input(index: u32) -> Document {
+    doc! {
+        "query": {
+            "status": "open",
+            "OR": [{ "customer": format!("customer_{}", index) }, { "paid": false }],
+            "GT": { "total": index as i32 },
+        },
+        "opts": { "per_page": 10, "page": 1 },
+    }
+}
+
+fn report(name: &str, elapsed: Duration) {
+    println!(
+        "{:<16} {:>10.2?} total {:>10.2?} per request",
+        name,
+        elapsed,
+        elapsed / ITERATIONS
+    );
+}
+
+#[tokio::main]
+async fn main() {
+    let subgraph_config = bench_config();
+    let sql_config = match &subgraph_config.service.data_sources[0] {
+        ServiceDataSourceConfig::SQL(config) => config.clone(),
+        _ => unreachable!(),
+    };
+    let args = CliArgs {
+        config: None,
+        port: None,
+        log_level: None,
+        generate_keypair: false,
+        migrate: None,
+        steps: None,
+        migration_name: None,
+        migrate_only: false,
+        watch: false,
+        host: false,
+    };
+    let data_source = match SqlDataSource::init(&sql_config, &args, subgraph_config.clone())
+        .await
+        .unwrap()
+    {
+        DataSource::SQL(data_source) => data_source,
+        _ => unreachable!(),
+    };
+    let entity = &subgraph_config.service.entities[0];
+
+    let start = Instant::now();
+    for index in 0..ITERATIONS {
+        SqlDataSource::create_query(
+            bench_input(index),
+            ResolverType::FindMany,
+            "orders",
+            sql_config.dialect.clone(),
+            entity,
+            &subgraph_config,
+        )
+        .unwrap();
+    }
+    report("create_query", start.elapsed());
+
+    let start = Instant::now();
+    for index in 0..ITERATIONS {
+        data_source
+            .create_cached_query(
+                bench_input(index),
+                ResolverType::FindMany,
+                "orders",
+                entity,
+                &subgraph_config,
+            )
+            .unwrap();
+    }
+    report("cached_query", start.elapsed());
+}
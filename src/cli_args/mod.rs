@@ -26,10 +26,25 @@ pub struct CliArgs {
     #[arg(long)]
     pub host: bool,
 
-    /// Run migrations
-    #[arg(short, long, value_parser = PossibleValuesParser::new(["run", "revert"]))]
+    /// Run migrations, revert applied migrations, list the status of migrations or generate
+    /// migrations from the entities of the subgraph config.
+    #[arg(short, long, value_parser = PossibleValuesParser::new(["run", "revert", "status", "generate", "baseline"]))]
     pub migrate: Option<String>,
 
+    /// Number of reversible migrations to undo when reverting. Defaults to 1.
+    #[arg(long, requires = "migrate")]
+    pub steps: Option<u32>,
+
+    /// Name used for the files created with `--migrate generate`.
+    #[serde(rename = "migration-name")]
+    #[arg(long, requires = "migrate")]
+    pub migration_name: Option<String>,
+
+    /// Exit after handling the migrate command, without starting the service.
+    #[serde(rename = "migrate-only")]
+    #[arg(long, requires = "migrate")]
+    pub migrate_only: bool,
+
     ///Generate Key Pair
     #[arg(short, long)]
     pub generate_keypair: bool,
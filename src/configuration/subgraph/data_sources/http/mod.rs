@@ -1,14 +1,223 @@
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
 
+/// Default number of retries when `retry` is configured.
+pub const DEFAULT_HTTP_MAX_RETRIES: u32 = 3;
+/// Default delay before the first retry, in milliseconds.
+pub const DEFAULT_HTTP_RETRY_BACKOFF: u64 = 100;
+/// Default longest delay between retries, in milliseconds.
+pub const DEFAULT_HTTP_RETRY_MAX_BACKOFF: u64 = 5000;
+/// Default consecutive failures opening the circuit.
+pub const DEFAULT_HTTP_FAILURE_THRESHOLD: u32 = 5;
+/// Default milliseconds the circuit stays open before a request is tried again.
+pub const DEFAULT_HTTP_RESET_TIMEOUT: u64 = 30000;
+/// Default milliseconds a cached response is fresh for.
+pub const DEFAULT_HTTP_CACHE_TTL: u64 = 60000;
+/// Default number of cached responses per data source.
+pub const DEFAULT_HTTP_CACHE_MAX_ENTRIES: usize = 1000;
+/// Default request headers a cached response varies by, those identifying the client.
+pub const DEFAULT_HTTP_CACHE_VARY_HEADERS: [&str; 4] = [
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "x-api-key",
+];
+/// Default number of upstream pages requested for a single page of results.
+pub const DEFAULT_HTTP_MAX_FOLLOW_PAGES: u32 = 1;
+pub const DEFAULT_HTTP_MAX_WALK_PAGES: u32 = 10;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DefaultHeader {
     pub name: String,
     pub value: String,
 }
 
+/// An HTTP data source. Timeouts are in milliseconds.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct HttpDataSourceConfig {
     pub name: String,
     pub url: String,
     pub default_headers: Option<Vec<DefaultHeader>>,
+    pub connect_timeout: Option<u64>,
+    /// Overridden by the `request_timeout` of a resolver.
+    pub request_timeout: Option<u64>,
+    /// Overridden by the `retry` of a resolver.
+    pub retry: Option<HttpRetryConfig>,
+    pub circuit_breaker: Option<HttpCircuitBreakerConfig>,
+    /// Client headers sent upstream with each request. Other client headers are not sent.
+    pub forward_headers: Option<Vec<HttpForwardHeader>>,
+    pub cache: Option<HttpCacheConfig>,
+}
+
+/// Caches the responses of `GET` requests in memory, keyed by the URL and the `vary_headers`
+/// of the request. Stale responses with an `ETag` are revalidated with `If-None-Match`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct HttpCacheConfig {
+    /// Milliseconds a response is fresh for, unless its `Cache-Control` sets a max age.
+    pub ttl: Option<u64>,
+    /// The number of cached responses, evicting the least recently used.
+    pub max_entries: Option<usize>,
+    /// Whether `no-store`, `no-cache` and `max-age` of responses are followed. Defaults to true.
+    pub respect_cache_control: Option<bool>,
+    /// Request headers a response varies by. Defaults to the authorization, cookie and API key
+    /// headers, so per request headers such as `traceparent` do not defeat the cache.
+    pub vary_headers: Option<Vec<String>>,
+}
+
+/// A header of the client request sent to the upstream API, with an optional new name and
+/// value. Headers with a `value` or `value_expr` are sent even when the client omits them.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HttpForwardHeader {
+    /// The name of the client header.
+    pub name: String,
+    /// The name of the header sent upstream. Defaults to `name`.
+    pub rename: Option<String>,
+    pub value: Option<String>,
+    /// An evalexpr expression with the functions of guards, such as `headers` and `token_data`.
+    pub value_expr: Option<String>,
+}
+
+impl HttpForwardHeader {
+    pub fn get_upstream_name(&self) -> &str {
+        self.rename.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// Retries of requests with idempotent methods, after a connection error, a timeout or a
+/// response with one of the `status_codes`. Delays are in milliseconds and double after
+/// each retry.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct HttpRetryConfig {
+    pub max_retries: Option<u32>,
+    pub backoff: Option<u64>,
+    pub max_backoff: Option<u64>,
+    /// Defaults to 429, 502, 503 and 504.
+    pub status_codes: Option<Vec<u16>>,
+}
+
+impl HttpRetryConfig {
+    pub fn get_max_retries(&self) -> u32 {
+        self.max_retries.unwrap_or(DEFAULT_HTTP_MAX_RETRIES)
+    }
+
+    /// The delay before a retry, starting at `backoff` for the first retry.
+    pub fn get_delay(&self, retry: u32) -> Duration {
+        let backoff = self.backoff.unwrap_or(DEFAULT_HTTP_RETRY_BACKOFF);
+        let max_backoff = self.max_backoff.unwrap_or(DEFAULT_HTTP_RETRY_MAX_BACKOFF);
+        let delay = backoff.saturating_mul(2u64.saturating_pow(retry));
+        Duration::from_millis(delay.min(max_backoff))
+    }
+
+    pub fn is_retry_status(&self, status: u16) -> bool {
+        match &self.status_codes {
+            Some(status_codes) => status_codes.contains(&status),
+            None => [429, 502, 503, 504].contains(&status),
+        }
+    }
+}
+
+/// Fails requests without sending them once `failure_threshold` requests in a row failed to
+/// connect, timed out or returned a server error. After `reset_timeout` milliseconds a single
+/// request is sent, closing the circuit when it succeeds.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct HttpCircuitBreakerConfig {
+    pub failure_threshold: Option<u32>,
+    pub reset_timeout: Option<u64>,
+}
+
+/// How an upstream API selects a page of records.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub enum HttpPaginationStyle {
+    /// A page number and a page size.
+    #[default]
+    Page,
+    /// The number of records to skip and a page size.
+    Offset,
+    /// A cursor read from the previous response.
+    Cursor,
+    /// A page number and a page size for the first page, then the `next` URL of the
+    /// `Link` header.
+    LinkHeader,
+}
+
+/// Where pagination and sort parameters are sent.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub enum HttpParameterLocation {
+    #[default]
+    Query,
+    Body,
+}
+
+/// How a sort field and its direction are written, joined by commas when sorting by
+/// several fields.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub enum HttpSortFormat {
+    /// `field` ascending and `-field` descending.
+    #[default]
+    Prefix,
+    /// `field:asc` and `field:desc`.
+    Colon,
+}
+
+/// Maps the `opts` of a find many resolver onto the parameters of the upstream API.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct HttpPaginationConfig {
+    pub style: Option<HttpPaginationStyle>,
+    pub location: Option<HttpParameterLocation>,
+    /// Defaults to `page`.
+    pub page_param: Option<String>,
+    /// Defaults to `per_page`.
+    pub per_page_param: Option<String>,
+    /// Defaults to `offset`.
+    pub offset_param: Option<String>,
+    /// Defaults to `cursor`.
+    pub cursor_param: Option<String>,
+    /// Dot separated path to the cursor of the next page within the response body.
+    pub next_cursor_path: Option<String>,
+    /// Sorting is not sent upstream unless set.
+    pub sort_param: Option<String>,
+    pub sort_format: Option<HttpSortFormat>,
+    /// The largest page size accepted upstream.
+    pub max_per_page: Option<i64>,
+    /// Upstream pages requested to fill a larger page of results. Defaults to 1.
+    pub max_follow_pages: Option<u32>,
+    /// Cursor pages walked to reach the start of the requested page. Defaults to 10.
+    pub max_walk_pages: Option<u32>,
+}
+
+impl HttpPaginationConfig {
+    pub fn get_style(&self) -> HttpPaginationStyle {
+        self.style.clone().unwrap_or_default()
+    }
+
+    pub fn get_location(&self) -> HttpParameterLocation {
+        self.location.clone().unwrap_or_default()
+    }
+
+    pub fn get_page_param(&self) -> &str {
+        self.page_param.as_deref().unwrap_or("page")
+    }
+
+    pub fn get_per_page_param(&self) -> &str {
+        self.per_page_param.as_deref().unwrap_or("per_page")
+    }
+
+    pub fn get_offset_param(&self) -> &str {
+        self.offset_param.as_deref().unwrap_or("offset")
+    }
+
+    pub fn get_cursor_param(&self) -> &str {
+        self.cursor_param.as_deref().unwrap_or("cursor")
+    }
+
+    pub fn get_max_follow_pages(&self) -> u32 {
+        self.max_follow_pages
+            .unwrap_or(DEFAULT_HTTP_MAX_FOLLOW_PAGES)
+            .max(1)
+    }
+
+    pub fn get_max_walk_pages(&self) -> u32 {
+        self.max_walk_pages.unwrap_or(DEFAULT_HTTP_MAX_WALK_PAGES)
+    }
 }
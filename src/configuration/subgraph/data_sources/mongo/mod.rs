@@ -1,8 +1,100 @@
+use mongodb::options::{
+    Acknowledgment, ReadConcern, ReadPreference, ReadPreferenceOptions, SelectionCriteria,
+    WriteConcern,
+};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct MongoDataSourceConfig {
     pub name: String,
     pub uri: String,
     pub db: String,
+    pub index_sync: Option<MongoIndexSync>,
+    pub read_preference: Option<MongoReadPreference>,
+    pub read_concern: Option<MongoReadConcern>,
+    pub write_concern: Option<MongoWriteConcern>,
+    pub max_pool_size: Option<u32>,
+    /// Milliseconds to wait for a server to be available before an operation fails.
+    pub server_selection_timeout: Option<u64>,
+    pub app_name: Option<String>,
+}
+
+/// How the indexes of the entities are reconciled when the service starts.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub enum MongoIndexSync {
+    /// Creates missing indexes and recreates changed indexes.
+    #[default]
+    Apply,
+    /// Also drops indexes which are not configured.
+    Prune,
+    /// Logs the changes without applying them.
+    DryRun,
+    Skip,
+}
+
+/// The members of the replica set which reads are routed to.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum MongoReadPreference {
+    Primary,
+    PrimaryPreferred,
+    Secondary,
+    SecondaryPreferred,
+    Nearest,
+}
+
+impl MongoReadPreference {
+    pub fn to_selection_criteria(&self) -> SelectionCriteria {
+        let options = ReadPreferenceOptions::default();
+        let read_preference = match self {
+            MongoReadPreference::Primary => ReadPreference::Primary,
+            MongoReadPreference::PrimaryPreferred => ReadPreference::PrimaryPreferred { options },
+            MongoReadPreference::Secondary => ReadPreference::Secondary { options },
+            MongoReadPreference::SecondaryPreferred => {
+                ReadPreference::SecondaryPreferred { options }
+            }
+            MongoReadPreference::Nearest => ReadPreference::Nearest { options },
+        };
+        SelectionCriteria::ReadPreference(read_preference)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum MongoReadConcern {
+    Local,
+    Available,
+    Majority,
+    Linearizable,
+    Snapshot,
+}
+
+impl MongoReadConcern {
+    pub fn to_read_concern(&self) -> ReadConcern {
+        match self {
+            MongoReadConcern::Local => ReadConcern::local(),
+            MongoReadConcern::Available => ReadConcern::available(),
+            MongoReadConcern::Majority => ReadConcern::majority(),
+            MongoReadConcern::Linearizable => ReadConcern::linearizable(),
+            MongoReadConcern::Snapshot => ReadConcern::snapshot(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MongoWriteConcern {
+    /// The number of members, `"majority"` or a custom tag acknowledging writes.
+    pub w: Option<Acknowledgment>,
+    pub journal: Option<bool>,
+    /// Milliseconds to wait for the acknowledgment before the write fails.
+    pub w_timeout: Option<u64>,
+}
+
+impl MongoWriteConcern {
+    pub fn to_write_concern(&self) -> WriteConcern {
+        WriteConcern::builder()
+            .w(self.w.clone())
+            .journal(self.journal)
+            .w_timeout(self.w_timeout.map(Duration::from_millis))
+            .build()
+    }
 }
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::configuration::subgraph::entities::service_entity_field::ServiceEntityFieldConfig;
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum DialectEnum {
     POSTGRES,
@@ -14,4 +16,33 @@ pub struct SqlDataSourceConfig {
     pub dialect: DialectEnum,
     pub sqlite_extensions: Option<Vec<String>>,
     pub migrations_path: Option<String>,
+    pub pool: Option<SqlPoolConfig>,
+    pub replicas: Option<Vec<String>>,
+    pub queries: Option<Vec<SqlQueryConfig>>,
+    pub schema: Option<String>,
+}
+
+/// Connection pool options for a SQL data source. Timeouts are in milliseconds.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SqlPoolConfig {
+    pub min_connections: Option<u32>,
+    pub max_connections: Option<u32>,
+    pub acquire_timeout: Option<u64>,
+    pub idle_timeout: Option<u64>,
+    pub statement_timeout: Option<u64>,
+    pub connect_retries: Option<u32>,
+    pub connect_backoff: Option<u64>,
+    pub lazy_connect: Option<bool>,
+    pub statement_cache_capacity: Option<usize>,
+    pub query_plan_cache_capacity: Option<usize>,
+    pub replica_probe_interval: Option<u64>,
+}
+
+/// A named SQL query, used by read only entities in place of a `table`.
+/// Parameters are referenced in the SQL as `:name` and bound from the resolver input.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SqlQueryConfig {
+    pub name: String,
+    pub sql: String,
+    pub params: Option<Vec<ServiceEntityFieldConfig>>,
 }
@@ -1,12 +1,21 @@
 use log::{debug, error, trace};
 use serde::{Deserialize, Serialize};
 
+use mongo_index::MongoIndexConfig;
 use service_entity_field::ServiceEntityFieldConfig;
 
 use crate::resolver_type::ResolverType;
 
-use super::{cors::MethodOption, guard::Guard};
+use super::{
+    cors::MethodOption,
+    data_sources::{
+        http::{HttpPaginationConfig, HttpRetryConfig},
+        mongo::{MongoReadConcern, MongoReadPreference, MongoWriteConcern},
+    },
+    guard::{guard_data_context::VariablePair, Guard},
+};
 
+pub mod mongo_index;
 pub mod service_entity_field;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -21,11 +30,23 @@ pub struct ServiceEntityResolversConfig {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct QueryPair(pub String, pub String);
 
+/// Maps an entity field to a dot separated path within a record of an HTTP response.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FieldPath(pub String, pub String);
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ServiceEntityDataSourceConfig {
     pub from: Option<String>,
     pub collection: Option<String>,
     pub table: Option<String>,
+    pub schema: Option<String>,
+    pub query: Option<String>,
+    pub pipeline: Option<String>,
+    pub pipeline_variables: Option<Vec<VariablePair>>,
+    pub indexes: Option<Vec<MongoIndexConfig>>,
+    pub read_preference: Option<MongoReadPreference>,
+    pub read_concern: Option<MongoReadConcern>,
+    pub write_concern: Option<MongoWriteConcern>,
     pub path: Option<String>,
     pub search_query: Option<Vec<QueryPair>>,
     pub resolvers: Option<ServiceEntityResolversConfig>,
@@ -38,6 +59,17 @@ pub struct ServiceEntityResolverConfig {
     pub search_query: Option<Vec<QueryPair>>,
     pub http_method: Option<MethodOption>,
     pub guards: Option<Vec<Guard>>,
+    /// Milliseconds to wait for the response of an HTTP data source.
+    pub request_timeout: Option<u64>,
+    pub retry: Option<HttpRetryConfig>,
+    /// Dot separated path to the records within the response body of an HTTP data source.
+    pub records_path: Option<String>,
+    /// Dot separated path to the total count within the response body of an HTTP data source.
+    pub total_count_path: Option<String>,
+    /// Response header containing the total count, used when no `total_count_path` is set.
+    pub total_count_header: Option<String>,
+    pub field_paths: Option<Vec<FieldPath>>,
+    pub pagination: Option<HttpPaginationConfig>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -127,6 +159,26 @@ impl ServiceEntityConfig {
         None
     }
 
+    /// The name of the SQL query backing the entity, if it is not backed by a table.
+    pub fn get_sql_query_name(entity: &ServiceEntityConfig) -> Option<String> {
+        entity.data_source.as_ref().and_then(|ds| ds.query.clone())
+    }
+
+    /// The base Mongo aggregation pipeline of the entity, if it is not backed by a collection alone.
+    pub fn get_mongo_pipeline(entity: &ServiceEntityConfig) -> Option<String> {
+        entity
+            .data_source
+            .as_ref()
+            .and_then(|ds| ds.pipeline.clone())
+    }
+
+    /// The name of the SQL table of the entity, which defaults to the name of the entity.
+    pub fn get_sql_table_name(entity: &ServiceEntityConfig) -> String {
+        ServiceEntityConfig::get_entity_data_source(entity)
+            .and_then(|data_source| data_source.table)
+            .unwrap_or(entity.name.clone())
+    }
+
     pub fn get_mongo_collection_name(entity: &ServiceEntityConfig) -> String {
         debug!("Found Entity Data Source: {:?}", entity.data_source);
         let data_source = ServiceEntityConfig::get_entity_data_source(entity);
@@ -219,34 +271,50 @@ impl ServiceEntityConfig {
         }
     }
 
+    /// Gets the single primary key field of the entity.
+    /// Errors if the entity uses a composite primary key, see `get_primary_key_fields`.
     pub fn get_primary_key_field(
         entity: &ServiceEntityConfig,
     ) -> Result<ServiceEntityFieldConfig, async_graphql::Error> {
         debug!("Get Primary Key Field");
-        let entity_fields = &entity.fields;
+        let primary_key_fields = ServiceEntityConfig::get_primary_key_fields(entity)?;
+
         // Make sure the entity does not have multiple primary keys
-        if entity_fields
-            .iter()
-            .filter(|field| field.primary_key.unwrap_or(false))
-            .count()
-            > 1
-        {
-            error!("Entity {} has multiple primary keys", entity.name);
+        if primary_key_fields.len() > 1 {
+            error!("Entity {} has a composite primary key", entity.name);
             return Err(async_graphql::Error::from(format!(
-                "Entity {} has multiple primary keys.",
+                "Entity {} has a composite primary key.",
                 entity.name
             )));
         }
 
-        for field in entity_fields {
-            if field.primary_key.unwrap_or(false) {
-                return Ok(field.clone());
-            }
+        Ok(primary_key_fields[0].clone())
+    }
+
+    /// Gets the primary key fields of the entity, in the order they are defined.
+    /// Multiple fields with `primary_key = true` create a composite primary key.
+    /// If none are defined, falls back to the `id` or `_id` field.
+    pub fn get_primary_key_fields(
+        entity: &ServiceEntityConfig,
+    ) -> Result<Vec<ServiceEntityFieldConfig>, async_graphql::Error> {
+        debug!("Get Primary Key Fields");
+        let entity_fields = &entity.fields;
+
+        let primary_key_fields = entity_fields
+            .iter()
+            .filter(|field| field.primary_key.unwrap_or(false))
+            .cloned()
+            .collect::<Vec<ServiceEntityFieldConfig>>();
+
+        if !primary_key_fields.is_empty() {
+            trace!("Primary Key Fields: {:?}", primary_key_fields);
+            return Ok(primary_key_fields);
         }
+
         // Try to find the id or the _id field
         for field in entity_fields {
             if field.name == "id" || field.name == "_id" {
-                return Ok(field.clone());
+                return Ok(vec![field.clone()]);
             }
         }
         Err(async_graphql::Error::from(format!(
@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+
+/// An index of the Mongo collection of an entity, reconciled when the service starts.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MongoIndexConfig {
+    /// Defaults to the name Mongo generates from the keys, such as `email_1`.
+    pub name: Option<String>,
+    pub keys: Vec<MongoIndexKey>,
+    pub unique: Option<bool>,
+    pub sparse: Option<bool>,
+    /// Seconds after the date of the indexed field before the document is removed.
+    pub expire_after: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MongoIndexKey {
+    pub field: String,
+    /// Defaults to `Asc`.
+    pub kind: Option<MongoIndexKind>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub enum MongoIndexKind {
+    #[default]
+    Asc,
+    Desc,
+    Text,
+    #[serde(rename = "2dsphere")]
+    Sphere2D,
+}
+
+impl MongoIndexKind {
+    /// The value of the key in the index specification.
+    pub fn to_bson(&self) -> bson::Bson {
+        match self {
+            MongoIndexKind::Asc => bson::Bson::Int32(1),
+            MongoIndexKind::Desc => bson::Bson::Int32(-1),
+            MongoIndexKind::Text => bson::Bson::String("text".to_string()),
+            MongoIndexKind::Sphere2D => bson::Bson::String("2dsphere".to_string()),
+        }
+    }
+}
+
+impl MongoIndexConfig {
+    /// The configured name, or the name Mongo generates from the keys.
+    pub fn get_name(&self) -> String {
+        if let Some(name) = &self.name {
+            return name.clone();
+        }
+        self.keys
+            .iter()
+            .map(|key| {
+                let kind = match key.kind.clone().unwrap_or_default().to_bson() {
+                    bson::Bson::Int32(direction) => direction.to_string(),
+                    bson::Bson::String(kind) => kind,
+                    _ => unreachable!(),
+                };
+                format!("{}_{}", key.field, kind)
+            })
+            .collect::<Vec<String>>()
+            .join("_")
+    }
+}
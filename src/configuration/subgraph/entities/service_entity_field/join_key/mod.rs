@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+/// An additional pair of fields used to join entities with a composite key.
+/// `join_on` is the field of the joined entity and `join_from` is the field of the parent.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct JoinKey {
+    pub join_on: String,
+    pub join_from: String,
+}
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+/// The field holding the key of the parent each row joined through a junction belongs to.
+pub const JOIN_THROUGH_KEY: &str = "__join_through_key";
+
+/// The junction entity of a many-to-many join.
+/// `join_from` is the column of the junction holding the key of the parent and `join_on` is the
+/// column holding the key of the joined entity.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct JoinThrough {
+    pub entity: String,
+    pub join_from: String,
+    pub join_on: String,
+}
@@ -3,9 +3,11 @@ use serde::{Deserialize, Serialize};
 
 use crate::{configuration::subgraph::guard::Guard, scalar_option::ScalarOption};
 
-use self::exclude_from_input::ExcludeFromInput;
+use self::{exclude_from_input::ExcludeFromInput, join_key::JoinKey, join_through::JoinThrough};
 
 pub mod exclude_from_input;
+pub mod join_key;
+pub mod join_through;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ServiceEntityFieldConfig {
@@ -20,11 +22,14 @@ pub struct ServiceEntityFieldConfig {
     pub as_type: Option<String>,
     pub join_on: Option<String>,
     pub join_from: Option<String>,
+    pub join_keys: Option<Vec<JoinKey>>,
+    pub join_through: Option<JoinThrough>,
     pub default_value: Option<String>,
     pub eager: Option<bool>,
     pub is_virtual: Option<bool>,
     pub primary_key: Option<bool>,
     pub enum_values: Option<Vec<String>>,
+    pub cost: Option<usize>,
 }
 
 impl ServiceEntityFieldConfig {
@@ -128,6 +133,15 @@ impl ServiceEntityFieldConfig {
         Ok(field_names)
     }
 
+    /// Whether the field is a list stored in a single SQL column, rather than a join.
+    /// Postgres stores these as native arrays, MySQL and SQLite as JSON arrays.
+    pub fn is_list_column(&self) -> bool {
+        self.list.unwrap_or(false)
+            && self.as_type.is_none()
+            && !self.is_virtual.unwrap_or(false)
+            && !matches!(self.scalar, ScalarOption::Object)
+    }
+
     pub fn is_excluded_input_field(
         entity_field: &ServiceEntityFieldConfig,
         excluded: Option<ExcludeFromInput>,
@@ -1,5 +1,5 @@
 use async_graphql::{Error, ErrorExtensions};
-use bson::{doc, Document};
+use bson::{doc, Bson, Document};
 use evalexpr::*;
 use guard_data_context::GuardDataContext;
 use http::HeaderMap;
@@ -9,7 +9,7 @@ use serde::{Deserialize, Serialize};
 use crate::{
     configuration::subgraph::SubGraphConfig, filter_operator::FilterOperator,
     graphql::schema::create_auth_service::TokenData, traits::evalexpr::FromSerdeJson,
-    utils::clean_string::clean_string,
+    update_operator::UpdateOperator, utils::clean_string::clean_string,
 };
 
 pub mod guard_data_context;
@@ -62,39 +62,62 @@ impl Guard {
         Ok(())
     }
 
-    pub fn extract_input_values(input_document: Document) -> Result<Document, EvalexprError> {
+    /// Extracts the `values` of the input, followed by the fields targeted by each update
+    /// operator, so that guards on values also apply to fields changed by operators. Unset
+    /// fields are extracted as null.
+    pub fn extract_input_values(input_document: Document) -> Result<Vec<Document>, EvalexprError> {
         let exclude_keys = FilterOperator::list()
             .iter()
             .map(|op| op.as_str().to_string())
             .collect::<Vec<String>>();
 
-        let values_input = match input_document.get("values") {
-            Some(values_input) => values_input.as_document(),
-            None => {
+        let mut documents = vec![];
+        match input_document.get("values") {
+            Some(values_input) => match values_input.as_document() {
+                Some(values_input) => {
+                    let mut cloned_values = values_input.clone();
+                    for key in &exclude_keys {
+                        if cloned_values.contains_key(key) {
+                            cloned_values.remove(key);
+                        }
+                    }
+                    documents.push(cloned_values);
+                }
+                None => {
+                    error!("Failed to parse `values` input.");
+                    return Err(EvalexprError::CustomMessage(
+                        "Failed to parse `values` input.".to_string(),
+                    ));
+                }
+            },
+            None if !input_document.contains_key("operators") => {
                 error!("Missing input property `values`.");
                 return Err(EvalexprError::CustomMessage(
                     "Missing input property `values`.".to_string(),
                 ));
             }
+            None => (),
         };
 
-        match values_input {
-            Some(values_input) => {
-                let mut cloned_values = values_input.clone();
-                for key in &exclude_keys {
-                    if cloned_values.contains_key(key) {
-                        cloned_values.remove(key);
-                    }
-                }
-                Ok(cloned_values)
-            }
-            None => {
-                error!("Failed to parse `values` input.");
-                return Err(EvalexprError::CustomMessage(
-                    "Failed to parse `values` input.".to_string(),
-                ));
+        if let Ok(operators) = input_document.get_document("operators") {
+            for update_operator in UpdateOperator::list() {
+                let targets = match operators.get_document(update_operator.as_str()) {
+                    Ok(targets) => targets,
+                    Err(_) => continue,
+                };
+                let targets = match update_operator {
+                    UpdateOperator::Unset => targets
+                        .iter()
+                        .filter(|(_, unset)| unset.as_bool().unwrap_or(false))
+                        .map(|(key, _)| (key.clone(), Bson::Null))
+                        .collect::<Document>(),
+                    _ => targets.clone(),
+                };
+                documents.push(targets);
             }
         }
+
+        Ok(documents)
     }
 
     /// The input object provided will have a recursive shape.
@@ -191,8 +214,7 @@ impl Guard {
                         if root == "query" {
                             documents = Guard::extract_input_queries(input_document.clone())?
                         } else {
-                            let values_document = Guard::extract_input_values(input_document.clone())?;
-                            documents = vec![values_document];
+                            documents = Guard::extract_input_values(input_document.clone())?;
                         }
 
                         let mut values_tuple = vec![];
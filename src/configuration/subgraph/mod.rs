@@ -11,6 +11,7 @@ pub mod cors;
 pub mod data_sources;
 pub mod entities;
 pub mod guard;
+pub mod mutations;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ServiceConfig {
@@ -25,8 +26,13 @@ pub struct ServiceConfig {
     #[serde(default)]
     pub entities: Vec<entities::ServiceEntityConfig>,
     pub data_sources: Vec<data_sources::ServiceDataSourceConfig>,
+    pub mutations: Option<Vec<mutations::ServiceMutationConfig>>,
     pub cors: Option<cors::CorsConfigOptions>,
     pub imports: Option<Vec<PathBuf>>,
+    pub max_depth: Option<usize>,
+    pub max_complexity: Option<usize>,
+    /// Exposes the `data_source_health` query, guarded by the service guards.
+    pub data_source_health: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+use super::{entities::service_entity_field::ServiceEntityFieldConfig, guard::Guard};
+
+/// A mutation executing a SQL statement or stored procedure call against a SQL data source.
+/// Arguments are referenced in the SQL as `:name`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ServiceMutationConfig {
+    pub name: String,
+    pub data_source: String,
+    pub sql: String,
+    pub args: Option<Vec<ServiceEntityFieldConfig>>,
+    pub returns: Option<String>,
+    pub guards: Option<Vec<Guard>>,
+}
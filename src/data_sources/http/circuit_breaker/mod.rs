@@ -0,0 +1,108 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use async_graphql::ErrorExtensions;
+use log::{debug, error, warn};
+
+use crate::configuration::subgraph::data_sources::http::{
+    HttpCircuitBreakerConfig, DEFAULT_HTTP_FAILURE_THRESHOLD, DEFAULT_HTTP_RESET_TIMEOUT,
+};
+
+#[derive(Debug, Default)]
+struct CircuitState {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    /// When the request trying the upstream after the reset timeout was allowed.
+    /// A trial that never reports back, such as a dropped request, expires after the reset timeout.
+    trial_started_at: Option<Instant>,
+}
+
+/// Tracks the health of an HTTP data source, shared by its clones.
+/// Without a config, every request is allowed.
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    reset_timeout: Duration,
+    is_enabled: bool,
+    state: Arc<Mutex<CircuitState>>,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: Option<&HttpCircuitBreakerConfig>) -> Self {
+        CircuitBreaker {
+            failure_threshold: config
+                .and_then(|config| config.failure_threshold)
+                .unwrap_or(DEFAULT_HTTP_FAILURE_THRESHOLD)
+                .max(1),
+            reset_timeout: Duration::from_millis(
+                config
+                    .and_then(|config| config.reset_timeout)
+                    .unwrap_or(DEFAULT_HTTP_RESET_TIMEOUT),
+            ),
+            is_enabled: config.is_some(),
+            state: Arc::new(Mutex::new(CircuitState::default())),
+        }
+    }
+
+    /// Checks whether a request may be sent. While the circuit is open the request fails fast,
+    /// until the reset timeout lets a single request try the upstream.
+    pub fn check(&self, data_source_name: &str) -> Result<(), async_graphql::Error> {
+        if !self.is_enabled {
+            return Ok(());
+        }
+        let mut state = self.state.lock().unwrap();
+        let opened_at = match state.opened_at {
+            Some(opened_at) => opened_at,
+            None => return Ok(()),
+        };
+
+        let is_trial_pending = state
+            .trial_started_at
+            .is_some_and(|trial_started_at| trial_started_at.elapsed() < self.reset_timeout);
+        if opened_at.elapsed() >= self.reset_timeout && !is_trial_pending {
+            debug!("Circuit Half Open: {}", data_source_name);
+            state.trial_started_at = Some(Instant::now());
+            return Ok(());
+        }
+
+        error!("Circuit open for HTTP data source {}", data_source_name);
+        Err(async_graphql::Error::new(format!(
+            "Circuit open for HTTP data source {}",
+            data_source_name
+        ))
+        .extend_with(|_err, e| e.set("code", "CIRCUIT_OPEN")))
+    }
+
+    pub fn record_success(&self) {
+        if !self.is_enabled {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        *state = CircuitState::default();
+    }
+
+    pub fn record_failure(&self, data_source_name: &str) {
+        if !self.is_enabled {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        state.consecutive_failures += 1;
+        let is_trial = state.trial_started_at.is_some();
+        if is_trial || state.consecutive_failures >= self.failure_threshold {
+            if state.opened_at.is_none() || is_trial {
+                warn!(
+                    "Opening circuit of HTTP data source {} after {} failures",
+                    data_source_name, state.consecutive_failures
+                );
+            }
+            state.opened_at = Some(Instant::now());
+            state.trial_started_at = None;
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.state.lock().unwrap().opened_at.is_some()
+    }
+}
@@ -0,0 +1,97 @@
+use bson::Document;
+use evalexpr::{eval_with_context_mut, Value};
+use http::{header::HeaderName, HeaderMap, HeaderValue};
+use log::{debug, error, trace, warn};
+
+use crate::{
+    configuration::subgraph::{guard::Guard, SubGraphConfig},
+    graphql::schema::create_auth_service::TokenData,
+    resolver_type::ResolverType,
+};
+
+use super::HttpDataSource;
+
+impl HttpDataSource {
+    /// Creates the headers of the client request which are forwarded upstream, renamed and
+    /// valued as configured by `forward_headers`.
+    pub fn create_forward_headers(
+        data_source: &HttpDataSource,
+        headers: &HeaderMap,
+        token_data: &Option<TokenData>,
+        resolver_type: ResolverType,
+        subgraph_config: &SubGraphConfig,
+    ) -> Result<HeaderMap, async_graphql::Error> {
+        debug!("Creating Forward Headers");
+
+        let mut forward_headers = HeaderMap::new();
+        let forward_header_configs = match &data_source.config.forward_headers {
+            Some(forward_header_configs) => forward_header_configs,
+            None => return Ok(forward_headers),
+        };
+
+        let mut context = None;
+
+        for forward_header in forward_header_configs {
+            let header_name =
+                match HeaderName::from_bytes(forward_header.get_upstream_name().as_bytes()) {
+                    Ok(header_name) => header_name,
+                    Err(_) => {
+                        error!(
+                            "Invalid forward header name: {}",
+                            forward_header.get_upstream_name()
+                        );
+                        return Err(async_graphql::Error::new(format!(
+                            "Invalid forward header name: {}",
+                            forward_header.get_upstream_name()
+                        )));
+                    }
+                };
+
+            let value = if let Some(value_expr) = &forward_header.value_expr {
+                if context.is_none() {
+                    context = Some(Guard::create_guard_context(
+                        headers.clone(),
+                        token_data.clone(),
+                        Document::new(),
+                        resolver_type.to_string(),
+                        None,
+                        None,
+                        subgraph_config.clone(),
+                    )?);
+                }
+                match eval_with_context_mut(value_expr, context.as_mut().unwrap()) {
+                    Ok(Value::String(value)) => Some(value),
+                    Ok(Value::Empty) => None,
+                    Ok(value) => Some(value.to_string()),
+                    Err(e) => {
+                        warn!(
+                            "Forward header `{}` not sent, failed to evaluate `{}`: {}",
+                            forward_header.name, value_expr, e
+                        );
+                        None
+                    }
+                }
+            } else {
+                forward_header.value.clone()
+            };
+
+            match value {
+                Some(value) => match HeaderValue::from_str(&value) {
+                    Ok(value) => {
+                        forward_headers.insert(header_name, value);
+                    }
+                    Err(_) => warn!("Invalid value for forward header `{}`", forward_header.name),
+                },
+                None if forward_header.value_expr.is_none() => {
+                    for value in headers.get_all(forward_header.name.as_str()) {
+                        forward_headers.append(header_name.clone(), value.clone());
+                    }
+                }
+                None => (),
+            }
+        }
+
+        trace!("Forward Headers: {:?}", forward_headers.keys());
+        Ok(forward_headers)
+    }
+}
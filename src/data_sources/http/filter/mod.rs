@@ -1,23 +1,44 @@
+use std::time::Duration;
+
 use async_graphql::Json;
 use bson::Document;
-use http::Method;
+use http::{HeaderMap, Method};
 use log::{debug, info};
 use reqwest::Url;
 
+pub mod forward_headers;
 pub mod method;
+pub mod pagination;
 pub mod request_body;
+pub mod response_mapping;
 pub mod url_path;
 pub mod url_search_query;
 
-use crate::{configuration::subgraph::entities::ServiceEntityConfig, resolver_type::ResolverType};
+use crate::{
+    configuration::subgraph::{
+        data_sources::http::HttpRetryConfig, entities::ServiceEntityConfig, SubGraphConfig,
+    },
+    graphql::schema::create_auth_service::TokenData,
+    resolver_type::ResolverType,
+};
+
+use self::{pagination::HttpPagination, response_mapping::HttpResponseMapping};
 
 use super::HttpDataSource;
 
 #[derive(Debug)]
 pub struct HttpDataSourceFilter {
+    pub entity_name: String,
     pub url: Url,
     pub request_body: Option<Json<Document>>,
     pub method: Method,
+    /// Client headers forwarded upstream.
+    pub headers: HeaderMap,
+    pub timeout: Option<Duration>,
+    pub retry: Option<HttpRetryConfig>,
+    pub response_mapping: HttpResponseMapping,
+    /// Set for find many resolvers with a `pagination` config.
+    pub pagination: Option<HttpPagination>,
 }
 
 impl HttpDataSource {
@@ -26,6 +47,9 @@ impl HttpDataSource {
         input: Document,
         entity: &ServiceEntityConfig,
         resolver_type: ResolverType,
+        headers: &HeaderMap,
+        token_data: &Option<TokenData>,
+        subgraph_config: &SubGraphConfig,
     ) -> Result<HttpDataSourceFilter, async_graphql::Error> {
         info!("Creating Path Filters");
         let mut url = Url::parse(&data_source.config.url)?;
@@ -36,14 +60,46 @@ impl HttpDataSource {
         url = HttpDataSource::create_path_filters(url, input.clone(), resolver_type).await?;
         url = HttpDataSource::create_parameratized_search_query(url, entity, resolver_type).await?;
         url = HttpDataSource::create_query_string_filters(url, input.clone()).await?;
+        let resolver = ServiceEntityConfig::get_resolver(entity, resolver_type);
+        let pagination = match resolver_type {
+            ResolverType::FindMany => {
+                HttpDataSource::create_pagination(resolver.as_ref(), &input, entity)?
+            }
+            _ => None,
+        };
         let request_body = HttpDataSource::create_body_filters(input, resolver_type);
 
         let method = HttpDataSource::get_method(entity, resolver_type);
+        let headers = HttpDataSource::create_forward_headers(
+            data_source,
+            headers,
+            token_data,
+            resolver_type,
+            subgraph_config,
+        )?;
+
+        // Resolvers override the timeout and retries of the data source.
+        let timeout = resolver
+            .as_ref()
+            .and_then(|resolver| resolver.request_timeout)
+            .or(data_source.config.request_timeout)
+            .map(Duration::from_millis);
+        let retry = resolver
+            .as_ref()
+            .and_then(|resolver| resolver.retry.clone())
+            .or(data_source.config.retry.clone());
+        let response_mapping = HttpDataSource::create_response_mapping(resolver.as_ref());
 
         Ok(HttpDataSourceFilter {
+            entity_name: entity.name.clone(),
             url,
             request_body,
             method,
+            headers,
+            timeout,
+            retry,
+            response_mapping,
+            pagination,
         })
     }
 }
@@ -0,0 +1,323 @@
+use async_graphql::Json;
+use bson::{Bson, Document};
+use http::HeaderMap;
+use json::JsonValue;
+use log::{debug, error, trace};
+use reqwest::Url;
+
+use crate::{
+    configuration::subgraph::{
+        data_sources::http::{
+            HttpPaginationConfig, HttpPaginationStyle, HttpParameterLocation, HttpSortFormat,
+        },
+        entities::{ServiceEntityConfig, ServiceEntityResolverConfig},
+    },
+    data_sources::http::services::Services,
+    graphql::schema::create_options_input::{DirectionEnum, OptionsInput},
+};
+
+use super::{HttpDataSource, HttpDataSourceFilter};
+
+/// A request for a single page of the upstream API.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HttpPageRequest {
+    Page(i64),
+    Offset(i64),
+    Cursor(Option<String>),
+    /// The `next` URL of a `Link` header.
+    Url(Url),
+}
+
+/// The `opts` of a find many resolver, mapped onto the pagination of the upstream API.
+#[derive(Debug, Clone)]
+pub struct HttpPagination {
+    pub config: HttpPaginationConfig,
+    /// The page requested by the client, starting at 1.
+    pub page: i64,
+    /// The page size requested by the client, `-1` for all records. The upstream default is
+    /// used when not set.
+    pub per_page: Option<i64>,
+    /// The page size sent upstream.
+    pub upstream_per_page: Option<i64>,
+    pub sort: Option<String>,
+}
+
+impl HttpDataSource {
+    /// Creates the pagination of a resolver with a `pagination` config from the `opts` input.
+    pub fn create_pagination(
+        resolver: Option<&ServiceEntityResolverConfig>,
+        input: &Document,
+        entity: &ServiceEntityConfig,
+    ) -> Result<Option<HttpPagination>, async_graphql::Error> {
+        debug!("Creating HTTP Pagination");
+
+        let config = match resolver.and_then(|resolver| resolver.pagination.clone()) {
+            Some(config) => config,
+            None => return Ok(None),
+        };
+
+        let opts = match input.get("opts") {
+            Some(Bson::Null) | None => None,
+            Some(opts) => Some(bson::from_bson::<OptionsInput>(opts.clone()).map_err(|e| {
+                error!("Invalid options input: {}", e);
+                async_graphql::Error::new(format!("Invalid options input: {}", e))
+            })?),
+        };
+        trace!("Options Input: {:?}", opts);
+
+        let page = opts
+            .as_ref()
+            .and_then(|opts| opts.page)
+            .map(|page| (page as i64).max(1))
+            .unwrap_or(1);
+        let per_page = opts
+            .as_ref()
+            .and_then(|opts| opts.per_page)
+            .map(|per_page| per_page as i64);
+        let upstream_per_page = match (per_page, config.max_per_page) {
+            (Some(per_page), Some(max_per_page)) if per_page > 0 => {
+                Some(per_page.min(max_per_page))
+            }
+            (Some(per_page), None) if per_page > 0 => Some(per_page),
+            (Some(_), max_per_page) => max_per_page,
+            (None, _) => None,
+        };
+
+        let sort = match (
+            &config.sort_param,
+            opts.as_ref().and_then(|opts| opts.sort.as_ref()),
+        ) {
+            (Some(_), Some(sort)) if !sort.is_empty() => {
+                let mut sort_values = Vec::new();
+                for sort_input in sort {
+                    if !entity
+                        .fields
+                        .iter()
+                        .any(|field| field.name == sort_input.field)
+                    {
+                        error!("Invalid sort field: {}", sort_input.field);
+                        return Err(async_graphql::Error::new(format!(
+                            "Invalid sort field: {}",
+                            sort_input.field
+                        )));
+                    }
+                    let is_desc = sort_input.direction == DirectionEnum::Desc;
+                    let value = match (config.sort_format.clone().unwrap_or_default(), is_desc) {
+                        (HttpSortFormat::Prefix, false) => sort_input.field.clone(),
+                        (HttpSortFormat::Prefix, true) => format!("-{}", sort_input.field),
+                        (HttpSortFormat::Colon, false) => format!("{}:asc", sort_input.field),
+                        (HttpSortFormat::Colon, true) => format!("{}:desc", sort_input.field),
+                    };
+                    sort_values.push(value);
+                }
+                Some(sort_values.join(","))
+            }
+            _ => None,
+        };
+
+        let pagination = HttpPagination {
+            config,
+            page,
+            per_page,
+            upstream_per_page,
+            sort,
+        };
+        trace!("HTTP Pagination: {:?}", pagination);
+        Ok(Some(pagination))
+    }
+}
+
+impl HttpPagination {
+    /// The number of records before the requested page.
+    pub fn get_start(&self) -> usize {
+        match self.per_page {
+            Some(per_page) if per_page > 0 => ((self.page - 1) * per_page) as usize,
+            _ => 0,
+        }
+    }
+
+    /// The number of records of the requested page, unlimited when not set.
+    pub fn get_limit(&self) -> Option<usize> {
+        match self.per_page {
+            Some(per_page) if per_page > 0 => Some(per_page as usize),
+            _ => None,
+        }
+    }
+
+    /// The first upstream request, with the number of its records to skip.
+    pub fn get_first_request(&self) -> (HttpPageRequest, usize) {
+        let start = self.get_start();
+        match self.config.get_style() {
+            HttpPaginationStyle::Page | HttpPaginationStyle::LinkHeader => {
+                match self.upstream_per_page {
+                    Some(size) => (
+                        HttpPageRequest::Page(start as i64 / size + 1),
+                        start % size as usize,
+                    ),
+                    None => (HttpPageRequest::Page(self.page), 0),
+                }
+            }
+            HttpPaginationStyle::Offset => (HttpPageRequest::Offset(start as i64), 0),
+            // Cursors can not jump to a page, so the pages before it are walked.
+            HttpPaginationStyle::Cursor => (HttpPageRequest::Cursor(None), start),
+        }
+    }
+
+    /// Rejects pages which would walk more than `max_walk_pages` cursor pages to reach their
+    /// start, when the upstream page size is known.
+    pub fn check_walk(&self) -> Result<(), async_graphql::Error> {
+        let (_, skip) = self.get_first_request();
+        let walked_pages = match self.upstream_per_page {
+            Some(size) if size > 0 => skip as i64 / size,
+            _ => return Ok(()),
+        };
+        if walked_pages > self.config.get_max_walk_pages() as i64 {
+            error!(
+                "Page {} is past the {} upstream pages which can be walked",
+                self.page,
+                self.config.get_max_walk_pages()
+            );
+            return Err(async_graphql::Error::new(format!(
+                "Page {} is past the {} upstream pages which can be walked",
+                self.page,
+                self.config.get_max_walk_pages()
+            )));
+        }
+        Ok(())
+    }
+
+    /// The upstream request following a response, if there are more records.
+    pub fn get_next_request(
+        &self,
+        request: &HttpPageRequest,
+        json: &JsonValue,
+        headers: &HeaderMap,
+        records_returned: usize,
+    ) -> Option<HttpPageRequest> {
+        if records_returned == 0 {
+            return None;
+        }
+        let is_last_page = self
+            .upstream_per_page
+            .is_some_and(|size| (records_returned as i64) < size);
+
+        match (self.config.get_style(), request) {
+            (HttpPaginationStyle::LinkHeader, _) => {
+                HttpPagination::get_next_link(headers).map(HttpPageRequest::Url)
+            }
+            (_, HttpPageRequest::Page(page)) if !is_last_page => {
+                Some(HttpPageRequest::Page(page + 1))
+            }
+            (_, HttpPageRequest::Offset(offset)) if !is_last_page => {
+                Some(HttpPageRequest::Offset(offset + records_returned as i64))
+            }
+            (_, HttpPageRequest::Cursor(_)) => {
+                let next_cursor_path = self.config.next_cursor_path.as_ref()?;
+                let cursor = Services::get_json_path(json, next_cursor_path);
+                let cursor = match cursor.as_str() {
+                    Some(cursor) => cursor.to_string(),
+                    None if cursor.is_number() => cursor.dump(),
+                    None => return None,
+                };
+                if cursor.is_empty() {
+                    return None;
+                }
+                Some(HttpPageRequest::Cursor(Some(cursor)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Reads the URL with `rel="next"` from a `Link` header.
+    pub fn get_next_link(headers: &HeaderMap) -> Option<Url> {
+        let link = headers.get(http::header::LINK)?.to_str().ok()?;
+        trace!("Link Header: {}", link);
+
+        link.split(',').find_map(|link_value| {
+            let mut parts = link_value.split(';');
+            let url = parts
+                .next()?
+                .trim()
+                .trim_start_matches('<')
+                .trim_end_matches('>');
+            let is_next = parts.any(|param| {
+                let param = param.trim().replace(' ', "");
+                param == "rel=\"next\"" || param == "rel=next"
+            });
+            if is_next {
+                Url::parse(url).ok()
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Whether a `Link` header URL has the scheme, host and port of the data source URL, so the
+    /// forwarded headers are only sent to the configured upstream.
+    pub fn is_same_origin(url: &Url, data_source_url: &str) -> bool {
+        match Url::parse(data_source_url) {
+            Ok(data_source_url) => url.origin() == data_source_url.origin(),
+            Err(_) => false,
+        }
+    }
+
+    /// Sets the pagination and sort parameters of the request on the filter.
+    pub fn set_params(&self, filter: &mut HttpDataSourceFilter, request: &HttpPageRequest) {
+        debug!("Setting Pagination Params: {:?}", request);
+
+        let config = &self.config;
+        let mut params = Vec::new();
+        match request {
+            HttpPageRequest::Url(url) => {
+                filter.url = url.clone();
+                return;
+            }
+            HttpPageRequest::Page(page) => {
+                params.push((config.get_page_param().to_string(), page.to_string()))
+            }
+            HttpPageRequest::Offset(offset) => {
+                params.push((config.get_offset_param().to_string(), offset.to_string()))
+            }
+            HttpPageRequest::Cursor(Some(cursor)) => {
+                params.push((config.get_cursor_param().to_string(), cursor.clone()))
+            }
+            HttpPageRequest::Cursor(None) => (),
+        }
+        if let Some(size) = self.upstream_per_page {
+            params.push((config.get_per_page_param().to_string(), size.to_string()));
+        }
+        if let (Some(sort_param), Some(sort)) = (&config.sort_param, &self.sort) {
+            params.push((sort_param.clone(), sort.clone()));
+        }
+        trace!("Pagination Params: {:?}", params);
+
+        match config.get_location() {
+            HttpParameterLocation::Query => {
+                let pairs = filter
+                    .url
+                    .query_pairs()
+                    .filter(|(key, _)| !params.iter().any(|(param, _)| param == key))
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+                    .collect::<Vec<(String, String)>>();
+                filter
+                    .url
+                    .query_pairs_mut()
+                    .clear()
+                    .extend_pairs(pairs)
+                    .extend_pairs(params);
+            }
+            HttpParameterLocation::Body => {
+                let body = filter
+                    .request_body
+                    .get_or_insert_with(|| Json(Document::new()));
+                for (param, value) in params {
+                    let value = match value.parse::<i64>() {
+                        Ok(number) if param != config.get_cursor_param() => Bson::Int64(number),
+                        _ => Bson::String(value),
+                    };
+                    body.0.insert(param, value);
+                }
+            }
+        }
+    }
+}
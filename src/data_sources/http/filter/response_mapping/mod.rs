@@ -0,0 +1,35 @@
+use log::{debug, trace};
+
+use crate::configuration::subgraph::entities::{FieldPath, ServiceEntityResolverConfig};
+
+use super::HttpDataSource;
+
+/// Locates the records and the total count within the response of an HTTP data source.
+#[derive(Debug, Clone, Default)]
+pub struct HttpResponseMapping {
+    pub records_path: Option<String>,
+    pub total_count_path: Option<String>,
+    pub total_count_header: Option<String>,
+    pub field_paths: Vec<FieldPath>,
+}
+
+impl HttpDataSource {
+    pub fn create_response_mapping(
+        resolver: Option<&ServiceEntityResolverConfig>,
+    ) -> HttpResponseMapping {
+        debug!("Creating Response Mapping");
+
+        let mapping = match resolver {
+            Some(resolver) => HttpResponseMapping {
+                records_path: resolver.records_path.clone(),
+                total_count_path: resolver.total_count_path.clone(),
+                total_count_header: resolver.total_count_header.clone(),
+                field_paths: resolver.field_paths.clone().unwrap_or_default(),
+            },
+            None => HttpResponseMapping::default(),
+        };
+
+        trace!("Response Mapping: {:?}", mapping);
+        mapping
+    }
+}
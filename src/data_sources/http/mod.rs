@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use async_graphql::dynamic::FieldValue;
 use bson::Document;
 use http::{header::HeaderName, HeaderMap, HeaderValue};
@@ -10,18 +12,27 @@ use crate::{
         entities::ServiceEntityConfig,
         SubGraphConfig,
     },
-    graphql::entity::create_return_types::{ResolverResponse, ResolverResponseMeta},
+    graphql::{
+        entity::create_return_types::{ResolverResponse, ResolverResponseMeta},
+        schema::create_auth_service::TokenData,
+    },
     resolver_type::ResolverType,
 };
 
+use self::{circuit_breaker::CircuitBreaker, response_cache::ResponseCache};
+
 use super::DataSource;
+pub mod circuit_breaker;
 pub mod filter;
+pub mod response_cache;
 pub mod services;
 
 #[derive(Debug, Clone)]
 pub struct HttpDataSource {
     pub client: Client,
     pub config: HttpDataSourceConfig,
+    pub circuit_breaker: CircuitBreaker,
+    pub response_cache: ResponseCache,
 }
 
 impl HttpDataSource {
@@ -45,12 +56,20 @@ impl HttpDataSource {
     pub async fn init(http_data_source_config: &HttpDataSourceConfig) -> DataSource {
         let header_config = http_data_source_config.default_headers.as_ref();
         let headers = HttpDataSource::get_headers(header_config.clone());
-        let client = Client::builder().default_headers(headers).build();
+        let mut client_builder = Client::builder().default_headers(headers);
+        if let Some(connect_timeout) = http_data_source_config.connect_timeout {
+            client_builder = client_builder.connect_timeout(Duration::from_millis(connect_timeout));
+        }
+        let client = client_builder.build();
 
         match client {
             Ok(client) => DataSource::HTTP(HttpDataSource {
                 client,
                 config: http_data_source_config.clone(),
+                circuit_breaker: CircuitBreaker::new(
+                    http_data_source_config.circuit_breaker.as_ref(),
+                ),
+                response_cache: ResponseCache::new(http_data_source_config.cache.as_ref()),
             }),
             Err(error) => {
                 log::error!("Failed to build HTTP Client.");
@@ -66,6 +85,8 @@ impl HttpDataSource {
         entity: ServiceEntityConfig,
         resolver_type: ResolverType,
         subgraph_config: &SubGraphConfig,
+        headers: &HeaderMap,
+        token_data: &Option<TokenData>,
     ) -> Result<Option<FieldValue<'a>>, async_graphql::Error> {
         debug!("Executing HTTP Data Source Operation");
 
@@ -76,15 +97,22 @@ impl HttpDataSource {
 
         trace!("HTTP Data Source: {:?}", data_source);
 
-        let filter =
-            HttpDataSource::create_filter(data_source, input, &entity, resolver_type).await?;
+        let filter = HttpDataSource::create_filter(
+            data_source,
+            input,
+            &entity,
+            resolver_type,
+            headers,
+            token_data,
+            subgraph_config,
+        )
+        .await?;
 
         trace!("Filter Created: {:?}", filter);
 
         match resolver_type {
             ResolverType::FindOne => {
-                let result =
-                    services::Services::find_one(data_source.client.clone(), filter).await?;
+                let result = services::Services::find_one(data_source, filter).await?;
                 let res = ResolverResponse {
                     data: vec![FieldValue::owned_any(result)],
                     meta: ResolverResponseMeta {
@@ -103,9 +131,20 @@ impl HttpDataSource {
                 Ok(Some(FieldValue::owned_any(res)))
             }
             ResolverType::FindMany => {
-                let results =
-                    services::Services::find_many(data_source.client.clone(), filter).await?;
+                let (page, per_page) = match &filter.pagination {
+                    Some(pagination) => (pagination.page, pagination.get_limit()),
+                    None => (1, None),
+                };
+                let (results, total_count) =
+                    services::Services::find_many(data_source, filter).await?;
                 let count = results.len();
+                // Without a page size, pages are as large as the records returned.
+                let page_size = per_page.unwrap_or(count) as i64;
+                let total_pages = if page_size == 0 {
+                    1
+                } else {
+                    (total_count + page_size - 1) / page_size
+                };
                 let res = ResolverResponse {
                     data: results
                         .into_iter()
@@ -118,17 +157,17 @@ impl HttpDataSource {
                         executed_at: chrono::Utc::now()
                             .to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
                         count: count as i64,
-                        total_count: count as i64,
-                        page: 1,
-                        total_pages: 1,
+                        total_count,
+                        page,
+                        total_pages,
                         user_uuid: None,
                     },
                 };
                 Ok(Some(FieldValue::owned_any(res)))
             }
             ResolverType::CreateOne => {
-                let result =
-                    services::Services::create_one(data_source.client.clone(), filter).await?;
+                let result = services::Services::create_one(data_source, filter).await?;
+                data_source.response_cache.invalidate(&entity.name);
                 let res = ResolverResponse {
                     data: vec![FieldValue::owned_any(result)],
                     meta: ResolverResponseMeta {
@@ -147,8 +186,8 @@ impl HttpDataSource {
                 Ok(Some(FieldValue::owned_any(res)))
             }
             ResolverType::UpdateOne => {
-                let result =
-                    services::Services::update_one(data_source.client.clone(), filter).await?;
+                let result = services::Services::update_one(data_source, filter).await?;
+                data_source.response_cache.invalidate(&entity.name);
                 let res = ResolverResponse {
                     data: vec![FieldValue::owned_any(result)],
                     meta: ResolverResponseMeta {
@@ -167,8 +206,8 @@ impl HttpDataSource {
                 Ok(Some(FieldValue::owned_any(res)))
             }
             ResolverType::UpdateMany => {
-                let results =
-                    services::Services::update_many(data_source.client.clone(), filter).await?;
+                let results = services::Services::update_many(data_source, filter).await?;
+                data_source.response_cache.invalidate(&entity.name);
                 let count = results.len();
                 let res = ResolverResponse {
                     data: results
@@ -0,0 +1,211 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use http::{header, HeaderMap};
+use log::{debug, trace};
+use reqwest::Url;
+
+use crate::configuration::subgraph::data_sources::http::{
+    HttpCacheConfig, DEFAULT_HTTP_CACHE_MAX_ENTRIES, DEFAULT_HTTP_CACHE_TTL,
+    DEFAULT_HTTP_CACHE_VARY_HEADERS,
+};
+
+use super::services::HttpResponse;
+
+#[derive(Debug)]
+struct CacheEntry {
+    response: HttpResponse,
+    entity_name: String,
+    etag: Option<String>,
+    expires_at: Instant,
+    last_used: Instant,
+}
+
+/// The result of looking up a request in the cache.
+#[derive(Debug)]
+pub enum CacheLookup {
+    Fresh(HttpResponse),
+    /// An expired response, revalidated with its `ETag`.
+    Stale(String),
+    Miss,
+}
+
+/// Caches the responses of an HTTP data source, shared by its clones.
+/// Without a config, nothing is cached.
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    ttl: Duration,
+    max_entries: usize,
+    respect_cache_control: bool,
+    vary_headers: Vec<String>,
+    is_enabled: bool,
+    entries: Arc<Mutex<HashMap<String, CacheEntry>>>,
+}
+
+impl ResponseCache {
+    pub fn new(config: Option<&HttpCacheConfig>) -> Self {
+        ResponseCache {
+            ttl: Duration::from_millis(
+                config
+                    .and_then(|config| config.ttl)
+                    .unwrap_or(DEFAULT_HTTP_CACHE_TTL),
+            ),
+            max_entries: config
+                .and_then(|config| config.max_entries)
+                .unwrap_or(DEFAULT_HTTP_CACHE_MAX_ENTRIES)
+                .max(1),
+            respect_cache_control: config
+                .and_then(|config| config.respect_cache_control)
+                .unwrap_or(true),
+            vary_headers: config
+                .and_then(|config| config.vary_headers.clone())
+                .map(|vary_headers| {
+                    vary_headers
+                        .iter()
+                        .map(|name| name.to_lowercase())
+                        .collect()
+                })
+                .unwrap_or_else(|| {
+                    DEFAULT_HTTP_CACHE_VARY_HEADERS
+                        .iter()
+                        .map(|name| name.to_string())
+                        .collect()
+                }),
+            is_enabled: config.is_some(),
+            entries: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.is_enabled
+    }
+
+    /// Responses differ by the URL and the vary headers of the request, such as the
+    /// `Authorization` header.
+    pub fn get_key(&self, url: &Url, headers: &HeaderMap) -> String {
+        let mut header_pairs = headers
+            .iter()
+            .filter(|(name, _)| self.vary_headers.iter().any(|vary| vary == name.as_str()))
+            .map(|(name, value)| format!("{}={}", name, String::from_utf8_lossy(value.as_bytes())))
+            .collect::<Vec<String>>();
+        header_pairs.sort();
+        format!("{}|{}", url, header_pairs.join("|"))
+    }
+
+    pub fn lookup(&self, key: &str) -> CacheLookup {
+        let mut entries = self.entries.lock().unwrap();
+        let now = Instant::now();
+        let lookup = match entries.get_mut(key) {
+            Some(entry) if entry.expires_at > now => {
+                entry.last_used = now;
+                CacheLookup::Fresh(entry.response.clone())
+            }
+            Some(entry) => match &entry.etag {
+                Some(etag) => CacheLookup::Stale(etag.clone()),
+                None => {
+                    entries.remove(key);
+                    CacheLookup::Miss
+                }
+            },
+            None => CacheLookup::Miss,
+        };
+        trace!("Cache Lookup: {:?}", lookup);
+        lookup
+    }
+
+    /// Stores a response for the entity, unless its `Cache-Control` forbids it.
+    pub fn store(&self, key: &str, entity_name: &str, response: &HttpResponse) {
+        let ttl = match self.get_ttl(&response.headers) {
+            Some(ttl) => ttl,
+            None => return,
+        };
+        let etag = response
+            .headers
+            .get(header::ETAG)
+            .and_then(|etag| etag.to_str().ok())
+            .map(|etag| etag.to_string());
+        if ttl.is_zero() && etag.is_none() {
+            return;
+        }
+
+        let mut entries = self.entries.lock().unwrap();
+        if !entries.contains_key(key) && entries.len() >= self.max_entries {
+            let least_recently_used = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(least_recently_used) = least_recently_used {
+                trace!("Evicting Cached Response: {}", least_recently_used);
+                entries.remove(&least_recently_used);
+            }
+        }
+
+        let now = Instant::now();
+        debug!("Caching Response: {}", key);
+        entries.insert(
+            key.to_string(),
+            CacheEntry {
+                response: response.clone(),
+                entity_name: entity_name.to_string(),
+                etag,
+                expires_at: now + ttl,
+                last_used: now,
+            },
+        );
+    }
+
+    /// Refreshes a stale response after the upstream replied `304 Not Modified`.
+    pub fn revalidate(&self, key: &str, headers: &HeaderMap) -> Option<HttpResponse> {
+        let ttl = self.get_ttl(headers).unwrap_or_default();
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries.get_mut(key)?;
+        debug!("Revalidated Cached Response: {}", key);
+        let now = Instant::now();
+        entry.expires_at = now + ttl;
+        entry.last_used = now;
+        Some(entry.response.clone())
+    }
+
+    /// Removes the cached responses of an entity, after it was mutated.
+    pub fn invalidate(&self, entity_name: &str) {
+        if !self.is_enabled {
+            return;
+        }
+        debug!("Invalidating Cached Responses: {}", entity_name);
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, entry| entry.entity_name != entity_name);
+    }
+
+    /// How long a response is fresh for, or `None` when it must not be stored.
+    fn get_ttl(&self, headers: &HeaderMap) -> Option<Duration> {
+        if !self.respect_cache_control {
+            return Some(self.ttl);
+        }
+        let cache_control = match headers
+            .get(header::CACHE_CONTROL)
+            .and_then(|value| value.to_str().ok())
+        {
+            Some(cache_control) => cache_control.to_lowercase(),
+            None => return Some(self.ttl),
+        };
+
+        let mut max_age = None;
+        for directive in cache_control.split(',').map(|directive| directive.trim()) {
+            match directive.split_once('=') {
+                None if directive == "no-store" => return None,
+                None if directive == "no-cache" => return Some(Duration::ZERO),
+                Some(("s-maxage", seconds)) => {
+                    max_age = seconds.trim_matches('"').parse::<u64>().ok().or(max_age)
+                }
+                Some(("max-age", seconds)) if max_age.is_none() => {
+                    max_age = seconds.trim_matches('"').parse::<u64>().ok()
+                }
+                _ => (),
+            }
+        }
+        Some(max_age.map(Duration::from_secs).unwrap_or(self.ttl))
+    }
+}
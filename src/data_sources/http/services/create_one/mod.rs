@@ -1,20 +1,19 @@
 use async_graphql::Error;
 use json::JsonValue;
 use log::{debug, error, trace};
-use reqwest::Client;
 
-use crate::data_sources::http::filter::HttpDataSourceFilter;
+use crate::data_sources::http::{filter::HttpDataSourceFilter, HttpDataSource};
 
 use super::Services;
 
 impl Services {
     pub async fn create_one(
-        client: Client,
+        data_source: &HttpDataSource,
         filter: HttpDataSourceFilter,
     ) -> Result<JsonValue, Error> {
         debug!("Executing Create One - HTTP Data Source");
 
-        let result = Services::request(client, filter).await?;
+        let result = Services::request(data_source, &filter).await?.body;
         trace!("Create One Result: {}", result);
 
         if result.is_empty() {
@@ -27,7 +26,7 @@ impl Services {
         let json = json::parse(&result);
 
         match json {
-            Ok(res) => Ok(res),
+            Ok(res) => Ok(Services::get_records(&res, &filter.response_mapping)),
             Err(error) => {
                 error!("Error parsing JSON: {}", error);
                 Err(Error::new("HTTP Create One Failed"))
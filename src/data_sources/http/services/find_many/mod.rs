@@ -1,35 +1,135 @@
 use async_graphql::{Error, ErrorExtensions};
 use json::JsonValue;
-use log::debug;
-use reqwest::Client;
+use log::{debug, error, trace, warn};
 
-use crate::data_sources::http::filter::HttpDataSourceFilter;
+use crate::data_sources::http::{
+    filter::{
+        pagination::{HttpPageRequest, HttpPagination},
+        HttpDataSourceFilter,
+    },
+    HttpDataSource,
+};
 
 use super::Services;
 
 impl Services {
+    /// Finds the records of a page, following upstream pages until the page is filled when
+    /// the filter has a pagination.
+    /// Pages walked to reach the start of the page, such as earlier cursor pages, count towards
+    /// the `max_walk_pages` rather than the `max_follow_pages`.
     pub async fn find_many(
-        client: Client,
-        filter: HttpDataSourceFilter,
-    ) -> Result<Vec<JsonValue>, async_graphql::Error> {
+        data_source: &HttpDataSource,
+        mut filter: HttpDataSourceFilter,
+    ) -> Result<(Vec<JsonValue>, i64), async_graphql::Error> {
         debug!("Executing Find Many - HTTP Data Source");
 
-        let response = Services::request(client, filter).await?;
-        debug!("Response Received: {:?}", response);
-
-        let json = json::parse(&response);
-        debug!("JSON Parsed: {:?}", json);
+        let pagination = filter.pagination.clone();
+        if let Some(pagination) = &pagination {
+            pagination.check_walk()?;
+        }
+        let (mut page_request, mut skip) = match &pagination {
+            Some(pagination) => {
+                let (page_request, skip) = pagination.get_first_request();
+                (Some(page_request), skip)
+            }
+            None => (None, 0),
+        };
+        let limit = pagination.as_ref().and_then(|p| p.get_limit());
+        let max_pages = pagination
+            .as_ref()
+            .map(|p| p.config.get_max_follow_pages())
+            .unwrap_or(1);
+        let max_walk_pages = pagination
+            .as_ref()
+            .map(|p| p.config.get_max_walk_pages())
+            .unwrap_or(0);
 
         let mut results = Vec::new();
+        let mut total_count = None;
+        let mut pages = 0;
+        let mut followed_pages = 0;
+        let mut walked_pages = 0;
 
-        match json {
-            Ok(mut res) => res
-                .members_mut()
-                .for_each(|result| results.push(result.to_owned())),
-            Err(_error) => Err(Error::new("HTTP Find One Failed")
-                .extend_with(|err, e| e.set("details", err.message.as_str())))?,
-        };
+        loop {
+            if let (Some(pagination), Some(page_request)) = (&pagination, &page_request) {
+                pagination.set_params(&mut filter, page_request);
+            }
+
+            let response = Services::request(data_source, &filter).await?;
+            debug!("Response Received: {:?}", response);
+
+            let json = json::parse(&response.body);
+            debug!("JSON Parsed: {:?}", json);
+
+            let json = match json {
+                Ok(json) => json,
+                Err(_error) => Err(Error::new("HTTP Find One Failed")
+                    .extend_with(|err, e| e.set("details", err.message.as_str())))?,
+            };
+
+            if pages == 0 {
+                total_count =
+                    Services::get_total_count(&json, &response.headers, &filter.response_mapping);
+            }
+
+            let mut records = Services::get_records(&json, &filter.response_mapping);
+            let records_returned = records.members().count();
+            // Pages which are skipped entirely only walk to the start of the page.
+            let is_walked = records_returned > 0 && skip >= records_returned;
+            records.members_mut().for_each(|result| {
+                if skip > 0 {
+                    skip -= 1;
+                } else if limit.is_none_or(|limit| results.len() < limit) {
+                    results.push(result.to_owned());
+                }
+            });
+            pages += 1;
+            if is_walked {
+                walked_pages += 1;
+            } else {
+                followed_pages += 1;
+            }
+            // Pages of an unknown size are only found to be walked once requested.
+            if walked_pages > max_walk_pages {
+                error!(
+                    "Page is past the {} upstream pages which can be walked",
+                    max_walk_pages
+                );
+                return Err(Error::new(format!(
+                    "Page is past the {} upstream pages which can be walked",
+                    max_walk_pages
+                )));
+            }
+
+            let is_filled = limit.is_some_and(|limit| results.len() >= limit);
+            if is_filled || followed_pages >= max_pages {
+                break;
+            }
+
+            page_request = match (&pagination, &page_request) {
+                (Some(pagination), Some(page_request)) => pagination.get_next_request(
+                    page_request,
+                    &json,
+                    &response.headers,
+                    records_returned,
+                ),
+                _ => None,
+            };
+            trace!("Next Page Request: {:?}", page_request);
+            if let Some(HttpPageRequest::Url(url)) = &page_request {
+                if !HttpPagination::is_same_origin(url, &data_source.config.url) {
+                    warn!("Not Following Link To Another Origin: {}", url);
+                    break;
+                }
+            }
+            if page_request.is_none() {
+                break;
+            }
+        }
+
+        let start = pagination.as_ref().map(|p| p.get_start()).unwrap_or(0);
+        let total_count = total_count.unwrap_or((start + results.len()) as i64);
 
-        Ok(results)
+        Ok((results, total_count))
     }
 }
@@ -1,31 +1,32 @@
 use async_graphql::{Error, ErrorExtensions};
 use json::JsonValue;
 use log::{debug, error, trace};
-use reqwest::Client;
 
-use crate::data_sources::http::filter::HttpDataSourceFilter;
+use crate::data_sources::http::{filter::HttpDataSourceFilter, HttpDataSource};
 
 use super::Services;
 
 impl Services {
     pub async fn find_one(
-        client: Client,
+        data_source: &HttpDataSource,
         filter: HttpDataSourceFilter,
     ) -> Result<JsonValue, async_graphql::Error> {
         debug!("Executing Find One - HTTP Data Source");
 
-        let response = Services::request(client, filter).await.map_err(|error| {
-            error!("{:?}", error);
-            Error::new("HTTP Find One Failed")
-                .extend_with(|err, e| e.set("details", err.message.as_str()))
-        })?;
+        let response = Services::request(data_source, &filter)
+            .await
+            .map_err(|error| {
+                error!("{:?}", error);
+                Error::new("HTTP Find One Failed")
+                    .extend_with(|err, e| e.set("details", err.message.as_str()))
+            })?;
         trace!("Response Received: {:?}", response);
 
-        let json = json::parse(&response);
+        let json = json::parse(&response.body);
         trace!("Response in JSON: {:?}", json);
 
         let res = match json {
-            Ok(res) => Ok(res),
+            Ok(res) => Ok(Services::get_records(&res, &filter.response_mapping)),
             Err(error) => {
                 error!("{:?}", error);
                 Err(Error::new("HTTP Find One Failed")
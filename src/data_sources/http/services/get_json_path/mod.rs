@@ -0,0 +1,19 @@
+use json::JsonValue;
+use log::trace;
+
+use super::Services;
+
+impl Services {
+    /// Resolves a dot separated path, such as `data.items` or `results.0.id`, within a JSON
+    /// value. A leading `$` is ignored. Returns null when the path does not exist.
+    pub fn get_json_path<'a>(value: &'a JsonValue, path: &str) -> &'a JsonValue {
+        trace!("Get JSON Path: {}", path);
+
+        path.split('.')
+            .filter(|segment| !segment.is_empty() && *segment != "$")
+            .fold(value, |current, segment| match segment.parse::<usize>() {
+                Ok(index) if current.is_array() => &current[index],
+                _ => &current[segment],
+            })
+    }
+}
@@ -0,0 +1,30 @@
+use json::JsonValue;
+use log::{debug, trace};
+
+use crate::data_sources::http::filter::response_mapping::HttpResponseMapping;
+
+use super::Services;
+
+impl Services {
+    /// Extracts the records from the response body with the response mapping.
+    /// The records are a single object or an array of objects.
+    pub fn get_records(json: &JsonValue, mapping: &HttpResponseMapping) -> JsonValue {
+        debug!("Get Records - HTTP Data Source");
+
+        let records = match &mapping.records_path {
+            Some(records_path) => Services::get_json_path(json, records_path).clone(),
+            None => json.clone(),
+        };
+        trace!("Records: {:?}", records);
+
+        match records {
+            JsonValue::Array(records) => JsonValue::Array(
+                records
+                    .into_iter()
+                    .map(|record| Services::map_record(record, &mapping.field_paths))
+                    .collect(),
+            ),
+            record => Services::map_record(record, &mapping.field_paths),
+        }
+    }
+}
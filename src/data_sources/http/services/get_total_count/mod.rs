@@ -0,0 +1,37 @@
+use http::HeaderMap;
+use json::JsonValue;
+use log::{debug, warn};
+
+use crate::data_sources::http::filter::response_mapping::HttpResponseMapping;
+
+use super::Services;
+
+impl Services {
+    /// Reads the total count from the response body or headers, when configured.
+    pub fn get_total_count(
+        json: &JsonValue,
+        headers: &HeaderMap,
+        mapping: &HttpResponseMapping,
+    ) -> Option<i64> {
+        debug!("Get Total Count - HTTP Data Source");
+
+        let total_count = if let Some(total_count_path) = &mapping.total_count_path {
+            let value = Services::get_json_path(json, total_count_path);
+            value
+                .as_i64()
+                .or_else(|| value.as_str().and_then(|value| value.parse().ok()))
+        } else if let Some(total_count_header) = &mapping.total_count_header {
+            headers
+                .get(total_count_header.as_str())
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse().ok())
+        } else {
+            return None;
+        };
+
+        if total_count.is_none() {
+            warn!("Total count not found in response, using the number of records.");
+        }
+        total_count
+    }
+}
@@ -0,0 +1,23 @@
+use json::JsonValue;
+use log::trace;
+
+use crate::configuration::subgraph::entities::FieldPath;
+
+use super::Services;
+
+impl Services {
+    /// Copies the values found at the source paths of the record onto their entity fields.
+    pub fn map_record(mut record: JsonValue, field_paths: &[FieldPath]) -> JsonValue {
+        if field_paths.is_empty() || !record.is_object() {
+            return record;
+        }
+
+        for FieldPath(field, path) in field_paths {
+            let value = Services::get_json_path(&record, path).clone();
+            trace!("Mapped `{}` to field `{}`: {:?}", path, field, value);
+            record[field.as_str()] = value;
+        }
+
+        record
+    }
+}
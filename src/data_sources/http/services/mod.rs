@@ -1,94 +1,166 @@
 use async_graphql::ErrorExtensions;
-use http::Method;
-use log::{debug, error, trace};
-use reqwest::Client;
+use http::{header, HeaderMap, Method, StatusCode};
+use log::{debug, error, trace, warn};
 
-use super::filter::HttpDataSourceFilter;
+use super::{filter::HttpDataSourceFilter, response_cache::CacheLookup, HttpDataSource};
 
 pub mod create_one;
 pub mod find_many;
 pub mod find_one;
+pub mod get_json_path;
+pub mod get_records;
+pub mod get_total_count;
+pub mod map_record;
 pub mod update_many;
 pub mod update_one;
 
 pub struct Services;
 
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub body: String,
+    pub headers: HeaderMap,
+}
+
 impl Services {
+    /// Sends the request of the filter, retrying idempotent methods as configured by the filter.
+    /// Requests fail fast while the circuit of the data source is open. `GET` requests are
+    /// answered from the cache of the data source while fresh.
     pub async fn request(
-        client: Client,
-        filter: HttpDataSourceFilter,
-    ) -> Result<String, async_graphql::Error> {
+        data_source: &HttpDataSource,
+        filter: &HttpDataSourceFilter,
+    ) -> Result<HttpResponse, async_graphql::Error> {
         debug!("Executing Request - HTTP Data Source");
 
-        let response = match filter.method {
-            Method::GET => {
-                trace!("Using GET Method");
-                let res = client.get(filter.url).send().await?;
-                match res.status().is_success() {
-                    true => res.text().await?,
-                    _ => {
-                        let res = res.text().await?;
-                        error!("Response Status: {:?}", res);
-                        Err(async_graphql::Error::new("HTTP Request Failed")
-                            .extend_with(|_err, e| e.set("error", res)))?
-                    }
-                }
+        match filter.method {
+            Method::GET | Method::POST | Method::PUT | Method::PATCH => {
+                trace!("Using {} Method", filter.method)
             }
-            Method::POST => {
-                trace!("Using POST Method");
-                let res = client
-                    .post(filter.url)
-                    .json(&filter.request_body)
-                    .send()
-                    .await?;
-                match res.status().is_success() {
-                    true => res.text().await?,
-                    _ => {
-                        let res = res.text().await?;
-                        error!("Response Status: {:?}", res);
-                        Err(async_graphql::Error::new("HTTP Request Failed")
-                            .extend_with(|_err, e| e.set("error", res)))?
-                    }
+            _ => {
+                error!("Unsupported Method");
+                Err(async_graphql::Error::new("Unsupported Method"))?
+            }
+        };
+
+        // Only requests which can be repeated without side effects are retried.
+        let is_idempotent = matches!(filter.method, Method::GET | Method::PUT);
+        let max_retries = match &filter.retry {
+            Some(retry) if is_idempotent => retry.get_max_retries(),
+            _ => 0,
+        };
+        let data_source_name = &data_source.config.name;
+        let mut retries = 0;
+
+        let cache = &data_source.response_cache;
+        let cache_key = match filter.method {
+            Method::GET if cache.is_enabled() => Some(cache.get_key(&filter.url, &filter.headers)),
+            _ => None,
+        };
+        let mut etag = None;
+        if let Some(cache_key) = &cache_key {
+            match cache.lookup(cache_key) {
+                CacheLookup::Fresh(response) => {
+                    debug!("Cached Response Found: {}", filter.url);
+                    return Ok(response);
                 }
+                CacheLookup::Stale(stale_etag) => etag = Some(stale_etag),
+                CacheLookup::Miss => (),
             }
-            Method::PUT => {
-                trace!("Using PUT Method");
-                let res = client
-                    .put(filter.url)
-                    .json(&filter.request_body)
-                    .send()
-                    .await?;
-                match res.status().is_success() {
-                    true => res.text().await?,
-                    _ => {
+        }
+
+        let response = loop {
+            data_source.circuit_breaker.check(data_source_name)?;
+
+            let mut request = data_source
+                .client
+                .request(filter.method.clone(), filter.url.clone())
+                .headers(filter.headers.clone());
+            if filter.method != Method::GET {
+                request = request.json(&filter.request_body);
+            }
+            if let Some(timeout) = filter.timeout {
+                request = request.timeout(timeout);
+            }
+            if let Some(etag) = &etag {
+                request = request.header(header::IF_NONE_MATCH, etag);
+            }
+
+            let can_retry = retries < max_retries;
+            match request.send().await {
+                Ok(res) => {
+                    let status = res.status();
+                    if status.is_server_error() {
+                        data_source.circuit_breaker.record_failure(data_source_name);
+                    } else {
+                        data_source.circuit_breaker.record_success();
+                    }
+
+                    if let (StatusCode::NOT_MODIFIED, Some(cache_key)) = (status, &cache_key) {
+                        match cache.revalidate(cache_key, res.headers()) {
+                            Some(response) => break response,
+                            // The stale response was evicted, so it is requested again once,
+                            // without the `ETag`.
+                            None if etag.is_some() => {
+                                etag = None;
+                                continue;
+                            }
+                            None => {
+                                error!("Unexpected 304 Not Modified: {}", filter.url);
+                                Err(
+                                    async_graphql::Error::new("HTTP Request Failed").extend_with(
+                                        |_err, e| e.set("error", "Unexpected 304 Not Modified"),
+                                    ),
+                                )?
+                            }
+                        }
+                    }
+
+                    if status.is_success() {
+                        let headers = res.headers().clone();
+                        let response = HttpResponse {
+                            body: res.text().await?,
+                            headers,
+                        };
+                        if let Some(cache_key) = &cache_key {
+                            cache.store(cache_key, &filter.entity_name, &response);
+                        }
+                        break response;
+                    }
+
+                    let is_retry_status = filter
+                        .retry
+                        .as_ref()
+                        .is_some_and(|retry| retry.is_retry_status(status.as_u16()));
+                    if can_retry && is_retry_status {
+                        warn!("HTTP Request Failed With Status {}, Retrying", status);
+                    } else {
                         let res = res.text().await?;
                         error!("Response Status: {:?}", res);
                         Err(async_graphql::Error::new("HTTP Request Failed")
                             .extend_with(|_err, e| e.set("error", res)))?
                     }
                 }
-            }
-            Method::PATCH => {
-                trace!("Using PATCH Method");
-                let res = client
-                    .patch(filter.url)
-                    .json(&filter.request_body)
-                    .send()
-                    .await?;
-                match res.status().is_success() {
-                    true => res.text().await?,
-                    _ => {
-                        let res = res.text().await?;
-                        error!("Response Status: {:?}", res);
+                Err(e) => {
+                    data_source.circuit_breaker.record_failure(data_source_name);
+
+                    if can_retry && (e.is_timeout() || e.is_connect()) {
+                        warn!("HTTP Request Failed, Retrying: {}", e);
+                    } else if e.is_timeout() {
+                        error!("HTTP Request Timed Out: {}", e);
+                        Err(async_graphql::Error::new("HTTP Request Timed Out")
+                            .extend_with(|_err, e| e.set("code", "TIMEOUT")))?
+                    } else {
+                        error!("HTTP Request Failed: {}", e);
                         Err(async_graphql::Error::new("HTTP Request Failed")
-                            .extend_with(|_err, e| e.set("error", res)))?
+                            .extend_with(|_err, ext| ext.set("error", e.to_string())))?
                     }
                 }
             }
-            _ => {
-                error!("Unsupported Method");
-                Err(async_graphql::Error::new("Unsupported Method"))?
+
+            if let Some(retry) = &filter.retry {
+                tokio::time::sleep(retry.get_delay(retries)).await;
             }
+            retries += 1;
         };
         trace!("Response: {:?}", response);
         Ok(response)
@@ -1,20 +1,19 @@
 use async_graphql::ErrorExtensions;
 use json::JsonValue;
 use log::debug;
-use reqwest::Client;
 
-use crate::data_sources::http::filter::HttpDataSourceFilter;
+use crate::data_sources::http::{filter::HttpDataSourceFilter, HttpDataSource};
 
 use super::Services;
 
 impl Services {
     pub async fn update_many(
-        client: Client,
+        data_source: &HttpDataSource,
         filter: HttpDataSourceFilter,
     ) -> Result<Vec<JsonValue>, async_graphql::Error> {
         debug!("Executing Update Many - HTTP");
 
-        let response = Services::request(client, filter).await?;
+        let response = Services::request(data_source, &filter).await?.body;
 
         debug!("Response Received: {:?}", response);
 
@@ -25,7 +24,7 @@ impl Services {
         let mut results = Vec::new();
 
         match json {
-            Ok(mut res) => res
+            Ok(res) => Services::get_records(&res, &filter.response_mapping)
                 .members_mut()
                 .for_each(|result| results.push(result.to_owned())),
             Err(_error) => Err(async_graphql::Error::new("HTTP Find One Failed")
@@ -1,27 +1,26 @@
 use async_graphql::{Error, ErrorExtensions};
 use json::JsonValue;
 use log::{debug, info};
-use reqwest::Client;
 
-use crate::data_sources::http::filter::HttpDataSourceFilter;
+use crate::data_sources::http::{filter::HttpDataSourceFilter, HttpDataSource};
 
 use super::Services;
 
 impl Services {
     pub async fn update_one(
-        client: Client,
+        data_source: &HttpDataSource,
         filter: HttpDataSourceFilter,
     ) -> Result<JsonValue, async_graphql::Error> {
         info!("Executing Update One - HTTP Data Source");
 
-        let response = Services::request(client, filter).await?;
+        let response = Services::request(data_source, &filter).await?.body;
         debug!("Response: {:?}", response);
 
         let json = json::parse(&response);
         debug!("JSON: {:?}", json);
 
         let res = match json {
-            Ok(json) => Ok(json),
+            Ok(json) => Ok(Services::get_records(&json, &filter.response_mapping)),
             Err(error) => {
                 debug!("{:?}", error);
                 Err(Error::new("HTTP Update One Failed")
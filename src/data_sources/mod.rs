@@ -1,3 +1,4 @@
+use ::http::HeaderMap;
 use async_graphql::dynamic::FieldValue;
 use bson::Document;
 use log::debug;
@@ -29,33 +30,68 @@ pub struct DataSources {
 
 pub struct TotalCount(i64);
 
+/// The health of a data source, as reported by the `data_source_health` query.
+#[derive(Debug, Clone)]
+pub struct DataSourceHealth {
+    pub name: String,
+    pub healthy: bool,
+}
+
 impl DataSources {
-    /// Initialize Data Sources
+    /// Initialize Data Sources. Fails when a SQL data source can not connect.
     pub async fn init(
         service_data_source_configs: Vec<ServiceDataSourceConfig>,
         args: &CliArgs,
         subgraph_config: &SubGraphConfig,
-    ) -> DataSources {
+    ) -> Result<DataSources, async_graphql::Error> {
         debug!("Initializing Data Sources");
         let mut data_sources = vec![];
         for service_data_source_config in service_data_source_configs {
             match service_data_source_config {
                 ServiceDataSourceConfig::Mongo(conf) => {
-                    data_sources.push(mongo::MongoDataSource::init(&conf).await);
+                    data_sources.push(mongo::MongoDataSource::init(&conf, subgraph_config).await);
                 }
                 ServiceDataSourceConfig::HTTP(conf) => {
                     data_sources.push(http::HttpDataSource::init(&conf).await);
                 }
                 ServiceDataSourceConfig::SQL(conf) => {
-                    data_sources
-                        .push(sql::SqlDataSource::init(&conf, args, subgraph_config.clone()).await);
+                    data_sources.push(
+                        sql::SqlDataSource::init(&conf, args, subgraph_config.clone()).await?,
+                    );
                 }
             };
         }
 
-        DataSources {
+        Ok(DataSources {
             sources: data_sources,
+        })
+    }
+
+    /// Run the `--migrate` command against every SQL data source and return.
+    pub async fn migrate(
+        service_data_source_configs: Vec<ServiceDataSourceConfig>,
+        args: &CliArgs,
+        subgraph_config: &SubGraphConfig,
+    ) -> Result<(), async_graphql::Error> {
+        debug!("Migrating Data Sources");
+        for service_data_source_config in service_data_source_configs {
+            if let ServiceDataSourceConfig::SQL(conf) = service_data_source_config {
+                sql::SqlDataSource::migrate(&conf, args, subgraph_config).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Check the health of the SQL data sources and their replicas.
+    pub async fn get_health(data_sources: &DataSources) -> Vec<DataSourceHealth> {
+        debug!("Checking Data Source Health");
+        let mut health = vec![];
+        for data_source in &data_sources.sources {
+            if let DataSource::SQL(ds) = data_source {
+                health.extend(ds.check_health().await);
+            }
         }
+        health
     }
 
     /// Provide entity and all data sources to get the data source for the entity.
@@ -64,25 +100,24 @@ impl DataSources {
         entity: &ServiceEntityConfig,
     ) -> &'a DataSource {
         debug!("Getting Data Source for Entity");
-        if entity.data_source.is_some() {
-            let data_source = match entity.data_source.as_ref().unwrap().from.as_ref() {
-                Some(ds_name) => {
-                    let data_source = data_sources
-                        .sources
-                        .iter()
-                        .find(|data_source| match data_source {
-                            DataSource::Mongo(ds) => &ds.config.name == ds_name,
-                            DataSource::HTTP(ds) => &ds.config.name == ds_name,
-                            DataSource::SQL(ds) => &ds.config.name == ds_name,
-                        })
-                        .unwrap(); //TODO: Unwrap here needs error handling
-                    data_source
-                }
-                _ => panic!("Data source specified for entity but not found."),
-            };
-            data_source
-        } else {
-            data_sources.sources.first().unwrap()
+        let from = entity
+            .data_source
+            .as_ref()
+            .and_then(|data_source| data_source.from.as_ref());
+        match from {
+            Some(ds_name) => {
+                data_sources
+                    .sources
+                    .iter()
+                    .find(|data_source| match data_source {
+                        DataSource::Mongo(ds) => &ds.config.name == ds_name,
+                        DataSource::HTTP(ds) => &ds.config.name == ds_name,
+                        DataSource::SQL(ds) => &ds.config.name == ds_name,
+                    })
+                    .unwrap() //TODO: Unwrap here needs error handling
+            }
+            // Entities without a `from` are stored by the first data source.
+            None => data_sources.sources.first().unwrap(),
         }
     }
 
@@ -108,6 +143,7 @@ impl DataSources {
         resolver_type: ResolverType,
         subgraph_config: &SubGraphConfig,
         token_data: &Option<TokenData>,
+        headers: &HeaderMap,
         has_selection_set: bool,
     ) -> Result<Option<FieldValue<'a>>, async_graphql::Error> {
         debug!("Executing Datasource Operation");
@@ -123,6 +159,8 @@ impl DataSources {
                 cloned_entity,
                 resolver_type,
                 subgraph_config,
+                headers,
+                token_data,
             )
             .await?),
             DataSource::HTTP(_ds) => Ok(http::HttpDataSource::execute_operation(
@@ -131,6 +169,8 @@ impl DataSources {
                 cloned_entity,
                 resolver_type,
                 subgraph_config,
+                headers,
+                token_data,
             )
             .await?),
             DataSource::SQL(_ds) => Ok(sql::SqlDataSource::execute_operation(
@@ -141,6 +181,7 @@ impl DataSources {
                 subgraph_config,
                 token_data,
                 has_selection_set,
+                None,
             )
             .await?),
         }
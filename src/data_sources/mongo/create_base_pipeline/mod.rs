@@ -0,0 +1,149 @@
+use bson::{Bson, Document};
+use evalexpr::{eval_with_context_mut, Value};
+use http::HeaderMap;
+use log::{debug, error, trace};
+
+use crate::{
+    configuration::subgraph::{
+        entities::ServiceEntityConfig,
+        guard::{guard_data_context::VariablePair, Guard},
+        SubGraphConfig,
+    },
+    data_sources::mongo::MongoDataSource,
+    graphql::schema::create_auth_service::TokenData,
+    resolver_type::ResolverType,
+};
+
+impl MongoDataSource {
+    /// Parses the aggregation pipeline of the entity data source, replacing its variables with
+    /// values evaluated against the guard context of the request.
+    /// Variables are JSON strings, such as `"{{region}}"`, replaced in the parsed pipeline with
+    /// typed BSON values, so request values can not change the structure of the pipeline.
+    /// Entities without a pipeline return an empty pipeline.
+    pub fn create_base_pipeline(
+        entity: &ServiceEntityConfig,
+        input: &Document,
+        resolver_type: &ResolverType,
+        headers: &HeaderMap,
+        token_data: &Option<TokenData>,
+        subgraph_config: &SubGraphConfig,
+    ) -> Result<Vec<Document>, async_graphql::Error> {
+        let pipeline = match ServiceEntityConfig::get_mongo_pipeline(entity) {
+            Some(pipeline) => pipeline,
+            None => return Ok(vec![]),
+        };
+        debug!("Creating Base Pipeline: {}", entity.name);
+
+        let stages = match serde_json::from_str::<serde_json::Value>(&pipeline) {
+            Ok(serde_json::Value::Array(stages)) => stages,
+            Ok(_) => {
+                error!(
+                    "Pipeline of entity {} must be a JSON array of stages.",
+                    entity.name
+                );
+                return Err(async_graphql::Error::new(format!(
+                    "Pipeline of entity {} must be a JSON array of stages.",
+                    entity.name
+                )));
+            }
+            Err(e) => {
+                error!("Failed to parse pipeline of entity {}: {}", entity.name, e);
+                return Err(async_graphql::Error::new(format!(
+                    "Failed to parse pipeline of entity {}: {}",
+                    entity.name, e
+                )));
+            }
+        };
+
+        let mut base_pipeline = Vec::new();
+        for stage in stages {
+            // Extended JSON, such as `{ "$oid": "..." }`, is converted to BSON values.
+            match Bson::try_from(stage) {
+                Ok(Bson::Document(stage)) => base_pipeline.push(stage),
+                _ => {
+                    error!(
+                        "Pipeline stages of entity {} must be JSON objects.",
+                        entity.name
+                    );
+                    return Err(async_graphql::Error::new(format!(
+                        "Pipeline stages of entity {} must be JSON objects.",
+                        entity.name
+                    )));
+                }
+            }
+        }
+
+        let variables = entity
+            .data_source
+            .as_ref()
+            .and_then(|data_source| data_source.pipeline_variables.clone())
+            .unwrap_or_default();
+        if !variables.is_empty() {
+            let mut guard_context = Guard::create_guard_context(
+                headers.clone(),
+                token_data.clone(),
+                input.clone(),
+                resolver_type.to_string(),
+                None,
+                None,
+                subgraph_config.clone(),
+            )?;
+            let mut values = Vec::new();
+            for VariablePair(key, expression) in variables {
+                let value = eval_with_context_mut(&expression, &mut guard_context)?;
+                let value = MongoDataSource::eval_value_to_bson(&value).ok_or_else(|| {
+                    error!("Pipeline variable {} has no value.", key);
+                    async_graphql::Error::new(format!("Pipeline variable {} has no value.", key))
+                })?;
+                values.push((key, value));
+            }
+            for stage in base_pipeline.iter_mut() {
+                for (_, value) in stage.iter_mut() {
+                    MongoDataSource::replace_pipeline_variables(value, &values);
+                }
+            }
+        }
+
+        trace!("Base Pipeline: {:?}", base_pipeline);
+        Ok(base_pipeline)
+    }
+
+    /// Converts an evaluated pipeline variable to BSON. Empty values return `None`.
+    fn eval_value_to_bson(value: &Value) -> Option<Bson> {
+        match value {
+            Value::String(value) => Some(Bson::String(value.clone())),
+            Value::Int(value) => Some(Bson::Int64(*value)),
+            Value::Float(value) => Some(Bson::Double(*value)),
+            Value::Boolean(value) => Some(Bson::Boolean(*value)),
+            Value::Tuple(values) => values
+                .iter()
+                .map(MongoDataSource::eval_value_to_bson)
+                .collect::<Option<Vec<Bson>>>()
+                .map(Bson::Array),
+            Value::Empty => None,
+        }
+    }
+
+    /// Replaces, recursively, the string values of the pipeline equal to a variable with the
+    /// value of the variable. Strings containing a variable within other text are left as is.
+    fn replace_pipeline_variables(value: &mut Bson, values: &[(String, Bson)]) {
+        match value {
+            Bson::String(string) => {
+                if let Some((_, replacement)) = values.iter().find(|(key, _)| key == string) {
+                    *value = replacement.clone();
+                }
+            }
+            Bson::Document(document) => {
+                for (_, value) in document.iter_mut() {
+                    MongoDataSource::replace_pipeline_variables(value, values);
+                }
+            }
+            Bson::Array(array) => {
+                for value in array.iter_mut() {
+                    MongoDataSource::replace_pipeline_variables(value, values);
+                }
+            }
+            _ => {}
+        }
+    }
+}
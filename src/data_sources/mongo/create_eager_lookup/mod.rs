@@ -0,0 +1,45 @@
+use bson::{doc, Document};
+use log::{debug, trace};
+
+use crate::data_sources::mongo::{EagerLoadOptions, MongoDataSource};
+
+impl MongoDataSource {
+    /// Creates the `$lookup` and `$unwind` stages of an eager loaded field.
+    /// The eager loads of the joined entity are chained within the pipeline of the lookup,
+    /// followed by a `$match` of the filters on the joined entity.
+    pub fn create_eager_lookup(eager_load_option: EagerLoadOptions) -> Vec<Document> {
+        debug!("Creating Eager Lookup: {}", eager_load_option.as_field);
+
+        let mut lookup = doc! {
+            "from": eager_load_option.from,
+            "localField": eager_load_option.local_field,
+            "foreignField": eager_load_option.foreign_field,
+            "as": eager_load_option.as_field.clone(),
+        };
+
+        let mut pipeline = vec![];
+        for nested_eager_load_option in eager_load_option.nested {
+            pipeline.extend(MongoDataSource::create_eager_lookup(
+                nested_eager_load_option,
+            ));
+        }
+        if !eager_load_option.filter.is_empty() {
+            pipeline.push(doc! { "$match": eager_load_option.filter });
+        }
+        if !pipeline.is_empty() {
+            lookup.insert("pipeline", pipeline);
+        }
+
+        let stages = vec![
+            doc! { "$lookup": lookup },
+            doc! {
+                "$unwind": {
+                    "path": format!("${}", eager_load_option.as_field),
+                    "preserveNullAndEmptyArrays": true,
+                }
+            },
+        ];
+        trace!("Eager Lookup: {:?}", stages);
+        stages
+    }
+}
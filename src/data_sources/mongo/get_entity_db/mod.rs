@@ -0,0 +1,48 @@
+use log::{debug, trace};
+use mongodb::{options::DatabaseOptions, Database};
+
+use crate::{
+    configuration::subgraph::entities::ServiceEntityConfig, data_sources::mongo::MongoDataSource,
+};
+
+impl MongoDataSource {
+    /// Gets the database used for the operations of an entity, applying the read preference,
+    /// read concern and write concern of the entity. Options which are not set on the entity are
+    /// inherited from the data source.
+    pub fn get_entity_db(&self, entity: &ServiceEntityConfig) -> Database {
+        let entity_data_source = match ServiceEntityConfig::get_entity_data_source(entity) {
+            Some(entity_data_source) => entity_data_source,
+            None => return self.db.clone(),
+        };
+
+        if entity_data_source.read_preference.is_none()
+            && entity_data_source.read_concern.is_none()
+            && entity_data_source.write_concern.is_none()
+        {
+            return self.db.clone();
+        }
+        debug!("Getting Database With Options Of Entity: {}", entity.name);
+
+        let selection_criteria = entity_data_source
+            .read_preference
+            .as_ref()
+            .map(|read_preference| read_preference.to_selection_criteria());
+        let read_concern = entity_data_source
+            .read_concern
+            .as_ref()
+            .map(|read_concern| read_concern.to_read_concern());
+        let write_concern = entity_data_source
+            .write_concern
+            .as_ref()
+            .map(|write_concern| write_concern.to_write_concern());
+
+        let options = DatabaseOptions::builder()
+            .selection_criteria(selection_criteria)
+            .read_concern(read_concern)
+            .write_concern(write_concern)
+            .build();
+        trace!("Database Options: {:?}", options);
+
+        self.client.database_with_options(&self.config.db, options)
+    }
+}
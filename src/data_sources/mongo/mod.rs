@@ -1,7 +1,9 @@
 use async_graphql::dynamic::FieldValue;
 use bson::{doc, to_document, Document};
+use http::HeaderMap;
 use log::{debug, error, trace, warn};
 use mongodb::{options::ClientOptions, Client, Database};
+use std::time::Duration;
 
 use crate::{
     configuration::subgraph::{
@@ -12,7 +14,10 @@ use crate::{
     filter_operator::FilterOperator,
     graphql::{
         entity::create_return_types::{ResolverResponse, ResolverResponseMeta},
-        schema::create_options_input::{DirectionEnum, OptionsInput},
+        schema::{
+            create_auth_service::TokenData,
+            create_options_input::{DirectionEnum, OptionsInput},
+        },
     },
     resolver_type::ResolverType,
     scalar_option::{to_mongo::MongoValue, ScalarOption},
@@ -20,7 +25,11 @@ use crate::{
 
 use super::DataSource;
 
+pub mod create_base_pipeline;
+pub mod create_eager_lookup;
+pub mod get_entity_db;
 pub mod services;
+pub mod sync_indexes;
 
 #[derive(Debug, Clone)]
 pub struct MongoDataSource {
@@ -35,15 +44,44 @@ pub struct EagerLoadOptions {
     pub local_field: String,
     pub foreign_field: String,
     pub as_field: String,
+    /// Filters on the fields of the joined entity, matched within the lookup.
+    pub filter: Document,
+    /// Eager loads of the joined entity, chained within the lookup.
+    pub nested: Vec<EagerLoadOptions>,
 }
 
 impl MongoDataSource {
-    pub async fn init(mongo_data_source_config: &MongoDataSourceConfig) -> DataSource {
+    pub async fn init(
+        mongo_data_source_config: &MongoDataSourceConfig,
+        subgraph_config: &SubGraphConfig,
+    ) -> DataSource {
         debug!("Initializing Mongo");
-        let client_options = ClientOptions::parse(&mongo_data_source_config.uri)
+        let mut client_options = ClientOptions::parse(&mongo_data_source_config.uri)
             .await
             .expect("Failed to parse mongo client options.");
 
+        // Options of the config take precedence over those of the uri.
+        if let Some(read_preference) = &mongo_data_source_config.read_preference {
+            client_options.selection_criteria = Some(read_preference.to_selection_criteria());
+        }
+        if let Some(read_concern) = &mongo_data_source_config.read_concern {
+            client_options.read_concern = Some(read_concern.to_read_concern());
+        }
+        if let Some(write_concern) = &mongo_data_source_config.write_concern {
+            client_options.write_concern = Some(write_concern.to_write_concern());
+        }
+        if let Some(max_pool_size) = mongo_data_source_config.max_pool_size {
+            client_options.max_pool_size = Some(max_pool_size);
+        }
+        if let Some(server_selection_timeout) = mongo_data_source_config.server_selection_timeout {
+            client_options.server_selection_timeout =
+                Some(Duration::from_millis(server_selection_timeout));
+        }
+        if let Some(app_name) = &mongo_data_source_config.app_name {
+            client_options.app_name = Some(app_name.clone());
+        }
+        trace!("Client Options: {:?}", client_options);
+
         let client = Client::with_options(client_options).expect("Failed to create client");
         let db = client.database(&mongo_data_source_config.db);
 
@@ -51,6 +89,15 @@ impl MongoDataSource {
         debug!("{:?}", client);
         debug!("{:?}", db);
 
+        if let Err(e) =
+            MongoDataSource::sync_indexes(&db, mongo_data_source_config, subgraph_config).await
+        {
+            error!(
+                "Failed to sync indexes of data source {}: {}",
+                mongo_data_source_config.name, e.message
+            );
+        }
+
         DataSource::Mongo(MongoDataSource {
             client,
             db,
@@ -207,7 +254,7 @@ impl MongoDataSource {
                         }
                     };
 
-                if let Some((eager_load_option, eager_entity)) = eager_load_options {
+                if let Some((mut eager_load_option, eager_entity)) = eager_load_options {
                     // Send through recursive function to convert the object id string to object id
                     // for eager loaded fields.
                     let (value, nested_eager_opts) =
@@ -227,7 +274,19 @@ impl MongoDataSource {
                                 return Err(e);
                             }
                         };
-                    combined_eager_options.extend(nested_eager_opts);
+                    // Eager loads of the joined entity follow the chain, within its lookup.
+                    eager_load_option.nested = nested_eager_opts;
+                    let joined_filter = value
+                        .iter()
+                        .filter(|(key, _)| {
+                            !FilterOperator::list()
+                                .iter()
+                                .any(|operator| operator.as_str() == key.as_str())
+                        })
+                        .map(|(key, value)| (key.clone(), value.clone()))
+                        .collect::<Document>();
+                    eager_load_option.filter =
+                        services::Services::create_nested_find_filter(&joined_filter);
 
                     // replace the key with the eager load key.
                     converted.remove(&k);
@@ -309,6 +368,8 @@ impl MongoDataSource {
             local_field: join_from,
             foreign_field: join_on,
             as_field: eager_key.clone(),
+            filter: Document::new(),
+            nested: vec![],
         };
 
         trace!("Eager load options: {:?}", eager_load_options);
@@ -423,33 +484,22 @@ impl MongoDataSource {
         Ok((finalized, eager_filters))
     }
 
+    /// Creates the aggregation of find resolvers. The stages of `base_pipeline` come first, so
+    /// filters, sorting and pagination apply to its output.
     pub fn create_aggregation(
         query_doc: &Document,
+        base_pipeline: Vec<Document>,
         eager_load_options: Vec<EagerLoadOptions>,
         opts_doc: Option<OptionsInput>,
     ) -> Result<Vec<Document>, async_graphql::Error> {
         debug!("Creating Aggregation");
         trace!("Query Doc: {:?}", query_doc);
+        trace!("Base Pipeline: {:?}", base_pipeline);
         trace!("Eager Load Options: {:?}", eager_load_options);
         trace!("Opts Doc: {:?}", opts_doc);
-        let mut pipeline = Vec::new();
+        let mut pipeline = base_pipeline;
         for eager_load_option in eager_load_options {
-            let lookup = doc! {
-                "$lookup": {
-                    "from": eager_load_option.from,
-                    "localField": eager_load_option.local_field,
-                    "foreignField": eager_load_option.foreign_field,
-                    "as": eager_load_option.as_field.clone(),
-                }
-            };
-            pipeline.push(lookup);
-            let unwind = doc! {
-                "$unwind": {
-                    "path": format!("${}", eager_load_option.as_field),
-                    "preserveNullAndEmptyArrays": true,
-                }
-            };
-            pipeline.push(unwind);
+            pipeline.extend(MongoDataSource::create_eager_lookup(eager_load_option));
         }
 
         let match_doc = doc! {
@@ -536,16 +586,27 @@ impl MongoDataSource {
         entity: ServiceEntityConfig,
         resolver_type: ResolverType,
         subgraph_config: &SubGraphConfig,
+        headers: &HeaderMap,
+        token_data: &Option<TokenData>,
     ) -> Result<Option<FieldValue<'a>>, async_graphql::Error> {
         debug!("Executing Operation - Mongo Data Source");
         trace!("Input: {:?}", input);
 
+        let base_pipeline = MongoDataSource::create_base_pipeline(
+            &entity,
+            &input,
+            &resolver_type,
+            headers,
+            token_data,
+            subgraph_config,
+        )?;
+
         let eager_load_options;
         (input, eager_load_options) =
             MongoDataSource::finalize_input(input, &entity, subgraph_config, &resolver_type)?;
 
         let db = match data_source {
-            DataSource::Mongo(ds) => ds.db.clone(),
+            DataSource::Mongo(ds) => ds.get_entity_db(&entity),
             _ => unreachable!(),
         };
 
@@ -558,9 +619,14 @@ impl MongoDataSource {
 
         match resolver_type {
             ResolverType::FindOne => {
-                let result =
-                    services::Services::find_one(db, input, collection_name, eager_load_options)
-                        .await?;
+                let result = services::Services::find_one(
+                    db,
+                    input,
+                    collection_name,
+                    base_pipeline,
+                    eager_load_options,
+                )
+                .await?;
                 let res = ResolverResponse {
                     data: vec![FieldValue::owned_any(result)],
                     meta: ResolverResponseMeta {
@@ -583,6 +649,7 @@ impl MongoDataSource {
                     db,
                     input.clone(),
                     collection_name,
+                    base_pipeline,
                     eager_load_options,
                 )
                 .await?;
@@ -28,7 +28,7 @@ impl Services {
         let insert_one_result = coll
             .insert_one(values_input_doc, None)
             .await
-            .expect("Failed to create document.");
+            .map_err(Services::map_write_error)?;
 
         let document = coll
             .find_one(doc! {"_id": insert_one_result.inserted_id }, None)
@@ -0,0 +1,110 @@
+use bson::{doc, to_document, Bson, Document};
+use log::{debug, error, trace};
+
+use crate::{
+    configuration::subgraph::entities::ServiceEntityConfig, scalar_option::to_mongo::MongoValue,
+    update_operator::UpdateOperator,
+};
+
+use super::Services;
+
+impl Services {
+    /// Creates the update document of update resolvers from the `values` and `operators` of the
+    /// input. Values are set, while each operator is compiled to its mongo update operator.
+    pub fn create_update_document(
+        input: &Document,
+        entity: &ServiceEntityConfig,
+    ) -> Result<Document, async_graphql::Error> {
+        debug!("Creating Update Document");
+        trace!("Input: {:?}", input);
+
+        let mut update_doc = Document::new();
+
+        if let Some(values) = input.get("values") {
+            let values = to_document(values)?;
+            let set_doc = Services::create_nested_fields(&values);
+            if !set_doc.is_empty() {
+                update_doc.insert("$set", set_doc);
+            }
+        }
+
+        let operators = match input.get_document("operators") {
+            Ok(operators) => operators.clone(),
+            Err(_) => Document::new(),
+        };
+
+        for operator in UpdateOperator::list() {
+            let operator_doc = match operators.get_document(operator.as_str()) {
+                Ok(operator_doc) => operator_doc,
+                Err(_) => continue,
+            };
+
+            let mut compiled = Document::new();
+            for (field_name, value) in operator_doc.iter() {
+                if value == &Bson::Null {
+                    continue;
+                }
+                match operator {
+                    UpdateOperator::Inc => {
+                        compiled.insert(field_name.clone(), value.clone());
+                    }
+                    UpdateOperator::Push | UpdateOperator::AddToSet | UpdateOperator::Pull => {
+                        let values = Services::get_operator_values(entity, field_name, value)?;
+                        if values.is_empty() {
+                            continue;
+                        }
+                        let modifier = if operator == UpdateOperator::Pull {
+                            "$in"
+                        } else {
+                            "$each"
+                        };
+                        compiled.insert(field_name.clone(), doc! { modifier: values });
+                    }
+                    UpdateOperator::Unset => {
+                        if value.as_bool().unwrap_or(false) {
+                            compiled.insert(field_name.clone(), "");
+                        }
+                    }
+                }
+            }
+
+            if !compiled.is_empty() {
+                update_doc.insert(operator.get_mongo_operator(), compiled);
+            }
+        }
+
+        if update_doc.is_empty() {
+            error!("No values or operators provided to update.");
+            return Err(async_graphql::Error::new(
+                "No values or operators provided to update.",
+            ));
+        }
+
+        trace!("Update Document: {:?}", update_doc);
+        Ok(update_doc)
+    }
+
+    /// Converts the values of a list operator to the values stored for the field.
+    fn get_operator_values(
+        entity: &ServiceEntityConfig,
+        field_name: &str,
+        value: &Bson,
+    ) -> Result<Vec<Bson>, async_graphql::Error> {
+        let field = ServiceEntityConfig::get_field(entity.clone(), field_name.to_string())?;
+        let values = match value.as_array() {
+            Some(values) => values.clone(),
+            None => vec![value.clone()],
+        };
+
+        let mut converted = Vec::new();
+        for value in values {
+            let value = match field.scalar.bson_to_mongo_value(&value)? {
+                Some(MongoValue::ObjectID(object_id)) => Bson::ObjectId(object_id),
+                Some(MongoValue::DateTime(date_time)) => Bson::from(date_time),
+                _ => value,
+            };
+            converted.push(value);
+        }
+        Ok(converted)
+    }
+}
@@ -1,7 +1,7 @@
 use async_graphql::{futures_util::StreamExt, Error, ErrorExtensions};
 use bson::{doc, Document};
 use log::{debug, error, trace};
-use mongodb::Database;
+use mongodb::{Collection, Database};
 
 use crate::{
     data_sources::mongo::{EagerLoadOptions, MongoDataSource},
@@ -15,6 +15,7 @@ impl Services {
         db: Database,
         filter: Document,
         collection: String,
+        base_pipeline: Vec<Document>,
         eager_load_options: Vec<EagerLoadOptions>,
     ) -> Result<(Vec<Option<Document>>, i64), async_graphql::Error> {
         let coll = db.collection::<Document>(&collection);
@@ -60,9 +61,22 @@ impl Services {
             }
         };
 
-        let aggregation =
-            MongoDataSource::create_aggregation(&nested_find_filter, eager_load_options, opts)?;
+        let aggregation = MongoDataSource::create_aggregation(
+            &nested_find_filter,
+            base_pipeline,
+            eager_load_options,
+            opts,
+        )?;
 
+        Services::aggregate_documents(coll, aggregation).await
+    }
+
+    /// Runs an aggregation ending with the `$facet` stage of `create_aggregation`, returning the
+    /// documents and the total count.
+    pub(super) async fn aggregate_documents(
+        coll: Collection<Document>,
+        aggregation: Vec<Document>,
+    ) -> Result<(Vec<Option<Document>>, i64), async_graphql::Error> {
         let mut cursor = coll.aggregate(aggregation, None).await?;
 
         trace!("Find Many Cursor: {:?}", cursor);
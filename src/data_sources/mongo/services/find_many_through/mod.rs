@@ -0,0 +1,93 @@
+use async_graphql::Error;
+use bson::{doc, Bson, Document};
+use log::{debug, trace};
+use mongodb::Database;
+
+use crate::{
+    configuration::subgraph::entities::service_entity_field::join_through::JOIN_THROUGH_KEY,
+    data_sources::mongo::{EagerLoadOptions, MongoDataSource},
+    graphql::schema::create_options_input::OptionsInput,
+};
+
+use super::Services;
+
+/// The collections and fields used to look up documents through a junction collection.
+#[derive(Debug, Clone)]
+pub struct JoinThroughLookup {
+    /// The junction collection.
+    pub junction: String,
+    /// The field of the junction holding the key of the parent.
+    pub junction_from: String,
+    /// The field of the junction holding the key of the joined document.
+    pub junction_on: String,
+    /// The collection of the joined documents.
+    pub from: String,
+    /// The field of the joined documents matching `junction_on`.
+    pub foreign_field: String,
+}
+
+impl Services {
+    /// Finds the documents joined to the parents with the provided keys through a junction
+    /// collection. Each document is returned once per parent, with the key of the parent set
+    /// as `__join_through_key`.
+    pub async fn find_many_through(
+        db: Database,
+        filter: Document,
+        lookup: JoinThroughLookup,
+        parent_keys: Vec<Bson>,
+        eager_load_options: Vec<EagerLoadOptions>,
+    ) -> Result<(Vec<Option<Document>>, i64), async_graphql::Error> {
+        debug!("Find Many Through: {:?}", lookup);
+
+        let query_doc = match filter.get("query").and_then(|query| query.as_document()) {
+            Some(query_doc) => query_doc.clone(),
+            None => return Err(Error::new("Query filter not found")),
+        };
+        let nested_find_filter = Services::create_nested_find_filter(&query_doc);
+
+        let mut opts = doc! { "per_page": -1, "page": 1 };
+        if let Some(sort) = filter
+            .get_document("opts")
+            .ok()
+            .and_then(|opts| opts.get("sort"))
+        {
+            opts.insert("sort", sort.clone());
+        }
+        let opts: Option<OptionsInput> = bson::from_document(opts)
+            .map_err(|_| Error::new("Failed to convert opts to OptionsInput."))?;
+
+        let joined_field = format!("{}_documents", JOIN_THROUGH_KEY);
+        let mut aggregation = vec![
+            doc! { "$match": { lookup.junction_from.clone(): { "$in": parent_keys } } },
+            doc! {
+                "$lookup": {
+                    "from": lookup.from,
+                    "localField": lookup.junction_on,
+                    "foreignField": lookup.foreign_field,
+                    "as": joined_field.clone(),
+                }
+            },
+            doc! { "$unwind": format!("${}", joined_field) },
+            doc! {
+                "$replaceRoot": {
+                    "newRoot": {
+                        "$mergeObjects": [
+                            format!("${}", joined_field),
+                            { JOIN_THROUGH_KEY: format!("${}", lookup.junction_from) },
+                        ]
+                    }
+                }
+            },
+        ];
+        aggregation.extend(MongoDataSource::create_aggregation(
+            &nested_find_filter,
+            vec![],
+            eager_load_options,
+            opts,
+        )?);
+        trace!("Find Many Through Aggregation: {:?}", aggregation);
+
+        let coll = db.collection::<Document>(&lookup.junction);
+        Services::aggregate_documents(coll, aggregation).await
+    }
+}
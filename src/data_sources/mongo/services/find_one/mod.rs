@@ -17,6 +17,7 @@ impl Services {
         db: Database,
         filter: Document,
         collection: String,
+        base_pipeline: Vec<Document>,
         eager_load_options: Vec<EagerLoadOptions>,
     ) -> Result<Option<Document>, async_graphql::Error> {
         debug!("Executing Find One - Mongo Data Source: {:?}", collection);
@@ -36,6 +37,24 @@ impl Services {
 
         let filter = Services::create_nested_find_filter(query_document);
 
+        // Documents of a pipeline entity are the output of the pipeline, not of the collection.
+        if !base_pipeline.is_empty() {
+            let opts = OptionsInput {
+                per_page: Some(1),
+                page: Some(1),
+                sort: None,
+            };
+            let aggregation = MongoDataSource::create_aggregation(
+                &filter,
+                base_pipeline,
+                eager_load_options,
+                Some(opts),
+            )?;
+            let (documents, _total_count) =
+                Services::aggregate_documents(collection, aggregation).await?;
+            return Ok(documents.into_iter().flatten().next());
+        }
+
         if let Some(opts_doc) = filter.get("opts") {
             let opts = match opts_doc.as_document() {
                 Some(opts) => opts.clone(),
@@ -56,8 +75,12 @@ impl Services {
             // Serialize the opts document to a OptionsInput
             let opts: OptionsInput = bson::from_bson(bson::Bson::Document(opts))?;
 
-            let aggregation =
-                MongoDataSource::create_aggregation(&filter, eager_load_options, Some(opts))?;
+            let aggregation = MongoDataSource::create_aggregation(
+                &filter,
+                base_pipeline,
+                eager_load_options,
+                Some(opts),
+            )?;
 
             // let document = collection.find_one(filter, None).await?;
             let mut cursor = collection.aggregate(aggregation, None).await?;
@@ -0,0 +1,57 @@
+use async_graphql::ErrorExtensions;
+use log::error;
+use mongodb::error::{ErrorKind, WriteFailure};
+
+use super::Services;
+
+/// Mongo reports writes violating a unique index with this code.
+const DUPLICATE_KEY: i32 = 11000;
+
+impl Services {
+    /// Converts the error of a write to a GraphQL error. Unique index violations are reported
+    /// with the `DUPLICATE_KEY` code and the name of the index.
+    pub fn map_write_error(e: mongodb::error::Error) -> async_graphql::Error {
+        let duplicate_message = match e.kind.as_ref() {
+            ErrorKind::Write(WriteFailure::WriteError(write_error))
+                if write_error.code == DUPLICATE_KEY =>
+            {
+                Some(write_error.message.clone())
+            }
+            ErrorKind::Command(command_error) if command_error.code == DUPLICATE_KEY => {
+                Some(command_error.message.clone())
+            }
+            ErrorKind::BulkWrite(bulk_write_failure) => bulk_write_failure
+                .write_errors
+                .as_ref()
+                .and_then(|write_errors| {
+                    write_errors
+                        .iter()
+                        .find(|write_error| write_error.code == DUPLICATE_KEY)
+                })
+                .map(|write_error| write_error.message.clone()),
+            _ => None,
+        };
+
+        let message = match duplicate_message {
+            Some(message) => message,
+            None => {
+                error!("Mongo Write Error: {}", e);
+                return async_graphql::Error::new(e.to_string());
+            }
+        };
+
+        // The message names the index, such as `... index: email_1 dup key: { email: "a" }`.
+        let index = message
+            .split(" index: ")
+            .nth(1)
+            .and_then(|rest| rest.split_whitespace().next())
+            .unwrap_or_default()
+            .to_string();
+        error!("Duplicate value for unique index {}: {}", index, message);
+        async_graphql::Error::new(format!("Duplicate value for unique index {}.", index))
+            .extend_with(|_, e| {
+                e.set("code", "DUPLICATE_KEY");
+                e.set("index", index.clone());
+            })
+    }
+}
@@ -4,11 +4,17 @@ use log::{debug, trace};
 use crate::filter_operator::FilterOperator;
 
 mod create_one;
+mod create_update_document;
 mod find_many;
+mod find_many_through;
 mod find_one;
+mod map_write_error;
+mod update_join_through;
 mod update_many;
 mod update_one;
 
+pub use find_many_through::JoinThroughLookup;
+
 #[derive(Debug)]
 pub struct Services;
 
@@ -0,0 +1,63 @@
+use bson::{doc, Bson, Document};
+use log::{debug, trace};
+use mongodb::Database;
+
+use crate::configuration::subgraph::entities::service_entity_field::join_through::JoinThrough;
+
+use super::Services;
+
+impl Services {
+    /// Connects and disconnects the joined keys from the parents by inserting and deleting
+    /// documents of the junction collection. Existing documents of connected keys are replaced,
+    /// so connecting a key twice does not duplicate it.
+    pub async fn update_join_through(
+        db: Database,
+        collection: String,
+        join_through: &JoinThrough,
+        parent_keys: &[Bson],
+        connect: &[Bson],
+        disconnect: &[Bson],
+    ) -> Result<(), async_graphql::Error> {
+        debug!("Updating Join Through: {}", collection);
+
+        if parent_keys.is_empty() || (connect.is_empty() && disconnect.is_empty()) {
+            trace!("No Join Through Keys To Update.");
+            return Ok(());
+        }
+
+        let coll = db.collection::<Document>(&collection);
+        let keys = connect
+            .iter()
+            .chain(disconnect.iter())
+            .cloned()
+            .collect::<Vec<Bson>>();
+        let filter = doc! {
+            join_through.join_from.clone(): { "$in": parent_keys },
+            join_through.join_on.clone(): { "$in": keys },
+        };
+        trace!("Join Through Delete Filter: {:?}", filter);
+        coll.delete_many(filter, None)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        if connect.is_empty() {
+            return Ok(());
+        }
+
+        let mut documents = Vec::new();
+        for parent_key in parent_keys {
+            for key in connect {
+                documents.push(doc! {
+                    join_through.join_from.clone(): parent_key.clone(),
+                    join_through.join_on.clone(): key.clone(),
+                });
+            }
+        }
+        trace!("Join Through Documents: {:?}", documents);
+        coll.insert_many(documents, None)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(())
+    }
+}
@@ -19,9 +19,7 @@ impl Services {
         let coll = db.collection::<Document>(&collection);
 
         let filter = to_document(input.get("query").unwrap()).unwrap();
-        let values = to_document(input.get("values").unwrap()).unwrap();
-
-        let update_doc = Services::create_nested_fields(&values);
+        let update_doc = Services::create_update_document(&input, entity)?;
 
         let mut cursor = coll
             .find(filter.clone(), None)
@@ -45,9 +43,9 @@ impl Services {
 
         let ids_doc = doc! {"_id": {"$in": primary_keys}};
 
-        coll.update_many(ids_doc.clone(), doc! {"$set": update_doc}, None)
+        coll.update_many(ids_doc.clone(), update_doc, None)
             .await
-            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+            .map_err(Services::map_write_error)?;
 
         let mut cursor = coll
             .find(ids_doc.clone(), None)
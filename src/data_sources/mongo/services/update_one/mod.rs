@@ -28,9 +28,7 @@ impl Services {
             .upsert(true)
             .build();
 
-        let values = to_document(input.get("values").unwrap())?;
-
-        let update_doc = Services::create_nested_fields(&values);
+        let update_doc = Services::create_update_document(&input, entity)?;
 
         let mut cursor = coll.find(filter.clone(), None).await.map_err(|e| {
             async_graphql::Error::new(format!(
@@ -70,9 +68,9 @@ impl Services {
         let filter = doc! {primary_key_field.name: primary_key};
 
         let document = coll
-            .find_one_and_update(filter, doc! {"$set": update_doc}, options)
+            .find_one_and_update(filter, update_doc, options)
             .await
-            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+            .map_err(Services::map_write_error)?;
 
         debug!("Update One Result: {:?}", document);
 
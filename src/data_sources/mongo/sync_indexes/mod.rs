@@ -0,0 +1,312 @@
+use std::{collections::HashMap, time::Duration};
+
+use async_graphql::futures_util::StreamExt;
+use bson::{Bson, Document};
+use log::{debug, error, info, trace, warn};
+use mongodb::{options::IndexOptions, Database, IndexModel};
+
+use crate::{
+    configuration::subgraph::{
+        data_sources::{
+            mongo::{MongoDataSourceConfig, MongoIndexSync},
+            ServiceDataSourceConfig,
+        },
+        entities::ServiceEntityConfig,
+        SubGraphConfig,
+    },
+    data_sources::mongo::MongoDataSource,
+};
+
+/// Mongo reports a missing collection with this code when listing its indexes.
+const NAMESPACE_NOT_FOUND: i32 = 26;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum IndexChangeAction {
+    Create,
+    Recreate,
+    Drop,
+    /// The index exists but is not configured. Only dropped when pruning.
+    Unmanaged,
+}
+
+/// A change needed for the indexes of a collection to match the configuration.
+#[derive(Debug, Clone)]
+pub struct IndexChange {
+    pub collection: String,
+    pub name: String,
+    pub action: IndexChangeAction,
+    pub model: Option<IndexModel>,
+}
+
+impl MongoDataSource {
+    /// Reconciles the indexes of the entities stored by the data source and returns the changes.
+    /// With `DryRun`, the changes are logged without being applied.
+    pub async fn sync_indexes(
+        db: &Database,
+        mongo_data_source_config: &MongoDataSourceConfig,
+        subgraph_config: &SubGraphConfig,
+    ) -> Result<Vec<IndexChange>, async_graphql::Error> {
+        let index_sync = mongo_data_source_config
+            .index_sync
+            .clone()
+            .unwrap_or_default();
+        if index_sync == MongoIndexSync::Skip {
+            return Ok(vec![]);
+        }
+        debug!("Syncing Mongo Indexes: {}", mongo_data_source_config.name);
+
+        let mut changes = Vec::new();
+        for (collection, desired) in
+            MongoDataSource::get_collection_indexes(mongo_data_source_config, subgraph_config)
+        {
+            let existing = MongoDataSource::list_indexes(db, &collection).await?;
+            changes.extend(MongoDataSource::plan_index_changes(
+                &collection,
+                &existing,
+                &desired,
+                index_sync == MongoIndexSync::Prune,
+            ));
+        }
+
+        for change in changes.iter() {
+            let message = format!(
+                "Index {:?}: {}.{}",
+                change.action, change.collection, change.name
+            );
+            match (&index_sync, &change.action) {
+                (_, IndexChangeAction::Unmanaged) => warn!("{}", message),
+                (MongoIndexSync::DryRun, _) => info!("Dry Run, {}", message),
+                _ => info!("{}", message),
+            }
+        }
+
+        if index_sync == MongoIndexSync::DryRun {
+            return Ok(changes);
+        }
+
+        for change in changes.iter() {
+            let collection = db.collection::<Document>(&change.collection);
+            if matches!(
+                change.action,
+                IndexChangeAction::Recreate | IndexChangeAction::Drop
+            ) {
+                collection.drop_index(&change.name, None).await?;
+            }
+            if let (IndexChangeAction::Create | IndexChangeAction::Recreate, Some(model)) =
+                (&change.action, &change.model)
+            {
+                if let Err(e) = collection.create_index(model.clone(), None).await {
+                    error!(
+                        "Failed to create index {}.{}: {}",
+                        change.collection, change.name, e
+                    );
+                    return Err(async_graphql::Error::new(format!(
+                        "Failed to create index {}.{}: {}",
+                        change.collection, change.name, e
+                    )));
+                }
+            }
+        }
+
+        Ok(changes)
+    }
+
+    /// Groups the configured indexes of the entities of the data source by collection. Entities
+    /// without a `from` are stored by the first data source, as resolved by
+    /// `DataSources::get_entity_data_soruce`.
+    pub fn get_collection_indexes(
+        mongo_data_source_config: &MongoDataSourceConfig,
+        subgraph_config: &SubGraphConfig,
+    ) -> HashMap<String, Vec<IndexModel>> {
+        let is_first_data_source = match subgraph_config.service.data_sources.first() {
+            Some(ServiceDataSourceConfig::Mongo(first)) => {
+                first.name == mongo_data_source_config.name
+            }
+            _ => false,
+        };
+
+        let mut collection_indexes: HashMap<String, Vec<IndexModel>> = HashMap::new();
+        for entity in subgraph_config.service.entities.iter() {
+            let data_source = match &entity.data_source {
+                Some(data_source) => data_source,
+                None => continue,
+            };
+            let is_stored = match &data_source.from {
+                Some(from) => from == &mongo_data_source_config.name,
+                None => is_first_data_source,
+            };
+            let indexes = match &data_source.indexes {
+                Some(indexes) if is_stored => indexes,
+                _ => continue,
+            };
+
+            let collection = ServiceEntityConfig::get_mongo_collection_name(entity);
+            let models = collection_indexes.entry(collection).or_default();
+            for index in indexes {
+                let name = index.get_name();
+                if models
+                    .iter()
+                    .any(|model| MongoDataSource::get_index_name(model) == name)
+                {
+                    continue;
+                }
+
+                let mut keys = Document::new();
+                for key in index.keys.iter() {
+                    keys.insert(
+                        key.field.clone(),
+                        key.kind.clone().unwrap_or_default().to_bson(),
+                    );
+                }
+                let options = IndexOptions::builder()
+                    .name(name)
+                    .unique(index.unique)
+                    .sparse(index.sparse)
+                    .expire_after(index.expire_after.map(Duration::from_secs))
+                    .build();
+                models.push(IndexModel::builder().keys(keys).options(options).build());
+            }
+        }
+        trace!("Collection Indexes: {:?}", collection_indexes);
+        collection_indexes
+    }
+
+    /// Compares the existing indexes of a collection with the configured indexes.
+    /// The default `_id_` index is never changed.
+    pub fn plan_index_changes(
+        collection: &str,
+        existing: &[IndexModel],
+        desired: &[IndexModel],
+        prune: bool,
+    ) -> Vec<IndexChange> {
+        let mut changes = Vec::new();
+        for model in desired {
+            let name = MongoDataSource::get_index_name(model);
+            let action = match existing
+                .iter()
+                .find(|existing| MongoDataSource::get_index_name(existing) == name)
+            {
+                None => IndexChangeAction::Create,
+                Some(existing)
+                    if MongoDataSource::get_index_signature(existing)
+                        != MongoDataSource::get_index_signature(model) =>
+                {
+                    IndexChangeAction::Recreate
+                }
+                Some(_) => continue,
+            };
+            changes.push(IndexChange {
+                collection: collection.to_string(),
+                name,
+                action,
+                model: Some(model.clone()),
+            });
+        }
+
+        for model in existing {
+            let name = MongoDataSource::get_index_name(model);
+            if name == "_id_"
+                || desired
+                    .iter()
+                    .any(|desired| MongoDataSource::get_index_name(desired) == name)
+            {
+                continue;
+            }
+            changes.push(IndexChange {
+                collection: collection.to_string(),
+                name,
+                action: if prune {
+                    IndexChangeAction::Drop
+                } else {
+                    IndexChangeAction::Unmanaged
+                },
+                model: None,
+            });
+        }
+        changes
+    }
+
+    async fn list_indexes(
+        db: &Database,
+        collection: &str,
+    ) -> Result<Vec<IndexModel>, async_graphql::Error> {
+        let mut cursor = match db
+            .collection::<Document>(collection)
+            .list_indexes(None)
+            .await
+        {
+            Ok(cursor) => cursor,
+            Err(e) => match *e.kind {
+                mongodb::error::ErrorKind::Command(ref command_error)
+                    if command_error.code == NAMESPACE_NOT_FOUND =>
+                {
+                    return Ok(vec![]);
+                }
+                _ => return Err(async_graphql::Error::new(e.to_string())),
+            },
+        };
+
+        let mut indexes = Vec::new();
+        while let Some(index) = cursor.next().await {
+            indexes.push(index?);
+        }
+        Ok(indexes)
+    }
+
+    fn get_index_name(model: &IndexModel) -> String {
+        model
+            .options
+            .as_ref()
+            .and_then(|options| options.name.clone())
+            .unwrap_or_default()
+    }
+
+    /// Describes the parts of an index compared when reconciling. Mongo stores text indexes
+    /// as `_fts` and `_ftsx` keys with the fields in `weights`, so text fields are compared by
+    /// name.
+    fn get_index_signature(model: &IndexModel) -> Document {
+        let weights = model
+            .options
+            .as_ref()
+            .and_then(|options| options.weights.clone());
+        let mut text_fields = match &weights {
+            Some(weights) => weights.keys().cloned().collect::<Vec<String>>(),
+            None => model
+                .keys
+                .iter()
+                .filter(|(_, kind)| kind.as_str() == Some("text"))
+                .map(|(field, _)| field.clone())
+                .collect(),
+        };
+        text_fields.sort();
+
+        let mut keys = Document::new();
+        for (field, kind) in model.keys.iter() {
+            if field == "_fts" || kind.as_str() == Some("text") {
+                for text_field in text_fields.iter() {
+                    keys.insert(text_field.clone(), "text");
+                }
+                text_fields.clear();
+                continue;
+            }
+            if field == "_ftsx" {
+                continue;
+            }
+            let kind = match kind {
+                Bson::Int32(direction) => Bson::Int32(*direction),
+                Bson::Int64(direction) => Bson::Int32(*direction as i32),
+                Bson::Double(direction) => Bson::Int32(*direction as i32),
+                kind => kind.clone(),
+            };
+            keys.insert(field.clone(), kind);
+        }
+
+        let options = model.options.clone().unwrap_or_default();
+        bson::doc! {
+            "keys": keys,
+            "unique": options.unique.unwrap_or(false),
+            "sparse": options.sparse.unwrap_or(false),
+            "expire_after": options.expire_after.map(|expire_after| expire_after.as_secs() as i64),
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use log::{debug, error};
+
+use crate::data_sources::DataSourceHealth;
+
+use super::{PoolEnum, SqlDataSource};
+
+impl SqlDataSource {
+    /// Checks that a connection can be acquired and a statement executed on the primary and on
+    /// each replica. Reporting does not change which replicas are read from.
+    pub async fn check_health(&self) -> Vec<DataSourceHealth> {
+        debug!("Checking SQL Data Source Health: {}", self.config.name);
+
+        let mut health = vec![DataSourceHealth {
+            name: self.config.name.clone(),
+            healthy: SqlDataSource::check_pool_health(&self.pool, &self.config.name).await,
+        }];
+
+        let replica_configs = SqlDataSource::get_replica_configs(&self.config);
+        for (pool, replica_config) in self.replica_pools.iter().zip(replica_configs) {
+            let healthy = SqlDataSource::check_pool_health(pool, &replica_config.name).await;
+            health.push(DataSourceHealth {
+                name: replica_config.name,
+                healthy,
+            });
+        }
+
+        health
+    }
+
+    pub async fn check_pool_health(pool: &PoolEnum, name: &str) -> bool {
+        let result = match pool {
+            PoolEnum::MySql(pool) => sqlx::query("SELECT 1").execute(pool).await.map(|_| ()),
+            PoolEnum::Postgres(pool) => sqlx::query("SELECT 1").execute(pool).await.map(|_| ()),
+            PoolEnum::SqLite(pool) => sqlx::query("SELECT 1").execute(pool).await.map(|_| ()),
+        };
+
+        match result {
+            Ok(_) => true,
+            Err(e) => {
+                error!("Data source {} is unhealthy: {}", name, e);
+                false
+            }
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use std::{str::FromStr, time::Duration};
+
+use log::{debug, error, trace, warn};
+use sqlx::{
+    mysql::{MySqlConnectOptions, MySqlPoolOptions},
+    pool::PoolOptions,
+    postgres::{PgConnectOptions, PgPoolOptions},
+    sqlite::{SqliteConnectOptions, SqlitePoolOptions},
+    Database, Executor,
+};
+
+use crate::configuration::subgraph::data_sources::sql::{
+    DialectEnum, SqlDataSourceConfig, SqlPoolConfig,
+};
+
+use super::{PoolEnum, SqlDataSource};
+
+/// Default delay before the first connect retry, doubled after every attempt.
+const DEFAULT_CONNECT_BACKOFF_MS: u64 = 500;
+const DEFAULT_MAX_CONNECTIONS: u32 = 5;
+
+impl SqlDataSource {
+    /// Creates the connection pool for the configured dialect, retrying with backoff when the
+    /// database can not be reached.
+    pub async fn create_pool(
+        sql_data_source_config: &SqlDataSourceConfig,
+    ) -> Result<PoolEnum, async_graphql::Error> {
+        debug!("Creating SQL Pool");
+        let pool_config = sql_data_source_config.pool.clone().unwrap_or_default();
+        let connect_retries = pool_config.connect_retries.unwrap_or(0);
+        let mut backoff = Duration::from_millis(
+            pool_config
+                .connect_backoff
+                .unwrap_or(DEFAULT_CONNECT_BACKOFF_MS),
+        );
+
+        let mut attempt = 0;
+        loop {
+            match SqlDataSource::connect(sql_data_source_config, false).await {
+                Ok(pool) => return Ok(pool),
+                Err(e) => {
+                    if attempt >= connect_retries {
+                        error!(
+                            "Failed to connect to data source {}: {}",
+                            sql_data_source_config.name, e.message
+                        );
+                        return Err(e);
+                    }
+                    attempt += 1;
+                    warn!(
+                        "Failed to connect to data source {}, retrying in {:?} ({}/{}): {}",
+                        sql_data_source_config.name, backoff, attempt, connect_retries, e.message
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+            }
+        }
+    }
+
+    /// Creates a pool that does not connect until it is first used.
+    pub async fn create_lazy_pool(
+        sql_data_source_config: &SqlDataSourceConfig,
+    ) -> Result<PoolEnum, async_graphql::Error> {
+        debug!("Creating Lazy SQL Pool");
+        SqlDataSource::connect(sql_data_source_config, true).await
+    }
+
+    /// Makes a single attempt to create the pool.
+    async fn connect(
+        sql_data_source_config: &SqlDataSourceConfig,
+        lazy: bool,
+    ) -> Result<PoolEnum, async_graphql::Error> {
+        let pool_config = sql_data_source_config.pool.clone().unwrap_or_default();
+
+        let pool = match sql_data_source_config.dialect {
+            DialectEnum::SQLITE => {
+                trace!("Creating SQLite Pool: {:?}", &sql_data_source_config.uri);
+
+                let mut options = SqliteConnectOptions::from_str(&sql_data_source_config.uri)?;
+                if let Some(extensions) = &sql_data_source_config.sqlite_extensions {
+                    trace!("Creating SQLite Pool with Extensions: {:?}", &extensions);
+                    for extension in extensions {
+                        options = options.extension(extension.clone());
+                    }
+                }
+
+                if let Some(capacity) = pool_config.statement_cache_capacity {
+                    options = options.statement_cache_capacity(capacity);
+                }
+
+                let pool_options: SqlitePoolOptions = SqlDataSource::get_pool_options(&pool_config);
+                if lazy {
+                    PoolEnum::SqLite(pool_options.connect_lazy_with(options))
+                } else {
+                    PoolEnum::SqLite(pool_options.connect_with(options).await?)
+                }
+            }
+            DialectEnum::POSTGRES => {
+                trace!("Creating Postgres Pool: {:?}", &sql_data_source_config.uri);
+
+                let mut options = PgConnectOptions::from_str(&sql_data_source_config.uri)?;
+                if let Some(statement_timeout) = pool_config.statement_timeout {
+                    options =
+                        options.options([("statement_timeout", statement_timeout.to_string())]);
+                }
+                if let Some(capacity) = pool_config.statement_cache_capacity {
+                    options = options.statement_cache_capacity(capacity);
+                }
+
+                let pool_options: PgPoolOptions = SqlDataSource::get_pool_options(&pool_config);
+                if lazy {
+                    PoolEnum::Postgres(pool_options.connect_lazy_with(options))
+                } else {
+                    PoolEnum::Postgres(pool_options.connect_with(options).await?)
+                }
+            }
+            DialectEnum::MYSQL => {
+                trace!("Creating MySql Pool: {:?}", &sql_data_source_config.uri);
+
+                let mut options = MySqlConnectOptions::from_str(&sql_data_source_config.uri)?;
+                if let Some(capacity) = pool_config.statement_cache_capacity {
+                    options = options.statement_cache_capacity(capacity);
+                }
+
+                let mut pool_options: MySqlPoolOptions =
+                    SqlDataSource::get_pool_options(&pool_config);
+                if let Some(statement_timeout) = pool_config.statement_timeout {
+                    // MySQL limits the execution time of read only statements.
+                    let statement =
+                        format!("SET SESSION max_execution_time = {}", statement_timeout);
+                    pool_options = pool_options.after_connect(move |conn, _meta| {
+                        let statement = statement.clone();
+                        Box::pin(async move {
+                            conn.execute(statement.as_str()).await?;
+                            Ok(())
+                        })
+                    });
+                }
+
+                if lazy {
+                    PoolEnum::MySql(pool_options.connect_lazy_with(options))
+                } else {
+                    PoolEnum::MySql(pool_options.connect_with(options).await?)
+                }
+            }
+        };
+
+        Ok(pool)
+    }
+
+    /// Applies the pool options shared by every dialect.
+    fn get_pool_options<DB: Database>(pool_config: &SqlPoolConfig) -> PoolOptions<DB> {
+        let mut pool_options = PoolOptions::<DB>::new().max_connections(
+            pool_config
+                .max_connections
+                .unwrap_or(DEFAULT_MAX_CONNECTIONS),
+        );
+
+        if let Some(min_connections) = pool_config.min_connections {
+            pool_options = pool_options.min_connections(min_connections);
+        }
+        if let Some(acquire_timeout) = pool_config.acquire_timeout {
+            pool_options = pool_options.acquire_timeout(Duration::from_millis(acquire_timeout));
+        }
+        if let Some(idle_timeout) = pool_config.idle_timeout {
+            pool_options = pool_options.idle_timeout(Duration::from_millis(idle_timeout));
+        }
+
+        trace!("Pool Options: {:?}", pool_options);
+        pool_options
+    }
+}
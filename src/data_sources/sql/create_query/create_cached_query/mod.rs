@@ -0,0 +1,229 @@
+use bson::{Bson, Document};
+use log::{debug, trace};
+
+use crate::{
+    configuration::subgraph::{entities::ServiceEntityConfig, SubGraphConfig},
+    data_sources::sql::{query_plan_cache::QueryPlan, SqlDataSource, SqlQuery},
+    filter_operator::FilterOperator,
+    resolver_type::ResolverType,
+    update_operator::UpdateOperator,
+};
+
+impl SqlDataSource {
+    /// Creates the query for the input, reusing the SQL generated for earlier inputs of the
+    /// same shape. Only the bound values are parsed when a plan is found.
+    pub fn create_cached_query(
+        &self,
+        input: Document,
+        resolver_type: ResolverType,
+        table_name: &str,
+        entity: &ServiceEntityConfig,
+        subgraph_config: &SubGraphConfig,
+    ) -> Result<SqlQuery, async_graphql::Error> {
+        debug!("Creating Cached SQL Query");
+
+        if !self.query_plan_cache.is_enabled() {
+            return SqlDataSource::create_query(
+                input,
+                resolver_type,
+                table_name,
+                self.config.dialect.clone(),
+                entity,
+                subgraph_config,
+            );
+        }
+
+        let mut key = format!("{}:{:?}:", entity.name, resolver_type);
+        SqlDataSource::get_input_shape(&Bson::Document(input.clone()), true, &mut key);
+        trace!("Query Plan Key: {}", key);
+
+        if let Some(plan) = self.query_plan_cache.get(&key) {
+            debug!("Query Plan Cache Hit");
+            return self.create_query_from_plan(
+                plan,
+                &input,
+                &resolver_type,
+                table_name,
+                entity,
+                subgraph_config,
+            );
+        }
+
+        debug!("Query Plan Cache Miss");
+        let pagination_clause = SqlDataSource::create_pagination_clause(&input);
+        let sql_query = SqlDataSource::create_query(
+            input,
+            resolver_type,
+            table_name,
+            self.config.dialect.clone(),
+            entity,
+            subgraph_config,
+        )?;
+
+        // The pagination of find many queries is added to the plan for each request.
+        let query = match resolver_type {
+            ResolverType::FindMany => sql_query
+                .query
+                .strip_suffix(&format!("{};", pagination_clause))
+                .map(|query| query.to_string()),
+            _ => Some(sql_query.query.clone()),
+        };
+        if let Some(query) = query {
+            self.query_plan_cache.insert(
+                key,
+                QueryPlan {
+                    query,
+                    count_query: sql_query.count_query.clone(),
+                    identifier_query: sql_query.identifier_query.clone(),
+                    schema: sql_query.schema.clone(),
+                },
+            );
+        }
+
+        Ok(sql_query)
+    }
+
+    /// Describes the input without its values. Inputs with the same shape generate the same
+    /// SQL. Sorting options and the columns unset are part of the SQL, so they are kept as is.
+    fn get_input_shape(value: &Bson, is_root: bool, shape: &mut String) {
+        match value {
+            Bson::Document(document) => {
+                shape.push('{');
+                for (key, value) in document {
+                    shape.push_str(key);
+                    shape.push(':');
+                    if is_root && key == "opts" {
+                        SqlDataSource::get_opts_shape(value, shape);
+                    } else if is_root && key == "operators" {
+                        SqlDataSource::get_operators_shape(value, shape);
+                    } else {
+                        SqlDataSource::get_input_shape(value, false, shape);
+                    }
+                    shape.push(',');
+                }
+                shape.push('}');
+            }
+            Bson::Array(array) => {
+                shape.push_str(&format!("[{}:", array.len()));
+                for value in array {
+                    SqlDataSource::get_input_shape(value, false, shape);
+                    shape.push(',');
+                }
+                shape.push(']');
+            }
+            value => shape.push_str(&format!("{:?}", value.element_type())),
+        }
+    }
+
+    /// Describes the options, keeping the sort fields. Pagination is added to the plan for each
+    /// request, so `page` and `per_page` are left out.
+    fn get_opts_shape(opts: &Bson, shape: &mut String) {
+        let opts = match opts.as_document() {
+            Some(opts) => opts,
+            None => return SqlDataSource::get_input_shape(opts, false, shape),
+        };
+        shape.push('{');
+        for (key, value) in opts {
+            if key == "page" || key == "per_page" {
+                continue;
+            }
+            shape.push_str(key);
+            shape.push(':');
+            if key == "sort" {
+                shape.push_str(&value.to_string());
+            } else {
+                SqlDataSource::get_input_shape(value, false, shape);
+            }
+            shape.push(',');
+        }
+        shape.push('}');
+    }
+
+    /// Describes the update operators, keeping whether each column is unset.
+    fn get_operators_shape(operators: &Bson, shape: &mut String) {
+        let operators = match operators.as_document() {
+            Some(operators) => operators,
+            None => return SqlDataSource::get_input_shape(operators, false, shape),
+        };
+        shape.push('{');
+        for (key, value) in operators {
+            shape.push_str(key);
+            shape.push(':');
+            if key == UpdateOperator::Unset.as_str() {
+                shape.push_str(&value.to_string());
+            } else {
+                SqlDataSource::get_input_shape(value, false, shape);
+            }
+            shape.push(',');
+        }
+        shape.push('}');
+    }
+
+    /// Creates the query from a cached plan. The values are parsed with the parsers of
+    /// `create_query`, without generating the SQL. The where values are those of
+    /// `create_nested_query_recursive`.
+    fn create_query_from_plan(
+        &self,
+        plan: QueryPlan,
+        input: &Document,
+        resolver_type: &ResolverType,
+        table_name: &str,
+        entity: &ServiceEntityConfig,
+        subgraph_config: &SubGraphConfig,
+    ) -> Result<SqlQuery, async_graphql::Error> {
+        let (_, mut where_values, value_keys, mut values) = match input.get("values") {
+            Some(values_input) => SqlDataSource::parse_values_input(
+                values_input,
+                vec![],
+                vec![],
+                vec![],
+                vec![],
+                entity,
+                resolver_type,
+                &self.config.dialect,
+            )?,
+            None => (vec![], vec![], vec![], vec![]),
+        };
+
+        if resolver_type == &ResolverType::UpdateOne || resolver_type == &ResolverType::UpdateMany {
+            let update_operators = SqlDataSource::get_update_operators(input)?;
+            values.extend(update_operators.inc_values);
+        }
+
+        if let Some(query_input) = input.get("query") {
+            if resolver_type != &ResolverType::CreateOne {
+                (_, where_values, _, _, _) = SqlDataSource::create_nested_query_recursive(
+                    &vec![query_input.clone()],
+                    entity,
+                    &self.config.dialect,
+                    FilterOperator::And,
+                    false,
+                    None,
+                    subgraph_config,
+                    None,
+                    false,
+                )?;
+            }
+        }
+
+        let query = match resolver_type {
+            ResolverType::FindMany => format!(
+                "{}{};",
+                plan.query,
+                SqlDataSource::create_pagination_clause(input)
+            ),
+            _ => plan.query,
+        };
+
+        Ok(SqlQuery {
+            query,
+            count_query: plan.count_query,
+            identifier_query: plan.identifier_query,
+            values,
+            where_values,
+            value_keys,
+            table: table_name.to_string(),
+            schema: plan.schema,
+        })
+    }
+}
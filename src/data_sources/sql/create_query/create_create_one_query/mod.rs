@@ -5,16 +5,19 @@ use crate::{
 impl SqlDataSource {
     pub fn create_create_one_query(
         table_name: &str,
+        schema: Option<&str>,
         value_keys: &Vec<String>,
         dialect: &DialectEnum,
     ) -> Result<String, async_graphql::Error> {
         let mut query = String::new();
         query.push_str("INSERT INTO ");
-        query.push_str(table_name);
+        query.push_str(&SqlDataSource::quote_table_name(
+            table_name, schema, dialect,
+        ));
         query.push_str(" (");
 
         for i in 0..value_keys.len() {
-            query.push_str(&value_keys[i]);
+            query.push_str(&SqlDataSource::quote_identifier(&value_keys[i], dialect));
             if i != value_keys.len() - 1 {
                 query.push_str(", ");
             }
@@ -31,8 +34,9 @@ impl SqlDataSource {
 
         query.push_str(")");
 
+        // MySQL does not support RETURNING, the row is re-fetched by primary key instead.
         match dialect {
-            DialectEnum::POSTGRES => {
+            DialectEnum::POSTGRES | DialectEnum::SQLITE => {
                 query.push_str(" RETURNING *");
             }
             _ => {}
@@ -1,7 +1,7 @@
 use std::str::FromStr;
 
 use bson::Document;
-use log::{debug, trace};
+use log::{debug, error, trace};
 
 use crate::{
     configuration::subgraph::{
@@ -38,11 +38,17 @@ impl SqlDataSource {
         } else {
             entity.name.clone()
         };
-        let select_statement = format!("SELECT {}.* FROM ", entity_table_name);
+        let select_statement = format!(
+            "SELECT {}.* FROM ",
+            SqlDataSource::quote_identifier(&entity_table_name, dialect)
+        );
         query.push_str(&select_statement);
-        query.push_str(table_name);
+        let schema = SqlDataSource::get_table_schema(entity, subgraph_config);
+        let quoted_table_name =
+            SqlDataSource::quote_table_name(table_name, schema.as_deref(), dialect);
+        query.push_str(&quoted_table_name);
 
-        let count_statement = format!("SELECT COUNT(*) as total_count FROM {}", table_name);
+        let count_statement = format!("SELECT COUNT(*) as total_count FROM {}", quoted_table_name);
         count_query.push_str(&count_statement);
 
         let query_input = input.get("query").unwrap();
@@ -82,45 +88,16 @@ impl SqlDataSource {
         }
 
         let opts_input = input.get("opts");
-        let mut per_page = 10;
-        let mut page = 1;
         let mut sort_vec = Vec::new();
 
         if let Some(opts_input) = opts_input {
             let opts = opts_input.as_document().unwrap();
-            if let Some(per_page_input) = opts.get("per_page") {
-                per_page = per_page_input
-                    .as_i32()
-                    .unwrap_or(per_page_input.as_i64().unwrap_or(10) as i32);
-            }
-            if let Some(page_input) = opts.get("page") {
-                page = page_input
-                    .as_i32()
-                    .unwrap_or(page_input.as_i64().unwrap_or(1) as i32);
-            }
-
-            if let Some(sort_input) = opts.get("sort") {
-                let sort_input = sort_input.as_array();
-                if sort_input.is_none() {
-                    return Err(async_graphql::Error::new("Sort input must be an array"));
-                }
-                let sort_input = sort_input.unwrap();
-                for sort_item in sort_input {
-                    let sort_item = sort_item.as_document().unwrap();
-                    let field = sort_item.get("field").unwrap();
-                    let field = field.as_str().unwrap();
-                    let direction = sort_item.get("direction").unwrap();
-                    let direction = DirectionEnum::from_str(direction.as_str().unwrap()).unwrap();
-
-                    sort_vec.push(SortInput {
-                        field: field.to_string(),
-                        direction,
-                    });
-                }
-            }
+            sort_vec = SqlDataSource::get_sort_inputs(entity, opts)?;
         }
 
         if sort_vec.len() > 0 {
+            let sort_table_name = SqlDataSource::quote_identifier(&entity_table_name, dialect);
+
             // If postgres, we need to add the sort fields to the group by clause
             if dialect == &DialectEnum::POSTGRES {
                 query.push_str(" GROUP BY ");
@@ -128,7 +105,11 @@ impl SqlDataSource {
                     if i > 0 {
                         query.push_str(", ");
                     }
-                    query.push_str(&format!("{} ", sort_item.field));
+                    query.push_str(&format!(
+                        "{}.{} ",
+                        sort_table_name,
+                        SqlDataSource::quote_identifier(&sort_item.field, dialect)
+                    ));
                 }
             }
             query.push_str(" ORDER BY ");
@@ -137,17 +118,15 @@ impl SqlDataSource {
                     query.push_str(", ");
                 }
                 query.push_str(&format!(
-                    "{} {}",
-                    sort_item.field,
+                    "{}.{} {}",
+                    sort_table_name,
+                    SqlDataSource::quote_identifier(&sort_item.field, dialect),
                     sort_item.direction.to_string()
                 ));
             }
         }
 
-        if per_page != -1 {
-            let offset = (page - 1) * per_page;
-            query.push_str(&format!(" LIMIT {} OFFSET {}", per_page, offset));
-        }
+        query.push_str(&SqlDataSource::create_pagination_clause(input));
 
         if !query.ends_with(';') {
             query.push(';');
@@ -164,4 +143,44 @@ impl SqlDataSource {
             combined_where_keys,
         ))
     }
+
+    /// Parses the `sort` option. Sort fields are provided by the client and used as column
+    /// names, so only non virtual fields of the entity are accepted.
+    pub fn get_sort_inputs(
+        entity: &ServiceEntityConfig,
+        opts: &Document,
+    ) -> Result<Vec<SortInput>, async_graphql::Error> {
+        let mut sort_vec = Vec::new();
+        if let Some(sort_input) = opts.get("sort") {
+            let sort_input = sort_input.as_array();
+            if sort_input.is_none() {
+                return Err(async_graphql::Error::new("Sort input must be an array"));
+            }
+            let sort_input = sort_input.unwrap();
+            for sort_item in sort_input {
+                let sort_item = sort_item.as_document().unwrap();
+                let field = sort_item.get("field").unwrap();
+                let field = field.as_str().unwrap();
+                let direction = sort_item.get("direction").unwrap();
+                let direction = DirectionEnum::from_str(direction.as_str().unwrap()).unwrap();
+
+                let is_column = entity.fields.iter().any(|entity_field| {
+                    entity_field.name == field && !entity_field.is_virtual.unwrap_or(false)
+                });
+                if !is_column {
+                    error!("Invalid sort field: {}", field);
+                    return Err(async_graphql::Error::new(format!(
+                        "Invalid sort field: {}",
+                        field
+                    )));
+                }
+
+                sort_vec.push(SortInput {
+                    field: field.to_string(),
+                    direction,
+                });
+            }
+        }
+        Ok(sort_vec)
+    }
 }
@@ -31,9 +31,17 @@ impl SqlDataSource {
         } else {
             entity.name.clone()
         };
-        let select_statement = format!("SELECT {}.* FROM ", entity_table_name);
+        let select_statement = format!(
+            "SELECT {}.* FROM ",
+            SqlDataSource::quote_identifier(&entity_table_name, dialect)
+        );
         query.push_str(&select_statement);
-        query.push_str(table_name);
+        let schema = SqlDataSource::get_table_schema(entity, subgraph_config);
+        query.push_str(&SqlDataSource::quote_table_name(
+            table_name,
+            schema.as_deref(),
+            dialect,
+        ));
 
         let query_input = match input.get("query") {
             Some(query_input) => query_input,
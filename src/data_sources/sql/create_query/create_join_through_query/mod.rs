@@ -0,0 +1,149 @@
+use bson::{doc, Bson, Document};
+use log::{debug, error, trace};
+
+use crate::{
+    configuration::subgraph::{
+        data_sources::sql::DialectEnum,
+        entities::{
+            service_entity_field::{join_through::JOIN_THROUGH_KEY, ServiceEntityFieldConfig},
+            ServiceEntityConfig,
+        },
+        SubGraphConfig,
+    },
+    data_sources::sql::{SqlDataSource, SqlQuery},
+    resolver_type::ResolverType,
+    sql_value::SqlValue,
+};
+
+impl SqlDataSource {
+    /// Creates a query for the rows of `entity` joined to the parents with the provided keys
+    /// through the junction entity of `as_field`. The query filters of the input are applied to
+    /// the joined entity, which is joined to the junction in a single statement:
+    /// SELECT "t".*, "j"."post_id" AS "__join_through_key" FROM (SELECT "tags".* FROM "tags"
+    /// WHERE ...) AS "t" JOIN "post_tags" AS "j" ON "j"."tag_id" = "t"."id" WHERE "j"."post_id"
+    /// IN (?, ?);
+    pub fn create_join_through_query(
+        input: &Document,
+        entity: &ServiceEntityConfig,
+        junction: &ServiceEntityConfig,
+        as_field: &ServiceEntityFieldConfig,
+        parent_keys: Vec<Bson>,
+        dialect: DialectEnum,
+        subgraph_config: &SubGraphConfig,
+    ) -> Result<SqlQuery, async_graphql::Error> {
+        debug!("Creating Join Through Query");
+
+        let (join_through, join_on) = match (&as_field.join_through, &as_field.join_on) {
+            (Some(join_through), Some(join_on)) => (join_through, join_on),
+            _ => {
+                error!("Field {} does not join through an entity.", as_field.name);
+                return Err(async_graphql::Error::new(format!(
+                    "Field {} does not join through an entity.",
+                    as_field.name
+                )));
+            }
+        };
+
+        let table = ServiceEntityConfig::get_sql_table_name(entity);
+        let junction_table = ServiceEntityConfig::get_sql_table_name(junction);
+        let junction_schema = SqlDataSource::get_table_schema(junction, subgraph_config);
+
+        let query_input = input
+            .get_document("query")
+            .cloned()
+            .unwrap_or(Document::new());
+        let opts = input
+            .get_document("opts")
+            .cloned()
+            .unwrap_or(Document::new());
+        let joined_query = SqlDataSource::create_query(
+            doc! { "query": query_input, "opts": { "per_page": -1, "page": 1 } },
+            ResolverType::FindMany,
+            &table,
+            dialect.clone(),
+            entity,
+            subgraph_config,
+        )?;
+
+        let mut where_values = joined_query.where_values;
+        let mut placeholders = Vec::new();
+        for parent_key in parent_keys.iter() {
+            placeholders.push(SqlDataSource::get_placeholder(
+                &dialect,
+                Some(where_values.len() as i32),
+            ));
+            where_values.push(SqlDataSource::get_join_through_value(parent_key)?);
+        }
+        let placeholders = placeholders.join(", ");
+
+        let joined_alias = SqlDataSource::quote_identifier("t", &dialect);
+        let junction_alias = SqlDataSource::quote_identifier("j", &dialect);
+        let from_clause = format!(
+            " FROM ({}) AS {} JOIN {} AS {} ON {}.{} = {}.{} WHERE {}.{} IN ({})",
+            joined_query.query.trim_end_matches(';'),
+            joined_alias,
+            SqlDataSource::quote_table_name(&junction_table, junction_schema.as_deref(), &dialect),
+            junction_alias,
+            junction_alias,
+            SqlDataSource::quote_identifier(&join_through.join_on, &dialect),
+            joined_alias,
+            SqlDataSource::quote_identifier(join_on, &dialect),
+            junction_alias,
+            SqlDataSource::quote_identifier(&join_through.join_from, &dialect),
+            placeholders
+        );
+
+        let mut query = format!(
+            "SELECT {}.*, {}.{} AS {}{}",
+            joined_alias,
+            junction_alias,
+            SqlDataSource::quote_identifier(&join_through.join_from, &dialect),
+            SqlDataSource::quote_identifier(JOIN_THROUGH_KEY, &dialect),
+            from_clause
+        );
+        let sort_vec = SqlDataSource::get_sort_inputs(entity, &opts)?;
+        for (i, sort_item) in sort_vec.iter().enumerate() {
+            query.push_str(if i == 0 { " ORDER BY " } else { ", " });
+            query.push_str(&format!(
+                "{}.{} {}",
+                joined_alias,
+                SqlDataSource::quote_identifier(&sort_item.field, &dialect),
+                sort_item.direction
+            ));
+        }
+        query.push(';');
+
+        let sql_query = SqlQuery {
+            query,
+            count_query: Some(format!("SELECT COUNT(*) as total_count{};", from_clause)),
+            identifier_query: None,
+            where_values,
+            values: Vec::new(),
+            value_keys: Vec::new(),
+            table,
+            schema: joined_query.schema,
+        };
+
+        trace!("Join Through Query: {:?}", sql_query);
+        Ok(sql_query)
+    }
+
+    /// Converts a key joined through a junction entity to the value bound to its placeholder.
+    pub fn get_join_through_value(key: &Bson) -> Result<SqlValue, async_graphql::Error> {
+        match key {
+            Bson::Int32(value) => Ok(SqlValue::Int(*value)),
+            Bson::Int64(value) => Ok(SqlValue::BigInt(*value)),
+            Bson::Boolean(value) => Ok(SqlValue::Bool(*value)),
+            Bson::String(value) => Ok(SqlValue::String(value.clone())),
+            // SQL data sources store object ids as strings.
+            Bson::ObjectId(value) => Ok(SqlValue::String(value.to_hex())),
+            _ => {
+                error!("Unsupported join through key: {:?}", key);
+                Err(async_graphql::Error::new(format!(
+                    "Unsupported join through key: {:?}",
+                    key
+                )))
+            }
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use bson::{Bson, Document};
+use log::{debug, error, trace};
+
+use crate::{
+    configuration::subgraph::{
+        data_sources::sql::{DialectEnum, SqlQueryConfig},
+        entities::ServiceEntityConfig,
+    },
+    data_sources::sql::{SqlDataSource, SqlQuery},
+    resolver_type::ResolverType,
+    sql_value::SqlValue,
+};
+
+impl SqlDataSource {
+    /// Creates the query for an entity backed by a named SQL query. The named query is used as
+    /// a derived table so that pagination and sorting can be applied to its rows.
+    pub fn create_named_query(
+        entity: &ServiceEntityConfig,
+        named_query: &SqlQueryConfig,
+        resolver_type: ResolverType,
+        dialect: &DialectEnum,
+        input: &Document,
+    ) -> Result<SqlQuery, async_graphql::Error> {
+        debug!("Creating Named Query: {}", named_query.name);
+
+        let params = match input.get("query") {
+            Some(Bson::Document(params)) => params.clone(),
+            _ => Document::new(),
+        };
+        let (sql, values) = SqlDataSource::bind_named_params(named_query, &params, dialect)?;
+        let alias = SqlDataSource::quote_identifier(&entity.name, dialect);
+
+        let mut query = format!("SELECT * FROM ({}) AS {}", sql, alias);
+        let mut count_query = None;
+
+        match resolver_type {
+            ResolverType::FindOne => {
+                query.push_str(" LIMIT 1");
+            }
+            ResolverType::FindMany => {
+                count_query = Some(format!(
+                    "SELECT COUNT(*) as total_count FROM ({}) AS {};",
+                    sql, alias
+                ));
+
+                let mut sort_vec = Vec::new();
+                if let Some(Bson::Document(opts)) = input.get("opts") {
+                    sort_vec = SqlDataSource::get_sort_inputs(entity, opts)?;
+                }
+
+                if !sort_vec.is_empty() {
+                    let order_by = sort_vec
+                        .iter()
+                        .map(|sort_item| {
+                            format!(
+                                "{}.{} {}",
+                                alias,
+                                SqlDataSource::quote_identifier(&sort_item.field, dialect),
+                                sort_item.direction
+                            )
+                        })
+                        .collect::<Vec<String>>();
+                    query.push_str(&format!(" ORDER BY {}", order_by.join(", ")));
+                }
+
+                query.push_str(&SqlDataSource::create_pagination_clause(input));
+            }
+            _ => {
+                error!(
+                    "Entity {} is backed by a SQL query and is read only.",
+                    entity.name
+                );
+                return Err(async_graphql::Error::new(format!(
+                    "Entity {} is read only.",
+                    entity.name
+                )));
+            }
+        }
+        query.push(';');
+
+        trace!("Named Query: {}", query);
+        trace!("Named Query Values: {:?}", values);
+
+        Ok(SqlQuery {
+            query,
+            count_query,
+            identifier_query: None,
+            values: Vec::new(),
+            where_values: values,
+            value_keys: Vec::new(),
+            table: entity.name.clone(),
+            schema: None,
+        })
+    }
+
+    /// Replaces the `:name` parameters of a named query with placeholders for the dialect and
+    /// returns the values to bind, in order. List values expand to one placeholder per item.
+    pub fn bind_named_params(
+        named_query: &SqlQueryConfig,
+        params: &Document,
+        dialect: &DialectEnum,
+    ) -> Result<(String, Vec<SqlValue>), async_graphql::Error> {
+        debug!("Binding Named Query Params");
+
+        let sql = named_query.sql.trim().trim_end_matches(';');
+        let chars = sql.chars().collect::<Vec<char>>();
+        let mut query = String::new();
+        let mut values = Vec::new();
+        let mut placeholder_count = 0;
+        let mut in_string = false;
+        let mut i = 0;
+
+        while i < chars.len() {
+            let c = chars[i];
+            if c == '\'' {
+                in_string = !in_string;
+            }
+
+            // Postgres casts, `::type`, are not parameters.
+            let is_param = !in_string
+                && c == ':'
+                && (i == 0 || chars[i - 1] != ':')
+                && i + 1 < chars.len()
+                && (chars[i + 1].is_ascii_alphabetic() || chars[i + 1] == '_');
+
+            if !is_param {
+                query.push(c);
+                i += 1;
+                continue;
+            }
+
+            let mut name = String::new();
+            i += 1;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                name.push(chars[i]);
+                i += 1;
+            }
+
+            let param = match named_query
+                .params
+                .as_ref()
+                .and_then(|params| params.iter().find(|param| param.name == name))
+            {
+                Some(param) => param,
+                None => {
+                    error!(
+                        "Parameter {} is not defined for query {}",
+                        name, named_query.name
+                    );
+                    return Err(async_graphql::Error::new(format!(
+                        "Parameter {} is not defined for query {}",
+                        name, named_query.name
+                    )));
+                }
+            };
+
+            let value = SqlDataSource::get_named_param_value(param, params, dialect)?;
+
+            let count = match &value {
+                SqlValue::StringList(list) | SqlValue::ObjectIDList(list) => list.len(),
+                SqlValue::IntList(list) => list.len(),
+                SqlValue::BoolList(list) => list.len(),
+                SqlValue::UUIDList(list) => list.len(),
+                SqlValue::DateTimeList(list) => list.len(),
+                _ => 1,
+            };
+            let placeholders = (0..count)
+                .map(|j| {
+                    SqlDataSource::get_placeholder(dialect, Some(placeholder_count + j as i32))
+                })
+                .collect::<Vec<String>>();
+            placeholder_count += count as i32;
+
+            query.push_str(&placeholders.join(", "));
+            values.push(value);
+        }
+
+        Ok((query, values))
+    }
+}
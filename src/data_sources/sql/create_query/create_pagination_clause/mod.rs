@@ -0,0 +1,35 @@
+use bson::Document;
+use log::debug;
+
+use crate::data_sources::sql::SqlDataSource;
+
+impl SqlDataSource {
+    /// Creates the `LIMIT` and `OFFSET` clause of a find many query from the `page` and
+    /// `per_page` options. A `per_page` of -1 returns every row.
+    pub fn create_pagination_clause(input: &Document) -> String {
+        debug!("Creating Pagination Clause");
+
+        let mut per_page = 10;
+        let mut page = 1;
+
+        if let Ok(opts) = input.get_document("opts") {
+            if let Some(per_page_input) = opts.get("per_page") {
+                per_page = per_page_input
+                    .as_i32()
+                    .unwrap_or(per_page_input.as_i64().unwrap_or(10) as i32);
+            }
+            if let Some(page_input) = opts.get("page") {
+                page = page_input
+                    .as_i32()
+                    .unwrap_or(page_input.as_i64().unwrap_or(1) as i32);
+            }
+        }
+
+        if per_page == -1 {
+            return String::new();
+        }
+
+        let offset = (page - 1) * per_page;
+        format!(" LIMIT {} OFFSET {}", per_page, offset)
+    }
+}
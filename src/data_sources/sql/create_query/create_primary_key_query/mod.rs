@@ -0,0 +1,75 @@
+use bson::Document;
+use log::{debug, error, trace};
+
+use crate::{
+    configuration::subgraph::data_sources::sql::DialectEnum,
+    data_sources::sql::SqlDataSource,
+    sql_value::{FromBson, SqlValue},
+};
+
+impl SqlDataSource {
+    /// Creates a query selecting the rows identified by the provided primary keys.
+    /// Each entry holds the values of every primary key field of a single row, so that
+    /// composite keys are matched as `(k1 = ? AND k2 = ?) OR (...)`.
+    pub fn create_primary_key_query(
+        table_name: &str,
+        schema: Option<&str>,
+        primary_keys: &Vec<Vec<(String, SqlValue)>>,
+        dialect: &DialectEnum,
+    ) -> Result<(String, Vec<SqlValue>), async_graphql::Error> {
+        debug!("Creating Primary Key Query");
+        trace!("Primary Keys: {:?}", primary_keys);
+
+        if primary_keys.is_empty() {
+            error!("No primary keys provided for table: {}", table_name);
+            return Err(async_graphql::Error::new(format!(
+                "No primary keys provided for table: {}",
+                table_name
+            )));
+        }
+
+        let mut query = String::new();
+        query.push_str("SELECT * FROM ");
+        query.push_str(&SqlDataSource::quote_table_name(
+            table_name, schema, dialect,
+        ));
+        query.push_str(" WHERE ");
+
+        let mut values = Vec::new();
+        let mut conditions = Vec::new();
+
+        for primary_key in primary_keys {
+            let mut keys = Vec::new();
+            for (key, value) in primary_key {
+                let placeholder =
+                    SqlDataSource::get_placeholder(dialect, Some(values.len() as i32));
+                keys.push(format!(
+                    "{} = {}",
+                    SqlDataSource::quote_identifier(key, dialect),
+                    placeholder
+                ));
+                values.push(value.clone());
+            }
+            conditions.push(format!("({})", keys.join(" AND ")));
+        }
+
+        query.push_str(&conditions.join(" OR "));
+        query.push(';');
+
+        trace!("Primary Key Query: {}", query);
+        Ok((query, values))
+    }
+
+    /// Converts a document of primary key values, such as a row returned by an identifier
+    /// query, to the values used by `create_primary_key_query`.
+    pub fn get_primary_key_values(
+        primary_key: &Document,
+        dialect: &DialectEnum,
+    ) -> Result<Vec<(String, SqlValue)>, async_graphql::Error> {
+        let mut values = Vec::new();
+        for (key, value) in primary_key {
+            values.push((key.clone(), value.to_sql_value(Some(dialect))?));
+        }
+        Ok(values)
+    }
+}
@@ -0,0 +1,120 @@
+use bson::{Bson, Document};
+use log::{debug, error, trace};
+
+use crate::{
+    configuration::subgraph::data_sources::sql::DialectEnum, data_sources::sql::SqlDataSource,
+    sql_value::SqlValue, update_operator::UpdateOperator,
+};
+
+/// The columns changed by the `operators` of an update input.
+#[derive(Debug, Clone, Default)]
+pub struct SqlUpdateOperators {
+    pub inc_keys: Vec<String>,
+    pub inc_values: Vec<SqlValue>,
+    pub unset_keys: Vec<String>,
+}
+
+impl SqlDataSource {
+    /// Gets the columns incremented and unset by the `operators` of an update input.
+    pub fn get_update_operators(
+        input: &Document,
+    ) -> Result<SqlUpdateOperators, async_graphql::Error> {
+        debug!("Getting Update Operators");
+        let mut update_operators = SqlUpdateOperators::default();
+
+        let operators = match input.get_document("operators") {
+            Ok(operators) => operators,
+            Err(_) => return Ok(update_operators),
+        };
+
+        if let Ok(inc) = operators.get_document(UpdateOperator::Inc.as_str()) {
+            for (key, value) in inc {
+                let value = match value {
+                    Bson::Null => continue,
+                    Bson::Int32(value) => *value,
+                    Bson::Int64(value) => i32::try_from(*value).map_err(|_| {
+                        error!("Invalid value to increment {}: {}", key, value);
+                        async_graphql::Error::new(format!(
+                            "Invalid value to increment {}: {}",
+                            key, value
+                        ))
+                    })?,
+                    _ => {
+                        error!("Invalid value to increment {}: {}", key, value);
+                        return Err(async_graphql::Error::new(format!(
+                            "Invalid value to increment {}: {}",
+                            key, value
+                        )));
+                    }
+                };
+                update_operators.inc_keys.push(key.clone());
+                update_operators.inc_values.push(SqlValue::Int(value));
+            }
+        }
+
+        if let Ok(unset) = operators.get_document(UpdateOperator::Unset.as_str()) {
+            for (key, value) in unset {
+                if value.as_bool().unwrap_or(false) {
+                    update_operators.unset_keys.push(key.clone());
+                }
+            }
+        }
+
+        trace!("Update Operators: {:?}", update_operators);
+        Ok(update_operators)
+    }
+
+    /// Creates the assignments of an update query, without the `SET` keyword.
+    /// Values are bound first, then the increments. Returns the clause and the number of
+    /// placeholders used, the offset of the where values.
+    pub fn create_set_clause(
+        value_keys: &Vec<String>,
+        input: &Document,
+        dialect: &DialectEnum,
+    ) -> Result<(String, i32), async_graphql::Error> {
+        debug!("Creating Set Clause");
+
+        let update_operators = SqlDataSource::get_update_operators(input)?;
+        let mut assignments = vec![];
+        let mut index = 0;
+
+        for key in value_keys {
+            assignments.push(format!(
+                "{} = {}",
+                SqlDataSource::quote_identifier(key, dialect),
+                SqlDataSource::get_placeholder(dialect, Some(index))
+            ));
+            index += 1;
+        }
+
+        // Null columns are incremented from 0, as with the `$inc` of Mongo.
+        for key in update_operators.inc_keys {
+            let column = SqlDataSource::quote_identifier(&key, dialect);
+            assignments.push(format!(
+                "{} = COALESCE({}, 0) + {}",
+                column,
+                column,
+                SqlDataSource::get_placeholder(dialect, Some(index))
+            ));
+            index += 1;
+        }
+
+        for key in update_operators.unset_keys {
+            assignments.push(format!(
+                "{} = NULL",
+                SqlDataSource::quote_identifier(&key, dialect)
+            ));
+        }
+
+        if assignments.is_empty() {
+            error!("No values or operators provided to update.");
+            return Err(async_graphql::Error::new(
+                "No values or operators provided to update.",
+            ));
+        }
+
+        let set_clause = assignments.join(", ");
+        trace!("Set Clause: {}", set_clause);
+        Ok((set_clause, index))
+    }
+}
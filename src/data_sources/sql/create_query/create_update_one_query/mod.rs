@@ -22,26 +22,33 @@ impl SqlDataSource {
         debug!("Creating Update One Query");
 
         let mut query = String::new();
+        let schema = SqlDataSource::get_table_schema(entity, subgraph_config);
+        let quoted_table_name =
+            SqlDataSource::quote_table_name(table_name, schema.as_deref(), dialect);
         query.push_str("UPDATE ");
-        query.push_str(table_name);
+        query.push_str(&quoted_table_name);
         query.push_str(" SET ");
 
         let mut identifier_query = String::new();
-        let primary_key_field = ServiceEntityConfig::get_primary_key_field(entity)?;
-        identifier_query
-            .push_str(format!("SELECT {} FROM {}", primary_key_field.name, table_name).as_str());
+        let primary_key_names = ServiceEntityConfig::get_primary_key_fields(entity)?
+            .into_iter()
+            .map(|field| SqlDataSource::quote_identifier(&field.name, dialect))
+            .collect::<Vec<String>>();
+        identifier_query.push_str(
+            format!(
+                "SELECT {} FROM {}",
+                primary_key_names.join(", "),
+                quoted_table_name
+            )
+            .as_str(),
+        );
 
-        for i in 0..value_keys.len() {
-            query.push_str(&value_keys[i]);
-            query.push_str(" = ");
-            query.push_str(SqlDataSource::get_placeholder(dialect, Some(i as i32)).as_str());
-            if i != value_keys.len() - 1 {
-                query.push_str(", ");
-            }
-        }
+        let (set_clause, set_placeholders) =
+            SqlDataSource::create_set_clause(value_keys, input, dialect)?;
+        query.push_str(&set_clause);
 
         // Offset used for postgres WHERE key placeholders, $1, $2
-        let offset = Some(value_keys.len() as i32);
+        let offset = Some(set_placeholders);
 
         query.push_str(" WHERE ");
         identifier_query.push_str(" WHERE ");
@@ -19,8 +19,6 @@ impl SqlDataSource {
             let mut query = String::new();
 
             for i in 0..where_keys.len() {
-                query.push_str(&where_keys[i]);
-
                 // If where_values[i] does not exist, return error.
                 if where_values.len() <= i {
                     error!("Where value for key does not exist: {}", where_keys[i]);
@@ -29,10 +27,51 @@ impl SqlDataSource {
                     )));
                 }
 
-                let is_list = match where_values[i] {
-                    SqlValue::StringList(_) | SqlValue::IntList(_) | SqlValue::BoolList(_) => true,
-                    _ => false,
-                };
+                // List columns match rows sharing at least one value with the filter.
+                if let SqlValue::Array(values) = &where_values[i] {
+                    // Comparison operators have no meaning for an overlap, so reject them.
+                    match filter_operator {
+                        FilterOperator::And | FilterOperator::Or => {}
+                        _ => {
+                            error!(
+                                "Filter operator {} is not supported on list field {}",
+                                filter_operator.as_str(),
+                                where_keys[i]
+                            );
+                            return Err(async_graphql::Error::new(format!(
+                                "Filter operator {} is not supported on list field {}",
+                                filter_operator.as_str(),
+                                where_keys[i]
+                            )));
+                        }
+                    }
+                    let index = pg_param_offset.map(|offset| i as i32 + offset).or(Some(0));
+                    let (clause, placeholder_count) = SqlDataSource::create_array_overlap_clause(
+                        &where_keys[i],
+                        values,
+                        dialect,
+                        index,
+                    );
+                    query.push_str(&clause);
+                    pg_param_offset =
+                        Some(pg_param_offset.unwrap_or(0) + placeholder_count as i32 - 1);
+                    if i != where_keys.len() - 1 {
+                        query.push_str(" AND ");
+                    }
+                    continue;
+                }
+
+                query.push_str(&where_keys[i]);
+
+                let is_list = matches!(
+                    where_values[i],
+                    SqlValue::StringList(_)
+                        | SqlValue::IntList(_)
+                        | SqlValue::BoolList(_)
+                        | SqlValue::UUIDList(_)
+                        | SqlValue::DateTimeList(_)
+                        | SqlValue::ObjectIDList(_)
+                );
 
                 if is_list {
                     query.push_str(" IN (");
@@ -52,9 +91,16 @@ impl SqlDataSource {
                 };
 
                 match where_values[i] {
-                    SqlValue::StringList(_) | SqlValue::IntList(_) | SqlValue::BoolList(_) => {
+                    SqlValue::StringList(_)
+                    | SqlValue::IntList(_)
+                    | SqlValue::BoolList(_)
+                    | SqlValue::UUIDList(_)
+                    | SqlValue::DateTimeList(_)
+                    | SqlValue::ObjectIDList(_) => {
                         let placeholder_count = match where_values[i] {
-                            SqlValue::StringList(ref list) => list.len(),
+                            SqlValue::StringList(ref list) | SqlValue::ObjectIDList(ref list) => {
+                                list.len()
+                            }
                             SqlValue::IntList(ref list) => list.len(),
                             SqlValue::BoolList(ref list) => list.len(),
                             SqlValue::UUIDList(ref list) => list.len(),
@@ -62,8 +108,12 @@ impl SqlDataSource {
                             _ => 0,
                         };
 
+                        // Each value of the list has its own placeholder.
                         for j in 0..placeholder_count {
-                            query.push_str(&SqlDataSource::get_placeholder(dialect, index));
+                            query.push_str(&SqlDataSource::get_placeholder(
+                                dialect,
+                                index.map(|index| index + j as i32),
+                            ));
                             if j != placeholder_count - 1 {
                                 query.push_str(", ");
                             }
@@ -92,4 +142,48 @@ impl SqlDataSource {
         trace!("Parameter Offset: {:?}", pg_param_offset.unwrap_or(0));
         Ok((parameterized_query, pg_param_offset.unwrap_or(0)))
     }
+
+    /// Creates the clause matching a list column against a list of values. Returns the clause
+    /// and the number of placeholders it uses.
+    fn create_array_overlap_clause(
+        where_key: &str,
+        values: &SqlValue,
+        dialect: &DialectEnum,
+        index: Option<i32>,
+    ) -> (String, usize) {
+        let placeholder_count = match values {
+            SqlValue::StringList(list) | SqlValue::ObjectIDList(list) => list.len(),
+            SqlValue::IntList(list) => list.len(),
+            SqlValue::BoolList(list) => list.len(),
+            SqlValue::UUIDList(list) => list.len(),
+            SqlValue::DateTimeList(list) => list.len(),
+            _ => 1,
+        };
+        let placeholders = (0..placeholder_count)
+            .map(|j| SqlDataSource::get_placeholder(dialect, index.map(|index| index + j as i32)))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        match dialect {
+            DialectEnum::POSTGRES => (
+                format!(
+                    "{} && {}",
+                    where_key,
+                    SqlDataSource::get_placeholder(dialect, index)
+                ),
+                1,
+            ),
+            DialectEnum::MYSQL => (
+                format!("JSON_OVERLAPS({}, JSON_ARRAY({}))", where_key, placeholders),
+                placeholder_count,
+            ),
+            DialectEnum::SQLITE => (
+                format!(
+                    "EXISTS (SELECT 1 FROM json_each({}) WHERE json_each.value IN ({}))",
+                    where_key, placeholders
+                ),
+                placeholder_count,
+            ),
+        }
+    }
 }
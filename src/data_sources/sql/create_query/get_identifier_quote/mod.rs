@@ -0,0 +1,68 @@
+use crate::{
+    configuration::subgraph::{
+        data_sources::{sql::DialectEnum, ServiceDataSourceConfig},
+        entities::ServiceEntityConfig,
+        SubGraphConfig,
+    },
+    data_sources::sql::SqlDataSource,
+};
+
+impl SqlDataSource {
+    /// Gets the character used to quote identifiers, such as table and column names.
+    pub fn get_identifier_quote(dialect: &DialectEnum) -> &'static str {
+        match dialect {
+            DialectEnum::POSTGRES => "\"",
+            DialectEnum::MYSQL | DialectEnum::SQLITE => "`",
+        }
+    }
+
+    /// Quotes a table or column name, escaping any quote characters within it.
+    pub fn quote_identifier(identifier: &str, dialect: &DialectEnum) -> String {
+        let d = SqlDataSource::get_identifier_quote(dialect);
+        format!("{d}{}{d}", identifier.replace(d, &format!("{d}{d}")))
+    }
+
+    /// Quotes a table name, qualified by its Postgres schema or MySQL database when provided.
+    /// Ex: `"billing"."invoices"`
+    pub fn quote_table_name(
+        table_name: &str,
+        schema: Option<&str>,
+        dialect: &DialectEnum,
+    ) -> String {
+        match schema {
+            Some(schema) => format!(
+                "{}.{}",
+                SqlDataSource::quote_identifier(schema, dialect),
+                SqlDataSource::quote_identifier(table_name, dialect)
+            ),
+            None => SqlDataSource::quote_identifier(table_name, dialect),
+        }
+    }
+
+    /// Gets the schema of the table of an entity. The `schema` of the entity data source
+    /// overrides the `schema` of the SQL data source it is stored in.
+    pub fn get_table_schema(
+        entity: &ServiceEntityConfig,
+        subgraph_config: &SubGraphConfig,
+    ) -> Option<String> {
+        let entity_data_source = ServiceEntityConfig::get_entity_data_source(entity);
+        if let Some(schema) = entity_data_source.as_ref().and_then(|ds| ds.schema.clone()) {
+            return Some(schema);
+        }
+
+        let data_source_name = entity_data_source.and_then(|ds| ds.from);
+        subgraph_config
+            .service
+            .data_sources
+            .iter()
+            .filter_map(|data_source| match data_source {
+                ServiceDataSourceConfig::SQL(sql_data_source) => Some(sql_data_source),
+                _ => None,
+            })
+            .find(|sql_data_source| match &data_source_name {
+                Some(name) => &sql_data_source.name == name,
+                None => true,
+            })
+            .and_then(|sql_data_source| sql_data_source.schema.clone())
+    }
+}
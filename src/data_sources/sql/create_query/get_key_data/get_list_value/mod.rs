@@ -0,0 +1,140 @@
+use std::str::FromStr;
+
+use bson::Bson;
+use log::{debug, error, trace};
+
+use crate::{
+    configuration::subgraph::{
+        data_sources::sql::DialectEnum, entities::service_entity_field::ServiceEntityFieldConfig,
+    },
+    data_sources::sql::SqlDataSource,
+    scalar_option::ScalarOption,
+    sql_value::SqlValue,
+};
+
+impl SqlDataSource {
+    /// Converts the value of a list column, provided by the client, to a list of the field scalar.
+    pub fn get_list_value(
+        value: &Bson,
+        field: &ServiceEntityFieldConfig,
+    ) -> Result<SqlValue, async_graphql::Error> {
+        debug!("Getting List Value For Field: {}", field.name);
+        trace!("Value: {:?}", value);
+
+        // A single value is treated as a list of one.
+        let values = match value {
+            Bson::Array(values) => values.clone(),
+            value => vec![value.clone()],
+        };
+
+        let invalid_value = |value: &Bson| {
+            error!("Invalid value {} for list field {}", value, field.name);
+            async_graphql::Error::new(format!(
+                "Invalid value {} for list field {}",
+                value, field.name
+            ))
+        };
+
+        let list_value = match field.scalar {
+            ScalarOption::String | ScalarOption::Enum | ScalarOption::ObjectID => {
+                SqlValue::StringList(
+                    values
+                        .iter()
+                        .map(|value| match value {
+                            Bson::String(s) => Ok(s.clone()),
+                            Bson::ObjectId(o) => Ok(o.to_hex()),
+                            _ => Err(invalid_value(value)),
+                        })
+                        .collect::<Result<Vec<String>, async_graphql::Error>>()?,
+                )
+            }
+            ScalarOption::Int => SqlValue::IntList(
+                values
+                    .iter()
+                    .map(|value| match value {
+                        Bson::Int32(i) => Ok(*i),
+                        Bson::Int64(i) => i32::try_from(*i).map_err(|_| invalid_value(value)),
+                        _ => Err(invalid_value(value)),
+                    })
+                    .collect::<Result<Vec<i32>, async_graphql::Error>>()?,
+            ),
+            ScalarOption::Boolean => SqlValue::BoolList(
+                values
+                    .iter()
+                    .map(|value| value.as_bool().ok_or_else(|| invalid_value(value)))
+                    .collect::<Result<Vec<bool>, async_graphql::Error>>()?,
+            ),
+            ScalarOption::UUID => SqlValue::UUIDList(
+                values
+                    .iter()
+                    .map(|value| {
+                        value
+                            .as_str()
+                            .and_then(|s| uuid::Uuid::parse_str(s).ok())
+                            .ok_or_else(|| invalid_value(value))
+                    })
+                    .collect::<Result<Vec<uuid::Uuid>, async_graphql::Error>>()?,
+            ),
+            ScalarOption::DateTime => SqlValue::DateTimeList(
+                values
+                    .iter()
+                    .map(|value| match value {
+                        Bson::DateTime(d) => Ok(d.to_chrono()),
+                        Bson::String(s) => chrono::DateTime::<chrono::Utc>::from_str(s)
+                            .map_err(|_| invalid_value(value)),
+                        _ => Err(invalid_value(value)),
+                    })
+                    .collect::<Result<Vec<chrono::DateTime<chrono::Utc>>, async_graphql::Error>>(
+                    )?,
+            ),
+            ScalarOption::Object => {
+                error!("Object list field {} is not a list column", field.name);
+                return Err(async_graphql::Error::new(format!(
+                    "Object list field {} is not a list column",
+                    field.name
+                )));
+            }
+        };
+
+        trace!("List Value: {:?}", list_value);
+        Ok(list_value)
+    }
+
+    /// Converts the value of a list column to the value stored by the dialect. Postgres stores
+    /// a native array, MySQL and SQLite store a JSON array.
+    pub fn get_list_column_value(
+        value: &Bson,
+        field: &ServiceEntityFieldConfig,
+        dialect: &DialectEnum,
+    ) -> Result<SqlValue, async_graphql::Error> {
+        let list_value = SqlDataSource::get_list_value(value, field)?;
+
+        let column_value = match dialect {
+            DialectEnum::POSTGRES => SqlValue::Array(Box::new(list_value)),
+            DialectEnum::MYSQL | DialectEnum::SQLITE => {
+                let json = match &list_value {
+                    SqlValue::StringList(list) => serde_json::to_string(list),
+                    SqlValue::IntList(list) => serde_json::to_string(list),
+                    SqlValue::BoolList(list) => serde_json::to_string(list),
+                    SqlValue::UUIDList(list) => serde_json::to_string(
+                        &list.iter().map(|u| u.to_string()).collect::<Vec<String>>(),
+                    ),
+                    SqlValue::DateTimeList(list) => serde_json::to_string(
+                        &list.iter().map(|d| d.to_rfc3339()).collect::<Vec<String>>(),
+                    ),
+                    _ => serde_json::to_string(&Vec::<String>::new()),
+                }
+                .map_err(|e| {
+                    error!("Failed to serialize list field {}: {:?}", field.name, e);
+                    async_graphql::Error::new(format!(
+                        "Failed to serialize list field {}: {:?}",
+                        field.name, e
+                    ))
+                })?;
+                SqlValue::Json(json)
+            }
+        };
+
+        Ok(column_value)
+    }
+}
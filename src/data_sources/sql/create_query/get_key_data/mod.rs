@@ -12,6 +12,7 @@ use crate::{
 
 use super::JoinClauses;
 
+mod get_list_value;
 mod parse_query_input;
 mod parse_values_input;
 
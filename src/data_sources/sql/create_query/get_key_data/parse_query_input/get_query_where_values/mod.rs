@@ -32,12 +32,28 @@ impl SqlDataSource {
         let mut where_values = Vec::new();
         let mut join_clauses = JoinClauses(Vec::new());
 
-        if value.as_array().is_some() {
+        // Keys of eager loaded entities are used as column names, make sure they are fields of
+        // the entity.
+        if let Some(as_type) = &as_type {
+            match subgraph_config.clone().get_entity(as_type) {
+                Some(entity) => {
+                    ServiceEntityConfig::get_field(entity, parent_key.to_string())?;
+                }
+                None => {
+                    error!("Could not find entity with name: {:?}", as_type);
+                    return Err(async_graphql::Error::new(format!(
+                        "Could not find entity with name: {}",
+                        as_type
+                    )));
+                }
+            }
+        }
+
+        if let Some(array) = value.as_array() {
             trace!("Parsing Values as Array");
-            let value = value.as_array().unwrap();
-            if value[0].as_document().is_some() {
+            if array[0].as_document().is_some() {
                 trace!("Receiving document type");
-                let value = value[0].as_document().unwrap();
+                let value = array[0].as_document().unwrap();
                 for (k, value) in value.iter() {
                     let (wk, wv, jc) = SqlDataSource::get_query_where_values(
                         value,
@@ -59,78 +75,8 @@ impl SqlDataSource {
                     }
                 }
             }
-            if value[0].as_str().is_some() {
-                trace!("Receiving string type");
-                // Check if all values are UUIDs
-                let is_valid = value.iter().all(|x| {
-                    let cleaned_value = clean_string(&x.to_string(), None);
-                    match uuid::Uuid::parse_str(&cleaned_value) {
-                        Ok(_) => true,
-                        Err(_) => false,
-                    }
-                });
-
-                if is_valid {
-                    trace!("Parsing Values as UUIDs");
-                    let values = value
-                        .iter()
-                        .map(|x| {
-                            let cleaned_value = clean_string(&x.to_string(), None);
-                            uuid::Uuid::parse_str(&cleaned_value).unwrap()
-                        })
-                        .collect::<Vec<uuid::Uuid>>();
-
-                    // If dialect is SQLITE, use strings, as SQLITE does not support UUIDs with
-                    // SQLX
-                    match dialect {
-                        DialectEnum::SQLITE => {
-                            where_values.push(SqlValue::StringList(
-                                values.iter().map(|x| x.to_string()).collect(),
-                            ));
-                            where_keys.push(parent_key.to_string());
-                        }
-                        _ => {
-                            where_values.push(SqlValue::UUIDList(values));
-                            where_keys.push(parent_key.to_string());
-                        }
-                    }
-                } else {
-                    let is_valid_dates = value.iter().all(|x| {
-                        let cleaned_value = clean_string(&x.to_string(), None);
-                        match chrono::DateTime::<chrono::Utc>::from_str(&cleaned_value) {
-                            Ok(_) => true,
-                            Err(_) => false,
-                        }
-                    });
-                    if is_valid_dates {
-                        trace!("Parsing Values as Dates");
-                        let values = value
-                            .iter()
-                            .map(|x| {
-                                let cleaned_value = clean_string(&x.to_string(), None);
-                                chrono::DateTime::from_str(&cleaned_value).unwrap()
-                            })
-                            .collect();
-                        where_values.push(SqlValue::DateTimeList(values));
-                        where_keys.push(parent_key.to_string());
-                    } else {
-                        trace!("Parsing Values as Strings");
-                        where_values.push(SqlValue::StringList(
-                            value
-                                .iter()
-                                .map(|x| clean_string(&x.to_string(), None))
-                                .collect(),
-                        ));
-                        where_keys.push(parent_key.to_string());
-                    }
-                }
-            } else if value[0].as_i32().is_some() || value[0].as_i64().is_some() {
-                let values = value.iter().map(|x| x.as_i32().unwrap()).collect();
-                where_values.push(SqlValue::IntList(values));
-                where_keys.push(parent_key.to_string());
-            } else if value[0].as_bool().is_some() {
-                let values = value.iter().map(|x| x.as_bool().unwrap()).collect();
-                where_values.push(SqlValue::BoolList(values));
+            if let Some(where_value) = SqlDataSource::get_where_value(value, dialect) {
+                where_values.push(where_value);
                 where_keys.push(parent_key.to_string());
             }
         } else if value.as_document().is_some() {
@@ -186,7 +132,11 @@ impl SqlDataSource {
                     if key.contains(".") {
                         where_keys.push(key);
                     } else {
-                        let key = format!("{}.{}", where_key_prefix, key);
+                        let key = format!(
+                            "{}.{}",
+                            where_key_prefix,
+                            SqlDataSource::quote_identifier(&key, dialect)
+                        );
                         where_keys.push(key);
                     }
                 }
@@ -194,53 +144,9 @@ impl SqlDataSource {
                     join_clauses.0.push(clause);
                 }
             }
-        } else {
-            trace!("Parsing Value: {:?}", value);
-            if value.as_str().is_some() {
-                trace!("Parsing String: {:?}", value);
-                let cleaned_value = clean_string(&value.to_string(), None);
-                match uuid::Uuid::parse_str(&cleaned_value) {
-                    Ok(uuid) => {
-                        trace!("Parsed UUID: {:?}", uuid);
-
-                        // Sqlite does not support UUIDs
-                        match dialect {
-                            DialectEnum::SQLITE | DialectEnum::MYSQL => {
-                                where_values.push(SqlValue::String(cleaned_value));
-                                where_keys.push(parent_key.to_string());
-                            }
-                            _ => {
-                                where_values.push(SqlValue::UUID(uuid));
-                                where_keys.push(parent_key.to_string());
-                            }
-                        }
-                    }
-                    Err(_) => match chrono::DateTime::from_str(&cleaned_value) {
-                        Ok(date) => {
-                            trace!("Parsed Date: {:?}", date);
-                            where_values.push(SqlValue::DateTime(date));
-                            where_keys.push(parent_key.to_string());
-                        }
-                        Err(_) => {
-                            trace!("Parsed String: {:?}", cleaned_value);
-                            where_values.push(SqlValue::String(cleaned_value));
-                            where_keys.push(parent_key.to_string());
-                        }
-                    },
-                }
-            } else if value.as_i32().is_some() {
-                where_values.push(SqlValue::Int(value.as_i32().unwrap()));
-                where_keys.push(parent_key.to_string());
-            } else if value.as_i64().is_some() {
-                where_values.push(SqlValue::Int(value.as_i64().unwrap() as i32));
-                where_keys.push(parent_key.to_string());
-            } else if value.as_bool().is_some() {
-                where_values.push(SqlValue::Bool(value.as_bool().unwrap()));
-                where_keys.push(parent_key.to_string());
-            } else if value.as_datetime().is_some() {
-                where_values.push(SqlValue::DateTime(value.as_datetime().unwrap().to_chrono()));
-                where_keys.push(parent_key.to_string());
-            }
+        } else if let Some(where_value) = SqlDataSource::get_where_value(value, dialect) {
+            where_values.push(where_value);
+            where_keys.push(parent_key.to_string());
         };
 
         trace!("Completed Get Query Where Values");
@@ -250,4 +156,113 @@ impl SqlDataSource {
 
         Ok((where_keys, where_values, join_clauses))
     }
+
+    /// Converts a scalar, or a list of scalars, provided in the query input to the where value
+    /// bound for it. Returns `None` for values that are not filtered on.
+    pub fn get_where_value(value: &Bson, dialect: &DialectEnum) -> Option<SqlValue> {
+        if let Some(value) = value.as_array() {
+            if value[0].as_str().is_some() {
+                trace!("Receiving string type");
+                // Check if all values are UUIDs
+                let is_valid = value.iter().all(|x| {
+                    let cleaned_value = clean_string(&x.to_string(), None);
+                    uuid::Uuid::parse_str(&cleaned_value).is_ok()
+                });
+
+                if is_valid {
+                    trace!("Parsing Values as UUIDs");
+                    let values = value
+                        .iter()
+                        .map(|x| {
+                            let cleaned_value = clean_string(&x.to_string(), None);
+                            uuid::Uuid::parse_str(&cleaned_value).unwrap()
+                        })
+                        .collect::<Vec<uuid::Uuid>>();
+
+                    // If dialect is SQLITE, use strings, as SQLITE does not support UUIDs with
+                    // SQLX
+                    return match dialect {
+                        DialectEnum::SQLITE => Some(SqlValue::StringList(
+                            values.iter().map(|x| x.to_string()).collect(),
+                        )),
+                        _ => Some(SqlValue::UUIDList(values)),
+                    };
+                }
+
+                let is_valid_dates = value.iter().all(|x| {
+                    let cleaned_value = clean_string(&x.to_string(), None);
+                    chrono::DateTime::<chrono::Utc>::from_str(&cleaned_value).is_ok()
+                });
+                if is_valid_dates {
+                    trace!("Parsing Values as Dates");
+                    let values = value
+                        .iter()
+                        .map(|x| {
+                            let cleaned_value = clean_string(&x.to_string(), None);
+                            chrono::DateTime::from_str(&cleaned_value).unwrap()
+                        })
+                        .collect();
+                    return Some(SqlValue::DateTimeList(values));
+                }
+
+                trace!("Parsing Values as Strings");
+                return Some(SqlValue::StringList(
+                    value
+                        .iter()
+                        .map(|x| clean_string(&x.to_string(), None))
+                        .collect(),
+                ));
+            } else if value[0].as_i32().is_some() || value[0].as_i64().is_some() {
+                let values = value.iter().map(|x| x.as_i32().unwrap()).collect();
+                return Some(SqlValue::IntList(values));
+            } else if value[0].as_bool().is_some() {
+                let values = value.iter().map(|x| x.as_bool().unwrap()).collect();
+                return Some(SqlValue::BoolList(values));
+            }
+            return None;
+        }
+
+        trace!("Parsing Value: {:?}", value);
+        if value.as_str().is_some() {
+            trace!("Parsing String: {:?}", value);
+            let cleaned_value = clean_string(&value.to_string(), None);
+            match uuid::Uuid::parse_str(&cleaned_value) {
+                Ok(uuid) => {
+                    trace!("Parsed UUID: {:?}", uuid);
+
+                    // Sqlite does not support UUIDs
+                    match dialect {
+                        DialectEnum::SQLITE | DialectEnum::MYSQL => {
+                            Some(SqlValue::String(cleaned_value))
+                        }
+                        _ => Some(SqlValue::UUID(uuid)),
+                    }
+                }
+                Err(_) => match chrono::DateTime::from_str(&cleaned_value) {
+                    Ok(date) => {
+                        trace!("Parsed Date: {:?}", date);
+                        Some(SqlValue::DateTime(date))
+                    }
+                    Err(_) => {
+                        trace!("Parsed String: {:?}", cleaned_value);
+                        Some(SqlValue::String(cleaned_value))
+                    }
+                },
+            }
+        } else if value.as_i32().is_some() {
+            Some(SqlValue::Int(value.as_i32().unwrap()))
+        } else if value.as_i64().is_some() {
+            let value = value.as_i64().unwrap();
+            match i32::try_from(value) {
+                Ok(value) => Some(SqlValue::Int(value)),
+                Err(_) => Some(SqlValue::BigInt(value)),
+            }
+        } else if value.as_bool().is_some() {
+            Some(SqlValue::Bool(value.as_bool().unwrap()))
+        } else {
+            value
+                .as_datetime()
+                .map(|value| SqlValue::DateTime(value.to_chrono()))
+        }
+    }
 }
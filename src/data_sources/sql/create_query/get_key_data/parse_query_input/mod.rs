@@ -100,13 +100,27 @@ impl SqlDataSource {
                             continue;
                         }
                         trace!("Adding Where Key: {:?}", key);
-                        let where_key = format!("{}.{}", where_key_prefix, key.to_string());
+                        let where_key = format!(
+                            "{}.{}",
+                            where_key_prefix,
+                            SqlDataSource::quote_identifier(&key, dialect)
+                        );
                         where_keys.push(where_key);
                     }
                     for join_clause in jc.0 {
                         join_clauses.0.push(join_clause);
                     }
                 }
+            } else if field.is_list_column() {
+                trace!("Parsing List Column Field");
+                where_keys.push(format!(
+                    "{}.{}",
+                    where_key_prefix,
+                    SqlDataSource::quote_identifier(key, dialect)
+                ));
+                where_values.push(SqlValue::Array(Box::new(SqlDataSource::get_list_value(
+                    value, &field,
+                )?)));
             } else {
                 trace!("Parsing Non Eager Loaded Field");
                 let (parsed_where_keys, parsed_where_values, parsed_join_clauses) =
@@ -124,7 +138,11 @@ impl SqlDataSource {
                         where_keys.push(key);
                         continue;
                     }
-                    let key = format!("{}.{}", where_key_prefix, key.to_string());
+                    let key = format!(
+                        "{}.{}",
+                        where_key_prefix,
+                        SqlDataSource::quote_identifier(&key, dialect)
+                    );
                     where_keys.push(key);
                 }
                 for value in parsed_where_values {
@@ -176,15 +194,7 @@ impl SqlDataSource {
                 }
             };
 
-            let table_name = if let Some(ds) = child_entity.data_source {
-                if ds.table.is_some() {
-                    ds.table.unwrap()
-                } else {
-                    child_entity.name.clone()
-                }
-            } else {
-                child_entity.name.clone()
-            };
+            let table_name = ServiceEntityConfig::get_sql_table_name(&child_entity);
 
             let parent_entity_data_source =
                 ServiceEntityConfig::get_entity_data_source(&parent_entity);
@@ -197,31 +207,45 @@ impl SqlDataSource {
                 parent_entity.name.clone()
             };
 
-            let d = match dialect {
-                DialectEnum::POSTGRES => "\"",
-                DialectEnum::MYSQL | DialectEnum::SQLITE => "`",
-            };
-
-            // Create the join clauses, to be used later.
-            let join_clause = format!(
-                " JOIN {} AS {} ON {}.{} = {}.{} ",
-                table_name,
-                format!(
-                    "{d}{}.{}.{}{d}",
+            let child_alias = SqlDataSource::quote_identifier(
+                &format!(
+                    "{}.{}.{}",
                     table_name,
                     parent_entity.name.clone(),
                     field.name.clone()
                 ),
-                format!(
-                    "{d}{}.{}.{}{d}",
-                    table_name,
-                    parent_entity.name.clone(),
-                    field.name.clone()
-                ),
-                field.join_on.clone().unwrap(),
-                parent_alias.unwrap_or(format!("{d}{}{d}", parent_table_name)),
-                field.join_from.clone().unwrap_or(field.name.clone())
+                dialect,
             );
+            let parent_alias = parent_alias
+                .unwrap_or(SqlDataSource::quote_identifier(&parent_table_name, dialect));
+
+            // Create the join clauses, to be used later.
+            let child_schema = SqlDataSource::get_table_schema(&child_entity, subgraph_config);
+            let mut join_clause = format!(
+                " JOIN {} AS {} ON {}.{} = {}.{}",
+                SqlDataSource::quote_table_name(&table_name, child_schema.as_deref(), dialect),
+                child_alias,
+                child_alias,
+                SqlDataSource::quote_identifier(&field.join_on.clone().unwrap(), dialect),
+                parent_alias,
+                SqlDataSource::quote_identifier(
+                    &field.join_from.clone().unwrap_or(field.name.clone()),
+                    dialect
+                )
+            );
+
+            // Composite keys join on each additional pair of fields.
+            for join_key in field.join_keys.clone().unwrap_or(vec![]) {
+                join_clause.push_str(&format!(
+                    " AND {}.{} = {}.{}",
+                    child_alias,
+                    SqlDataSource::quote_identifier(&join_key.join_on, dialect),
+                    parent_alias,
+                    SqlDataSource::quote_identifier(&join_key.join_from, dialect)
+                ));
+            }
+            join_clause.push(' ');
+
             Some(join_clause)
         } else {
             None
@@ -253,6 +277,7 @@ impl SqlDataSource {
         } else {
             entity.name.clone()
         };
+        where_key_prefix = SqlDataSource::quote_identifier(&where_key_prefix, dialect);
 
         // If the field is a eager loaded field, get the correct prefix
         if field.eager.is_some() && !disable_eager_loading {
@@ -286,16 +311,14 @@ impl SqlDataSource {
                 child_entity.name.clone()
             };
 
-            let d = match dialect {
-                DialectEnum::POSTGRES => "\"",
-                DialectEnum::MYSQL | DialectEnum::SQLITE => "`",
-            };
-
-            where_key_prefix = format!(
-                "{d}{}.{}.{}{d}",
-                table_name,
-                entity.name.clone(),
-                field.name.clone()
+            where_key_prefix = SqlDataSource::quote_identifier(
+                &format!(
+                    "{}.{}.{}",
+                    table_name,
+                    entity.name.clone(),
+                    field.name.clone()
+                ),
+                dialect,
             );
         }
         trace!("Where Key Prefix: {:?}", where_key_prefix);
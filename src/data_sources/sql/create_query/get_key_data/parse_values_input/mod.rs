@@ -62,7 +62,16 @@ impl SqlDataSource {
                 }
             };
 
-            let sql_value = value.to_sql_value(Some(dialect))?;
+            let field = field.unwrap();
+            let sql_value = if field.is_list_column() {
+                if is_where_clause {
+                    SqlValue::Array(Box::new(SqlDataSource::get_list_value(value, &field)?))
+                } else {
+                    SqlDataSource::get_list_column_value(value, &field, dialect)?
+                }
+            } else {
+                value.to_sql_value(Some(dialect))?
+            };
 
             if is_where_clause {
                 where_keys.push(key.to_string());
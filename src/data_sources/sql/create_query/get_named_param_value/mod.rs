@@ -0,0 +1,62 @@
+use bson::{Bson, Document};
+use evalexpr::{eval, Value};
+use log::{debug, error};
+
+use crate::{
+    configuration::subgraph::{
+        data_sources::sql::DialectEnum, entities::service_entity_field::ServiceEntityFieldConfig,
+    },
+    data_sources::sql::SqlDataSource,
+    sql_value::{FromBson, SqlValue},
+};
+
+impl SqlDataSource {
+    /// Gets the value of a named query parameter from the input. Optional parameters missing
+    /// from the input use the `default_value` of the parameter, or null without one.
+    pub fn get_named_param_value(
+        param: &ServiceEntityFieldConfig,
+        params: &Document,
+        dialect: &DialectEnum,
+    ) -> Result<SqlValue, async_graphql::Error> {
+        debug!("Getting Named Param Value: {}", param.name);
+
+        match params.get(&param.name) {
+            Some(value) if value != &Bson::Null => return value.to_sql_value(Some(dialect)),
+            _ => {}
+        }
+
+        if param.required.unwrap_or(false) {
+            error!("Missing parameter {}", param.name);
+            return Err(async_graphql::Error::new(format!(
+                "Missing parameter: {}",
+                param.name
+            )));
+        }
+
+        let default_value_expr = match &param.default_value {
+            Some(default_value_expr) => default_value_expr,
+            None => return Ok(SqlValue::Null(param.scalar.clone())),
+        };
+
+        let default_value = match eval(default_value_expr) {
+            Ok(Value::String(value)) if value == "null" => {
+                return Ok(SqlValue::Null(param.scalar.clone()))
+            }
+            Ok(Value::String(value)) => Bson::String(value),
+            Ok(Value::Int(value)) => Bson::Int64(value),
+            Ok(Value::Boolean(value)) => Bson::Boolean(value),
+            _ => {
+                error!(
+                    "Invalid default value {} for parameter {}",
+                    default_value_expr, param.name
+                );
+                return Err(async_graphql::Error::new(format!(
+                    "Invalid Default Value Expression: {}",
+                    default_value_expr
+                )));
+            }
+        };
+
+        default_value.to_sql_value(Some(dialect))
+    }
+}
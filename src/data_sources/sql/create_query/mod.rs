@@ -10,14 +10,22 @@ use crate::{
 
 use super::{SqlDataSource, SqlQuery};
 
+pub mod create_cached_query;
 pub mod create_create_one_query;
 pub mod create_find_many_query;
 pub mod create_find_one_query;
+pub mod create_join_through_query;
+pub mod create_named_query;
 pub mod create_nested_query_recursive;
+pub mod create_pagination_clause;
+pub mod create_primary_key_query;
+pub mod create_set_clause;
 pub mod create_update_many_query;
 pub mod create_update_one_query;
 pub mod create_where_clause;
+pub mod get_identifier_quote;
 pub mod get_key_data;
+pub mod get_named_param_value;
 pub mod get_placeholder;
 
 #[derive(Debug, Clone)]
@@ -34,7 +42,7 @@ impl SqlDataSource {
     ) -> Result<SqlQuery, async_graphql::Error> {
         debug!("Creating SQL Query");
 
-        let (_where_keys, mut where_values, value_keys, values, join_clauses) =
+        let (_where_keys, mut where_values, value_keys, mut values, join_clauses) =
             SqlDataSource::get_key_data(
                 &input,
                 entity,
@@ -45,6 +53,13 @@ impl SqlDataSource {
             )?;
         let mut count_query = None;
         let mut identifier_query = None;
+        let schema = SqlDataSource::get_table_schema(entity, subgraph_config);
+
+        // Increments are bound after the values of update resolvers.
+        if resolver_type == ResolverType::UpdateOne || resolver_type == ResolverType::UpdateMany {
+            let update_operators = SqlDataSource::get_update_operators(&input)?;
+            values.extend(update_operators.inc_values);
+        }
 
         // Generate the query string and get the where values.
         let query = match resolver_type {
@@ -76,9 +91,12 @@ impl SqlDataSource {
                 count_query = Some(count_q);
                 query_string
             }
-            ResolverType::CreateOne => {
-                SqlDataSource::create_create_one_query(table_name, &value_keys, &dialect)?
-            }
+            ResolverType::CreateOne => SqlDataSource::create_create_one_query(
+                table_name,
+                schema.as_deref(),
+                &value_keys,
+                &dialect,
+            )?,
             ResolverType::UpdateOne => {
                 let (query_string, combined_where_value, _combined_where_keys, identifier_q) =
                     SqlDataSource::create_update_one_query(
@@ -116,7 +134,9 @@ impl SqlDataSource {
             identifier_query,
             where_values,
             values,
+            value_keys,
             table: table_name.to_string(),
+            schema,
         };
 
         debug!("Query: {:?}", sql_query);
@@ -0,0 +1,45 @@
+use bson::Document;
+use log::{debug, trace};
+
+use crate::configuration::subgraph::{
+    data_sources::sql::SqlQueryConfig, mutations::ServiceMutationConfig,
+};
+
+use super::{
+    services::{ResponseRow, Services},
+    SqlDataSource, SqlQuery,
+};
+
+impl SqlDataSource {
+    /// Executes a custom mutation with the arguments provided by the client. Rows are returned
+    /// when the mutation returns an entity, else the number of affected rows.
+    pub async fn execute_mutation(
+        &self,
+        mutation: &ServiceMutationConfig,
+        args: &Document,
+    ) -> Result<(Vec<Option<ResponseRow>>, u64), async_graphql::Error> {
+        debug!("Executing Custom Mutation: {}", mutation.name);
+
+        let named_query = SqlQueryConfig {
+            name: mutation.name.clone(),
+            sql: mutation.sql.clone(),
+            params: mutation.args.clone(),
+        };
+        let (query, where_values) =
+            SqlDataSource::bind_named_params(&named_query, args, &self.config.dialect)?;
+
+        let sql_query = SqlQuery {
+            query: format!("{};", query),
+            count_query: None,
+            identifier_query: None,
+            values: Vec::new(),
+            where_values,
+            value_keys: Vec::new(),
+            table: mutation.name.clone(),
+            schema: None,
+        };
+        trace!("Custom Mutation Query: {:?}", sql_query);
+
+        Services::execute_statement(&self.pool, &sql_query, mutation.returns.is_some()).await
+    }
+}
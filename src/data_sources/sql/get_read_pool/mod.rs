@@ -0,0 +1,53 @@
+use std::sync::atomic::Ordering;
+
+use log::{debug, trace};
+
+use crate::configuration::subgraph::data_sources::sql::SqlDataSourceConfig;
+
+use super::{PoolEnum, SqlDataSource};
+
+impl SqlDataSource {
+    /// Gets the pool to read from, rotating through the healthy replicas, along with the index
+    /// of the replica. Falls back to the primary when no replicas are configured or none are
+    /// healthy.
+    pub fn get_read_pool(&self) -> (Option<usize>, &PoolEnum) {
+        if self.replica_pools.is_empty() {
+            return (None, &self.pool);
+        }
+        let start = self.replica_index.fetch_add(1, Ordering::Relaxed);
+        for offset in 0..self.replica_pools.len() {
+            let index = (start + offset) % self.replica_pools.len();
+            if self.replica_health[index].load(Ordering::Relaxed) {
+                trace!("Reading from replica {} of {}", index, self.config.name);
+                return (Some(index), &self.replica_pools[index]);
+            }
+        }
+        debug!(
+            "No Healthy Replicas, Reading From Primary: {}",
+            self.config.name
+        );
+        (None, &self.pool)
+    }
+
+    /// Creates a config for each replica of the data source, sharing the pool options of the
+    /// primary.
+    pub fn get_replica_configs(
+        sql_data_source_config: &SqlDataSourceConfig,
+    ) -> Vec<SqlDataSourceConfig> {
+        debug!("Getting Replica Configs");
+        sql_data_source_config
+            .replicas
+            .clone()
+            .unwrap_or(vec![])
+            .into_iter()
+            .enumerate()
+            .map(|(i, uri)| SqlDataSourceConfig {
+                name: format!("{}_replica_{}", sql_data_source_config.name, i),
+                uri,
+                replicas: None,
+                migrations_path: None,
+                ..sql_data_source_config.clone()
+            })
+            .collect()
+    }
+}
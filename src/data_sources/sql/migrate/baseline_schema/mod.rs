@@ -0,0 +1,52 @@
+use std::{fs, path::Path};
+
+use log::{debug, error, info};
+
+use crate::{
+    configuration::subgraph::{data_sources::sql::SqlDataSourceConfig, SubGraphConfig},
+    data_sources::sql::{migrate::schema_snapshot::SchemaSnapshot, SqlDataSource},
+};
+
+impl SqlDataSource {
+    /// Writes the snapshot of the entities without generating a migration, marking an existing
+    /// database as matching the entities. Later migrations are generated from this snapshot.
+    pub fn baseline_schema(
+        sql_data_source_config: &SqlDataSourceConfig,
+        subgraph_config: &SubGraphConfig,
+    ) -> Result<(), async_graphql::Error> {
+        debug!("Writing Baseline Schema Snapshot");
+
+        let migrations_path = match &sql_data_source_config.migrations_path {
+            Some(path) => path,
+            None => {
+                error!(
+                    "Migrations path required to baseline the schema: {}",
+                    sql_data_source_config.name
+                );
+                return Err(async_graphql::Error::new(format!(
+                    "Migrations path required to baseline the schema: {}",
+                    sql_data_source_config.name
+                )));
+            }
+        };
+
+        let current = SchemaSnapshot::from_entities(
+            &sql_data_source_config.name,
+            &sql_data_source_config.dialect,
+            subgraph_config,
+        )?;
+
+        let path = Path::new(migrations_path);
+        fs::create_dir_all(path).map_err(|e| {
+            error!("Failed to create migrations path {:?}: {:?}", path, e);
+            async_graphql::Error::new(format!("Failed to create migrations path: {}", e))
+        })?;
+        current.write(migrations_path)?;
+
+        info!(
+            "Wrote Baseline Schema Snapshot For Data Source: {}",
+            sql_data_source_config.name
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,550 @@
+use std::{fs, path::Path};
+
+use log::{debug, error, info, trace};
+
+use crate::{
+    configuration::subgraph::{
+        data_sources::sql::{DialectEnum, SqlDataSourceConfig},
+        SubGraphConfig,
+    },
+    data_sources::sql::{
+        migrate::schema_snapshot::{
+            ColumnSnapshot, ForeignKeySnapshot, SchemaSnapshot, TableSnapshot,
+        },
+        PoolEnum, SqlDataSource,
+    },
+};
+
+/// A single schema change, along with the statement that undoes it.
+#[derive(Debug, Clone)]
+pub struct MigrationStep {
+    pub up: String,
+    pub down: String,
+}
+
+impl SqlDataSource {
+    /// Diffs the entities of the data source against the last snapshot, or the live schema
+    /// before the first snapshot, and writes the up and down migration files to the migrations
+    /// path. Returns the version of the generated migration, or none if nothing changed.
+    pub async fn generate_migration(
+        pool: &PoolEnum,
+        sql_data_source_config: &SqlDataSourceConfig,
+        subgraph_config: &SubGraphConfig,
+        migration_name: Option<String>,
+    ) -> Result<Option<i64>, async_graphql::Error> {
+        debug!("Generating Migration");
+
+        let migrations_path = match &sql_data_source_config.migrations_path {
+            Some(path) => path,
+            None => {
+                error!(
+                    "Migrations path required to generate migrations: {}",
+                    sql_data_source_config.name
+                );
+                return Err(async_graphql::Error::new(format!(
+                    "Migrations path required to generate migrations: {}",
+                    sql_data_source_config.name
+                )));
+            }
+        };
+
+        let current = SchemaSnapshot::from_entities(
+            &sql_data_source_config.name,
+            &sql_data_source_config.dialect,
+            subgraph_config,
+        )?;
+        let previous = match SchemaSnapshot::read(migrations_path)? {
+            Some(previous) => previous,
+            None => SqlDataSource::introspect_schema(pool, &current).await?,
+        };
+
+        let steps =
+            SqlDataSource::diff_schema(&previous, &current, &sql_data_source_config.dialect);
+
+        if steps.is_empty() {
+            info!(
+                "No schema changes detected for data source: {}",
+                sql_data_source_config.name
+            );
+            return Ok(None);
+        }
+
+        let version = chrono::Utc::now()
+            .format("%Y%m%d%H%M%S")
+            .to_string()
+            .parse::<i64>()
+            .unwrap();
+        let name = migration_name.unwrap_or("subgraph_generated".to_string());
+        let name = name.replace(' ', "_");
+
+        let up = steps
+            .iter()
+            .map(|step| step.up.clone())
+            .collect::<Vec<String>>()
+            .join("\n");
+        let down = steps
+            .iter()
+            .rev()
+            .map(|step| step.down.clone())
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let path = Path::new(migrations_path);
+        fs::create_dir_all(path).map_err(|e| {
+            error!("Failed to create migrations path {:?}: {:?}", path, e);
+            async_graphql::Error::new(format!("Failed to create migrations path: {}", e))
+        })?;
+        let up_path = path.join(format!("{}_{}.up.sql", version, name));
+        let down_path = path.join(format!("{}_{}.down.sql", version, name));
+
+        for (file_path, contents) in [(&up_path, up), (&down_path, down)] {
+            fs::write(
+                file_path,
+                format!("-- Generated by subgraph\n{}\n", contents),
+            )
+            .map_err(|e| {
+                error!("Failed to write migration {:?}: {:?}", file_path, e);
+                async_graphql::Error::new(format!("Failed to write migration: {}", e))
+            })?;
+        }
+
+        current.write(migrations_path)?;
+
+        info!("Generated Migration: {:?}", up_path);
+        Ok(Some(version))
+    }
+
+    /// Creates the steps needed to move the database from the previous snapshot to the current.
+    pub fn diff_schema(
+        previous: &SchemaSnapshot,
+        current: &SchemaSnapshot,
+        dialect: &DialectEnum,
+    ) -> Vec<MigrationStep> {
+        debug!("Diffing Schema Snapshots");
+
+        let mut create_tables = vec![];
+        let mut alter_tables = vec![];
+        let mut drop_tables = vec![];
+
+        // Created tables are ordered so referenced tables are created first.
+        for table in SqlDataSource::sort_tables_by_reference(&current.tables) {
+            match previous.get_table(table.schema.as_deref(), &table.name) {
+                Some(previous_table) => {
+                    alter_tables.extend(SqlDataSource::diff_table(previous_table, table, dialect))
+                }
+                None => create_tables.extend(SqlDataSource::create_table_steps(table, dialect)),
+            }
+        }
+
+        for table in SqlDataSource::sort_tables_by_reference(&previous.tables)
+            .into_iter()
+            .rev()
+        {
+            if current
+                .get_table(table.schema.as_deref(), &table.name)
+                .is_none()
+            {
+                let mut steps = SqlDataSource::create_table_steps(table, dialect);
+                steps.reverse();
+                drop_tables.extend(steps.into_iter().map(|step| MigrationStep {
+                    up: step.down,
+                    down: step.up,
+                }));
+            }
+        }
+
+        let mut steps = vec![];
+        steps.extend(create_tables);
+        steps.extend(alter_tables);
+        steps.extend(drop_tables);
+
+        trace!("Migration Steps: {:?}", steps);
+        steps
+    }
+
+    /// Steps to create a table. Postgres and MySQL add foreign keys after the table exists.
+    fn create_table_steps(table: &TableSnapshot, dialect: &DialectEnum) -> Vec<MigrationStep> {
+        let d = SqlDataSource::get_identifier_quote(dialect);
+
+        let mut definitions = table
+            .columns
+            .iter()
+            .map(|column| SqlDataSource::column_definition(column, table, dialect))
+            .collect::<Vec<String>>();
+
+        // SQLite declares integer primary keys inline, to alias the rowid.
+        let is_inline_primary_key = SqlDataSource::is_sqlite_rowid(table, dialect);
+
+        if !table.primary_key.is_empty() && !is_inline_primary_key {
+            let constraint = match dialect {
+                DialectEnum::POSTGRES => format!("CONSTRAINT {d}{}_pkey{d} ", table.name),
+                _ => String::new(),
+            };
+            definitions.push(format!(
+                "{}PRIMARY KEY ({})",
+                constraint,
+                SqlDataSource::quote_list(&table.primary_key, dialect)
+            ));
+        }
+
+        // SQLite references tables in the same schema, so the reference is not qualified.
+        if dialect == &DialectEnum::SQLITE {
+            for foreign_key in &table.foreign_keys {
+                definitions.push(format!(
+                    "FOREIGN KEY ({d}{}{d}) REFERENCES {d}{}{d} ({d}{}{d})",
+                    foreign_key.column, foreign_key.references_table, foreign_key.references_column
+                ));
+            }
+        }
+
+        let mut steps = vec![MigrationStep {
+            up: format!(
+                "CREATE TABLE {} (\n  {}\n);",
+                table.quote_name(dialect),
+                definitions.join(",\n  ")
+            ),
+            down: format!("DROP TABLE {};", table.quote_name(dialect)),
+        }];
+
+        if dialect != &DialectEnum::SQLITE {
+            for foreign_key in &table.foreign_keys {
+                steps.push(SqlDataSource::add_foreign_key_step(
+                    table,
+                    foreign_key,
+                    dialect,
+                ));
+            }
+        }
+
+        steps
+    }
+
+    fn diff_table(
+        previous: &TableSnapshot,
+        current: &TableSnapshot,
+        dialect: &DialectEnum,
+    ) -> Vec<MigrationStep> {
+        let d = SqlDataSource::get_identifier_quote(dialect);
+        let table_name = current.quote_name(dialect);
+        let mut steps = vec![];
+
+        // Foreign keys are dropped before columns change.
+        for foreign_key in &previous.foreign_keys {
+            if !current.foreign_keys.contains(foreign_key) {
+                let step = SqlDataSource::add_foreign_key_step(current, foreign_key, dialect);
+                steps.push(MigrationStep {
+                    up: step.down,
+                    down: step.up,
+                });
+            }
+        }
+
+        for column in &current.columns {
+            match previous.get_column(&column.name) {
+                None => steps.push(MigrationStep {
+                    up: format!(
+                        "ALTER TABLE {} ADD COLUMN {};",
+                        table_name,
+                        SqlDataSource::column_definition(column, current, dialect)
+                    ),
+                    down: format!(
+                        "ALTER TABLE {} DROP COLUMN {d}{}{d};",
+                        table_name, column.name
+                    ),
+                }),
+                Some(previous_column) if previous_column != column => {
+                    steps.extend(SqlDataSource::alter_column_steps(
+                        previous_column,
+                        column,
+                        previous,
+                        current,
+                        dialect,
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        if previous.primary_key != current.primary_key {
+            steps.push(SqlDataSource::alter_primary_key_step(
+                previous, current, dialect,
+            ));
+        }
+
+        for foreign_key in &current.foreign_keys {
+            if !previous.foreign_keys.contains(foreign_key) {
+                steps.push(SqlDataSource::add_foreign_key_step(
+                    current,
+                    foreign_key,
+                    dialect,
+                ));
+            }
+        }
+
+        for column in &previous.columns {
+            if current.get_column(&column.name).is_none() {
+                steps.push(MigrationStep {
+                    up: format!(
+                        "ALTER TABLE {} DROP COLUMN {d}{}{d};",
+                        table_name, column.name
+                    ),
+                    down: format!(
+                        "ALTER TABLE {} ADD COLUMN {};",
+                        table_name,
+                        SqlDataSource::column_definition(column, previous, dialect)
+                    ),
+                });
+            }
+        }
+
+        steps
+    }
+
+    fn alter_column_steps(
+        previous: &ColumnSnapshot,
+        current: &ColumnSnapshot,
+        previous_table: &TableSnapshot,
+        current_table: &TableSnapshot,
+        dialect: &DialectEnum,
+    ) -> Vec<MigrationStep> {
+        let d = SqlDataSource::get_identifier_quote(dialect);
+        let table_name = current_table.quote_name(dialect);
+
+        match dialect {
+            DialectEnum::POSTGRES => {
+                let mut steps = vec![];
+                if previous.data_type != current.data_type {
+                    steps.push(MigrationStep {
+                        up: format!(
+                            "ALTER TABLE {} ALTER COLUMN {d}{}{d} TYPE {} USING {d}{}{d}::{};",
+                            table_name,
+                            current.name,
+                            current.data_type,
+                            current.name,
+                            current.data_type
+                        ),
+                        down: format!(
+                            "ALTER TABLE {} ALTER COLUMN {d}{}{d} TYPE {} USING {d}{}{d}::{};",
+                            table_name,
+                            previous.name,
+                            previous.data_type,
+                            previous.name,
+                            previous.data_type
+                        ),
+                    });
+                }
+                if previous.not_null != current.not_null {
+                    let set = |not_null: bool| if not_null { "SET" } else { "DROP" };
+                    steps.push(MigrationStep {
+                        up: format!(
+                            "ALTER TABLE {} ALTER COLUMN {d}{}{d} {} NOT NULL;",
+                            table_name,
+                            current.name,
+                            set(current.not_null)
+                        ),
+                        down: format!(
+                            "ALTER TABLE {} ALTER COLUMN {d}{}{d} {} NOT NULL;",
+                            table_name,
+                            previous.name,
+                            set(previous.not_null)
+                        ),
+                    });
+                }
+                steps
+            }
+            DialectEnum::MYSQL => vec![MigrationStep {
+                up: format!(
+                    "ALTER TABLE {} MODIFY COLUMN {};",
+                    table_name,
+                    SqlDataSource::column_definition(current, current_table, dialect)
+                ),
+                down: format!(
+                    "ALTER TABLE {} MODIFY COLUMN {};",
+                    table_name,
+                    SqlDataSource::column_definition(previous, previous_table, dialect)
+                ),
+            }],
+            DialectEnum::SQLITE => {
+                let note = format!(
+                    "-- SQLite can not alter column {} on {}. Rebuild the table manually.",
+                    current.name, current_table.name
+                );
+                vec![MigrationStep {
+                    up: note.clone(),
+                    down: note,
+                }]
+            }
+        }
+    }
+
+    fn alter_primary_key_step(
+        previous: &TableSnapshot,
+        current: &TableSnapshot,
+        dialect: &DialectEnum,
+    ) -> MigrationStep {
+        let d = SqlDataSource::get_identifier_quote(dialect);
+        let table_name = current.quote_name(dialect);
+
+        let statement = |from: &TableSnapshot, to: &TableSnapshot| -> String {
+            let add = if to.primary_key.is_empty() {
+                String::new()
+            } else {
+                format!(
+                    "ADD PRIMARY KEY ({})",
+                    SqlDataSource::quote_list(&to.primary_key, dialect)
+                )
+            };
+            match dialect {
+                DialectEnum::POSTGRES => {
+                    let mut actions = vec![];
+                    if !from.primary_key.is_empty() {
+                        actions.push(format!("DROP CONSTRAINT {d}{}_pkey{d}", current.name));
+                    }
+                    if !add.is_empty() {
+                        actions.push(format!(
+                            "ADD CONSTRAINT {d}{}_pkey{d} PRIMARY KEY ({})",
+                            current.name,
+                            SqlDataSource::quote_list(&to.primary_key, dialect)
+                        ));
+                    }
+                    format!("ALTER TABLE {} {};", table_name, actions.join(", "))
+                }
+                DialectEnum::MYSQL => {
+                    let mut actions = vec![];
+                    if !from.primary_key.is_empty() {
+                        actions.push("DROP PRIMARY KEY".to_string());
+                    }
+                    if !add.is_empty() {
+                        actions.push(add);
+                    }
+                    format!("ALTER TABLE {} {};", table_name, actions.join(", "))
+                }
+                DialectEnum::SQLITE => format!(
+                    "-- SQLite can not alter the primary key of {}. Rebuild the table manually.",
+                    current.name
+                ),
+            }
+        };
+
+        MigrationStep {
+            up: statement(previous, current),
+            down: statement(current, previous),
+        }
+    }
+
+    fn add_foreign_key_step(
+        table: &TableSnapshot,
+        foreign_key: &ForeignKeySnapshot,
+        dialect: &DialectEnum,
+    ) -> MigrationStep {
+        let d = SqlDataSource::get_identifier_quote(dialect);
+        let table_name = table.quote_name(dialect);
+        let constraint_name = format!("fk_{}_{}", table.name, foreign_key.column);
+
+        match dialect {
+            DialectEnum::POSTGRES | DialectEnum::MYSQL => {
+                let drop = match dialect {
+                    DialectEnum::MYSQL => "DROP FOREIGN KEY",
+                    _ => "DROP CONSTRAINT",
+                };
+                MigrationStep {
+                    up: format!(
+                        "ALTER TABLE {} ADD CONSTRAINT {d}{}{d} FOREIGN KEY ({d}{}{d}) REFERENCES {} ({d}{}{d});",
+                        table_name,
+                        constraint_name,
+                        foreign_key.column,
+                        foreign_key.quote_references_table(dialect),
+                        foreign_key.references_column
+                    ),
+                    down: format!(
+                        "ALTER TABLE {} {} {d}{}{d};",
+                        table_name, drop, constraint_name
+                    ),
+                }
+            }
+            DialectEnum::SQLITE => {
+                let note = format!(
+                    "-- SQLite can not alter foreign key {} on {}. Rebuild the table manually.",
+                    foreign_key.column, table.name
+                );
+                MigrationStep {
+                    up: note.clone(),
+                    down: note,
+                }
+            }
+        }
+    }
+
+    fn column_definition(
+        column: &ColumnSnapshot,
+        table: &TableSnapshot,
+        dialect: &DialectEnum,
+    ) -> String {
+        let d = SqlDataSource::get_identifier_quote(dialect);
+
+        let definition = if column.auto_increment {
+            match dialect {
+                DialectEnum::POSTGRES => "SERIAL NOT NULL".to_string(),
+                DialectEnum::MYSQL => format!("{} NOT NULL AUTO_INCREMENT", column.data_type),
+                DialectEnum::SQLITE => {
+                    if SqlDataSource::is_sqlite_rowid(table, dialect) {
+                        "INTEGER PRIMARY KEY AUTOINCREMENT".to_string()
+                    } else {
+                        format!("{} NOT NULL", column.data_type)
+                    }
+                }
+            }
+        } else if column.not_null {
+            format!("{} NOT NULL", column.data_type)
+        } else {
+            column.data_type.clone()
+        };
+
+        format!("{d}{}{d} {}", column.name, definition)
+    }
+
+    fn is_sqlite_rowid(table: &TableSnapshot, dialect: &DialectEnum) -> bool {
+        dialect == &DialectEnum::SQLITE
+            && table.primary_key.len() == 1
+            && table
+                .get_column(&table.primary_key[0])
+                .map(|column| column.auto_increment)
+                .unwrap_or(false)
+    }
+
+    fn quote_list(names: &[String], dialect: &DialectEnum) -> String {
+        let d = SqlDataSource::get_identifier_quote(dialect);
+        names
+            .iter()
+            .map(|name| format!("{d}{}{d}", name))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    /// Orders tables so that tables referenced by foreign keys come first.
+    fn sort_tables_by_reference(tables: &[TableSnapshot]) -> Vec<&TableSnapshot> {
+        let mut sorted: Vec<&TableSnapshot> = vec![];
+        let mut remaining: Vec<&TableSnapshot> = tables.iter().collect();
+
+        while !remaining.is_empty() {
+            let position = remaining.iter().position(|table| {
+                table.foreign_keys.iter().all(|foreign_key| {
+                    let is_referenced = |other: &&TableSnapshot| {
+                        other.is_table(
+                            foreign_key.references_schema.as_deref(),
+                            &foreign_key.references_table,
+                        )
+                    };
+                    is_referenced(table)
+                        || sorted.iter().any(is_referenced)
+                        || !remaining.iter().any(is_referenced)
+                })
+            });
+            // Circular references keep their original order.
+            let position = position.unwrap_or(0);
+            sorted.push(remaining.remove(position));
+        }
+
+        sorted
+    }
+}
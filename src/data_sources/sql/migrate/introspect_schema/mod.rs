@@ -0,0 +1,350 @@
+use log::{debug, error, trace};
+use regex::Regex;
+use sqlx::Row;
+
+use crate::{
+    configuration::subgraph::data_sources::sql::DialectEnum,
+    data_sources::sql::{
+        migrate::schema_snapshot::{
+            ColumnSnapshot, ForeignKeySnapshot, SchemaSnapshot, TableSnapshot,
+        },
+        PoolEnum, SqlDataSource,
+    },
+};
+
+/// Tables managed by the migrator rather than by entities.
+const IGNORED_TABLES: [&str; 1] = ["_sqlx_migrations"];
+
+/// A column as reported by the database, before its type is normalized.
+struct LiveColumn {
+    name: String,
+    data_type: String,
+    not_null: bool,
+    auto_increment: bool,
+}
+
+impl SqlDataSource {
+    /// Creates the snapshot of the live database, used when no snapshot was saved yet. Every
+    /// table in the schemas of the entities is read with its column types, nullability, primary
+    /// key and foreign keys, so drift from the entities and tables or columns which are no
+    /// longer configured are migrated.
+    pub async fn introspect_schema(
+        pool: &PoolEnum,
+        current: &SchemaSnapshot,
+    ) -> Result<SchemaSnapshot, async_graphql::Error> {
+        debug!("Introspecting Live Schema");
+
+        let mut schemas: Vec<Option<String>> = vec![];
+        for table in &current.tables {
+            if !schemas.contains(&table.schema) {
+                schemas.push(table.schema.clone());
+            }
+        }
+
+        let mut tables = vec![];
+        for schema in &schemas {
+            let table_names = SqlDataSource::get_live_table_names(pool, schema.as_deref()).await?;
+            trace!("Live Tables of {:?}: {:?}", schema, table_names);
+            for table_name in table_names {
+                if IGNORED_TABLES.contains(&table_name.as_str()) {
+                    continue;
+                }
+                let table =
+                    SqlDataSource::get_live_table(pool, schema.as_deref(), &table_name).await?;
+                trace!("Live Table: {:?}", table);
+                tables.push(table);
+            }
+        }
+
+        Ok(SchemaSnapshot { tables })
+    }
+
+    /// Gets the names of the tables in a schema, or in the default schema.
+    async fn get_live_table_names(
+        pool: &PoolEnum,
+        schema: Option<&str>,
+    ) -> Result<Vec<String>, async_graphql::Error> {
+        let table_names = match pool {
+            PoolEnum::SqLite(pool) => sqlx::query(
+                "SELECT name FROM pragma_table_list WHERE schema = ? AND type = 'table' AND name NOT LIKE 'sqlite_%'",
+            )
+            .bind(schema.unwrap_or("main"))
+            .fetch_all(pool)
+            .await
+            .map(|rows| rows.iter().map(|row| row.try_get(0)).collect()),
+            PoolEnum::Postgres(pool) => sqlx::query(
+                "SELECT table_name::text FROM information_schema.tables WHERE table_schema = COALESCE($1, current_schema()) AND table_type = 'BASE TABLE'",
+            )
+            .bind(schema)
+            .fetch_all(pool)
+            .await
+            .map(|rows| rows.iter().map(|row| row.try_get(0)).collect()),
+            PoolEnum::MySql(pool) => sqlx::query(
+                "SELECT CAST(table_name AS CHAR) FROM information_schema.tables WHERE table_schema = COALESCE(?, DATABASE()) AND table_type = 'BASE TABLE'",
+            )
+            .bind(schema)
+            .fetch_all(pool)
+            .await
+            .map(|rows| rows.iter().map(|row| row.try_get(0)).collect()),
+        };
+
+        table_names
+            .and_then(|table_names| table_names)
+            .map_err(|e| {
+                error!("Failed to introspect tables of {:?}: {:?}", schema, e);
+                async_graphql::Error::new(format!("Failed to introspect tables: {}", e))
+            })
+    }
+
+    /// Gets the columns, primary key and foreign keys of a table.
+    async fn get_live_table(
+        pool: &PoolEnum,
+        schema: Option<&str>,
+        name: &str,
+    ) -> Result<TableSnapshot, async_graphql::Error> {
+        let map_err = |e: sqlx::Error| {
+            error!("Failed to introspect table {}: {:?}", name, e);
+            async_graphql::Error::new(format!("Failed to introspect table {}: {}", name, e))
+        };
+
+        let mut columns = vec![];
+        let mut primary_key = vec![];
+        let mut foreign_keys = vec![];
+
+        match pool {
+            PoolEnum::SqLite(pool) => {
+                let (table_info, foreign_key_list, master) = match schema {
+                    Some(schema) => (
+                        "SELECT name, type, \"notnull\", pk FROM pragma_table_info(?, ?) ORDER BY cid",
+                        "SELECT \"from\", \"table\", \"to\" FROM pragma_foreign_key_list(?, ?)",
+                        format!(
+                            "SELECT sql FROM {}.sqlite_master WHERE type = 'table' AND name = ?",
+                            SqlDataSource::quote_identifier(schema, &DialectEnum::SQLITE)
+                        ),
+                    ),
+                    None => (
+                        "SELECT name, type, \"notnull\", pk FROM pragma_table_info(?) ORDER BY cid",
+                        "SELECT \"from\", \"table\", \"to\" FROM pragma_foreign_key_list(?)",
+                        "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ?"
+                            .to_string(),
+                    ),
+                };
+
+                let mut query = sqlx::query(table_info).bind(name);
+                if let Some(schema) = schema {
+                    query = query.bind(schema);
+                }
+                let rows = query.fetch_all(pool).await.map_err(map_err)?;
+
+                let sql: String = sqlx::query(&master)
+                    .bind(name)
+                    .fetch_one(pool)
+                    .await
+                    .and_then(|row| row.try_get(0))
+                    .map_err(map_err)?;
+                let has_autoincrement = sql.to_uppercase().contains("AUTOINCREMENT");
+
+                let mut key_columns = vec![];
+                for row in &rows {
+                    let column_name: String = row.try_get(0).map_err(map_err)?;
+                    let data_type: String = row.try_get(1).map_err(map_err)?;
+                    let not_null: i64 = row.try_get(2).map_err(map_err)?;
+                    let key_position: i64 = row.try_get(3).map_err(map_err)?;
+                    if key_position > 0 {
+                        key_columns.push((key_position, column_name.clone()));
+                    }
+                    columns.push(LiveColumn {
+                        name: column_name,
+                        data_type,
+                        not_null: not_null == 1 || key_position > 0,
+                        auto_increment: false,
+                    });
+                }
+                key_columns.sort();
+                primary_key = key_columns.into_iter().map(|(_, name)| name).collect();
+
+                // Only an integer primary key, an alias of the rowid, increments.
+                if has_autoincrement && primary_key.len() == 1 {
+                    for column in columns.iter_mut() {
+                        if column.name == primary_key[0] {
+                            column.auto_increment = true;
+                        }
+                    }
+                }
+
+                let mut query = sqlx::query(foreign_key_list).bind(name);
+                if let Some(schema) = schema {
+                    query = query.bind(schema);
+                }
+                for row in query.fetch_all(pool).await.map_err(map_err)? {
+                    foreign_keys.push(ForeignKeySnapshot {
+                        column: row.try_get(0).map_err(map_err)?,
+                        references_table: row.try_get(1).map_err(map_err)?,
+                        references_schema: schema.map(|schema| schema.to_string()),
+                        references_column: row.try_get(2).map_err(map_err)?,
+                    });
+                }
+            }
+            PoolEnum::Postgres(pool) => {
+                let rows = sqlx::query(
+                    "SELECT c.column_name::text, format_type(a.atttypid, a.atttypmod), c.is_nullable::text, COALESCE(c.column_default, '')::text, c.is_identity::text
+                    FROM information_schema.columns c
+                    JOIN pg_catalog.pg_attribute a ON a.attrelid = format('%I.%I', c.table_schema, c.table_name)::regclass AND a.attname = c.column_name
+                    WHERE c.table_name = $1 AND c.table_schema = COALESCE($2, current_schema())
+                    ORDER BY c.ordinal_position",
+                )
+                .bind(name)
+                .bind(schema)
+                .fetch_all(pool)
+                .await
+                .map_err(map_err)?;
+                for row in rows {
+                    let is_nullable: String = row.try_get(2).map_err(map_err)?;
+                    let column_default: String = row.try_get(3).map_err(map_err)?;
+                    let is_identity: String = row.try_get(4).map_err(map_err)?;
+                    columns.push(LiveColumn {
+                        name: row.try_get(0).map_err(map_err)?,
+                        data_type: row.try_get(1).map_err(map_err)?,
+                        not_null: is_nullable == "NO",
+                        auto_increment: column_default.starts_with("nextval(")
+                            || is_identity == "YES",
+                    });
+                }
+
+                primary_key = sqlx::query(
+                    "SELECT kcu.column_name::text
+                    FROM information_schema.table_constraints tc
+                    JOIN information_schema.key_column_usage kcu ON kcu.constraint_name = tc.constraint_name AND kcu.constraint_schema = tc.constraint_schema AND kcu.table_name = tc.table_name
+                    WHERE tc.constraint_type = 'PRIMARY KEY' AND tc.table_name = $1 AND tc.table_schema = COALESCE($2, current_schema())
+                    ORDER BY kcu.ordinal_position",
+                )
+                .bind(name)
+                .bind(schema)
+                .fetch_all(pool)
+                .await
+                .and_then(|rows| rows.iter().map(|row| row.try_get(0)).collect())
+                .map_err(map_err)?;
+
+                let rows = sqlx::query(
+                    "SELECT kcu.column_name::text, ccu.table_schema::text, ccu.table_name::text, ccu.column_name::text, current_schema()::text
+                    FROM information_schema.table_constraints tc
+                    JOIN information_schema.key_column_usage kcu ON kcu.constraint_name = tc.constraint_name AND kcu.constraint_schema = tc.constraint_schema AND kcu.table_name = tc.table_name
+                    JOIN information_schema.constraint_column_usage ccu ON ccu.constraint_name = tc.constraint_name AND ccu.constraint_schema = tc.constraint_schema
+                    WHERE tc.constraint_type = 'FOREIGN KEY' AND tc.table_name = $1 AND tc.table_schema = COALESCE($2, current_schema())",
+                )
+                .bind(name)
+                .bind(schema)
+                .fetch_all(pool)
+                .await
+                .map_err(map_err)?;
+                for row in rows {
+                    let references_schema: String = row.try_get(1).map_err(map_err)?;
+                    let default_schema: String = row.try_get(4).map_err(map_err)?;
+                    foreign_keys.push(ForeignKeySnapshot {
+                        column: row.try_get(0).map_err(map_err)?,
+                        references_table: row.try_get(2).map_err(map_err)?,
+                        references_schema: Some(references_schema)
+                            .filter(|references_schema| references_schema != &default_schema),
+                        references_column: row.try_get(3).map_err(map_err)?,
+                    });
+                }
+            }
+            PoolEnum::MySql(pool) => {
+                let rows = sqlx::query(
+                    "SELECT CAST(column_name AS CHAR), CAST(column_type AS CHAR), CAST(is_nullable AS CHAR), CAST(extra AS CHAR)
+                    FROM information_schema.columns
+                    WHERE table_name = ? AND table_schema = COALESCE(?, DATABASE())
+                    ORDER BY ordinal_position",
+                )
+                .bind(name)
+                .bind(schema)
+                .fetch_all(pool)
+                .await
+                .map_err(map_err)?;
+                for row in rows {
+                    let is_nullable: String = row.try_get(2).map_err(map_err)?;
+                    let extra: String = row.try_get(3).map_err(map_err)?;
+                    columns.push(LiveColumn {
+                        name: row.try_get(0).map_err(map_err)?,
+                        data_type: row.try_get(1).map_err(map_err)?,
+                        not_null: is_nullable == "NO",
+                        auto_increment: extra.to_lowercase().contains("auto_increment"),
+                    });
+                }
+
+                primary_key = sqlx::query(
+                    "SELECT CAST(column_name AS CHAR)
+                    FROM information_schema.key_column_usage
+                    WHERE constraint_name = 'PRIMARY' AND table_name = ? AND table_schema = COALESCE(?, DATABASE())
+                    ORDER BY ordinal_position",
+                )
+                .bind(name)
+                .bind(schema)
+                .fetch_all(pool)
+                .await
+                .and_then(|rows| rows.iter().map(|row| row.try_get(0)).collect())
+                .map_err(map_err)?;
+
+                let rows = sqlx::query(
+                    "SELECT CAST(column_name AS CHAR), CAST(referenced_table_schema AS CHAR), CAST(referenced_table_name AS CHAR), CAST(referenced_column_name AS CHAR), CAST(DATABASE() AS CHAR)
+                    FROM information_schema.key_column_usage
+                    WHERE referenced_table_name IS NOT NULL AND table_name = ? AND table_schema = COALESCE(?, DATABASE())",
+                )
+                .bind(name)
+                .bind(schema)
+                .fetch_all(pool)
+                .await
+                .map_err(map_err)?;
+                for row in rows {
+                    let references_schema: String = row.try_get(1).map_err(map_err)?;
+                    let default_schema: String = row.try_get(4).map_err(map_err)?;
+                    foreign_keys.push(ForeignKeySnapshot {
+                        column: row.try_get(0).map_err(map_err)?,
+                        references_table: row.try_get(2).map_err(map_err)?,
+                        references_schema: Some(references_schema)
+                            .filter(|references_schema| references_schema != &default_schema),
+                        references_column: row.try_get(3).map_err(map_err)?,
+                    });
+                }
+            }
+        }
+
+        Ok(TableSnapshot {
+            name: name.to_string(),
+            schema: schema.map(|schema| schema.to_string()),
+            columns: columns
+                .into_iter()
+                .map(|column| ColumnSnapshot {
+                    data_type: SqlDataSource::normalize_live_type(pool, &column.data_type),
+                    name: column.name,
+                    not_null: column.not_null,
+                    auto_increment: column.auto_increment,
+                })
+                .collect(),
+            primary_key,
+            foreign_keys,
+        })
+    }
+
+    /// Converts the type reported by the database to the name used by `to_sql_type`, such as
+    /// `character varying(24)` to `VARCHAR(24)` on Postgres or `tinyint(1)` to `BOOLEAN` on MySQL.
+    fn normalize_live_type(pool: &PoolEnum, data_type: &str) -> String {
+        let data_type = data_type.trim().to_uppercase();
+        match pool {
+            PoolEnum::SqLite(_) => data_type,
+            PoolEnum::Postgres(_) => data_type
+                .replace("CHARACTER VARYING", "VARCHAR")
+                .replace("CHARACTER(", "CHAR(")
+                .replace("TIMESTAMP WITHOUT TIME ZONE", "TIMESTAMP"),
+            PoolEnum::MySql(_) => {
+                if data_type == "TINYINT(1)" {
+                    return "BOOLEAN".to_string();
+                }
+                // MySQL 5.7 reports the display width of integers, such as `int(11)`.
+                let display_width =
+                    Regex::new(r"^(TINYINT|SMALLINT|MEDIUMINT|INT|BIGINT)\(\d+\)").unwrap();
+                display_width.replace(&data_type, "$1").to_string()
+            }
+        }
+    }
+}
@@ -0,0 +1,243 @@
+use std::{collections::HashMap, path::Path};
+
+use log::{debug, error, info, trace};
+use sqlx::migrate::{Migrate, MigrateError, Migrator};
+
+use crate::{
+    cli_args::CliArgs,
+    configuration::subgraph::{data_sources::sql::SqlDataSourceConfig, SubGraphConfig},
+    data_sources::sql::{PoolEnum, SqlDataSource},
+};
+
+pub mod baseline_schema;
+pub mod generate_migration;
+pub mod introspect_schema;
+pub mod schema_snapshot;
+
+/// The status of a single migration found in the `migrations_path` of a data source.
+#[derive(Debug, Clone)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub description: String,
+    pub applied: bool,
+    pub reversible: bool,
+}
+
+impl SqlDataSource {
+    /// Handles the `--migrate` flag for a SQL data source.
+    /// Supports `run`, `revert`, `status`, `generate` and `baseline`.
+    pub async fn handle_migrations(
+        pool: &PoolEnum,
+        sql_data_source_config: &SqlDataSourceConfig,
+        args: &CliArgs,
+        subgraph_config: &SubGraphConfig,
+    ) -> Result<(), async_graphql::Error> {
+        debug!("Handling Migrations");
+
+        let migrate = match &args.migrate {
+            Some(migrate) => migrate,
+            None => return Ok(()),
+        };
+
+        // Generating and baselining require a migrations path, which they check themselves.
+        match migrate.as_str() {
+            "generate" => {
+                SqlDataSource::generate_migration(
+                    pool,
+                    sql_data_source_config,
+                    subgraph_config,
+                    args.migration_name.clone(),
+                )
+                .await?;
+                return Ok(());
+            }
+            "baseline" => {
+                return SqlDataSource::baseline_schema(sql_data_source_config, subgraph_config)
+            }
+            _ => (),
+        }
+
+        let path = match &sql_data_source_config.migrations_path {
+            Some(path) => path,
+            None => {
+                info!(
+                    "No migrations path provided for data source: {}",
+                    sql_data_source_config.name
+                );
+                return Ok(());
+            }
+        };
+
+        let migrator = Migrator::new(Path::new(path)).await.map_err(|e| {
+            error!("Migrations Failed: {:?}", e);
+            async_graphql::Error::new(format!("Migrations Failed: {}", e))
+        })?;
+
+        match migrate.as_str() {
+            "run" => {
+                info!("Running Migrations: {:?}", path);
+                SqlDataSource::run_migrations(pool, &migrator).await?;
+                info!("Migration Complete");
+            }
+            "revert" => {
+                let steps = args.steps.unwrap_or(1);
+                info!("Reverting {} Migration(s): {:?}", steps, path);
+                SqlDataSource::revert_migrations(pool, &migrator, steps).await?;
+                info!("Revert Complete");
+            }
+            "status" => {
+                let statuses = SqlDataSource::get_migration_status(pool, &migrator).await?;
+                SqlDataSource::print_migration_status(&sql_data_source_config.name, &statuses);
+            }
+            _ => {
+                error!("Invalid migrate command: {}", migrate);
+                return Err(async_graphql::Error::new(format!(
+                    "Invalid migrate command: {}",
+                    migrate
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Applies all pending migrations.
+    pub async fn run_migrations(
+        pool: &PoolEnum,
+        migrator: &Migrator,
+    ) -> Result<(), async_graphql::Error> {
+        debug!("Running Migrations");
+        let completed = match pool {
+            PoolEnum::MySql(pool) => migrator.run(pool).await,
+            PoolEnum::Postgres(pool) => migrator.run(pool).await,
+            PoolEnum::SqLite(pool) => migrator.run(pool).await,
+        };
+
+        completed.map_err(|e| {
+            error!("Migration Error: {:?}", e);
+            async_graphql::Error::new(format!("Migration Error: {}", e))
+        })
+    }
+
+    /// Reverts the last `steps` applied reversible migrations.
+    pub async fn revert_migrations(
+        pool: &PoolEnum,
+        migrator: &Migrator,
+        steps: u32,
+    ) -> Result<(), async_graphql::Error> {
+        debug!("Reverting Migrations");
+
+        let applied = SqlDataSource::get_applied_migrations(pool).await?;
+        let target = SqlDataSource::get_revert_target(migrator, &applied, steps);
+        trace!("Revert Target Version: {}", target);
+
+        let completed = match pool {
+            PoolEnum::MySql(pool) => migrator.undo(pool, target).await,
+            PoolEnum::Postgres(pool) => migrator.undo(pool, target).await,
+            PoolEnum::SqLite(pool) => migrator.undo(pool, target).await,
+        };
+
+        completed.map_err(|e| {
+            error!("Revert Error: {:?}", e);
+            async_graphql::Error::new(format!("Revert Error: {}", e))
+        })
+    }
+
+    /// Lists every migration along with whether it has been applied to the database.
+    pub async fn get_migration_status(
+        pool: &PoolEnum,
+        migrator: &Migrator,
+    ) -> Result<Vec<MigrationStatus>, async_graphql::Error> {
+        debug!("Getting Migration Status");
+
+        let applied = SqlDataSource::get_applied_migrations(pool).await?;
+
+        // Index reversible versions, as the up and down migrations share a version.
+        let mut reversible = HashMap::new();
+        for migration in migrator.iter() {
+            if migration.migration_type.is_down_migration() {
+                reversible.insert(migration.version, true);
+            }
+        }
+
+        let statuses = migrator
+            .iter()
+            .filter(|migration| !migration.migration_type.is_down_migration())
+            .map(|migration| MigrationStatus {
+                version: migration.version,
+                description: migration.description.to_string(),
+                applied: applied.contains(&migration.version),
+                reversible: reversible.contains_key(&migration.version),
+            })
+            .collect();
+
+        trace!("Migration Status: {:?}", statuses);
+        Ok(statuses)
+    }
+
+    /// Gets the versions of the migrations that have been applied to the database.
+    async fn get_applied_migrations(pool: &PoolEnum) -> Result<Vec<i64>, async_graphql::Error> {
+        let applied = match pool {
+            PoolEnum::MySql(pool) => {
+                let mut conn = pool.acquire().await?;
+                SqlDataSource::list_applied_versions(&mut *conn).await
+            }
+            PoolEnum::Postgres(pool) => {
+                let mut conn = pool.acquire().await?;
+                SqlDataSource::list_applied_versions(&mut *conn).await
+            }
+            PoolEnum::SqLite(pool) => {
+                let mut conn = pool.acquire().await?;
+                SqlDataSource::list_applied_versions(&mut *conn).await
+            }
+        };
+
+        applied.map_err(|e| {
+            error!("Failed to list applied migrations: {:?}", e);
+            async_graphql::Error::new(format!("Failed to list applied migrations: {}", e))
+        })
+    }
+
+    async fn list_applied_versions<C: Migrate>(conn: &mut C) -> Result<Vec<i64>, MigrateError> {
+        conn.ensure_migrations_table().await?;
+        let applied = conn.list_applied_migrations().await?;
+        Ok(applied.into_iter().map(|m| m.version).collect())
+    }
+
+    /// Finds the version to revert down to, such that only the last `steps` applied
+    /// reversible migrations are reverted.
+    pub fn get_revert_target(migrator: &Migrator, applied: &[i64], steps: u32) -> i64 {
+        let mut reversible_applied = migrator
+            .iter()
+            .filter(|migration| migration.migration_type.is_down_migration())
+            .filter(|migration| applied.contains(&migration.version))
+            .map(|migration| migration.version)
+            .collect::<Vec<i64>>();
+        reversible_applied.sort_unstable_by(|a, b| b.cmp(a));
+
+        match reversible_applied.get(steps as usize) {
+            Some(version) => *version,
+            None => 0,
+        }
+    }
+
+    fn print_migration_status(data_source_name: &str, statuses: &[MigrationStatus]) {
+        println!("Migrations for data source: {}", data_source_name);
+        if statuses.is_empty() {
+            println!("  No migrations found.");
+        }
+        for status in statuses {
+            println!(
+                "  {} {:<9} {:<12} {}",
+                status.version,
+                if status.applied { "applied" } else { "pending" },
+                if status.reversible {
+                    "reversible"
+                } else {
+                    "irreversible"
+                },
+                status.description
+            );
+        }
+    }
+}
@@ -0,0 +1,337 @@
+use std::{fs, path::Path};
+
+use log::{debug, error, trace};
+use serde::{Deserialize, Serialize};
+
+use crate::configuration::subgraph::{
+    data_sources::{sql::DialectEnum, ServiceDataSourceConfig},
+    entities::{
+        service_entity_field::{exclude_from_input::ExcludeFromInput, ServiceEntityFieldConfig},
+        ServiceEntityConfig,
+    },
+    SubGraphConfig,
+};
+use crate::{data_sources::sql::SqlDataSource, scalar_option::ScalarOption};
+
+/// File name of the snapshot stored alongside the migrations of a data source.
+pub const SNAPSHOT_FILE_NAME: &str = "subgraph_snapshot.json";
+
+/// The shape of the tables of a SQL data source, as described by the entities when the last
+/// migration was generated.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct SchemaSnapshot {
+    pub tables: Vec<TableSnapshot>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TableSnapshot {
+    pub name: String,
+    /// The Postgres schema or MySQL database of the table, when it is qualified.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    pub columns: Vec<ColumnSnapshot>,
+    pub primary_key: Vec<String>,
+    pub foreign_keys: Vec<ForeignKeySnapshot>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ColumnSnapshot {
+    pub name: String,
+    pub data_type: String,
+    pub not_null: bool,
+    pub auto_increment: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ForeignKeySnapshot {
+    pub column: String,
+    pub references_table: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub references_schema: Option<String>,
+    pub references_column: String,
+}
+
+impl TableSnapshot {
+    pub fn get_column(&self, name: &str) -> Option<&ColumnSnapshot> {
+        self.columns.iter().find(|column| column.name == name)
+    }
+
+    /// Whether this is the table with the provided schema and name.
+    pub fn is_table(&self, schema: Option<&str>, name: &str) -> bool {
+        self.schema.as_deref() == schema && self.name == name
+    }
+
+    /// The quoted name of the table, qualified by its schema.
+    pub fn quote_name(&self, dialect: &DialectEnum) -> String {
+        SqlDataSource::quote_table_name(&self.name, self.schema.as_deref(), dialect)
+    }
+}
+
+impl ForeignKeySnapshot {
+    /// The quoted name of the referenced table, qualified by its schema.
+    pub fn quote_references_table(&self, dialect: &DialectEnum) -> String {
+        SqlDataSource::quote_table_name(
+            &self.references_table,
+            self.references_schema.as_deref(),
+            dialect,
+        )
+    }
+}
+
+impl SchemaSnapshot {
+    pub fn get_table(&self, schema: Option<&str>, name: &str) -> Option<&TableSnapshot> {
+        self.tables
+            .iter()
+            .find(|table| table.is_table(schema, name))
+    }
+
+    /// Reads the snapshot from the migrations path, if one was written.
+    pub fn read(migrations_path: &str) -> Result<Option<SchemaSnapshot>, async_graphql::Error> {
+        debug!("Reading Schema Snapshot");
+        let path = Path::new(migrations_path).join(SNAPSHOT_FILE_NAME);
+
+        if !path.exists() {
+            trace!("No Schema Snapshot Found: {:?}", path);
+            return Ok(None);
+        }
+
+        let contents = fs::read_to_string(&path).map_err(|e| {
+            error!("Failed to read schema snapshot: {:?}", e);
+            async_graphql::Error::new(format!("Failed to read schema snapshot: {}", e))
+        })?;
+
+        serde_json::from_str(&contents).map(Some).map_err(|e| {
+            error!("Invalid schema snapshot: {:?}", e);
+            async_graphql::Error::new(format!("Invalid schema snapshot: {}", e))
+        })
+    }
+
+    /// Writes the snapshot to the migrations path.
+    pub fn write(&self, migrations_path: &str) -> Result<(), async_graphql::Error> {
+        debug!("Writing Schema Snapshot");
+        let path = Path::new(migrations_path).join(SNAPSHOT_FILE_NAME);
+
+        let contents = serde_json::to_string_pretty(self).map_err(|e| {
+            error!("Failed to serialize schema snapshot: {:?}", e);
+            async_graphql::Error::new(format!("Failed to serialize schema snapshot: {}", e))
+        })?;
+
+        fs::write(&path, contents).map_err(|e| {
+            error!("Failed to write schema snapshot: {:?}", e);
+            async_graphql::Error::new(format!("Failed to write schema snapshot: {}", e))
+        })
+    }
+
+    /// Creates a snapshot from the entities that belong to the data source.
+    pub fn from_entities(
+        data_source_name: &str,
+        dialect: &DialectEnum,
+        subgraph_config: &SubGraphConfig,
+    ) -> Result<SchemaSnapshot, async_graphql::Error> {
+        debug!("Creating Schema Snapshot From Entities");
+
+        let entities = SchemaSnapshot::get_data_source_entities(data_source_name, subgraph_config);
+
+        let mut tables = vec![];
+        for entity in &entities {
+            let primary_key = SchemaSnapshot::get_primary_key_names(entity);
+            let mut columns = vec![];
+
+            for field in &entity.fields {
+                if !SchemaSnapshot::is_column(field) {
+                    continue;
+                }
+
+                let is_primary_key = primary_key.contains(&field.name);
+                let auto_increment = is_primary_key
+                    && primary_key.len() == 1
+                    && matches!(field.scalar, ScalarOption::Int)
+                    && ServiceEntityFieldConfig::is_excluded_input_field(
+                        field,
+                        Some(ExcludeFromInput::CreateOne),
+                    );
+
+                columns.push(ColumnSnapshot {
+                    name: field.name.clone(),
+                    data_type: field
+                        .scalar
+                        .to_sql_type(dialect, field.list.unwrap_or(false)),
+                    not_null: field.required.unwrap_or(false) || is_primary_key,
+                    auto_increment,
+                });
+            }
+
+            tables.push(TableSnapshot {
+                name: SchemaSnapshot::get_table_name(entity),
+                schema: SqlDataSource::get_table_schema(entity, subgraph_config),
+                columns,
+                primary_key,
+                foreign_keys: vec![],
+            });
+        }
+
+        // Foreign keys are resolved once every table is known, as the key may live on either side
+        // of the join.
+        for entity in &entities {
+            let table_name = SchemaSnapshot::get_table_name(entity);
+            let table_schema = SqlDataSource::get_table_schema(entity, subgraph_config);
+            for field in &entity.fields {
+                let (as_type, join_on) = match (&field.as_type, &field.join_on) {
+                    (Some(as_type), Some(join_on)) => (as_type, join_on),
+                    _ => continue,
+                };
+                let child_entity = match entities.iter().find(|e| &e.name == as_type) {
+                    Some(child_entity) => child_entity,
+                    None => continue,
+                };
+                let child_table_name = SchemaSnapshot::get_table_name(child_entity);
+                let child_schema = SqlDataSource::get_table_schema(child_entity, subgraph_config);
+                let join_from = field.join_from.clone().unwrap_or(field.name.clone());
+
+                // Joins through a junction entity reference both sides from the junction.
+                let foreign_keys = if let Some(join_through) = &field.join_through {
+                    let junction = match entities.iter().find(|e| e.name == join_through.entity) {
+                        Some(junction) => junction,
+                        None => continue,
+                    };
+                    let junction_table_name = SchemaSnapshot::get_table_name(junction);
+                    let junction_schema =
+                        SqlDataSource::get_table_schema(junction, subgraph_config);
+                    vec![
+                        (
+                            junction_schema.clone(),
+                            junction_table_name.clone(),
+                            ForeignKeySnapshot {
+                                column: join_through.join_from.clone(),
+                                references_table: table_name.clone(),
+                                references_schema: table_schema.clone(),
+                                references_column: join_from,
+                            },
+                        ),
+                        (
+                            junction_schema,
+                            junction_table_name,
+                            ForeignKeySnapshot {
+                                column: join_through.join_on.clone(),
+                                references_table: child_table_name,
+                                references_schema: child_schema,
+                                references_column: join_on.clone(),
+                            },
+                        ),
+                    ]
+                } else if SchemaSnapshot::get_primary_key_names(child_entity)
+                    == vec![join_on.clone()]
+                {
+                    vec![(
+                        table_schema.clone(),
+                        table_name.clone(),
+                        ForeignKeySnapshot {
+                            column: join_from,
+                            references_table: child_table_name,
+                            references_schema: child_schema,
+                            references_column: join_on.clone(),
+                        },
+                    )]
+                } else if SchemaSnapshot::get_primary_key_names(entity) == vec![join_from.clone()] {
+                    vec![(
+                        child_schema,
+                        child_table_name,
+                        ForeignKeySnapshot {
+                            column: join_on.clone(),
+                            references_table: table_name.clone(),
+                            references_schema: table_schema.clone(),
+                            references_column: join_from,
+                        },
+                    )]
+                } else {
+                    trace!(
+                        "Skipping Foreign Key, Join Not On Primary Key: {}",
+                        field.name
+                    );
+                    continue;
+                };
+
+                // Only create the key if the column exists on the referencing table.
+                for (schema, name, foreign_key) in foreign_keys {
+                    let table = tables
+                        .iter_mut()
+                        .find(|t| t.is_table(schema.as_deref(), &name))
+                        .unwrap();
+                    if table.get_column(&foreign_key.column).is_none()
+                        || table.foreign_keys.contains(&foreign_key)
+                    {
+                        continue;
+                    }
+                    table.foreign_keys.push(foreign_key);
+                }
+            }
+        }
+
+        trace!("Schema Snapshot: {:?}", tables);
+        Ok(SchemaSnapshot { tables })
+    }
+
+    /// Gets the entities that are stored in the data source. Entities without a data source
+    /// belong to the first data source of the service.
+    pub fn get_data_source_entities(
+        data_source_name: &str,
+        subgraph_config: &SubGraphConfig,
+    ) -> Vec<ServiceEntityConfig> {
+        let is_default = match subgraph_config.service.data_sources.first() {
+            Some(ServiceDataSourceConfig::SQL(ds)) => ds.name == data_source_name,
+            _ => false,
+        };
+
+        subgraph_config
+            .service
+            .entities
+            .iter()
+            .filter(|entity| match &entity.data_source {
+                Some(ds) => ds.from.as_deref() == Some(data_source_name) && ds.query.is_none(),
+                None => is_default,
+            })
+            .cloned()
+            .collect()
+    }
+
+    pub fn get_table_name(entity: &ServiceEntityConfig) -> String {
+        match &entity.data_source {
+            Some(ds) => ds.table.clone().unwrap_or(entity.name.clone()),
+            None => entity.name.clone(),
+        }
+    }
+
+    /// Fields flagged as primary keys, falling back to the `id` field.
+    pub fn get_primary_key_names(entity: &ServiceEntityConfig) -> Vec<String> {
+        let primary_keys = entity
+            .fields
+            .iter()
+            .filter(|field| field.primary_key.unwrap_or(false))
+            .map(|field| field.name.clone())
+            .collect::<Vec<String>>();
+
+        if !primary_keys.is_empty() {
+            return primary_keys;
+        }
+
+        entity
+            .fields
+            .iter()
+            .filter(|field| field.name == "id")
+            .map(|field| field.name.clone())
+            .collect()
+    }
+
+    /// Virtual fields and joins resolved from another column are not stored on the table.
+    fn is_column(field: &ServiceEntityFieldConfig) -> bool {
+        if field.is_virtual.unwrap_or(false) || field.join_through.is_some() {
+            return false;
+        }
+        if field.as_type.is_some() {
+            if let Some(join_from) = &field.join_from {
+                return join_from == &field.name;
+            }
+        }
+        true
+    }
+}
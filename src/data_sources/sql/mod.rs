@@ -1,14 +1,17 @@
-use std::{path::Path, str::FromStr};
+use std::sync::{
+    atomic::{AtomicBool, AtomicUsize},
+    Arc,
+};
 
 use async_graphql::dynamic::FieldValue;
 use bson::{to_document, Document};
-use log::{debug, error, info, trace};
-use sqlx::{sqlite::SqliteConnectOptions, MySql, Pool, Postgres, Sqlite};
+use log::{debug, error, trace};
+use sqlx::{MySql, Pool, Postgres, Sqlite, Transaction};
 
 use crate::{
     cli_args::CliArgs,
     configuration::subgraph::{
-        data_sources::sql::{DialectEnum, SqlDataSourceConfig},
+        data_sources::sql::{SqlDataSourceConfig, SqlQueryConfig},
         entities::ServiceEntityConfig,
         SubGraphConfig,
     },
@@ -20,16 +23,33 @@ use crate::{
     sql_value::SqlValue,
 };
 
+use self::query_plan_cache::{QueryPlanCache, DEFAULT_QUERY_PLAN_CACHE_CAPACITY};
+
 use super::DataSource;
 
+pub mod check_health;
+pub mod create_pool;
 pub mod create_query;
+pub mod execute_mutation;
+pub mod get_read_pool;
+pub mod migrate;
+pub mod probe_replicas;
+pub mod query_plan_cache;
+pub mod read;
 pub mod services;
+pub mod transaction;
+pub mod update_join_through;
+pub mod validate_config;
 
 #[derive(Debug, Clone)]
 pub struct SqlDataSource {
     pub pool: PoolEnum,
+    pub replica_pools: Vec<PoolEnum>,
+    pub replica_index: Arc<AtomicUsize>,
+    pub replica_health: Arc<Vec<AtomicBool>>,
     pub config: SqlDataSourceConfig,
     pub subgraph_config: SubGraphConfig,
+    pub query_plan_cache: QueryPlanCache,
 }
 
 #[derive(Debug, Clone)]
@@ -44,6 +64,14 @@ pub enum PoolEnum {
     SqLite(Pool<Sqlite>),
 }
 
+/// A transaction on the primary of a data source.
+#[derive(Debug)]
+pub enum TransactionEnum {
+    MySql(Transaction<'static, MySql>),
+    Postgres(Transaction<'static, Postgres>),
+    SqLite(Transaction<'static, Sqlite>),
+}
+
 #[derive(Debug, Clone)]
 pub struct SqlQuery {
     query: String,
@@ -51,7 +79,9 @@ pub struct SqlQuery {
     identifier_query: Option<String>,
     values: Vec<SqlValue>,
     where_values: Vec<SqlValue>,
+    value_keys: Vec<String>,
     table: String,
+    schema: Option<String>,
 }
 
 impl SqlDataSource {
@@ -59,114 +89,132 @@ impl SqlDataSource {
         sql_data_source_config: &SqlDataSourceConfig,
         args: &CliArgs,
         subgraph_config: SubGraphConfig,
-    ) -> DataSource {
+    ) -> Result<DataSource, async_graphql::Error> {
         debug!("Initializing SQL Data Source");
+        SqlDataSource::validate_config(sql_data_source_config)?;
 
-        // Create the pool
-        let pool: PoolEnum = match sql_data_source_config.dialect {
-            DialectEnum::SQLITE => {
-                trace!("Creating SQLite Pool: {:?}", &sql_data_source_config.uri);
-
-                let options = if let Some(extensions) = &sql_data_source_config.sqlite_extensions {
-                    trace!("Creating SQLite Pool with Extensions: {:?}", &extensions);
-                    let mut options = SqliteConnectOptions::from_str(&sql_data_source_config.uri)
-                        .expect("Failed to create SqliteConnectOptions with extensions");
-                    for extension in extensions {
-                        options = options.extension(extension.clone());
-                    }
-                    options
-                } else {
-                    SqliteConnectOptions::from_str(&sql_data_source_config.uri)
-                        .expect("Failed to create SqliteConnectOptions")
-                };
+        let pool = SqlDataSource::init_pool(sql_data_source_config).await?;
 
-                let pool = sqlx::sqlite::SqlitePoolOptions::new()
-                    .max_connections(5)
-                    .connect_with(options)
-                    .await
-                    .unwrap();
-
-                PoolEnum::SqLite(pool)
-            }
-            DialectEnum::POSTGRES => {
-                trace!("Creating Postgres Pool: {:?}", &sql_data_source_config.uri);
-                let pool = sqlx::postgres::PgPoolOptions::new()
-                    .max_connections(5)
-                    .connect(&sql_data_source_config.uri)
-                    .await
-                    .unwrap();
-                PoolEnum::Postgres(pool)
-            }
-            DialectEnum::MYSQL => {
-                trace!("Creating MySql Pool: {:?}", &sql_data_source_config.uri);
-                let pool = sqlx::mysql::MySqlPoolOptions::new()
-                    .max_connections(5)
-                    .connect(&sql_data_source_config.uri)
-                    .await
-                    .unwrap();
-                PoolEnum::MySql(pool)
+        // Replicas which can not be reached are started as unhealthy, so reads use the primary.
+        let mut replica_pools = Vec::new();
+        let mut replica_health = Vec::new();
+        for replica_config in SqlDataSource::get_replica_configs(sql_data_source_config) {
+            match SqlDataSource::init_pool(&replica_config).await {
+                Ok(replica_pool) => {
+                    // Lazily connected replicas are only read from once a check passes.
+                    let healthy =
+                        SqlDataSource::check_pool_health(&replica_pool, &replica_config.name).await;
+                    replica_pools.push(replica_pool);
+                    replica_health.push(AtomicBool::new(healthy));
+                }
+                Err(_) => {
+                    replica_pools.push(SqlDataSource::create_lazy_pool(&replica_config).await?);
+                    replica_health.push(AtomicBool::new(false));
+                }
             }
-        };
+        }
 
-        if let Some(migrate) = &args.migrate {
-            if migrate == "run" {
-                let path = sql_data_source_config.migrations_path.clone();
-                if path.is_some() {
-                    let path = path.unwrap();
-                    info!("Running Migrations: {:?}", &path);
-
-                    let migration = sqlx::migrate::Migrator::new(Path::new(&path)).await;
-                    match migration {
-                        Ok(migration) => match &pool {
-                            PoolEnum::MySql(pool) => {
-                                let migration_completed = migration.run(pool).await;
-                                match migration_completed {
-                                    Ok(_) => {
-                                        info!("Migration Complete");
-                                    }
-                                    Err(e) => {
-                                        error!("MySQL Migration Error: {:?}", e);
-                                    }
-                                }
-                            }
-                            PoolEnum::Postgres(pool) => {
-                                let completed = migration.run(pool).await;
-                                match completed {
-                                    Ok(_) => {
-                                        info!("Migration Complete");
-                                    }
-                                    Err(e) => {
-                                        error!("Postgres Migration Error: {:?}", e);
-                                    }
-                                }
-                            }
-                            PoolEnum::SqLite(pool) => {
-                                let completed = migration.run(pool).await;
-                                match completed {
-                                    Ok(_) => {
-                                        info!("Migration Complete");
-                                    }
-                                    Err(e) => {
-                                        error!("SQLITE Migration Error: {:?}", e);
-                                    }
-                                }
-                            }
-                        },
-                        Err(e) => {
-                            error!("Migrations Failed: {:?}", e);
-                        }
-                    }
-                }
-            } else if migrate == "revert" {
-                //TODO:
+        match SqlDataSource::handle_migrations(
+            &pool,
+            sql_data_source_config,
+            args,
+            &subgraph_config,
+        )
+        .await
+        {
+            Ok(_) => {}
+            Err(e) => {
+                error!("Migrations Failed: {:?}", e.message);
             }
         }
 
-        DataSource::SQL(SqlDataSource {
+        let query_plan_cache_capacity = sql_data_source_config
+            .pool
+            .as_ref()
+            .and_then(|pool| pool.query_plan_cache_capacity)
+            .unwrap_or(DEFAULT_QUERY_PLAN_CACHE_CAPACITY);
+
+        let data_source = SqlDataSource {
             pool,
+            replica_pools,
+            replica_index: Arc::new(AtomicUsize::new(0)),
+            replica_health: Arc::new(replica_health),
             config: sql_data_source_config.clone(),
             subgraph_config,
-        })
+            query_plan_cache: QueryPlanCache::new(query_plan_cache_capacity),
+        };
+        data_source.spawn_replica_probe();
+
+        Ok(DataSource::SQL(data_source))
+    }
+
+    /// Connects to the database of the config. When lazy connect is enabled the service starts
+    /// with the data source reported as unhealthy, connecting once the database is reachable.
+    /// Otherwise a database which can not be reached is an error.
+    async fn init_pool(
+        sql_data_source_config: &SqlDataSourceConfig,
+    ) -> Result<PoolEnum, async_graphql::Error> {
+        let lazy_connect = sql_data_source_config
+            .pool
+            .as_ref()
+            .and_then(|pool| pool.lazy_connect)
+            .unwrap_or(false);
+
+        match SqlDataSource::create_pool(sql_data_source_config).await {
+            Ok(pool) => Ok(pool),
+            Err(e) if lazy_connect => {
+                error!(
+                    "Data source {} is unhealthy, connecting lazily: {}",
+                    sql_data_source_config.name, e.message
+                );
+                SqlDataSource::create_lazy_pool(sql_data_source_config).await
+            }
+            Err(e) => {
+                error!(
+                    "Failed to connect to data source {}: {}",
+                    sql_data_source_config.name, e.message
+                );
+                Err(async_graphql::Error::new(format!(
+                    "Failed to connect to data source {}: {}",
+                    sql_data_source_config.name, e.message
+                )))
+            }
+        }
+    }
+
+    /// Runs the `--migrate` command against the data source without initializing it for the
+    /// service. Errors are returned rather than logged.
+    pub async fn migrate(
+        sql_data_source_config: &SqlDataSourceConfig,
+        args: &CliArgs,
+        subgraph_config: &SubGraphConfig,
+    ) -> Result<(), async_graphql::Error> {
+        debug!("Migrating SQL Data Source: {}", sql_data_source_config.name);
+        SqlDataSource::validate_config(sql_data_source_config)?;
+        let pool = SqlDataSource::create_pool(sql_data_source_config).await?;
+        SqlDataSource::handle_migrations(&pool, sql_data_source_config, args, subgraph_config).await
+    }
+
+    /// Gets a named query from the data source config.
+    pub fn get_named_query(&self, name: &str) -> Result<&SqlQueryConfig, async_graphql::Error> {
+        let named_query = self
+            .config
+            .queries
+            .as_ref()
+            .and_then(|queries| queries.iter().find(|query| query.name == name));
+        match named_query {
+            Some(named_query) => Ok(named_query),
+            None => {
+                error!(
+                    "Query {} not found in data source {}",
+                    name, self.config.name
+                );
+                Err(async_graphql::Error::new(format!(
+                    "Query {} not found in data source {}",
+                    name, self.config.name
+                )))
+            }
+        }
     }
 
     pub async fn execute_operation<'a>(
@@ -177,6 +225,7 @@ impl SqlDataSource {
         subgraph_config: &SubGraphConfig,
         token_data: &Option<TokenData>,
         has_selection_set: bool,
+        transaction: Option<&mut TransactionEnum>,
     ) -> Result<Option<FieldValue<'a>>, async_graphql::Error> {
         debug!("Executing SQL Operation");
 
@@ -200,14 +249,26 @@ impl SqlDataSource {
             table = entity.name.to_string();
         }
 
-        let query = SqlDataSource::create_query(
-            input.clone(),
-            resolver_type,
-            &table,
-            data_source.config.dialect.clone(),
-            &entity,
-            &subgraph_config,
-        )?;
+        // Entities backed by a named query run it in place of a generated query.
+        let query = match ServiceEntityConfig::get_sql_query_name(&entity) {
+            Some(query_name) => {
+                let named_query = data_source.get_named_query(&query_name)?;
+                SqlDataSource::create_named_query(
+                    &entity,
+                    named_query,
+                    resolver_type,
+                    &data_source.config.dialect,
+                    &input,
+                )?
+            }
+            None => data_source.create_cached_query(
+                input.clone(),
+                resolver_type,
+                &table,
+                &entity,
+                subgraph_config,
+            )?,
+        };
 
         let user_uuid = if token_data.is_some() {
             Some(token_data.as_ref().unwrap().user_uuid.to_string())
@@ -218,7 +279,7 @@ impl SqlDataSource {
         // Return the result from the database as a FieldValue
         match resolver_type {
             ResolverType::FindOne => {
-                let result = services::Services::find_one(&data_source.pool, &query).await?;
+                let result = data_source.read_one(&query).await?;
                 let res = ResolverResponse {
                     data: vec![FieldValue::owned_any(result)],
                     meta: ResolverResponseMeta {
@@ -238,8 +299,7 @@ impl SqlDataSource {
             }
             ResolverType::FindMany => {
                 let (entities, total_count) =
-                    services::Services::find_many(&data_source.pool, &query, &has_selection_set)
-                        .await?;
+                    data_source.read_many(&query, &has_selection_set).await?;
                 let count = entities.len();
                 let opts_doc = if input.clone().get("opts").is_some() {
                     trace!("opts: {:?}", input.get("opts").unwrap());
@@ -285,14 +345,19 @@ impl SqlDataSource {
                 Ok(Some(FieldValue::owned_any(res)))
             }
             ResolverType::CreateOne => {
-                let result = services::Services::create_one(
-                    &entity,
-                    &data_source.pool,
-                    &query,
-                    data_source.config.dialect.clone(),
-                    &subgraph_config,
-                )
-                .await?;
+                let result = match transaction {
+                    Some(transaction) => {
+                        services::Services::create_one(&entity, transaction, &query).await?
+                    }
+                    None => {
+                        let mut transaction = data_source.begin_transaction().await?;
+                        let result =
+                            services::Services::create_one(&entity, &mut transaction, &query)
+                                .await?;
+                        transaction.commit().await?;
+                        result
+                    }
+                };
 
                 let res = ResolverResponse {
                     data: vec![FieldValue::owned_any(result)],
@@ -313,8 +378,19 @@ impl SqlDataSource {
                 Ok(Some(FieldValue::owned_any(res)))
             }
             ResolverType::UpdateOne => {
-                let result =
-                    services::Services::update_one(&entity, &data_source.pool, &query).await?;
+                let result = match transaction {
+                    Some(transaction) => {
+                        services::Services::update_one(&entity, transaction, &query).await?
+                    }
+                    None => {
+                        let mut transaction = data_source.begin_transaction().await?;
+                        let result =
+                            services::Services::update_one(&entity, &mut transaction, &query)
+                                .await?;
+                        transaction.commit().await?;
+                        result
+                    }
+                };
                 let res = ResolverResponse {
                     data: vec![FieldValue::owned_any(result)],
                     meta: ResolverResponseMeta {
@@ -333,8 +409,19 @@ impl SqlDataSource {
                 Ok(Some(FieldValue::owned_any(res)))
             }
             ResolverType::UpdateMany => {
-                let results =
-                    services::Services::update_many(&entity, &data_source.pool, &query).await?;
+                let results = match transaction {
+                    Some(transaction) => {
+                        services::Services::update_many(&entity, transaction, &query).await?
+                    }
+                    None => {
+                        let mut transaction = data_source.begin_transaction().await?;
+                        let results =
+                            services::Services::update_many(&entity, &mut transaction, &query)
+                                .await?;
+                        transaction.commit().await?;
+                        results
+                    }
+                };
                 let count = results.len();
                 let res = ResolverResponse {
                     data: results
@@ -0,0 +1,64 @@
+use std::{
+    sync::{atomic::Ordering, Arc},
+    time::Duration,
+};
+
+use log::{debug, trace, warn};
+
+use super::SqlDataSource;
+
+/// Milliseconds between replica health probes when `replica_probe_interval` is not set.
+pub const DEFAULT_REPLICA_PROBE_INTERVAL: u64 = 5000;
+
+impl SqlDataSource {
+    /// Spawns a task checking the health of each replica on an interval, so replicas which fail
+    /// are skipped for reads and replicas which recover are read from again. The task stops once
+    /// the data source is dropped.
+    pub fn spawn_replica_probe(&self) {
+        if self.replica_pools.is_empty() {
+            return;
+        }
+        let interval = self
+            .config
+            .pool
+            .as_ref()
+            .and_then(|pool| pool.replica_probe_interval)
+            .unwrap_or(DEFAULT_REPLICA_PROBE_INTERVAL);
+        debug!(
+            "Probing Replicas Of {} Every {}ms",
+            self.config.name, interval
+        );
+
+        let replica_health = Arc::downgrade(&self.replica_health);
+        let replica_pools = self.replica_pools.clone();
+        let replica_configs = SqlDataSource::get_replica_configs(&self.config);
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(Duration::from_millis(interval)).await;
+                let replica_health = match replica_health.upgrade() {
+                    Some(replica_health) => replica_health,
+                    None => break,
+                };
+                for (index, (pool, replica_config)) in
+                    replica_pools.iter().zip(&replica_configs).enumerate()
+                {
+                    let healthy =
+                        SqlDataSource::check_pool_health(pool, &replica_config.name).await;
+                    trace!("Replica {} Healthy: {}", replica_config.name, healthy);
+                    replica_health[index].store(healthy, Ordering::Relaxed);
+                }
+            }
+        });
+    }
+
+    /// Checks a replica after a read on it failed. Connection errors mark the replica unhealthy
+    /// until the probe finds it healthy again, while errors of the query itself leave it in use.
+    pub async fn probe_failed_replica(&self, index: usize) {
+        let name = format!("{}_replica_{}", self.config.name, index);
+        let healthy = SqlDataSource::check_pool_health(&self.replica_pools[index], &name).await;
+        if !healthy {
+            warn!("Marking Replica {} Unhealthy", name);
+            self.replica_health[index].store(false, Ordering::Relaxed);
+        }
+    }
+}
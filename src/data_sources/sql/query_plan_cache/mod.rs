@@ -0,0 +1,88 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use log::{debug, trace};
+
+/// Default number of query plans cached per SQL data source.
+pub const DEFAULT_QUERY_PLAN_CACHE_CAPACITY: usize = 512;
+
+/// The SQL generated for an input shape. Only the bound values and the pagination clause
+/// differ between requests sharing a plan.
+#[derive(Debug, Clone)]
+pub struct QueryPlan {
+    /// The query, without the pagination clause of find many queries.
+    pub query: String,
+    pub count_query: Option<String>,
+    pub identifier_query: Option<String>,
+    pub schema: Option<String>,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    plan: QueryPlan,
+    last_used: Instant,
+}
+
+/// Query plans keyed by entity, resolver type and input shape. Once full, the least recently
+/// used plan is evicted.
+#[derive(Debug, Clone)]
+pub struct QueryPlanCache {
+    capacity: usize,
+    plans: Arc<Mutex<HashMap<String, CacheEntry>>>,
+}
+
+impl QueryPlanCache {
+    /// Creates a cache holding up to `capacity` plans. A capacity of 0 disables the cache.
+    pub fn new(capacity: usize) -> Self {
+        QueryPlanCache {
+            capacity,
+            plans: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    pub fn get(&self, key: &str) -> Option<QueryPlan> {
+        let mut plans = self.plans.lock().unwrap();
+        plans.get_mut(key).map(|entry| {
+            entry.last_used = Instant::now();
+            entry.plan.clone()
+        })
+    }
+
+    /// Caches the plan of a shape, evicting the least recently used plan when full.
+    pub fn insert(&self, key: String, plan: QueryPlan) {
+        let mut plans = self.plans.lock().unwrap();
+        if !plans.contains_key(&key) && plans.len() >= self.capacity {
+            let least_recently_used = plans
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(least_recently_used) = least_recently_used {
+                debug!("Evicting Query Plan: {}", least_recently_used);
+                plans.remove(&least_recently_used);
+            }
+        }
+        trace!("Caching Query Plan: {} {:?}", key, plan);
+        plans.insert(
+            key,
+            CacheEntry {
+                plan,
+                last_used: Instant::now(),
+            },
+        );
+    }
+
+    pub fn len(&self) -> usize {
+        self.plans.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
@@ -0,0 +1,39 @@
+use log::debug;
+
+use crate::data_sources::TotalCount;
+
+use super::{
+    services::{ResponseRow, Services},
+    SqlDataSource, SqlQuery,
+};
+
+impl SqlDataSource {
+    /// Finds one row on the read pool, checking the replica used when the read fails.
+    pub async fn read_one(
+        &self,
+        sql_query: &SqlQuery,
+    ) -> Result<Option<ResponseRow>, async_graphql::Error> {
+        debug!("Reading One: {}", self.config.name);
+        let (replica, pool) = self.get_read_pool();
+        let result = Services::find_one(pool, sql_query).await;
+        if let (Err(_), Some(index)) = (&result, replica) {
+            self.probe_failed_replica(index).await;
+        }
+        result
+    }
+
+    /// Finds many rows on the read pool, checking the replica used when the read fails.
+    pub async fn read_many(
+        &self,
+        sql_query: &SqlQuery,
+        has_selection_set: &bool,
+    ) -> Result<(Vec<Option<ResponseRow>>, TotalCount), async_graphql::Error> {
+        debug!("Reading Many: {}", self.config.name);
+        let (replica, pool) = self.get_read_pool();
+        let result = Services::find_many(pool, sql_query, has_selection_set).await;
+        if let (Err(_), Some(index)) = (&result, replica) {
+            self.probe_failed_replica(index).await;
+        }
+        result
+    }
+}
@@ -0,0 +1,29 @@
+use log::error;
+use sqlx::{postgres::PgArguments, query::Query, Postgres};
+
+use crate::sql_value::SqlValue;
+
+use super::Services;
+
+impl Services {
+    /// Binds a list value as a single native postgres array, such as `text[]` or `int[]`.
+    pub fn bind_postgres_array<'q>(
+        query: Query<'q, Postgres, PgArguments>,
+        value: &SqlValue,
+    ) -> Query<'q, Postgres, PgArguments> {
+        match value {
+            SqlValue::StringList(values) | SqlValue::ObjectIDList(values) => {
+                query.bind(values.clone())
+            }
+            SqlValue::IntList(values) => query.bind(values.clone()),
+            SqlValue::BoolList(values) => query.bind(values.clone()),
+            SqlValue::UUIDList(values) => query.bind(values.clone()),
+            SqlValue::DateTimeList(values) => query.bind(values.clone()),
+            SqlValue::Array(value) => Services::bind_postgres_array(query, value),
+            _ => {
+                error!("Binding scalar value as array: {:?}", value);
+                Services::bind_sql_value(query, value)
+            }
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use sqlx::{postgres::PgArguments, query::Query, Postgres};
+
+use crate::sql_value::SqlValue;
+
+use super::Services;
+
+impl Services {
+    /// Binds a single `SqlValue` to a postgres query. Arrays are bound as native postgres arrays,
+    /// other values as with `bind_sql_value`.
+    pub fn bind_postgres_value<'q>(
+        query: Query<'q, Postgres, PgArguments>,
+        value: &SqlValue,
+    ) -> Query<'q, Postgres, PgArguments> {
+        match value {
+            SqlValue::Array(values) => Services::bind_postgres_array(query, values),
+            _ => Services::bind_sql_value(query, value),
+        }
+    }
+}
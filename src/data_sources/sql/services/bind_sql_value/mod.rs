@@ -0,0 +1,77 @@
+use sqlx::{database::HasArguments, query::Query, Database, Encode, Type};
+
+use crate::{scalar_option::ScalarOption, sql_value::SqlValue};
+
+use super::Services;
+
+impl Services {
+    /// Binds a single `SqlValue` to a query. List values are bound one element at a time.
+    /// Arrays are bound one element at a time too, postgres binds them with `bind_postgres_array`.
+    pub fn bind_sql_value<'q, DB>(
+        mut query: Query<'q, DB, <DB as HasArguments<'q>>::Arguments>,
+        value: &SqlValue,
+    ) -> Query<'q, DB, <DB as HasArguments<'q>>::Arguments>
+    where
+        DB: Database,
+        String: Encode<'q, DB> + Type<DB>,
+        i32: Encode<'q, DB> + Type<DB>,
+        i64: Encode<'q, DB> + Type<DB>,
+        bool: Encode<'q, DB> + Type<DB>,
+        uuid::Uuid: Encode<'q, DB> + Type<DB>,
+        chrono::DateTime<chrono::Utc>: Encode<'q, DB> + Type<DB>,
+        Option<String>: Encode<'q, DB> + Type<DB>,
+        Option<i32>: Encode<'q, DB> + Type<DB>,
+        Option<bool>: Encode<'q, DB> + Type<DB>,
+        Option<uuid::Uuid>: Encode<'q, DB> + Type<DB>,
+        Option<chrono::DateTime<chrono::Utc>>: Encode<'q, DB> + Type<DB>,
+    {
+        match value {
+            SqlValue::String(v) | SqlValue::ObjectID(v) | SqlValue::Json(v) => {
+                query.bind(v.clone())
+            }
+            SqlValue::Int(v) => query.bind(*v),
+            SqlValue::BigInt(v) => query.bind(*v),
+            SqlValue::Bool(v) => query.bind(*v),
+            SqlValue::StringList(values) | SqlValue::ObjectIDList(values) => {
+                for v in values {
+                    query = query.bind(v.clone());
+                }
+                query
+            }
+            SqlValue::IntList(values) => {
+                for v in values {
+                    query = query.bind(*v);
+                }
+                query
+            }
+            SqlValue::BoolList(values) => {
+                for v in values {
+                    query = query.bind(*v);
+                }
+                query
+            }
+            SqlValue::UUID(v) => query.bind(*v),
+            SqlValue::UUIDList(values) => {
+                for v in values {
+                    query = query.bind(*v);
+                }
+                query
+            }
+            SqlValue::DateTime(v) => query.bind(*v),
+            SqlValue::DateTimeList(values) => {
+                for v in values {
+                    query = query.bind(*v);
+                }
+                query
+            }
+            SqlValue::Array(values) => Services::bind_sql_value(query, values),
+            SqlValue::Null(scalar) => match scalar {
+                ScalarOption::Int => query.bind(None::<i32>),
+                ScalarOption::Boolean => query.bind(None::<bool>),
+                ScalarOption::UUID => query.bind(None::<uuid::Uuid>),
+                ScalarOption::DateTime => query.bind(None::<chrono::DateTime<chrono::Utc>>),
+                _ => query.bind(None::<String>),
+            },
+        }
+    }
+}
@@ -1,11 +1,10 @@
-use bson::doc;
+use std::sync::Arc;
+
 use log::{debug, error};
 
 use crate::{
-    configuration::subgraph::{
-        data_sources::sql::DialectEnum, entities::ServiceEntityConfig, SubGraphConfig,
-    },
-    data_sources::sql::{PoolEnum, SqlDataSource, SqlQuery},
+    configuration::subgraph::{data_sources::sql::DialectEnum, entities::ServiceEntityConfig},
+    data_sources::sql::{SqlDataSource, SqlQuery, TransactionEnum},
     sql_value::SqlValue,
 };
 
@@ -14,220 +13,85 @@ use super::{ResponseRow, Services};
 impl Services {
     pub async fn create_one(
         entity: &ServiceEntityConfig,
-        pool_enum: &PoolEnum,
+        transaction_enum: &mut TransactionEnum,
         sql_query: &SqlQuery,
-        dialect: DialectEnum,
-        subgraph_config: &SubGraphConfig,
     ) -> Result<Option<ResponseRow>, async_graphql::Error> {
         debug!("Executing Create One Query: {:?}", sql_query);
 
-        match pool_enum {
-            PoolEnum::MySql(pool) => {
+        match transaction_enum {
+            TransactionEnum::MySql(transaction) => {
                 let mut query = sqlx::query(&sql_query.query);
 
                 for value in &sql_query.values {
-                    match value {
-                        SqlValue::String(v) | SqlValue::ObjectID(v) => {
-                            query = query.bind(v);
-                        }
-                        SqlValue::Int(v) => {
-                            query = query.bind(v);
-                        }
-                        SqlValue::Bool(v) => {
-                            query = query.bind(v);
-                        }
-                        SqlValue::StringList(values) | SqlValue::ObjectIDList(values) => {
-                            for string in values {
-                                query = query.bind(string)
-                            }
-                        }
-                        SqlValue::IntList(values) => {
-                            for int in values {
-                                query = query.bind(int)
-                            }
-                        }
-                        SqlValue::BoolList(values) => {
-                            for bool in values {
-                                query = query.bind(bool)
-                            }
-                        }
-                        SqlValue::UUID(v) => {
-                            query = query.bind(v);
-                        }
-                        SqlValue::UUIDList(values) => {
-                            for uuid in values {
-                                query = query.bind(uuid)
-                            }
-                        }
-                        SqlValue::DateTime(v) => {
-                            query = query.bind(v);
-                        }
-                        SqlValue::DateTimeList(values) => {
-                            for datetime in values {
-                                query = query.bind(datetime)
+                    query = Services::bind_sql_value(query, value);
+                }
+
+                let last_inserted_id = query.execute(&mut **transaction).await?.last_insert_id();
+
+                // MySQL does not support `RETURNING`, so the row is re-fetched by its primary
+                // key. Keys provided by the client are used as is, a missing key is the value
+                // generated by the auto increment column.
+                let mut primary_key = vec![];
+                for field in ServiceEntityConfig::get_primary_key_fields(entity)? {
+                    let value = match sql_query.value_keys.iter().position(|k| k == &field.name) {
+                        Some(index) => sql_query.values[index].clone(),
+                        None => {
+                            if last_inserted_id == 0 {
+                                error!(
+                                    "No value provided for primary key {} of entity {}",
+                                    field.name, entity.name
+                                );
+                                return Err(async_graphql::Error::new(format!(
+                                    "No value provided for primary key {} of entity {}",
+                                    field.name, entity.name
+                                )));
                             }
+                            SqlValue::BigInt(last_inserted_id as i64)
                         }
-                    }
+                    };
+                    primary_key.push((field.name, value));
                 }
 
-                let last_inserted_id = query.execute(pool).await?.last_insert_id();
-
-                let input_document = doc! {
-                    "query": {
-                        "id": last_inserted_id as i32,
-                    }
-                };
-
-                let (find_one_query, ..) = SqlDataSource::create_find_one_query(
-                    entity,
+                let (find_one_query, primary_key_values) = SqlDataSource::create_primary_key_query(
                     &sql_query.table,
-                    &dialect,
-                    &input_document,
-                    subgraph_config,
-                    None,
+                    sql_query.schema.as_deref(),
+                    &vec![primary_key],
+                    &DialectEnum::MYSQL,
                 )?;
 
-                let result = sqlx::query(&find_one_query)
-                    .bind(last_inserted_id)
-                    .fetch_one(pool)
-                    .await?;
+                let mut find_one_query = sqlx::query(&find_one_query);
+                for value in &primary_key_values {
+                    find_one_query = Services::bind_sql_value(find_one_query, value);
+                }
+                let result = find_one_query.fetch_one(&mut **transaction).await?;
 
-                Ok(Some(ResponseRow::MySql(result)))
+                Ok(Some(ResponseRow::MySql(Arc::new(result))))
             }
-            PoolEnum::Postgres(pool) => {
+            TransactionEnum::Postgres(transaction) => {
                 let mut query = sqlx::query(&sql_query.query);
 
                 for value in &sql_query.values {
-                    match value {
-                        SqlValue::String(v) | SqlValue::ObjectID(v) => {
-                            query = query.bind(v);
-                        }
-                        SqlValue::Int(v) => {
-                            query = query.bind(v);
-                        }
-                        SqlValue::Bool(v) => {
-                            query = query.bind(v);
-                        }
-                        SqlValue::StringList(values) | SqlValue::ObjectIDList(values) => {
-                            for string in values {
-                                query = query.bind(string)
-                            }
-                        }
-                        SqlValue::IntList(values) => {
-                            for int in values {
-                                query = query.bind(int)
-                            }
-                        }
-                        SqlValue::BoolList(values) => {
-                            for bool in values {
-                                query = query.bind(bool)
-                            }
-                        }
-                        SqlValue::UUID(v) => {
-                            query = query.bind(v);
-                        }
-                        SqlValue::UUIDList(values) => {
-                            for uuid in values {
-                                query = query.bind(uuid)
-                            }
-                        }
-                        SqlValue::DateTime(v) => {
-                            query = query.bind(v);
-                        }
-                        SqlValue::DateTimeList(values) => {
-                            for datetime in values {
-                                query = query.bind(datetime)
-                            }
-                        }
-                    }
+                    query = Services::bind_postgres_value(query, value);
                 }
 
-                let result = query.fetch_one(pool).await?;
+                let result = query.fetch_one(&mut **transaction).await?;
 
-                Ok(Some(ResponseRow::Postgres(result)))
+                Ok(Some(ResponseRow::Postgres(Arc::new(result))))
             }
-            PoolEnum::SqLite(pool) => {
+            TransactionEnum::SqLite(transaction) => {
                 let mut query = sqlx::query(&sql_query.query);
 
                 for value in &sql_query.values {
-                    match value {
-                        SqlValue::String(v) | SqlValue::ObjectID(v) => {
-                            query = query.bind(v);
-                        }
-                        SqlValue::Int(v) => {
-                            query = query.bind(v);
-                        }
-                        SqlValue::Bool(v) => {
-                            query = query.bind(v);
-                        }
-                        SqlValue::StringList(values) | SqlValue::ObjectIDList(values) => {
-                            for string in values {
-                                query = query.bind(string)
-                            }
-                        }
-                        SqlValue::IntList(values) => {
-                            for int in values {
-                                query = query.bind(int)
-                            }
-                        }
-                        SqlValue::BoolList(values) => {
-                            for bool in values {
-                                query = query.bind(bool)
-                            }
-                        }
-                        SqlValue::UUID(v) => {
-                            query = query.bind(v);
-                        }
-                        SqlValue::UUIDList(values) => {
-                            for uuid in values {
-                                query = query.bind(uuid)
-                            }
-                        }
-                        SqlValue::DateTime(v) => {
-                            query = query.bind(v);
-                        }
-                        SqlValue::DateTimeList(values) => {
-                            for datetime in values {
-                                query = query.bind(datetime)
-                            }
-                        }
-                    }
+                    query = Services::bind_sql_value(query, value);
                 }
 
-                let last_inserted_rowid = query
-                    .execute(pool)
-                    .await
-                    .map_err(|e| {
-                        error!("Error executing sqlite create statement: {}", e);
-                        e
-                    })?
-                    .last_insert_rowid();
-
-                let input_document = doc! {
-                    "query": {
-                        "id": last_inserted_rowid as i32,
-                    }
-                };
-
-                let (find_one_query, ..) = SqlDataSource::create_find_one_query(
-                    entity,
-                    &sql_query.table,
-                    &dialect,
-                    &input_document,
-                    subgraph_config,
-                    None,
-                )?;
-
-                let result = sqlx::query(&find_one_query)
-                    .bind(last_inserted_rowid)
-                    .fetch_one(pool)
-                    .await
-                    .map_err(|e| {
-                        error!("Error refetching result: {}", e);
-                        e
-                    })?;
+                // The create query returns the inserted row.
+                let result = query.fetch_one(&mut **transaction).await.map_err(|e| {
+                    error!("Error executing sqlite create statement: {}", e);
+                    e
+                })?;
 
-                Ok(Some(ResponseRow::SqLite(result)))
+                Ok(Some(ResponseRow::SqLite(Arc::new(result))))
             }
         }
     }
@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use async_graphql::ErrorExtensions;
+use log::{debug, error, trace};
+
+use crate::data_sources::sql::{PoolEnum, SqlQuery};
+
+use super::{ResponseRow, Services};
+
+impl Services {
+    /// Executes a statement, such as a custom mutation. When `fetch_rows` is true the rows
+    /// returned by the statement are collected, else the number of affected rows is returned.
+    pub async fn execute_statement(
+        pool_enum: &PoolEnum,
+        sql_query: &SqlQuery,
+        fetch_rows: bool,
+    ) -> Result<(Vec<Option<ResponseRow>>, u64), async_graphql::Error> {
+        debug!("Executing Statement");
+        trace!("{:?}", sql_query);
+
+        let map_error = |e: sqlx::Error| {
+            error!(
+                "Error executing statement: {:?} \n Error: {:?}",
+                sql_query, e
+            );
+            async_graphql::Error::new("Error executing statement.")
+                .extend_with(|_, err| err.set("cause", e.to_string()))
+        };
+
+        match pool_enum {
+            PoolEnum::MySql(pool) => {
+                let mut query = sqlx::query(&sql_query.query);
+                for value in &sql_query.where_values {
+                    query = Services::bind_sql_value(query, value);
+                }
+                if fetch_rows {
+                    let rows = query.fetch_all(pool).await.map_err(map_error)?;
+                    let count = rows.len() as u64;
+                    Ok((
+                        rows.into_iter()
+                            .map(|row| Some(ResponseRow::MySql(Arc::new(row))))
+                            .collect(),
+                        count,
+                    ))
+                } else {
+                    let result = query.execute(pool).await.map_err(map_error)?;
+                    Ok((Vec::new(), result.rows_affected()))
+                }
+            }
+            PoolEnum::Postgres(pool) => {
+                let mut query = sqlx::query(&sql_query.query);
+                for value in &sql_query.where_values {
+                    query = Services::bind_sql_value(query, value);
+                }
+                if fetch_rows {
+                    let rows = query.fetch_all(pool).await.map_err(map_error)?;
+                    let count = rows.len() as u64;
+                    Ok((
+                        rows.into_iter()
+                            .map(|row| Some(ResponseRow::Postgres(Arc::new(row))))
+                            .collect(),
+                        count,
+                    ))
+                } else {
+                    let result = query.execute(pool).await.map_err(map_error)?;
+                    Ok((Vec::new(), result.rows_affected()))
+                }
+            }
+            PoolEnum::SqLite(pool) => {
+                let mut query = sqlx::query(&sql_query.query);
+                for value in &sql_query.where_values {
+                    query = Services::bind_sql_value(query, value);
+                }
+                if fetch_rows {
+                    let rows = query.fetch_all(pool).await.map_err(map_error)?;
+                    let count = rows.len() as u64;
+                    Ok((
+                        rows.into_iter()
+                            .map(|row| Some(ResponseRow::SqLite(Arc::new(row))))
+                            .collect(),
+                        count,
+                    ))
+                } else {
+                    let result = query.execute(pool).await.map_err(map_error)?;
+                    Ok((Vec::new(), result.rows_affected()))
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use async_graphql::ErrorExtensions;
+use log::{debug, error, trace};
+
+use crate::data_sources::sql::{SqlQuery, TransactionEnum};
+
+use super::Services;
+
+impl Services {
+    /// Executes the statements in the transaction. The caller commits the transaction, so all of
+    /// the statements are rolled back if any fails.
+    pub async fn execute_transaction(
+        transaction_enum: &mut TransactionEnum,
+        sql_queries: &[SqlQuery],
+    ) -> Result<(), async_graphql::Error> {
+        debug!("Executing Transaction");
+        trace!("{:?}", sql_queries);
+
+        let map_error = |e: sqlx::Error| {
+            error!("Error executing transaction: {:?}", e);
+            async_graphql::Error::new("Error executing transaction.")
+                .extend_with(|_, err| err.set("cause", e.to_string()))
+        };
+
+        match transaction_enum {
+            TransactionEnum::MySql(transaction) => {
+                for sql_query in sql_queries {
+                    let mut query = sqlx::query(&sql_query.query);
+                    for value in &sql_query.where_values {
+                        query = Services::bind_sql_value(query, value);
+                    }
+                    query.execute(&mut **transaction).await.map_err(map_error)?;
+                }
+            }
+            TransactionEnum::Postgres(transaction) => {
+                for sql_query in sql_queries {
+                    let mut query = sqlx::query(&sql_query.query);
+                    for value in &sql_query.where_values {
+                        query = Services::bind_sql_value(query, value);
+                    }
+                    query.execute(&mut **transaction).await.map_err(map_error)?;
+                }
+            }
+            TransactionEnum::SqLite(transaction) => {
+                for sql_query in sql_queries {
+                    let mut query = sqlx::query(&sql_query.query);
+                    for value in &sql_query.where_values {
+                        query = Services::bind_sql_value(query, value);
+                    }
+                    query.execute(&mut **transaction).await.map_err(map_error)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,13 +1,12 @@
+use std::sync::Arc;
+
 use async_graphql::ErrorExtensions;
 use log::{debug, error, trace};
 use sqlx::{mysql::MySqlArguments, MySql, Row};
 
-use crate::{
-    data_sources::{
-        sql::{PoolEnum, SqlQuery},
-        TotalCount,
-    },
-    sql_value::SqlValue,
+use crate::data_sources::{
+    sql::{PoolEnum, SqlQuery},
+    TotalCount,
 };
 
 use super::{ResponseRow, Services};
@@ -29,58 +28,8 @@ impl Services {
                 let mut count_query = sqlx::query(&count_query_str);
 
                 for value in &sql_query.where_values {
-                    match value {
-                        SqlValue::String(v) | SqlValue::ObjectID(v) => {
-                            query = query.bind(v);
-                            count_query = count_query.bind(v);
-                        }
-                        SqlValue::Int(v) => {
-                            query = query.bind(v);
-                            count_query = count_query.bind(v);
-                        }
-                        SqlValue::Bool(v) => {
-                            query = query.bind(v);
-                            count_query = count_query.bind(v);
-                        }
-                        SqlValue::StringList(values) | SqlValue::ObjectIDList(values) => {
-                            for string in values {
-                                query = query.bind(string);
-                                count_query = count_query.bind(string);
-                            }
-                        }
-                        SqlValue::IntList(values) => {
-                            for int in values {
-                                query = query.bind(int);
-                                count_query = count_query.bind(int);
-                            }
-                        }
-                        SqlValue::BoolList(values) => {
-                            for bool in values {
-                                query = query.bind(bool);
-                                count_query = count_query.bind(bool);
-                            }
-                        }
-                        SqlValue::UUID(v) => {
-                            query = query.bind(v);
-                            count_query = count_query.bind(v);
-                        }
-                        SqlValue::UUIDList(values) => {
-                            for uuid in values {
-                                query = query.bind(uuid);
-                                count_query = count_query.bind(uuid);
-                            }
-                        }
-                        SqlValue::DateTime(v) => {
-                            query = query.bind(v);
-                            count_query = count_query.bind(v);
-                        }
-                        SqlValue::DateTimeList(values) => {
-                            for datetime in values {
-                                query = query.bind(datetime);
-                                count_query = count_query.bind(datetime);
-                            }
-                        }
-                    }
+                    query = Services::bind_sql_value(query, value);
+                    count_query = Services::bind_sql_value(count_query, value);
                 }
 
                 let rows = if *has_selection_set {
@@ -95,7 +44,7 @@ impl Services {
 
                 let mut response_rows = Vec::new();
                 for row in rows {
-                    response_rows.push(Some(ResponseRow::MySql(row)));
+                    response_rows.push(Some(ResponseRow::MySql(Arc::new(row))));
                 }
 
                 let count = count_query.fetch_one(pool).await.map_err(|e| {
@@ -115,58 +64,8 @@ impl Services {
                 let mut count_query = sqlx::query(&count_query_str);
 
                 for value in &sql_query.where_values {
-                    match value {
-                        SqlValue::String(v) | SqlValue::ObjectID(v) => {
-                            query = query.bind(v);
-                            count_query = count_query.bind(v);
-                        }
-                        SqlValue::Int(v) => {
-                            query = query.bind(v);
-                            count_query = count_query.bind(v);
-                        }
-                        SqlValue::Bool(v) => {
-                            query = query.bind(v);
-                            count_query = count_query.bind(v);
-                        }
-                        SqlValue::StringList(values) | SqlValue::ObjectIDList(values) => {
-                            for string in values {
-                                query = query.bind(string);
-                                count_query = count_query.bind(string);
-                            }
-                        }
-                        SqlValue::IntList(values) => {
-                            for int in values {
-                                query = query.bind(int);
-                                count_query = count_query.bind(int);
-                            }
-                        }
-                        SqlValue::BoolList(values) => {
-                            for bool in values {
-                                query = query.bind(bool);
-                                count_query = count_query.bind(bool);
-                            }
-                        }
-                        SqlValue::UUID(v) => {
-                            query = query.bind(v);
-                            count_query = count_query.bind(v);
-                        }
-                        SqlValue::UUIDList(values) => {
-                            for uuid in values {
-                                query = query.bind(uuid);
-                                count_query = count_query.bind(uuid);
-                            }
-                        }
-                        SqlValue::DateTime(v) => {
-                            query = query.bind(v);
-                            count_query = count_query.bind(v);
-                        }
-                        SqlValue::DateTimeList(values) => {
-                            for datetime in values {
-                                query = query.bind(datetime);
-                                count_query = count_query.bind(datetime);
-                            }
-                        }
-                    }
+                    query = Services::bind_postgres_value(query, value);
+                    count_query = Services::bind_postgres_value(count_query, value);
                 }
 
                 let rows = if *has_selection_set {
@@ -181,7 +80,7 @@ impl Services {
 
                 let mut response_rows = Vec::new();
                 for row in rows {
-                    response_rows.push(Some(ResponseRow::Postgres(row)));
+                    response_rows.push(Some(ResponseRow::Postgres(Arc::new(row))));
                 }
 
                 let count = count_query.fetch_one(pool).await.map_err(|e| {
@@ -204,58 +103,8 @@ impl Services {
                 let mut count_query = sqlx::query(&count_query_str);
 
                 for value in &sql_query.where_values {
-                    match value {
-                        SqlValue::String(v) | SqlValue::ObjectID(v) => {
-                            query = query.bind(v);
-                            count_query = count_query.bind(v);
-                        }
-                        SqlValue::Int(v) => {
-                            query = query.bind(v);
-                            count_query = count_query.bind(v);
-                        }
-                        SqlValue::Bool(v) => {
-                            query = query.bind(v);
-                            count_query = count_query.bind(v);
-                        }
-                        SqlValue::StringList(values) | SqlValue::ObjectIDList(values) => {
-                            for string in values {
-                                query = query.bind(string);
-                                count_query = count_query.bind(string)
-                            }
-                        }
-                        SqlValue::IntList(values) => {
-                            for int in values {
-                                query = query.bind(int);
-                                count_query = count_query.bind(int)
-                            }
-                        }
-                        SqlValue::BoolList(values) => {
-                            for bool in values {
-                                query = query.bind(bool);
-                                count_query = count_query.bind(bool)
-                            }
-                        }
-                        SqlValue::UUID(v) => {
-                            query = query.bind(v);
-                            count_query = count_query.bind(v);
-                        }
-                        SqlValue::UUIDList(values) => {
-                            for uuid in values {
-                                query = query.bind(uuid);
-                                count_query = count_query.bind(uuid)
-                            }
-                        }
-                        SqlValue::DateTime(v) => {
-                            query = query.bind(v);
-                            count_query = count_query.bind(v);
-                        }
-                        SqlValue::DateTimeList(values) => {
-                            for datetime in values {
-                                count_query = count_query.bind(datetime);
-                                query = query.bind(datetime)
-                            }
-                        }
-                    }
+                    query = Services::bind_sql_value(query, value);
+                    count_query = Services::bind_sql_value(count_query, value);
                 }
 
                 let rows = if *has_selection_set {
@@ -270,7 +119,7 @@ impl Services {
 
                 let mut response_rows = Vec::new();
                 for row in rows {
-                    response_rows.push(Some(ResponseRow::SqLite(row)));
+                    response_rows.push(Some(ResponseRow::SqLite(Arc::new(row))));
                 }
 
                 let count = count_query.fetch_one(pool).await.map_err(|e| {
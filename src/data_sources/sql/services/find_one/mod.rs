@@ -1,9 +1,8 @@
+use std::sync::Arc;
+
 use log::{debug, error, trace, warn};
 
-use crate::{
-    data_sources::sql::{PoolEnum, SqlQuery},
-    sql_value::SqlValue,
-};
+use crate::data_sources::sql::{PoolEnum, SqlQuery};
 
 use super::{ResponseRow, Services};
 
@@ -19,48 +18,7 @@ impl Services {
                 let mut query = sqlx::query(&sql_query.query);
 
                 for value in &sql_query.where_values {
-                    match value {
-                        SqlValue::String(v) | SqlValue::ObjectID(v) => {
-                            query = query.bind(v);
-                        }
-                        SqlValue::Int(v) => {
-                            query = query.bind(v);
-                        }
-                        SqlValue::Bool(v) => {
-                            query = query.bind(v);
-                        }
-                        SqlValue::StringList(values) | SqlValue::ObjectIDList(values) => {
-                            for string in values {
-                                query = query.bind(string)
-                            }
-                        }
-                        SqlValue::IntList(values) => {
-                            for int in values {
-                                query = query.bind(int)
-                            }
-                        }
-                        SqlValue::BoolList(values) => {
-                            for bool in values {
-                                query = query.bind(bool)
-                            }
-                        }
-                        SqlValue::UUID(v) => {
-                            query = query.bind(v);
-                        }
-                        SqlValue::UUIDList(values) => {
-                            for uuid in values {
-                                query = query.bind(uuid)
-                            }
-                        }
-                        SqlValue::DateTime(v) => {
-                            query = query.bind(v);
-                        }
-                        SqlValue::DateTimeList(values) => {
-                            for datetime in values {
-                                query = query.bind(datetime)
-                            }
-                        }
-                    }
+                    query = Services::bind_sql_value(query, value);
                 }
 
                 let row = query.fetch_optional(pool).await?;
@@ -71,55 +29,14 @@ impl Services {
                     return Ok(None);
                 }
 
-                Ok(Some(ResponseRow::MySql(row.unwrap())))
+                Ok(Some(ResponseRow::MySql(Arc::new(row.unwrap()))))
             }
             PoolEnum::Postgres(pool) => {
                 debug!("Executing POSTGRES Query");
                 let mut query = sqlx::query(&sql_query.query);
 
                 for value in &sql_query.where_values {
-                    match value {
-                        SqlValue::String(v) | SqlValue::ObjectID(v) => {
-                            query = query.bind(v);
-                        }
-                        SqlValue::Int(v) => {
-                            query = query.bind(v);
-                        }
-                        SqlValue::Bool(v) => {
-                            query = query.bind(v);
-                        }
-                        SqlValue::StringList(values) | SqlValue::ObjectIDList(values) => {
-                            for string in values {
-                                query = query.bind(string)
-                            }
-                        }
-                        SqlValue::IntList(values) => {
-                            for int in values {
-                                query = query.bind(int)
-                            }
-                        }
-                        SqlValue::BoolList(values) => {
-                            for bool in values {
-                                query = query.bind(bool)
-                            }
-                        }
-                        SqlValue::UUID(v) => {
-                            query = query.bind(v);
-                        }
-                        SqlValue::UUIDList(values) => {
-                            for uuid in values {
-                                query = query.bind(uuid)
-                            }
-                        }
-                        SqlValue::DateTime(v) => {
-                            query = query.bind(v);
-                        }
-                        SqlValue::DateTimeList(values) => {
-                            for datetime in values {
-                                query = query.bind(datetime)
-                            }
-                        }
-                    }
+                    query = Services::bind_postgres_value(query, value);
                 }
 
                 let row = query.fetch_optional(pool).await?;
@@ -128,55 +45,14 @@ impl Services {
                     return Ok(None);
                 }
 
-                Ok(Some(ResponseRow::Postgres(row.unwrap())))
+                Ok(Some(ResponseRow::Postgres(Arc::new(row.unwrap()))))
             }
             PoolEnum::SqLite(pool) => {
                 debug!("Executing SQLITE Query: {:?}", sql_query.query);
                 let mut query = sqlx::query(&sql_query.query);
 
                 for value in &sql_query.where_values {
-                    match value {
-                        SqlValue::String(v) | SqlValue::ObjectID(v) => {
-                            query = query.bind(v);
-                        }
-                        SqlValue::Int(v) => {
-                            query = query.bind(v);
-                        }
-                        SqlValue::Bool(v) => {
-                            query = query.bind(v);
-                        }
-                        SqlValue::StringList(values) | SqlValue::ObjectIDList(values) => {
-                            for string in values {
-                                query = query.bind(string)
-                            }
-                        }
-                        SqlValue::IntList(values) => {
-                            for int in values {
-                                query = query.bind(int)
-                            }
-                        }
-                        SqlValue::BoolList(values) => {
-                            for bool in values {
-                                query = query.bind(bool)
-                            }
-                        }
-                        SqlValue::UUID(v) => {
-                            query = query.bind(v);
-                        }
-                        SqlValue::UUIDList(values) => {
-                            for uuid in values {
-                                query = query.bind(uuid)
-                            }
-                        }
-                        SqlValue::DateTime(v) => {
-                            query = query.bind(v);
-                        }
-                        SqlValue::DateTimeList(values) => {
-                            for datetime in values {
-                                query = query.bind(datetime)
-                            }
-                        }
-                    }
+                    query = Services::bind_sql_value(query, value);
                 }
 
                 let row = query.fetch_optional(pool).await.map_err(|e| {
@@ -189,7 +65,7 @@ impl Services {
                     return Ok(None);
                 }
                 trace!("Row Found: {:?}", row.is_some());
-                Ok(Some(ResponseRow::SqLite(row.unwrap())))
+                Ok(Some(ResponseRow::SqLite(Arc::new(row.unwrap()))))
             }
         }
     }
@@ -1,6 +1,13 @@
+use std::sync::Arc;
+
 use sqlx::{mysql::MySqlRow, postgres::PgRow, sqlite::SqliteRow};
 
+pub mod bind_postgres_array;
+pub mod bind_postgres_value;
+pub mod bind_sql_value;
 pub mod create_one;
+pub mod execute_statement;
+pub mod execute_transaction;
 pub mod find_many;
 pub mod find_one;
 pub mod update_many;
@@ -9,8 +16,11 @@ pub mod update_one;
 #[derive(Debug)]
 pub struct Services;
 
+/// A row returned from a SQL data source. Rows are shared so that a single row may be returned
+/// to more than one parent, such as when batching joins.
+#[derive(Clone)]
 pub enum ResponseRow {
-    MySql(MySqlRow),
-    Postgres(PgRow),
-    SqLite(SqliteRow),
+    MySql(Arc<MySqlRow>),
+    Postgres(Arc<PgRow>),
+    SqLite(Arc<SqliteRow>),
 }
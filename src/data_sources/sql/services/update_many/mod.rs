@@ -1,10 +1,12 @@
+use std::sync::Arc;
+
 use log::{debug, error, trace};
-use sqlx::Row;
 
 use crate::{
-    configuration::subgraph::entities::ServiceEntityConfig,
-    data_sources::sql::{PoolEnum, SqlQuery},
+    configuration::subgraph::{data_sources::sql::DialectEnum, entities::ServiceEntityConfig},
+    data_sources::sql::{SqlDataSource, SqlQuery, TransactionEnum},
     sql_value::SqlValue,
+    traits::sqlx::{mysql_row::FromMySqlRow, sqlite_row::FromSqliteRow},
     utils::clean_string::{clean_string, CleanOptions},
 };
 
@@ -13,17 +15,48 @@ use super::{ResponseRow, Services};
 impl Services {
     pub async fn update_many(
         entity: &ServiceEntityConfig,
-        pool_enum: &PoolEnum,
+        transaction_enum: &mut TransactionEnum,
         sql_query: &SqlQuery,
     ) -> Result<Vec<Option<ResponseRow>>, async_graphql::Error> {
         debug!("Update Many SQL Data Source");
 
+        let primary_key_names = ServiceEntityConfig::get_primary_key_fields(entity)?
+            .into_iter()
+            .map(|field| field.name)
+            .collect::<Vec<String>>();
+
         let clean_options = CleanOptions {
             newline: Some(false),
             quotes: Some(true),
         };
-        match pool_enum {
-            PoolEnum::MySql(pool) => {
+        let clean_value = |value: &SqlValue| match value {
+            SqlValue::String(v) => SqlValue::String(clean_string(v, Some(clean_options.clone()))),
+            SqlValue::ObjectID(v) => {
+                SqlValue::ObjectID(clean_string(v, Some(clean_options.clone())))
+            }
+            SqlValue::StringList(values) => SqlValue::StringList(
+                values
+                    .iter()
+                    .map(|v| clean_string(v, Some(clean_options.clone())))
+                    .collect(),
+            ),
+            SqlValue::ObjectIDList(values) => SqlValue::ObjectIDList(
+                values
+                    .iter()
+                    .map(|v| clean_string(v, Some(clean_options.clone())))
+                    .collect(),
+            ),
+            value => value.clone(),
+        };
+        let values = sql_query.values.iter().map(clean_value).collect::<Vec<_>>();
+        let where_values = sql_query
+            .where_values
+            .iter()
+            .map(clean_value)
+            .collect::<Vec<_>>();
+
+        match transaction_enum {
+            TransactionEnum::MySql(transaction) => {
                 let identifier_query = match &sql_query.identifier_query {
                     Some(query) => query,
                     None => {
@@ -41,259 +74,102 @@ impl Services {
                 let mut identifier_query = sqlx::query(&identifier_query);
 
                 let mut update_query = sqlx::query(&sql_query.query);
-                for value in &sql_query.values {
-                    match value {
-                        SqlValue::String(v) | SqlValue::ObjectID(v) => {
-                            let v = clean_string(v, Some(clean_options.clone()));
-                            update_query = update_query.bind(v.clone());
-                        }
-                        SqlValue::Int(v) => {
-                            update_query = update_query.bind(v);
-                        }
-                        SqlValue::Bool(v) => {
-                            update_query = update_query.bind(v);
-                        }
-                        SqlValue::StringList(values) | SqlValue::ObjectIDList(values) => {
-                            for v in values {
-                                let v = clean_string(v, Some(clean_options.clone()));
-                                update_query = update_query.bind(v.clone());
-                            }
-                        }
-                        SqlValue::IntList(values) => {
-                            for int in values {
-                                update_query = update_query.bind(int);
-                            }
-                        }
-                        SqlValue::BoolList(values) => {
-                            for bool in values {
-                                update_query = update_query.bind(bool);
-                            }
-                        }
-                        SqlValue::UUID(uuid) => update_query = update_query.bind(uuid),
-                        SqlValue::UUIDList(uuids) => {
-                            for uuid in uuids {
-                                update_query = update_query.bind(uuid);
-                            }
-                        }
-                        SqlValue::DateTime(date_time) => {
-                            update_query = update_query.bind(date_time);
-                        }
-                        SqlValue::DateTimeList(date_times) => {
-                            for date_time in date_times {
-                                update_query = update_query.bind(date_time);
-                            }
-                        }
-                    }
+                for value in &values {
+                    update_query = Services::bind_sql_value(update_query, value);
                 }
-                for value in &sql_query.where_values {
-                    match value {
-                        SqlValue::String(v) | SqlValue::ObjectID(v) => {
-                            let v = clean_string(v, Some(clean_options.clone()));
-                            update_query = update_query.bind(v.clone());
-                            identifier_query = identifier_query.bind(v);
-                        }
-                        SqlValue::Int(v) => {
-                            update_query = update_query.bind(v);
-                            identifier_query = identifier_query.bind(v);
-                        }
-                        SqlValue::Bool(v) => {
-                            update_query = update_query.bind(v);
-                            identifier_query = identifier_query.bind(v);
-                        }
-                        SqlValue::StringList(values) | SqlValue::ObjectIDList(values) => {
-                            for v in values {
-                                let v = clean_string(v, Some(clean_options.clone()));
-                                update_query = update_query.bind(v.clone());
-                                identifier_query = identifier_query.bind(v);
-                            }
-                        }
-                        SqlValue::IntList(values) => {
-                            for int in values {
-                                update_query = update_query.bind(int);
-                                identifier_query = identifier_query.bind(int);
-                            }
-                        }
-                        SqlValue::BoolList(values) => {
-                            for bool in values {
-                                update_query = update_query.bind(bool);
-                                identifier_query = identifier_query.bind(bool);
-                            }
-                        }
-                        SqlValue::UUID(uuid) => update_query = update_query.bind(uuid),
-                        SqlValue::UUIDList(uuids) => {
-                            for uuid in uuids {
-                                update_query = update_query.bind(uuid);
-                                identifier_query = identifier_query.bind(uuid);
-                            }
-                        }
-                        SqlValue::DateTime(date_time) => {
-                            update_query = update_query.bind(date_time);
-                            identifier_query = identifier_query.bind(date_time);
-                        }
-                        SqlValue::DateTimeList(date_times) => {
-                            for date_time in date_times {
-                                update_query = update_query.bind(date_time);
-                                identifier_query = identifier_query.bind(date_time);
-                            }
-                        }
-                    }
+                for value in &where_values {
+                    update_query = Services::bind_sql_value(update_query, value);
+                    identifier_query = Services::bind_sql_value(identifier_query, value);
                 }
 
-                let identifier_results = identifier_query.fetch_all(pool).await.map_err(|e| {
-                    error!("Error executing identifier query: {}", e);
-                    e
-                })?;
+                let identifier_results = identifier_query
+                    .fetch_all(&mut **transaction)
+                    .await
+                    .map_err(|e| {
+                        error!("Error executing identifier query: {}", e);
+                        e
+                    })?;
 
-                update_query.execute(pool).await.map_err(|e| {
-                    error!("Error executing update many query: {}", e);
-                    e
-                })?;
+                update_query
+                    .execute(&mut **transaction)
+                    .await
+                    .map_err(|e| {
+                        error!("Error executing update many query: {}", e);
+                        e
+                    })?;
 
-                let mut ids = Vec::new();
+                let mut primary_keys = Vec::new();
                 for row in identifier_results {
                     trace!("Row: {:?}", row);
-                    let identifier: i64 = row.try_get("id").map_err(|e| {
-                        error!("Error getting primary key from row: {}", e);
-                        e
-                    })?;
-                    ids.push(identifier);
+                    let primary_key = row.to_document(Some(
+                        primary_key_names.iter().map(|name| name.as_str()).collect(),
+                    ))?;
+                    primary_keys.push(SqlDataSource::get_primary_key_values(
+                        &primary_key,
+                        &DialectEnum::MYSQL,
+                    )?);
                 }
 
-                trace!("Identifiers: {:?}", ids);
+                trace!("Identifiers: {:?}", primary_keys);
 
-                if ids.is_empty() {
+                if primary_keys.is_empty() {
                     return Ok(Vec::new());
                 }
 
-                let query = format!(
-                    "SELECT * FROM {} WHERE id IN ({});",
-                    sql_query.table,
-                    ids.iter()
-                        .map(|id| id.to_string())
-                        .collect::<Vec<String>>()
-                        .join(",")
-                );
+                let (query, primary_key_values) = SqlDataSource::create_primary_key_query(
+                    &sql_query.table,
+                    sql_query.schema.as_deref(),
+                    &primary_keys,
+                    &DialectEnum::MYSQL,
+                )?;
                 trace!("Query: {}", query);
 
-                let response_query = sqlx::query(&query);
+                let mut response_query = sqlx::query(&query);
+                for value in &primary_key_values {
+                    response_query = Services::bind_sql_value(response_query, value);
+                }
 
-                let rows = response_query.fetch_all(pool).await.map_err(|e| {
-                    error!("Error finding data: {}", e);
-                    e
-                })?;
+                let rows = response_query
+                    .fetch_all(&mut **transaction)
+                    .await
+                    .map_err(|e| {
+                        error!("Error finding data: {}", e);
+                        e
+                    })?;
 
                 let mut response_rows = Vec::new();
 
                 for row in rows {
-                    response_rows.push(Some(ResponseRow::MySql(row)));
+                    response_rows.push(Some(ResponseRow::MySql(Arc::new(row))));
                 }
 
                 Ok(response_rows)
             }
-            PoolEnum::Postgres(pool) => {
+            TransactionEnum::Postgres(transaction) => {
                 let mut update_query = sqlx::query(&sql_query.query);
                 debug!("PG VALUES: {:?}", sql_query);
-                for value in &sql_query.values {
-                    match value {
-                        SqlValue::String(v) | SqlValue::ObjectID(v) => {
-                            let v = clean_string(v, Some(clean_options.clone()));
-                            update_query = update_query.bind(v);
-                        }
-                        SqlValue::Int(v) => {
-                            update_query = update_query.bind(v);
-                        }
-                        SqlValue::Bool(v) => {
-                            update_query = update_query.bind(v);
-                        }
-                        SqlValue::StringList(values) | SqlValue::ObjectIDList(values) => {
-                            for v in values {
-                                let v = clean_string(v, Some(clean_options.clone()));
-                                update_query = update_query.bind(v)
-                            }
-                        }
-                        SqlValue::IntList(values) => {
-                            for int in values {
-                                update_query = update_query.bind(int)
-                            }
-                        }
-                        SqlValue::BoolList(values) => {
-                            for bool in values {
-                                update_query = update_query.bind(bool)
-                            }
-                        }
-                        SqlValue::UUID(uuid) => update_query = update_query.bind(uuid),
-                        SqlValue::UUIDList(uuids) => {
-                            for uuid in uuids {
-                                update_query = update_query.bind(uuid)
-                            }
-                        }
-                        SqlValue::DateTime(date_time) => {
-                            update_query = update_query.bind(date_time)
-                        }
-                        SqlValue::DateTimeList(date_times) => {
-                            for date_time in date_times {
-                                update_query = update_query.bind(date_time)
-                            }
-                        }
-                    }
+                for value in &values {
+                    update_query = Services::bind_postgres_value(update_query, value);
                 }
-                for value in &sql_query.where_values {
-                    match value {
-                        SqlValue::String(v) | SqlValue::ObjectID(v) => {
-                            let v = clean_string(v, Some(clean_options.clone()));
-                            update_query = update_query.bind(v);
-                        }
-                        SqlValue::Int(v) => {
-                            update_query = update_query.bind(v);
-                        }
-                        SqlValue::Bool(v) => {
-                            update_query = update_query.bind(v);
-                        }
-                        SqlValue::StringList(values) | SqlValue::ObjectIDList(values) => {
-                            for v in values {
-                                let v = clean_string(v, Some(clean_options.clone()));
-                                update_query = update_query.bind(v)
-                            }
-                        }
-                        SqlValue::IntList(values) => {
-                            for int in values {
-                                update_query = update_query.bind(int)
-                            }
-                        }
-                        SqlValue::BoolList(values) => {
-                            for bool in values {
-                                update_query = update_query.bind(bool)
-                            }
-                        }
-                        SqlValue::UUID(uuid) => update_query = update_query.bind(uuid),
-                        SqlValue::UUIDList(uuids) => {
-                            for uuid in uuids {
-                                update_query = update_query.bind(uuid)
-                            }
-                        }
-                        SqlValue::DateTime(date_time) => {
-                            update_query = update_query.bind(date_time)
-                        }
-                        SqlValue::DateTimeList(date_times) => {
-                            for date_time in date_times {
-                                update_query = update_query.bind(date_time)
-                            }
-                        }
-                    }
+                for value in &where_values {
+                    update_query = Services::bind_postgres_value(update_query, value);
                 }
 
-                let rows = update_query.fetch_all(pool).await.map_err(|e| {
-                    error!("Error: {:?}", e);
-                    e
-                })?;
+                let rows = update_query
+                    .fetch_all(&mut **transaction)
+                    .await
+                    .map_err(|e| {
+                        error!("Error: {:?}", e);
+                        e
+                    })?;
 
                 let mut response_rows = Vec::new();
                 for row in rows {
-                    response_rows.push(Some(ResponseRow::Postgres(row)));
+                    response_rows.push(Some(ResponseRow::Postgres(Arc::new(row))));
                 }
                 Ok(response_rows)
             }
-            PoolEnum::SqLite(pool) => {
+            TransactionEnum::SqLite(transaction) => {
                 let identifier_query = match &sql_query.identifier_query {
                     Some(identifier_query) => identifier_query,
                     None => {
@@ -306,146 +182,72 @@ impl Services {
                 let mut update_query = sqlx::query(&sql_query.query);
 
                 //Bind the values first, example: SET title = ?
-                for value in &sql_query.values {
-                    match value {
-                        SqlValue::String(v) | SqlValue::ObjectID(v) => {
-                            let v = clean_string(v, Some(clean_options.clone()));
-                            update_query = update_query.bind(v.clone());
-                        }
-                        SqlValue::Int(v) => {
-                            update_query = update_query.bind(v.clone());
-                        }
-                        SqlValue::Bool(v) => {
-                            update_query = update_query.bind(v);
-                        }
-                        SqlValue::StringList(values) | SqlValue::ObjectIDList(values) => {
-                            for v in values {
-                                let v = clean_string(v, Some(clean_options.clone()));
-                                update_query = update_query.bind(v.clone());
-                            }
-                        }
-                        SqlValue::IntList(values) => {
-                            for int in values {
-                                update_query = update_query.bind(int);
-                            }
-                        }
-                        SqlValue::BoolList(values) => {
-                            for bool in values {
-                                update_query = update_query.bind(bool);
-                            }
-                        }
-                        SqlValue::UUID(uuid) => update_query = update_query.bind(uuid),
-                        SqlValue::UUIDList(uuids) => {
-                            for uuid in uuids {
-                                update_query = update_query.bind(uuid);
-                            }
-                        }
-                        SqlValue::DateTime(date_time) => {
-                            update_query = update_query.bind(date_time);
-                        }
-                        SqlValue::DateTimeList(date_times) => {
-                            for date_time in date_times {
-                                update_query = update_query.bind(date_time);
-                            }
-                        }
-                    }
+                for value in &values {
+                    update_query = Services::bind_sql_value(update_query, value);
                 }
 
                 // Bind the where values, example: WHERE id = ?
-                for value in &sql_query.where_values {
-                    match value {
-                        SqlValue::String(v) | SqlValue::ObjectID(v) => {
-                            let v = clean_string(v, Some(clean_options.clone()));
-                            update_query = update_query.bind(v.clone());
-                            identifier_query = identifier_query.bind(v);
-                        }
-                        SqlValue::Int(v) => {
-                            update_query = update_query.bind(v);
-                            identifier_query = identifier_query.bind(v);
-                        }
-                        SqlValue::Bool(v) => {
-                            update_query = update_query.bind(v);
-                            identifier_query = identifier_query.bind(v);
-                        }
-                        SqlValue::StringList(values) | SqlValue::ObjectIDList(values) => {
-                            for v in values {
-                                update_query = update_query.bind(v);
-                                identifier_query = identifier_query.bind(v);
-                            }
-                        }
-                        SqlValue::IntList(values) => {
-                            for int in values {
-                                update_query = update_query.bind(int);
-                                identifier_query = identifier_query.bind(int);
-                            }
-                        }
-                        SqlValue::BoolList(values) => {
-                            for bool in values {
-                                update_query = update_query.bind(bool);
-                                identifier_query = identifier_query.bind(bool);
-                            }
-                        }
-                        SqlValue::UUID(uuid) => update_query = update_query.bind(uuid),
-                        SqlValue::UUIDList(uuids) => {
-                            for uuid in uuids {
-                                update_query = update_query.bind(uuid);
-                                identifier_query = identifier_query.bind(uuid);
-                            }
-                        }
-                        SqlValue::DateTime(date_time) => {
-                            update_query = update_query.bind(date_time);
-                            identifier_query = identifier_query.bind(date_time);
-                        }
-                        SqlValue::DateTimeList(date_times) => {
-                            for date_time in date_times {
-                                update_query = update_query.bind(date_time);
-                                identifier_query = identifier_query.bind(date_time);
-                            }
-                        }
-                    }
+                for value in &where_values {
+                    update_query = Services::bind_sql_value(update_query, value);
+                    identifier_query = Services::bind_sql_value(identifier_query, value);
                 }
 
                 // Construct a query to get the updated data
-                let identifer_results = identifier_query.fetch_all(pool).await.map_err(|e| {
-                    error!("Error: {:?}", e);
-                    e
-                })?;
+                let identifer_results = identifier_query
+                    .fetch_all(&mut **transaction)
+                    .await
+                    .map_err(|e| {
+                        error!("Error: {:?}", e);
+                        e
+                    })?;
 
-                update_query.execute(pool).await.map_err(|e| {
-                    error!("Error: {:?}", e);
-                    e
-                })?;
+                update_query
+                    .execute(&mut **transaction)
+                    .await
+                    .map_err(|e| {
+                        error!("Error: {:?}", e);
+                        e
+                    })?;
 
-                let mut ids = vec![];
+                let mut primary_keys = vec![];
                 for row in identifer_results {
-                    let id: i64 = row.try_get("id")?;
-                    ids.push(id);
+                    let primary_key = row.to_document(Some(
+                        primary_key_names.iter().map(|name| name.as_str()).collect(),
+                    ))?;
+                    primary_keys.push(SqlDataSource::get_primary_key_values(
+                        &primary_key,
+                        &DialectEnum::SQLITE,
+                    )?);
                 }
 
-                if ids.is_empty() {
+                if primary_keys.is_empty() {
                     return Ok(vec![]);
                 }
 
-                let query = format!(
-                    "SELECT * FROM {} WHERE id IN ({})",
-                    sql_query.table,
-                    ids.iter()
-                        .map(|id| id.to_string())
-                        .collect::<Vec<String>>()
-                        .join(",")
-                );
+                let (query, primary_key_values) = SqlDataSource::create_primary_key_query(
+                    &sql_query.table,
+                    sql_query.schema.as_deref(),
+                    &primary_keys,
+                    &DialectEnum::SQLITE,
+                )?;
 
-                let response_query = sqlx::query(&query);
+                let mut response_query = sqlx::query(&query);
+                for value in &primary_key_values {
+                    response_query = Services::bind_sql_value(response_query, value);
+                }
 
-                let rows = response_query.fetch_all(pool).await.map_err(|e| {
-                    error!("Error finding data: {}", e);
-                    e
-                })?;
+                let rows = response_query
+                    .fetch_all(&mut **transaction)
+                    .await
+                    .map_err(|e| {
+                        error!("Error finding data: {}", e);
+                        e
+                    })?;
 
                 let mut response_rows = Vec::new();
 
                 for row in rows {
-                    response_rows.push(Some(ResponseRow::SqLite(row)));
+                    response_rows.push(Some(ResponseRow::SqLite(Arc::new(row))));
                 }
 
                 Ok(response_rows)
@@ -1,10 +1,11 @@
+use std::sync::Arc;
+
 use log::{debug, error};
-use sqlx::Row;
 
 use crate::{
-    configuration::subgraph::entities::ServiceEntityConfig,
-    data_sources::sql::{PoolEnum, SqlQuery},
-    sql_value::SqlValue,
+    configuration::subgraph::{data_sources::sql::DialectEnum, entities::ServiceEntityConfig},
+    data_sources::sql::{SqlDataSource, SqlQuery, TransactionEnum},
+    traits::sqlx::mysql_row::FromMySqlRow,
 };
 
 use super::{ResponseRow, Services};
@@ -12,13 +13,13 @@ use super::{ResponseRow, Services};
 impl Services {
     pub async fn update_one(
         entity: &ServiceEntityConfig,
-        pool_enum: &PoolEnum,
+        transaction_enum: &mut TransactionEnum,
         sql_query: &SqlQuery,
     ) -> Result<Option<ResponseRow>, async_graphql::Error> {
         debug!("Executing Update One Query: {:?}", sql_query);
 
-        match pool_enum {
-            PoolEnum::MySql(pool) => {
+        match transaction_enum {
+            TransactionEnum::MySql(transaction) => {
                 let identifier_query = match &sql_query.identifier_query {
                     Some(query) => query,
                     None => {
@@ -33,105 +34,15 @@ impl Services {
                 let mut update_query = sqlx::query(&sql_query.query);
 
                 for value in &sql_query.values {
-                    match value {
-                        SqlValue::String(v) | SqlValue::ObjectID(v) => {
-                            update_query = update_query.bind(v);
-                        }
-                        SqlValue::Int(v) => {
-                            update_query = update_query.bind(v);
-                        }
-                        SqlValue::Bool(v) => {
-                            update_query = update_query.bind(v);
-                        }
-                        SqlValue::StringList(values) | SqlValue::ObjectIDList(values) => {
-                            for string in values {
-                                update_query = update_query.bind(string)
-                            }
-                        }
-                        SqlValue::IntList(values) => {
-                            for int in values {
-                                update_query = update_query.bind(int)
-                            }
-                        }
-                        SqlValue::BoolList(values) => {
-                            for bool in values {
-                                update_query = update_query.bind(bool)
-                            }
-                        }
-                        SqlValue::UUID(v) => {
-                            update_query = update_query.bind(v);
-                        }
-                        SqlValue::UUIDList(values) => {
-                            for uuid in values {
-                                update_query = update_query.bind(uuid)
-                            }
-                        }
-                        SqlValue::DateTime(v) => {
-                            update_query = update_query.bind(v);
-                        }
-                        SqlValue::DateTimeList(values) => {
-                            for datetime in values {
-                                update_query = update_query.bind(datetime)
-                            }
-                        }
-                    }
+                    update_query = Services::bind_sql_value(update_query, value);
                 }
 
                 for value in &sql_query.where_values {
-                    match value {
-                        SqlValue::String(v) | SqlValue::ObjectID(v) => {
-                            update_query = update_query.bind(v);
-                            identifier_query = identifier_query.bind(v);
-                        }
-                        SqlValue::Int(v) => {
-                            update_query = update_query.bind(v);
-                            identifier_query = identifier_query.bind(v);
-                        }
-                        SqlValue::Bool(v) => {
-                            update_query = update_query.bind(v);
-                            identifier_query = identifier_query.bind(v);
-                        }
-                        SqlValue::StringList(values) | SqlValue::ObjectIDList(values) => {
-                            for string in values {
-                                update_query = update_query.bind(string);
-                                identifier_query = identifier_query.bind(string);
-                            }
-                        }
-                        SqlValue::IntList(values) => {
-                            for int in values {
-                                update_query = update_query.bind(int);
-                                identifier_query = identifier_query.bind(int);
-                            }
-                        }
-                        SqlValue::BoolList(values) => {
-                            for bool in values {
-                                update_query = update_query.bind(bool);
-                                identifier_query = identifier_query.bind(bool);
-                            }
-                        }
-                        SqlValue::UUID(v) => {
-                            update_query = update_query.bind(v);
-                        }
-                        SqlValue::UUIDList(values) => {
-                            for uuid in values {
-                                update_query = update_query.bind(uuid);
-                                identifier_query = identifier_query.bind(uuid);
-                            }
-                        }
-                        SqlValue::DateTime(v) => {
-                            update_query = update_query.bind(v);
-                            identifier_query = identifier_query.bind(v);
-                        }
-                        SqlValue::DateTimeList(values) => {
-                            for datetime in values {
-                                update_query = update_query.bind(datetime);
-                                identifier_query = identifier_query.bind(datetime);
-                            }
-                        }
-                    }
+                    update_query = Services::bind_sql_value(update_query, value);
+                    identifier_query = Services::bind_sql_value(identifier_query, value);
                 }
 
-                let identifier_results = identifier_query.fetch_all(pool).await?;
+                let identifier_results = identifier_query.fetch_all(&mut **transaction).await?;
 
                 if identifier_results.len() == 0 {
                     error!("No results found for entity: {}", entity.name);
@@ -149,29 +60,33 @@ impl Services {
                     )));
                 }
 
-                let id: i64 = match identifier_results[0].get("id") {
-                    Some(id) => id,
-                    None => {
-                        error!("No primary key found for entity: {}", entity.name);
-                        return Err(async_graphql::Error::new(format!(
-                            "No primary key found for entity: {}",
-                            entity.name
-                        )));
-                    }
-                };
+                let primary_key_names = ServiceEntityConfig::get_primary_key_fields(entity)?
+                    .into_iter()
+                    .map(|field| field.name)
+                    .collect::<Vec<String>>();
+                let primary_key = identifier_results[0].to_document(Some(
+                    primary_key_names.iter().map(|name| name.as_str()).collect(),
+                ))?;
 
-                update_query.execute(pool).await?;
+                update_query.execute(&mut **transaction).await?;
 
-                let response_query = format!(
-                    "SELECT * FROM {} WHERE id = {}",
-                    sql_query.table,
-                    id.to_string()
-                );
-                let response_query = sqlx::query(&response_query);
+                let (response_query, primary_key_values) = SqlDataSource::create_primary_key_query(
+                    &sql_query.table,
+                    sql_query.schema.as_deref(),
+                    &vec![SqlDataSource::get_primary_key_values(
+                        &primary_key,
+                        &DialectEnum::MYSQL,
+                    )?],
+                    &DialectEnum::MYSQL,
+                )?;
+                let mut response_query = sqlx::query(&response_query);
+                for value in &primary_key_values {
+                    response_query = Services::bind_sql_value(response_query, value);
+                }
 
-                let find_one_result = response_query.fetch_one(pool).await?;
+                let find_one_result = response_query.fetch_one(&mut **transaction).await?;
 
-                Ok(Some(ResponseRow::MySql(find_one_result)))
+                Ok(Some(ResponseRow::MySql(Arc::new(find_one_result))))
             }
             _ => Err(async_graphql::Error::from("Update One Not Supported")),
         }
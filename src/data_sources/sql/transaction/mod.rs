@@ -0,0 +1,39 @@
+use async_graphql::ErrorExtensions;
+use log::{debug, error};
+
+use super::{PoolEnum, SqlDataSource, TransactionEnum};
+
+impl SqlDataSource {
+    /// Begins a transaction on the primary, used by mutations and the junction writes of
+    /// their joined fields.
+    pub async fn begin_transaction(&self) -> Result<TransactionEnum, async_graphql::Error> {
+        debug!("Beginning Transaction: {}", self.config.name);
+        let transaction = match &self.pool {
+            PoolEnum::MySql(pool) => pool.begin().await.map(TransactionEnum::MySql),
+            PoolEnum::Postgres(pool) => pool.begin().await.map(TransactionEnum::Postgres),
+            PoolEnum::SqLite(pool) => pool.begin().await.map(TransactionEnum::SqLite),
+        };
+        transaction.map_err(|e| {
+            error!("Error beginning transaction: {:?}", e);
+            async_graphql::Error::new("Error beginning transaction.")
+                .extend_with(|_, err| err.set("cause", e.to_string()))
+        })
+    }
+}
+
+impl TransactionEnum {
+    /// Commits the transaction. Transactions dropped without a commit are rolled back.
+    pub async fn commit(self) -> Result<(), async_graphql::Error> {
+        debug!("Committing Transaction");
+        let result = match self {
+            TransactionEnum::MySql(transaction) => transaction.commit().await,
+            TransactionEnum::Postgres(transaction) => transaction.commit().await,
+            TransactionEnum::SqLite(transaction) => transaction.commit().await,
+        };
+        result.map_err(|e| {
+            error!("Error committing transaction: {:?}", e);
+            async_graphql::Error::new("Error committing transaction.")
+                .extend_with(|_, err| err.set("cause", e.to_string()))
+        })
+    }
+}
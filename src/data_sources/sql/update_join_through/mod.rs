@@ -0,0 +1,110 @@
+use bson::Bson;
+use log::{debug, trace};
+
+use crate::configuration::subgraph::entities::{
+    service_entity_field::join_through::JoinThrough, ServiceEntityConfig,
+};
+
+use super::{services::Services, SqlDataSource, SqlQuery, TransactionEnum};
+
+impl SqlDataSource {
+    /// Connects and disconnects the joined keys from the parents by inserting and deleting rows
+    /// of the junction table, within the transaction of the mutation. Existing rows of connected
+    /// keys are replaced, so connecting a key twice does not duplicate it.
+    pub async fn update_join_through(
+        &self,
+        transaction: &mut TransactionEnum,
+        junction: &ServiceEntityConfig,
+        join_through: &JoinThrough,
+        parent_keys: &[Bson],
+        connect: &[Bson],
+        disconnect: &[Bson],
+    ) -> Result<(), async_graphql::Error> {
+        debug!("Updating Join Through: {}", junction.name);
+
+        let dialect = &self.config.dialect;
+        let table = ServiceEntityConfig::get_sql_table_name(junction);
+        let schema = ServiceEntityConfig::get_entity_data_source(junction)
+            .and_then(|data_source| data_source.schema)
+            .or(self.config.schema.clone());
+        let quoted_table = SqlDataSource::quote_table_name(&table, schema.as_deref(), dialect);
+        let join_from = SqlDataSource::quote_identifier(&join_through.join_from, dialect);
+        let join_on = SqlDataSource::quote_identifier(&join_through.join_on, dialect);
+
+        if parent_keys.is_empty() || (connect.is_empty() && disconnect.is_empty()) {
+            trace!("No Join Through Keys To Update.");
+            return Ok(());
+        }
+
+        // Delete the rows of all keys provided, then insert the connected keys.
+        let mut where_values = Vec::new();
+        let placeholders = |keys: &[Bson], where_values: &mut Vec<_>| {
+            let mut placeholders = Vec::new();
+            for key in keys {
+                placeholders.push(SqlDataSource::get_placeholder(
+                    dialect,
+                    Some(where_values.len() as i32),
+                ));
+                where_values.push(SqlDataSource::get_join_through_value(key)?);
+            }
+            Ok::<String, async_graphql::Error>(placeholders.join(", "))
+        };
+        let parent_placeholders = placeholders(parent_keys, &mut where_values)?;
+        let keys = connect
+            .iter()
+            .chain(disconnect.iter())
+            .cloned()
+            .collect::<Vec<Bson>>();
+        let key_placeholders = placeholders(&keys, &mut where_values)?;
+        let delete_query = SqlQuery {
+            query: format!(
+                "DELETE FROM {} WHERE {} IN ({}) AND {} IN ({});",
+                quoted_table, join_from, parent_placeholders, join_on, key_placeholders
+            ),
+            count_query: None,
+            identifier_query: None,
+            values: Vec::new(),
+            where_values,
+            value_keys: Vec::new(),
+            table: table.clone(),
+            schema: schema.clone(),
+        };
+        trace!("Join Through Delete Query: {:?}", delete_query);
+        let mut queries = vec![delete_query];
+
+        if connect.is_empty() {
+            return Services::execute_transaction(transaction, &queries).await;
+        }
+
+        let mut where_values = Vec::new();
+        let mut rows = Vec::new();
+        for parent_key in parent_keys {
+            for key in connect {
+                let parent_placeholder =
+                    placeholders(std::slice::from_ref(parent_key), &mut where_values)?;
+                let key_placeholder = placeholders(std::slice::from_ref(key), &mut where_values)?;
+                rows.push(format!("({}, {})", parent_placeholder, key_placeholder));
+            }
+        }
+        let insert_query = SqlQuery {
+            query: format!(
+                "INSERT INTO {} ({}, {}) VALUES {};",
+                quoted_table,
+                join_from,
+                join_on,
+                rows.join(", ")
+            ),
+            count_query: None,
+            identifier_query: None,
+            values: Vec::new(),
+            where_values,
+            value_keys: Vec::new(),
+            table,
+            schema,
+        };
+        trace!("Join Through Insert Query: {:?}", insert_query);
+        queries.push(insert_query);
+
+        Services::execute_transaction(transaction, &queries).await
+    }
+}
@@ -0,0 +1,31 @@
+use log::{debug, error};
+
+use crate::configuration::subgraph::data_sources::sql::{DialectEnum, SqlDataSourceConfig};
+
+use super::SqlDataSource;
+
+impl SqlDataSource {
+    /// Rejects options the dialect of the data source does not support, before connecting.
+    pub fn validate_config(
+        sql_data_source_config: &SqlDataSourceConfig,
+    ) -> Result<(), async_graphql::Error> {
+        debug!("Validating SQL Data Source Config");
+        let statement_timeout = sql_data_source_config
+            .pool
+            .as_ref()
+            .and_then(|pool| pool.statement_timeout);
+
+        if sql_data_source_config.dialect == DialectEnum::SQLITE && statement_timeout.is_some() {
+            error!(
+                "Statement timeout is not supported for SQLite data source: {}",
+                sql_data_source_config.name
+            );
+            return Err(async_graphql::Error::new(format!(
+                "Statement timeout is not supported for SQLite data source: {}",
+                sql_data_source_config.name
+            )));
+        }
+
+        Ok(())
+    }
+}
@@ -1,7 +1,10 @@
 use log::{debug, error};
 use sqlx::Row;
 
-use crate::{data_sources::sql::services::ResponseRow, graphql::entity::ServiceEntity};
+use crate::{
+    data_sources::sql::services::ResponseRow, graphql::entity::ServiceEntity,
+    scalar_option::ScalarOption,
+};
 
 impl ServiceEntity {
     // From response row to String
@@ -309,4 +312,84 @@ impl ServiceEntity {
             None => Ok(None),
         }
     }
+
+    /// Resolves a list column. Postgres columns are native arrays, MySQL and SQLite columns
+    /// hold a JSON array.
+    pub fn resolve_sql_list_scalar(
+        response_row: &ResponseRow,
+        field_name: &str,
+        scalar: &ScalarOption,
+    ) -> Result<Option<serde_json::Value>, async_graphql::Error> {
+        debug!("Resolving SQL List Scalar");
+
+        let map_err = |e: sqlx::Error| {
+            error!("Error resolving List field: {:?}", e.to_string());
+            async_graphql::Error::new(format!("Error resolving List field: {:?}", e.to_string()))
+        };
+
+        let json = match response_row {
+            ResponseRow::MySql(row) => row
+                .try_get_unchecked::<Option<String>, _>(field_name)
+                .map_err(map_err)?,
+            ResponseRow::SqLite(row) => row
+                .try_get::<Option<String>, _>(field_name)
+                .map_err(map_err)?,
+            ResponseRow::Postgres(row) => {
+                let value = match scalar {
+                    ScalarOption::Int => row
+                        .try_get::<Option<Vec<i32>>, _>(field_name)
+                        .map_err(map_err)?
+                        .map(|values| serde_json::json!(values)),
+                    ScalarOption::Boolean => row
+                        .try_get::<Option<Vec<bool>>, _>(field_name)
+                        .map_err(map_err)?
+                        .map(|values| serde_json::json!(values)),
+                    ScalarOption::UUID => row
+                        .try_get::<Option<Vec<uuid::Uuid>>, _>(field_name)
+                        .map_err(map_err)?
+                        .map(|values| {
+                            serde_json::json!(values
+                                .iter()
+                                .map(|value| value.to_string())
+                                .collect::<Vec<String>>())
+                        }),
+                    ScalarOption::DateTime => row
+                        .try_get::<Option<Vec<chrono::DateTime<chrono::Utc>>>, _>(field_name)
+                        .map_err(map_err)?
+                        .map(|values| {
+                            serde_json::json!(values
+                                .iter()
+                                .map(|value| value.to_rfc3339())
+                                .collect::<Vec<String>>())
+                        }),
+                    _ => row
+                        .try_get::<Option<Vec<String>>, _>(field_name)
+                        .map_err(map_err)?
+                        .map(|values| serde_json::json!(values)),
+                };
+                return Ok(value);
+            }
+        };
+
+        match json {
+            Some(json) => {
+                let value: serde_json::Value = serde_json::from_str(&json).map_err(|e| {
+                    error!("Error resolving List field: {:?}", e.to_string());
+                    async_graphql::Error::new(format!(
+                        "Error resolving List field: {:?}",
+                        e.to_string()
+                    ))
+                })?;
+                if !value.is_array() {
+                    error!("List field {} is not a JSON array", field_name);
+                    return Err(async_graphql::Error::new(format!(
+                        "List field {} is not a JSON array",
+                        field_name
+                    )));
+                }
+                Ok(Some(value))
+            }
+            None => Ok(None),
+        }
+    }
 }
@@ -98,7 +98,7 @@ impl ServiceEntity {
                     }
                 };
 
-                let value = json_value.to_async_graphql_value();
+                let value = json_value[field_name].to_async_graphql_value();
 
                 Ok(Some(value))
             }
@@ -107,10 +107,21 @@ impl ServiceEntity {
                 {
                     Ok(response_row) => {
                         if let Some(rr) = response_row {
-                            let value = entity_field
-                                .scalar
-                                .clone()
-                                .rr_to_async_graphql_value(rr, field_name)?;
+                            let value = if entity_field.is_list_column() {
+                                match ServiceEntity::resolve_sql_list_scalar(
+                                    rr,
+                                    field_name,
+                                    &entity_field.scalar,
+                                )? {
+                                    Some(json) => Value::from_json(json)?,
+                                    None => Value::Null,
+                                }
+                            } else {
+                                entity_field
+                                    .scalar
+                                    .clone()
+                                    .rr_to_async_graphql_value(rr, field_name)?
+                            };
                             Ok(Some(value))
                         } else {
                             if entity_required {
@@ -53,6 +53,13 @@ impl ServiceEntity {
 
         let mut type_def = Object::new(&self.type_name);
 
+        // Root entities are keyed by their primary key, which may be composite.
+        if self.is_root {
+            if let Some(key) = self.get_federation_key() {
+                type_def = type_def.key(key);
+            }
+        }
+
         // Before processing the fields, we need to create the return types
         let return_types = self.create_return_types();
         for return_type in return_types {
@@ -98,4 +105,26 @@ impl ServiceEntity {
 
         (type_defs, enum_defs)
     }
+
+    /// Creates the federation `@key` fields from the primary key fields of the entity.
+    /// Returns None if a primary key field is not available in the output type.
+    fn get_federation_key(&self) -> Option<String> {
+        let primary_key_fields = ServiceEntityConfig::get_primary_key_fields(&self.entity).ok()?;
+
+        if primary_key_fields
+            .iter()
+            .any(|field| field.exclude_from_output.unwrap_or(false))
+        {
+            debug!("Primary Key Excluded From Output, Skipping Federation Key");
+            return None;
+        }
+
+        let key = primary_key_fields
+            .iter()
+            .map(|field| field.name.clone())
+            .collect::<Vec<String>>()
+            .join(" ");
+        debug!("Federation Key: {}", key);
+        Some(key)
+    }
 }
@@ -0,0 +1,33 @@
+use async_graphql::dynamic::{InputObject, InputValue};
+use log::debug;
+
+use crate::{
+    configuration::subgraph::entities::service_entity_field::ServiceEntityFieldConfig,
+    resolver_type::ResolverType,
+};
+
+use super::ServiceInput;
+
+impl ServiceInput {
+    /// Creates the input connecting and disconnecting the entities joined to a field through a
+    /// junction entity, by the keys of the joined entities.
+    pub fn create_connect_input(
+        entity_field: &ServiceEntityFieldConfig,
+        resolver_type: &ResolverType,
+        parent_input_prefix: &str,
+    ) -> Result<InputObject, async_graphql::Error> {
+        debug!("Creating Connect Input For {:?}", entity_field.name);
+
+        let input_name = format!(
+            "{}_{}_connect_input",
+            parent_input_prefix, entity_field.name
+        );
+        let type_ref = entity_field
+            .scalar
+            .to_input_type_ref(true, false, resolver_type, None)?;
+
+        Ok(InputObject::new(input_name)
+            .field(InputValue::new("connect", type_ref.clone()))
+            .field(InputValue::new("disconnect", type_ref)))
+    }
+}
@@ -0,0 +1,84 @@
+use async_graphql::dynamic::{InputObject, InputValue, TypeRef};
+use log::debug;
+
+use crate::{
+    configuration::subgraph::entities::service_entity_field::{
+        exclude_from_input::ExcludeFromInput, ServiceEntityFieldConfig,
+    },
+    data_sources::DataSource,
+    resolver_type::ResolverType,
+    update_operator::UpdateOperator,
+};
+
+use super::ServiceInput;
+
+impl ServiceInput {
+    /// Creates the input of the update operators, with an input per operator listing the fields
+    /// it supports. The operators input is last. Returns no inputs when no field supports an
+    /// operator.
+    pub fn create_update_operators_input(
+        fields: &Vec<ServiceEntityFieldConfig>,
+        resolver_type: &ResolverType,
+        exclude_from_input: Option<ExcludeFromInput>,
+        parent_input_prefix: &str,
+        entity_data_source: &DataSource,
+    ) -> Vec<InputObject> {
+        debug!(
+            "Creating Update Operators Input For {:?}",
+            parent_input_prefix
+        );
+
+        let input_name = format!("{}_operators_input", parent_input_prefix);
+        let mut operators_input = InputObject::new(&input_name);
+        let mut inputs = Vec::new();
+
+        for operator in UpdateOperator::list() {
+            let operator_input_name = format!(
+                "{}_operators_{}_input",
+                parent_input_prefix,
+                operator.as_str()
+            );
+            let mut operator_input = InputObject::new(&operator_input_name);
+            let mut has_fields = false;
+
+            for field in fields {
+                if ServiceEntityFieldConfig::is_excluded_input_field(
+                    field,
+                    exclude_from_input.clone(),
+                ) || !operator.supports_field(field, entity_data_source)
+                {
+                    continue;
+                }
+                let type_ref = match operator {
+                    UpdateOperator::Inc => TypeRef::named(TypeRef::INT),
+                    // List operators take one or more values of the list.
+                    UpdateOperator::Push | UpdateOperator::Pull | UpdateOperator::AddToSet => {
+                        match field
+                            .scalar
+                            .to_input_type_ref(true, false, resolver_type, None)
+                        {
+                            Ok(type_ref) => type_ref,
+                            Err(_) => continue,
+                        }
+                    }
+                    UpdateOperator::Unset => TypeRef::named(TypeRef::BOOLEAN),
+                };
+                operator_input = operator_input.field(InputValue::new(&field.name, type_ref));
+                has_fields = true;
+            }
+
+            if has_fields {
+                operators_input = operators_input.field(InputValue::new(
+                    operator.as_str(),
+                    TypeRef::named(&operator_input_name),
+                ));
+                inputs.push(operator_input);
+            }
+        }
+
+        if !inputs.is_empty() {
+            inputs.push(operators_input);
+        }
+        inputs
+    }
+}
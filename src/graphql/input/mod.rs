@@ -1,5 +1,5 @@
-use async_graphql::dynamic::{InputObject, InputValue};
-use log::debug;
+use async_graphql::dynamic::{InputObject, InputValue, TypeRef};
+use log::{debug, error};
 
 use crate::{
     configuration::subgraph::entities::service_entity_field::{
@@ -10,6 +10,8 @@ use crate::{
     resolver_type::ResolverType,
 };
 
+mod create_connect_input;
+mod create_update_operators_input;
 mod get_entity_field_type;
 
 pub struct ServiceInput {
@@ -52,7 +54,35 @@ impl ServiceInput {
                 self.exclude_from_input.clone(),
             );
 
-            if !is_excluded {
+            // Joins through a junction entity are not filterable, values connect and
+            // disconnect the joined entities instead.
+            if !is_excluded && field.join_through.is_some() {
+                if include_filters.unwrap_or(false) {
+                    excluded_count += 1;
+                    continue;
+                }
+                let parent_input_name = &self.input_name.clone().replace("_input", "");
+                let connect_input = match ServiceInput::create_connect_input(
+                    field,
+                    &self.resolver_type,
+                    parent_input_name,
+                ) {
+                    Ok(connect_input) => connect_input,
+                    Err(e) => {
+                        error!(
+                            "Failed to create connect input for {}: {}",
+                            field.name, e.message
+                        );
+                        excluded_count += 1;
+                        continue;
+                    }
+                };
+                input = input.field(InputValue::new(
+                    field.name.clone(),
+                    TypeRef::named(connect_input.type_name()),
+                ));
+                inputs.push(connect_input);
+            } else if !is_excluded {
                 let parent_input_name = &self.input_name.clone().replace("_input", "");
 
                 // Get the type refs and the inputs for the field.
@@ -0,0 +1,213 @@
+use std::{
+    collections::HashMap,
+    hash::{Hash, Hasher},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use async_graphql::{
+    async_trait::async_trait,
+    dataloader::{DataLoader, HashMapCache, Loader},
+    extensions::{Extension, ExtensionContext, ExtensionFactory, NextPrepareRequest},
+    Request, ServerResult,
+};
+use bson::{Bson, Document};
+use log::{debug, trace};
+
+use crate::data_sources::sql::services::ResponseRow;
+
+/// A row resolved for a join, from either a Mongo or a SQL data source.
+#[derive(Clone)]
+pub enum JoinedRow {
+    Document(Document),
+    Row(ResponseRow),
+}
+
+/// Fetches the rows of a join for the `join_on` values of many parents.
+#[async_trait]
+pub trait JoinFetch: Send + Sync {
+    /// Returns the fetched rows along with the join keys of each row.
+    async fn fetch(
+        &self,
+        values: Vec<Bson>,
+    ) -> Result<Vec<(Vec<String>, JoinedRow)>, async_graphql::Error>;
+}
+
+/// A join loaded for many parents at once. Parents share a batch when they load the same `key`,
+/// the joined entity along with the query and sort of the client.
+pub struct JoinBatch {
+    pub key: String,
+    pub fetch: Arc<dyn JoinFetch>,
+}
+
+/// A `join_on` value of a parent within its batch. Keys are equal when they share the batch and
+/// the key of the value.
+#[derive(Clone)]
+pub struct JoinKey {
+    batch: Arc<JoinBatch>,
+    key: String,
+    value: Bson,
+}
+
+impl JoinKey {
+    /// Returns `None` for values that can not be batched.
+    pub fn new(batch: Arc<JoinBatch>, value: Bson) -> Option<JoinKey> {
+        let key = JoinLoader::get_join_key(&value)?;
+        Some(JoinKey { batch, key, value })
+    }
+}
+
+impl PartialEq for JoinKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.batch.key == other.batch.key && self.key == other.key
+    }
+}
+
+impl Eq for JoinKey {}
+
+impl Hash for JoinKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.batch.key.hash(state);
+        self.key.hash(state);
+    }
+}
+
+/// A joined row, along with the fetch it came from and its position within the fetch.
+#[derive(Clone)]
+pub struct LoadedRow {
+    fetch_id: usize,
+    position: usize,
+    row: JoinedRow,
+}
+
+/// Loads the rows joined to the `join_on` values of all parents resolving the same join with a
+/// single fetch per batch. Used through a `DataLoader`, which caches the loaded rows for the rest
+/// of the request.
+#[derive(Default)]
+pub struct JoinLoader {
+    fetch_count: AtomicUsize,
+}
+
+impl JoinLoader {
+    /// Creates the key used to match a `join_on` value of a parent to the rows of the joined
+    /// entity. Returns `None` for values that can not be batched.
+    pub fn get_join_key(value: &Bson) -> Option<String> {
+        match value {
+            Bson::String(value) => Some(value.clone()),
+            Bson::Int32(value) => Some(value.to_string()),
+            Bson::Int64(value) => Some(value.to_string()),
+            Bson::Boolean(value) => Some(value.to_string()),
+            Bson::ObjectId(value) => Some(value.to_hex()),
+            _ => None,
+        }
+    }
+
+    /// Creates the data loader of a request.
+    pub fn create_data_loader() -> DataLoader<JoinLoader, HashMapCache> {
+        DataLoader::with_cache(JoinLoader::default(), tokio::spawn, HashMapCache::default())
+    }
+
+    /// Loads the rows joined to the values of a parent, in the order they were fetched.
+    pub async fn load_rows(
+        data_loader: &DataLoader<JoinLoader, HashMapCache>,
+        batch: Arc<JoinBatch>,
+        values: Vec<Bson>,
+    ) -> Result<Vec<JoinedRow>, async_graphql::Error> {
+        debug!("Loading Joined Rows: {}", batch.key);
+
+        let keys = values
+            .into_iter()
+            .filter_map(|value| JoinKey::new(batch.clone(), value))
+            .collect::<Vec<JoinKey>>();
+        if keys.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut rows = data_loader
+            .load_many(keys)
+            .await?
+            .into_values()
+            .flatten()
+            .collect::<Vec<LoadedRow>>();
+        rows.sort_by_key(|row| (row.fetch_id, row.position));
+        rows.dedup_by_key(|row| (row.fetch_id, row.position));
+
+        Ok(rows.into_iter().map(|row| row.row).collect())
+    }
+}
+
+impl Loader<JoinKey> for JoinLoader {
+    type Value = Vec<LoadedRow>;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[JoinKey]) -> Result<HashMap<JoinKey, Self::Value>, Self::Error> {
+        let mut batches: Vec<(Arc<JoinBatch>, Vec<Bson>)> = Vec::new();
+        for join_key in keys {
+            match batches
+                .iter_mut()
+                .find(|(batch, _)| batch.key == join_key.batch.key)
+            {
+                Some((_, values)) => values.push(join_key.value.clone()),
+                None => batches.push((join_key.batch.clone(), vec![join_key.value.clone()])),
+            }
+        }
+
+        // Values without rows are loaded as well, so that they are cached.
+        let mut loaded = keys
+            .iter()
+            .map(|join_key| (join_key.clone(), Vec::new()))
+            .collect::<HashMap<JoinKey, Vec<LoadedRow>>>();
+        for (batch, values) in batches {
+            trace!(
+                "Dispatching Batched Join {} With {} Values",
+                batch.key,
+                values.len()
+            );
+            let fetch_id = self.fetch_count.fetch_add(1, Ordering::SeqCst);
+            let fetched = batch.fetch.fetch(values).await?;
+
+            for (position, (row_keys, row)) in fetched.into_iter().enumerate() {
+                for row_key in row_keys {
+                    let join_key = JoinKey {
+                        batch: batch.clone(),
+                        key: row_key,
+                        value: Bson::Null,
+                    };
+                    if let Some(rows) = loaded.get_mut(&join_key) {
+                        rows.push(LoadedRow {
+                            fetch_id,
+                            position,
+                            row: row.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(loaded)
+    }
+}
+
+/// Provides each request with its own join `DataLoader`.
+pub struct JoinLoaderExtension;
+
+impl ExtensionFactory for JoinLoaderExtension {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(JoinLoaderExtension)
+    }
+}
+
+#[async_trait]
+impl Extension for JoinLoaderExtension {
+    async fn prepare_request(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        request: Request,
+        next: NextPrepareRequest<'_>,
+    ) -> ServerResult<Request> {
+        next.run(ctx, request.data(JoinLoader::create_data_loader()))
+            .await
+    }
+}
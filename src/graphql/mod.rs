@@ -1,4 +1,5 @@
 pub mod entity;
 pub mod input;
+pub mod join_loader;
 pub mod resolver;
 pub mod schema;
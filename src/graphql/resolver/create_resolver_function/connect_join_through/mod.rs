@@ -0,0 +1,380 @@
+use std::str::FromStr;
+
+use async_graphql::dynamic::FieldValue;
+use bson::{doc, oid::ObjectId, Bson, Document};
+use http::HeaderMap;
+use log::{debug, error, trace};
+
+use crate::{
+    configuration::subgraph::{
+        entities::{
+            service_entity_field::{join_through::JoinThrough, ServiceEntityFieldConfig},
+            ServiceEntityConfig,
+        },
+        guard::Guard,
+        SubGraphConfig,
+    },
+    data_sources::{
+        mongo,
+        sql::{services::ResponseRow, SqlDataSource, TransactionEnum},
+        DataSource, DataSources,
+    },
+    graphql::{
+        entity::create_return_types::ResolverResponse, resolver::ServiceResolver,
+        schema::create_auth_service::TokenData,
+    },
+    resolver_type::ResolverType,
+    scalar_option::ScalarOption,
+    traits::sqlx::{
+        mysql_row::FromMySqlRow, postgres_row::FromPostgresRow, sqlite_row::FromSqliteRow,
+    },
+};
+
+/// The keys connected to and disconnected from the mutated entities by a field joined through a
+/// junction entity.
+pub struct JoinThroughValues {
+    pub field: ServiceEntityFieldConfig,
+    pub connect: Vec<Bson>,
+    pub disconnect: Vec<Bson>,
+}
+
+/// The request of a mutation connecting joined entities, used to execute it and check the
+/// guards of the junction entities.
+pub struct JoinThroughRequest<'a> {
+    pub data_sources: &'a DataSources,
+    pub subgraph_config: &'a SubGraphConfig,
+    pub token_data: &'a Option<TokenData>,
+    pub headers: &'a HeaderMap,
+    pub service_guards: Option<Vec<Guard>>,
+}
+
+impl ServiceResolver {
+    /// Removes the values of fields joined through a junction entity from the mutation input,
+    /// as they are not stored by the mutated entity.
+    pub fn take_join_through_values(
+        input_document: &mut Document,
+        entity: &ServiceEntityConfig,
+    ) -> Vec<JoinThroughValues> {
+        debug!("Taking Join Through Values");
+
+        let mut join_through_values = Vec::new();
+        let values = match input_document.get_document_mut("values") {
+            Ok(values) => values,
+            Err(_) => return join_through_values,
+        };
+
+        for field in entity.fields.iter() {
+            if field.join_through.is_none() {
+                continue;
+            }
+            let field_values = match values.remove(&field.name) {
+                Some(Bson::Document(field_values)) => field_values,
+                _ => continue,
+            };
+            // Keys provided more than once are only connected or disconnected once.
+            let get_keys = |key: &str| {
+                let mut keys: Vec<Bson> = Vec::new();
+                if let Ok(field_keys) = field_values.get_array(key) {
+                    for field_key in field_keys {
+                        if !keys.contains(field_key) {
+                            keys.push(field_key.clone());
+                        }
+                    }
+                }
+                keys
+            };
+            join_through_values.push(JoinThroughValues {
+                field: field.clone(),
+                connect: get_keys("connect"),
+                disconnect: get_keys("disconnect"),
+            });
+        }
+
+        trace!("Join Through Values: {}", join_through_values.len());
+        join_through_values
+    }
+
+    /// Executes a mutation connecting and disconnecting joined entities. With a SQL data source
+    /// the mutation and the junction writes share a transaction, so neither is kept when the
+    /// other fails.
+    pub async fn execute_join_through<'a>(
+        input_document: Document,
+        entity: ServiceEntityConfig,
+        operation_type: ResolverType,
+        join_through_values: Vec<JoinThroughValues>,
+        request: &JoinThroughRequest<'_>,
+    ) -> Result<Option<FieldValue<'a>>, async_graphql::Error> {
+        debug!("Executing Join Through Mutation");
+
+        let JoinThroughRequest {
+            data_sources,
+            subgraph_config,
+            token_data,
+            headers,
+            ..
+        } = *request;
+        let data_source = DataSources::get_entity_data_soruce(data_sources, &entity);
+        let ds = match data_source {
+            DataSource::SQL(ds) => ds,
+            _ => {
+                // Connecting joined entities requires the keys of the mutated entities.
+                let results = DataSources::execute(
+                    data_sources,
+                    input_document,
+                    entity.clone(),
+                    operation_type,
+                    subgraph_config,
+                    token_data,
+                    headers,
+                    true,
+                )
+                .await?;
+                ServiceResolver::connect_join_through(
+                    &results,
+                    &entity,
+                    join_through_values,
+                    request,
+                    None,
+                )
+                .await?;
+                return Ok(results);
+            }
+        };
+
+        let mut transaction = ds.begin_transaction().await?;
+        let results = SqlDataSource::execute_operation(
+            data_source,
+            input_document,
+            entity.clone(),
+            operation_type,
+            subgraph_config,
+            token_data,
+            true,
+            Some(&mut transaction),
+        )
+        .await?;
+        ServiceResolver::connect_join_through(
+            &results,
+            &entity,
+            join_through_values,
+            request,
+            Some(&mut transaction),
+        )
+        .await?;
+        transaction.commit().await?;
+
+        Ok(results)
+    }
+
+    /// Connects and disconnects the joined entities from the entities returned by a mutation, by
+    /// updating the junction entity of each field. The guards of the junction entity are checked
+    /// as for creating the connected rows and updating the disconnected rows.
+    pub async fn connect_join_through(
+        results: &Option<FieldValue<'_>>,
+        entity: &ServiceEntityConfig,
+        join_through_values: Vec<JoinThroughValues>,
+        request: &JoinThroughRequest<'_>,
+        mut transaction: Option<&mut TransactionEnum>,
+    ) -> Result<(), async_graphql::Error> {
+        debug!("Connecting Join Through Values");
+
+        let JoinThroughRequest {
+            data_sources,
+            subgraph_config,
+            ..
+        } = *request;
+
+        let response = match results
+            .as_ref()
+            .and_then(|results| results.try_downcast_ref::<ResolverResponse>().ok())
+        {
+            Some(response) => response,
+            None => return Ok(()),
+        };
+
+        for JoinThroughValues {
+            field,
+            connect,
+            disconnect,
+        } in join_through_values
+        {
+            let join_through = field.join_through.clone().unwrap();
+            let join_from = field.join_from.clone().unwrap_or(field.name.clone());
+            let joined_entity = match field
+                .as_type
+                .as_ref()
+                .and_then(|as_type| subgraph_config.clone().get_entity(as_type))
+            {
+                Some(joined_entity) => joined_entity,
+                None => {
+                    error!("Field {} must define as_type to join through.", field.name);
+                    return Err(async_graphql::Error::new(format!(
+                        "Field {} must define as_type to join through.",
+                        field.name
+                    )));
+                }
+            };
+            let junction = ServiceResolver::get_junction_entity(
+                &field,
+                &joined_entity,
+                data_sources,
+                subgraph_config,
+            )?;
+
+            let mut parent_keys = Vec::new();
+            for value in response.data.iter() {
+                let document = if let Ok(row) = value.try_downcast_ref::<Option<ResponseRow>>() {
+                    match row {
+                        Some(ResponseRow::SqLite(rr)) => rr.to_document(Some(vec![&join_from]))?,
+                        Some(ResponseRow::MySql(rr)) => rr.to_document(Some(vec![&join_from]))?,
+                        Some(ResponseRow::Postgres(rr)) => {
+                            rr.to_document(Some(vec![&join_from]))?
+                        }
+                        None => continue,
+                    }
+                } else if let Ok(Some(document)) = value.try_downcast_ref::<Option<Document>>() {
+                    document.clone()
+                } else {
+                    continue;
+                };
+                if let Some(parent_key) = document.get(&join_from) {
+                    parent_keys.push(parent_key.clone());
+                }
+            }
+            trace!("Join Through Parent Keys: {:?}", parent_keys);
+
+            let junction_data_source = DataSources::get_entity_data_soruce(data_sources, &junction);
+            if !std::ptr::eq(
+                junction_data_source,
+                DataSources::get_entity_data_soruce(data_sources, entity),
+            ) {
+                error!(
+                    "Junction entity {} must share the data source of {}.",
+                    junction.name, entity.name
+                );
+                return Err(async_graphql::Error::new(format!(
+                    "Junction entity {} must share the data source of {}.",
+                    junction.name, entity.name
+                )));
+            }
+
+            match junction_data_source {
+                DataSource::SQL(ds) => {
+                    ServiceResolver::guard_join_through(
+                        &junction,
+                        &join_through,
+                        &parent_keys,
+                        &connect,
+                        &disconnect,
+                        request,
+                    )
+                    .await?;
+                    let transaction = match transaction.as_deref_mut() {
+                        Some(transaction) => transaction,
+                        None => {
+                            error!(
+                                "No transaction to update junction entity {}.",
+                                junction.name
+                            );
+                            return Err(async_graphql::Error::new(format!(
+                                "No transaction to update junction entity {}.",
+                                junction.name
+                            )));
+                        }
+                    };
+                    ds.update_join_through(
+                        transaction,
+                        &junction,
+                        &join_through,
+                        &parent_keys,
+                        &connect,
+                        &disconnect,
+                    )
+                    .await?
+                }
+                DataSource::Mongo(ds) => {
+                    // Object ids are provided by the client as strings.
+                    let to_object_ids = |keys: Vec<Bson>| match field.scalar {
+                        ScalarOption::ObjectID => keys
+                            .into_iter()
+                            .map(|key| match &key {
+                                Bson::String(value) => {
+                                    ObjectId::from_str(value).map(Bson::ObjectId).unwrap_or(key)
+                                }
+                                _ => key,
+                            })
+                            .collect(),
+                        _ => keys,
+                    };
+                    let connect = to_object_ids(connect);
+                    let disconnect = to_object_ids(disconnect);
+                    ServiceResolver::guard_join_through(
+                        &junction,
+                        &join_through,
+                        &parent_keys,
+                        &connect,
+                        &disconnect,
+                        request,
+                    )
+                    .await?;
+                    mongo::services::Services::update_join_through(
+                        ds.get_entity_db(&junction),
+                        ServiceEntityConfig::get_mongo_collection_name(&junction),
+                        &join_through,
+                        &parent_keys,
+                        &connect,
+                        &disconnect,
+                    )
+                    .await?
+                }
+                DataSource::HTTP(_) => unreachable!(),
+            };
+        }
+
+        Ok(())
+    }
+
+    /// Checks the guards of the junction entity for each row connected, as a `CreateOne` of the
+    /// row, and each row disconnected, as an `UpdateMany` of the row.
+    async fn guard_join_through(
+        junction: &ServiceEntityConfig,
+        join_through: &JoinThrough,
+        parent_keys: &[Bson],
+        connect: &[Bson],
+        disconnect: &[Bson],
+        request: &JoinThroughRequest<'_>,
+    ) -> Result<(), async_graphql::Error> {
+        debug!("Guarding Join Through: {}", junction.name);
+
+        for parent_key in parent_keys {
+            let rows = connect
+                .iter()
+                .map(|key| (key, ResolverType::CreateOne))
+                .chain(disconnect.iter().map(|key| (key, ResolverType::UpdateMany)));
+            for (key, resolver_type) in rows {
+                let row = doc! {
+                    join_through.join_from.clone(): parent_key.clone(),
+                    join_through.join_on.clone(): key.clone(),
+                };
+                let input_document = match resolver_type {
+                    ResolverType::CreateOne => doc! { "values": row },
+                    _ => doc! { "values": {}, "query": row },
+                };
+                ServiceResolver::guard_resolver_function(
+                    vec![],
+                    &input_document,
+                    junction,
+                    request.service_guards.clone(),
+                    &resolver_type,
+                    request.headers.clone(),
+                    request.token_data,
+                    request.data_sources,
+                    request.subgraph_config,
+                )
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -3,7 +3,9 @@ use bson::{doc, Document};
 use log::{debug, trace};
 
 use crate::{
-    configuration::subgraph::entities::service_entity_field::ServiceEntityFieldConfig,
+    configuration::subgraph::entities::{
+        service_entity_field::ServiceEntityFieldConfig, ServiceEntityConfig,
+    },
     data_sources::DataSource,
 };
 
@@ -21,6 +23,7 @@ impl ServiceResolver {
         as_type_field: ServiceEntityFieldConfig,
         join_on_field: ServiceEntityFieldConfig,
         data_source: &DataSource,
+        entity: &ServiceEntityConfig,
     ) -> Result<Option<Document>, async_graphql::Error> {
         debug!("Creating Internal Input: {:?}", ctx.field().name());
         trace!("As Type Field: {:?}", as_type_field);
@@ -41,6 +44,11 @@ impl ServiceResolver {
             }
         };
 
+        // Joins through a junction entity are resolved with a query of their own.
+        if as_type_field.join_through.is_some() {
+            return Ok(Some(field_input));
+        }
+
         let join_on = match as_type_field.join_on.clone() {
             Some(join_on) => join_on,
             None => {
@@ -92,6 +100,37 @@ impl ServiceResolver {
             )?;
         }
 
+        // Composite keys add a filter for each additional pair of fields.
+        for join_key in as_type_field.join_keys.clone().unwrap_or(vec![]) {
+            if query_input.is_empty() {
+                break;
+            }
+
+            let join_key_field =
+                ServiceEntityConfig::get_field(entity.clone(), join_key.join_on.clone())?;
+            let parent_value = ServiceResolver::get_parent_value(ctx, &join_key.join_from)?
+                .unwrap_or(Document::new());
+
+            if is_http_ds {
+                query_input = ServiceResolver::combine_http_input_value(
+                    &parent_value,
+                    &mut query_input,
+                    &join_key.join_from,
+                    &join_key_field.scalar,
+                    &join_key.join_on,
+                )?;
+            } else {
+                query_input = ServiceResolver::combine_input_value(
+                    &parent_value,
+                    &mut query_input,
+                    &join_key.join_from,
+                    &join_key_field.scalar,
+                    &join_key.join_on,
+                    join_key_field.eager.unwrap_or(false),
+                )?;
+            }
+        }
+
         if query_input.is_empty() {
             trace!("Empty Internal Query Input.");
             return Ok(None);
@@ -53,12 +53,16 @@ impl ServiceResolver {
                     as_field.clone(),
                     join_on_field,
                     data_source,
+                    entity,
                 )?
             }
             _ => {
-                let input = ctx.args.try_get(&format!("{}_input", ctx.field().name()))?;
-                let input_document = &input.deserialize::<Document>().unwrap();
-                Some(input_document.clone())
+                // Resolvers without any input, such as a named query without parameters, resolve
+                // with an empty input.
+                match ctx.args.get(&format!("{}_input", ctx.field().name())) {
+                    Some(input) => Some(input.deserialize::<Document>().unwrap()),
+                    None => Some(Document::new()),
+                }
             }
         };
 
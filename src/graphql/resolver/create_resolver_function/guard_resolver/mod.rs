@@ -16,7 +16,8 @@ use crate::{
     },
     data_sources::{sql::services::ResponseRow, DataSources},
     graphql::{
-        entity::create_return_types::ResolverResponse, schema::create_auth_service::TokenData,
+        entity::{create_return_types::ResolverResponse, ServiceEntity},
+        schema::create_auth_service::TokenData,
     },
     resolver_type::ResolverType,
     utils::clean_string::clean_string,
@@ -266,6 +267,7 @@ impl ServiceResolver {
                 ResolverType::FindMany,
                 subgraph_config,
                 token_data,
+                &headers,
                 has_selection_set,
             )
             .await?;
@@ -314,10 +316,19 @@ impl ServiceResolver {
                         if field.is_virtual.unwrap_or(false) {
                             continue;
                         }
-                        let json_value = field
-                            .scalar
-                            .clone()
-                            .rr_to_serde_json_value(response_row, &field.name)?;
+                        let json_value = if field.is_list_column() {
+                            ServiceEntity::resolve_sql_list_scalar(
+                                response_row,
+                                &field.name,
+                                &field.scalar,
+                            )?
+                            .unwrap_or(serde_json::Value::Null)
+                        } else {
+                            field
+                                .scalar
+                                .clone()
+                                .rr_to_serde_json_value(response_row, &field.name)?
+                        };
                         json_obj[field.name.clone()] = json_value;
                     }
 
@@ -0,0 +1,568 @@
+use std::{str::FromStr, sync::Arc};
+
+use async_graphql::{
+    async_trait::async_trait,
+    dataloader::{DataLoader, HashMapCache},
+    dynamic::{FieldValue, ResolverContext},
+};
+use bson::{doc, oid::ObjectId, Bson, Document};
+use log::{debug, error, trace};
+
+use crate::{
+    configuration::subgraph::{
+        entities::{
+            service_entity_field::{join_through::JOIN_THROUGH_KEY, ServiceEntityFieldConfig},
+            ServiceEntityConfig,
+        },
+        SubGraphConfig,
+    },
+    data_sources::{
+        mongo::{self, services::JoinThroughLookup, MongoDataSource},
+        sql::{services::ResponseRow, SqlDataSource},
+        DataSource, DataSources,
+    },
+    filter_operator::FilterOperator,
+    graphql::{
+        entity::create_return_types::{ResolverResponse, ResolverResponseMeta},
+        join_loader::{JoinBatch, JoinFetch, JoinLoader, JoinedRow},
+        resolver::ServiceResolver,
+    },
+    resolver_type::ResolverType,
+    scalar_option::ScalarOption,
+    traits::sqlx::{
+        mysql_row::FromMySqlRow, postgres_row::FromPostgresRow, sqlite_row::FromSqliteRow,
+    },
+    utils::document::get_from_document::DocumentValue,
+};
+
+/// Fetches the joined entity for the values of a batch, along with the client query and sort.
+struct EntityJoinFetch {
+    data_source: DataSource,
+    entity: ServiceEntityConfig,
+    as_field: ServiceEntityFieldConfig,
+    junction: Option<ServiceEntityConfig>,
+    query_input: Document,
+    sort_input: Bson,
+    subgraph_config: SubGraphConfig,
+}
+
+#[async_trait]
+impl JoinFetch for EntityJoinFetch {
+    async fn fetch(
+        &self,
+        values: Vec<Bson>,
+    ) -> Result<Vec<(Vec<String>, JoinedRow)>, async_graphql::Error> {
+        if let Some(junction) = &self.junction {
+            let mut opts = Document::new();
+            if self.sort_input != Bson::Null {
+                opts.insert("sort", self.sort_input.clone());
+            }
+            let input = doc! { "query": self.query_input.clone(), "opts": opts };
+            trace!("Join Through Input: {:?}", input);
+
+            return ServiceResolver::fetch_joined_rows_through(
+                &self.data_source,
+                input,
+                values,
+                &self.entity,
+                &self.as_field,
+                junction,
+                &self.subgraph_config,
+            )
+            .await;
+        }
+
+        let join_on = self.as_field.join_on.clone().unwrap_or_default();
+        let mut filters = Vec::new();
+        if !self.query_input.is_empty() {
+            filters.push(Bson::Document(self.query_input.clone()));
+        }
+        filters.push(Bson::Document(doc! { join_on.clone(): values }));
+        let mut opts = doc! { "per_page": -1, "page": 1 };
+        if self.sort_input != Bson::Null {
+            opts.insert("sort", self.sort_input.clone());
+        }
+        let input = doc! {
+            "query": { FilterOperator::And.as_str(): filters },
+            "opts": opts,
+        };
+        trace!("Batched Join Input: {:?}", input);
+
+        ServiceResolver::fetch_joined_rows(
+            &self.data_source,
+            input,
+            &self.entity,
+            &self.subgraph_config,
+            &join_on,
+        )
+        .await
+    }
+}
+
+impl ServiceResolver {
+    /// Resolves a join through the join `DataLoader` of the request, fetching the joined entity
+    /// for all parents at once. The query and sort of the client are shared by the batch, while
+    /// pagination applies to each parent. Returns `None` when the join can not be batched, in
+    /// which case it is resolved for the parent alone:
+    /// - Joins to HTTP entities, SQL entities backed by a named query and Mongo pipelines.
+    /// - Joins with composite `join_keys`.
+    /// - Joins on eager fields, or on scalars other than String, Int, Boolean, ObjectID and UUID.
+    pub async fn load_batched_join<'a>(
+        ctx: &ResolverContext<'_>,
+        as_field: &ServiceEntityFieldConfig,
+        entity: &ServiceEntityConfig,
+        data_sources: &DataSources,
+        subgraph_config: &SubGraphConfig,
+        user_uuid: Option<String>,
+    ) -> Result<Option<FieldValue<'a>>, async_graphql::Error> {
+        debug!("Loading Batched Join: {}", ctx.field().name());
+
+        // Joins through a junction entity are always resolved with a single query.
+        let junction = match &as_field.join_through {
+            Some(_) => Some(ServiceResolver::get_junction_entity(
+                as_field,
+                entity,
+                data_sources,
+                subgraph_config,
+            )?),
+            None => None,
+        };
+        let request_loader = ctx.data_opt::<DataLoader<JoinLoader, HashMapCache>>();
+        let local_loader;
+        let data_loader = match request_loader {
+            Some(data_loader) => data_loader,
+            None if junction.is_some() => {
+                local_loader = JoinLoader::create_data_loader();
+                &local_loader
+            }
+            None => return Ok(None),
+        };
+        let join_on = match &as_field.join_on {
+            Some(join_on) => join_on.clone(),
+            None => return Ok(None),
+        };
+        if as_field
+            .join_keys
+            .as_ref()
+            .is_some_and(|keys| !keys.is_empty())
+        {
+            trace!("Composite joins are not batched.");
+            return Ok(None);
+        }
+
+        let data_source = DataSources::get_entity_data_soruce(data_sources, entity);
+        match data_source {
+            DataSource::HTTP(_) => {
+                trace!("HTTP joins are not batched.");
+                return Ok(None);
+            }
+            DataSource::SQL(_) if ServiceEntityConfig::get_sql_query_name(entity).is_some() => {
+                trace!("Joins to named queries are not batched.");
+                return Ok(None);
+            }
+            // Pipeline variables are evaluated per request, so pipeline entities are not batched.
+            DataSource::Mongo(_) if ServiceEntityConfig::get_mongo_pipeline(entity).is_some() => {
+                trace!("Joins to pipelines are not batched.");
+                return Ok(None);
+            }
+            _ => (),
+        };
+
+        let join_on_field = ServiceEntityConfig::get_field(entity.clone(), join_on.clone())?;
+        let scalar = as_field.scalar.clone();
+        let is_batchable_scalar = matches!(
+            scalar,
+            ScalarOption::String
+                | ScalarOption::Int
+                | ScalarOption::Boolean
+                | ScalarOption::ObjectID
+                | ScalarOption::UUID
+        );
+        if junction.is_none() && (join_on_field.eager.unwrap_or(false) || !is_batchable_scalar) {
+            trace!("Eager joins and joins on {:?} are not batched.", scalar);
+            return Ok(None);
+        }
+
+        // Collect the values of the parent to join on.
+        let field_name = as_field
+            .join_from
+            .clone()
+            .unwrap_or(ctx.field().name().to_string());
+        let parent_value =
+            ServiceResolver::get_parent_value(ctx, &field_name)?.unwrap_or(Document::new());
+        let is_list = parent_value.get_array(&field_name).is_ok();
+        let join_values = match scalar.get_from_document(&parent_value, &field_name, is_list) {
+            // Junctions hold the keys of the parent as stored by the parent.
+            _ if junction.is_some() => match parent_value.get(&field_name) {
+                Some(Bson::Array(values)) => values.clone(),
+                Some(Bson::Null) | None => Vec::new(),
+                Some(value) => vec![value.clone()],
+            },
+            Ok(DocumentValue::String(v)) => vec![Bson::String(v)],
+            Ok(DocumentValue::StringArray(v)) => v.into_iter().map(Bson::String).collect(),
+            Ok(DocumentValue::Int(v)) => vec![Bson::Int32(v)],
+            Ok(DocumentValue::IntArray(v)) => v.into_iter().map(Bson::Int32).collect(),
+            Ok(DocumentValue::Boolean(v)) => vec![Bson::Boolean(v)],
+            Ok(DocumentValue::BooleanArray(v)) => v.into_iter().map(Bson::Boolean).collect(),
+            Ok(DocumentValue::ObjectID(v)) => vec![Bson::ObjectId(v)],
+            Ok(DocumentValue::ObjectIDArray(v)) => v.into_iter().map(Bson::ObjectId).collect(),
+            Ok(DocumentValue::UUID(v)) => vec![Bson::String(v.to_string())],
+            Ok(DocumentValue::UUIDArray(v)) => {
+                v.into_iter().map(|v| Bson::String(v.to_string())).collect()
+            }
+            // SQL data sources store object ids as strings.
+            _ if matches!(scalar, ScalarOption::ObjectID) => {
+                match parent_value.get_str(&field_name) {
+                    Ok(value) => match ObjectId::from_str(value) {
+                        Ok(value) => vec![Bson::ObjectId(value)],
+                        Err(_) => return Ok(None),
+                    },
+                    Err(_) => return Ok(None),
+                }
+            }
+            _ => return Ok(None),
+        };
+        if join_values.is_empty() && junction.is_none() {
+            return Ok(None);
+        }
+        trace!("Join Values: {:?}", join_values);
+
+        // The query and sort from the client apply to the batch, pagination to each parent.
+        let client_input = ServiceResolver::get_join_client_input(ctx, entity)?;
+        let query_input = client_input
+            .get_document("query")
+            .cloned()
+            .unwrap_or(Document::new());
+        let opts_input = client_input
+            .get_document("opts")
+            .cloned()
+            .unwrap_or(doc! { "per_page": 10, "page": 1 });
+        let sort_input = opts_input.get("sort").cloned().unwrap_or(Bson::Null);
+
+        let join_name = match &as_field.join_through {
+            Some(join_through) => format!("{}.{}", join_through.entity, join_through.join_from),
+            None => format!("{}.{}", entity.name, join_on),
+        };
+        let batch = Arc::new(JoinBatch {
+            key: format!(
+                "{}:{}:{}",
+                join_name,
+                Bson::Document(query_input.clone()).into_relaxed_extjson(),
+                sort_input.clone().into_relaxed_extjson()
+            ),
+            fetch: Arc::new(EntityJoinFetch {
+                data_source: data_source.clone(),
+                entity: entity.clone(),
+                as_field: as_field.clone(),
+                junction,
+                query_input,
+                sort_input,
+                subgraph_config: subgraph_config.clone(),
+            }),
+        });
+        let rows = JoinLoader::load_rows(data_loader, batch, join_values).await?;
+
+        let is_sql = matches!(data_source, DataSource::SQL(_));
+        let to_field_value = |row: Option<JoinedRow>| match row {
+            Some(JoinedRow::Document(document)) => FieldValue::owned_any(Some(document)),
+            Some(JoinedRow::Row(row)) => FieldValue::owned_any(Some(row)),
+            None if is_sql => FieldValue::owned_any(None::<ResponseRow>),
+            None => FieldValue::owned_any(None::<Document>),
+        };
+
+        let total_count = rows.len() as i64;
+        let (data, page, total_pages) = if as_field.list.unwrap_or(false) {
+            let page = opts_input
+                .get("page")
+                .and_then(|page| page.as_i64().or(page.as_i32().map(|page| page as i64)))
+                .unwrap_or(1);
+            let per_page = opts_input
+                .get("per_page")
+                .and_then(|per_page| {
+                    per_page
+                        .as_i64()
+                        .or(per_page.as_i32().map(|per_page| per_page as i64))
+                })
+                .unwrap_or(10);
+            let (skip, total_pages) = if per_page == -1 {
+                (0, 1)
+            } else {
+                (
+                    ((page - 1) * per_page).max(0) as usize,
+                    (total_count + per_page - 1) / per_page,
+                )
+            };
+            let take = if per_page == -1 {
+                rows.len()
+            } else {
+                per_page as usize
+            };
+            let data = rows
+                .into_iter()
+                .skip(skip)
+                .take(take)
+                .map(|row| to_field_value(Some(row)))
+                .collect::<Vec<FieldValue>>();
+            (data, page, total_pages.max(1))
+        } else {
+            (vec![to_field_value(rows.into_iter().next())], 1, 1)
+        };
+
+        let res = ResolverResponse {
+            meta: ResolverResponseMeta {
+                request_id: uuid::Uuid::new_v4().to_string(),
+                service_name: subgraph_config.service.name.clone(),
+                service_version: subgraph_config.service.version.clone(),
+                executed_at: chrono::Utc::now()
+                    .to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+                count: data.len() as i64,
+                total_count,
+                page,
+                total_pages,
+                user_uuid,
+            },
+            data,
+        };
+
+        Ok(Some(FieldValue::owned_any(res)))
+    }
+
+    /// Fetches the rows of a batched join, along with the join keys of each row.
+    pub async fn fetch_joined_rows(
+        data_source: &DataSource,
+        input: Document,
+        entity: &ServiceEntityConfig,
+        subgraph_config: &SubGraphConfig,
+        join_on: &str,
+    ) -> Result<Vec<(Vec<String>, JoinedRow)>, async_graphql::Error> {
+        debug!("Fetching Joined Rows");
+
+        let mut joined_rows = Vec::new();
+        match data_source {
+            DataSource::SQL(ds) => {
+                let table = ServiceEntityConfig::get_entity_data_source(entity)
+                    .and_then(|entity_data_source| entity_data_source.table)
+                    .unwrap_or(entity.name.clone());
+                let query = ds.create_cached_query(
+                    input,
+                    ResolverType::FindMany,
+                    &table,
+                    entity,
+                    subgraph_config,
+                )?;
+                let (rows, _total_count) = ds.read_many(&query, &true).await?;
+
+                for row in rows.into_iter().flatten() {
+                    let document = match &row {
+                        ResponseRow::SqLite(rr) => rr.to_document(Some(vec![join_on]))?,
+                        ResponseRow::MySql(rr) => rr.to_document(Some(vec![join_on]))?,
+                        ResponseRow::Postgres(rr) => rr.to_document(Some(vec![join_on]))?,
+                    };
+                    let keys = ServiceResolver::get_row_join_keys(&document, join_on);
+                    joined_rows.push((keys, JoinedRow::Row(row)));
+                }
+            }
+            DataSource::Mongo(ds) => {
+                let (input, eager_load_options) = MongoDataSource::finalize_input(
+                    input,
+                    entity,
+                    subgraph_config,
+                    &ResolverType::FindMany,
+                )?;
+                let (documents, _total_count) = mongo::services::Services::find_many(
+                    ds.get_entity_db(entity),
+                    input,
+                    ServiceEntityConfig::get_mongo_collection_name(entity),
+                    vec![],
+                    eager_load_options,
+                )
+                .await?;
+
+                for document in documents.into_iter().flatten() {
+                    let keys = ServiceResolver::get_row_join_keys(&document, join_on);
+                    joined_rows.push((keys, JoinedRow::Document(document)));
+                }
+            }
+            DataSource::HTTP(_) => unreachable!(),
+        }
+
+        trace!("Fetched {} Joined Rows", joined_rows.len());
+        Ok(joined_rows)
+    }
+
+    /// Fetches the rows joined to the parents through the junction entity, keyed by the parent
+    /// each row is joined to.
+    pub async fn fetch_joined_rows_through(
+        data_source: &DataSource,
+        input: Document,
+        parent_keys: Vec<Bson>,
+        entity: &ServiceEntityConfig,
+        as_field: &ServiceEntityFieldConfig,
+        junction: &ServiceEntityConfig,
+        subgraph_config: &SubGraphConfig,
+    ) -> Result<Vec<(Vec<String>, JoinedRow)>, async_graphql::Error> {
+        debug!("Fetching Joined Rows Through: {}", junction.name);
+
+        let mut joined_rows = Vec::new();
+        match data_source {
+            DataSource::SQL(ds) => {
+                let query = SqlDataSource::create_join_through_query(
+                    &input,
+                    entity,
+                    junction,
+                    as_field,
+                    parent_keys,
+                    ds.config.dialect.clone(),
+                    subgraph_config,
+                )?;
+                let (rows, _total_count) = ds.read_many(&query, &true).await?;
+
+                for row in rows.into_iter().flatten() {
+                    let document = match &row {
+                        ResponseRow::SqLite(rr) => rr.to_document(Some(vec![JOIN_THROUGH_KEY]))?,
+                        ResponseRow::MySql(rr) => rr.to_document(Some(vec![JOIN_THROUGH_KEY]))?,
+                        ResponseRow::Postgres(rr) => {
+                            rr.to_document(Some(vec![JOIN_THROUGH_KEY]))?
+                        }
+                    };
+                    let keys = ServiceResolver::get_row_join_keys(&document, JOIN_THROUGH_KEY);
+                    joined_rows.push((keys, JoinedRow::Row(row)));
+                }
+            }
+            DataSource::Mongo(ds) => {
+                let (join_through, join_on) = match (&as_field.join_through, &as_field.join_on) {
+                    (Some(join_through), Some(join_on)) => (join_through, join_on),
+                    _ => unreachable!(),
+                };
+                let (input, eager_load_options) = MongoDataSource::finalize_input(
+                    input,
+                    entity,
+                    subgraph_config,
+                    &ResolverType::FindMany,
+                )?;
+                let lookup = JoinThroughLookup {
+                    junction: ServiceEntityConfig::get_mongo_collection_name(junction),
+                    junction_from: join_through.join_from.clone(),
+                    junction_on: join_through.join_on.clone(),
+                    from: ServiceEntityConfig::get_mongo_collection_name(entity),
+                    foreign_field: join_on.clone(),
+                };
+                let (documents, _total_count) = mongo::services::Services::find_many_through(
+                    ds.get_entity_db(entity),
+                    input,
+                    lookup,
+                    parent_keys,
+                    eager_load_options,
+                )
+                .await?;
+
+                for mut document in documents.into_iter().flatten() {
+                    let keys = ServiceResolver::get_row_join_keys(&document, JOIN_THROUGH_KEY);
+                    document.remove(JOIN_THROUGH_KEY);
+                    joined_rows.push((keys, JoinedRow::Document(document)));
+                }
+            }
+            DataSource::HTTP(_) => unreachable!(),
+        }
+
+        trace!("Fetched {} Joined Rows Through", joined_rows.len());
+        Ok(joined_rows)
+    }
+
+    /// Gets the junction entity of a field joined through it. The junction must be stored by
+    /// the same SQL or Mongo data source as the joined entity.
+    pub fn get_junction_entity(
+        as_field: &ServiceEntityFieldConfig,
+        entity: &ServiceEntityConfig,
+        data_sources: &DataSources,
+        subgraph_config: &SubGraphConfig,
+    ) -> Result<ServiceEntityConfig, async_graphql::Error> {
+        let join_through = match &as_field.join_through {
+            Some(join_through) => join_through,
+            None => {
+                return Err(async_graphql::Error::new(format!(
+                    "Field {} does not join through an entity.",
+                    as_field.name
+                )))
+            }
+        };
+        if as_field.join_on.is_none() || as_field.join_from.is_none() {
+            error!(
+                "Field {} must define join_on and join_from to join through {}.",
+                as_field.name, join_through.entity
+            );
+            return Err(async_graphql::Error::new(format!(
+                "Field {} must define join_on and join_from to join through {}.",
+                as_field.name, join_through.entity
+            )));
+        }
+
+        let junction = match subgraph_config.clone().get_entity(&join_through.entity) {
+            Some(junction) => junction,
+            None => {
+                error!("Junction entity {} not found.", join_through.entity);
+                return Err(async_graphql::Error::new(format!(
+                    "Junction entity {} not found.",
+                    join_through.entity
+                )));
+            }
+        };
+
+        let data_source = DataSources::get_entity_data_soruce(data_sources, entity);
+        let junction_data_source = DataSources::get_entity_data_soruce(data_sources, &junction);
+        let is_supported = match data_source {
+            DataSource::SQL(_) => {
+                ServiceEntityConfig::get_sql_query_name(entity).is_none()
+                    && ServiceEntityConfig::get_sql_query_name(&junction).is_none()
+            }
+            DataSource::Mongo(_) => true,
+            DataSource::HTTP(_) => false,
+        };
+        if !is_supported || !std::ptr::eq(data_source, junction_data_source) {
+            error!(
+                "Junction entity {} must share the SQL or Mongo data source of {}.",
+                junction.name, entity.name
+            );
+            return Err(async_graphql::Error::new(format!(
+                "Junction entity {} must share the SQL or Mongo data source of {}.",
+                junction.name, entity.name
+            )));
+        }
+
+        Ok(junction)
+    }
+
+    /// Gets the input of the client for a joined field, without virtual fields. The internal
+    /// input of the parent also filters on the value of the parent, which the batch replaces.
+    fn get_join_client_input(
+        ctx: &ResolverContext<'_>,
+        entity: &ServiceEntityConfig,
+    ) -> Result<Document, async_graphql::Error> {
+        let field_name = ctx.field().name();
+        let client_input = match ctx.args.try_get(field_name)?.deserialize::<Document>() {
+            Ok(client_input) => client_input,
+            Err(_) => {
+                error!("Invalid input for field: {}", field_name);
+                return Err(async_graphql::Error::new(format!(
+                    "Invalid input for field: {}",
+                    field_name
+                )));
+            }
+        };
+
+        Ok(ServiceResolver::remove_virtual_fields(
+            &client_input,
+            &entity.fields,
+        ))
+    }
+
+    fn get_row_join_keys(document: &Document, join_on: &str) -> Vec<String> {
+        match document.get(join_on) {
+            Some(Bson::Array(values)) => {
+                values.iter().filter_map(JoinLoader::get_join_key).collect()
+            }
+            Some(value) => JoinLoader::get_join_key(value).into_iter().collect(),
+            None => Vec::new(),
+        }
+    }
+}
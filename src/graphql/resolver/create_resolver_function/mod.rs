@@ -2,21 +2,27 @@ use async_graphql::{
     dynamic::{FieldFuture, FieldValue, ResolverContext},
     SelectionField,
 };
+use bson::doc;
 use http::HeaderMap;
 use log::debug;
 
 use crate::{
     data_sources::DataSources,
     graphql::entity::create_return_types::{ResolverResponse, ResolverResponseMeta},
+    resolver_type::ResolverType,
 };
 
+use self::connect_join_through::JoinThroughRequest;
+
 use super::ServiceResolver;
 
+mod connect_join_through;
 mod get_operation_type;
 mod get_resolver_input;
 mod get_token_data;
 mod guard_resolver;
 mod handle_default_values;
+mod load_batched_join;
 mod remove_virtual_fields;
 
 impl ServiceResolver {
@@ -105,7 +111,7 @@ impl ServiceResolver {
                     &entity,
                     service_guards.clone(),
                     &resolver_type,
-                    headers,
+                    headers.clone(),
                     &token_data,
                     &data_sources,
                     &subgraph_config,
@@ -123,7 +129,64 @@ impl ServiceResolver {
                 let input_document =
                     ServiceResolver::remove_virtual_fields(&input_document, &entity.fields);
 
-                let operation_type = ServiceResolver::get_operation_type(&resolver_type, &as_field);
+                let mut input_document = input_document;
+                let join_through_values =
+                    ServiceResolver::take_join_through_values(&mut input_document, &entity);
+
+                let mut operation_type =
+                    ServiceResolver::get_operation_type(&resolver_type, &as_field);
+
+                // Updates only connecting joined entities find the entities to connect instead.
+                let has_values = ["values", "operators"].iter().any(|key| {
+                    input_document
+                        .get_document(key)
+                        .is_ok_and(|values| !values.is_empty())
+                });
+                if !join_through_values.is_empty() && !has_values {
+                    match operation_type {
+                        ResolverType::UpdateOne => operation_type = ResolverType::FindOne,
+                        ResolverType::UpdateMany => {
+                            operation_type = ResolverType::FindMany;
+                            input_document.insert("opts", doc! { "per_page": -1, "page": 1 });
+                        }
+                        _ => (),
+                    }
+                }
+
+                // Joins are batched across all parents of the request when possible.
+                if let (ResolverType::InternalType, Some(as_field)) = (&resolver_type, &as_field) {
+                    let results = ServiceResolver::load_batched_join(
+                        &ctx,
+                        as_field,
+                        &entity,
+                        &data_sources,
+                        &subgraph_config,
+                        token_data
+                            .as_ref()
+                            .map(|token_data| token_data.user_uuid.to_string()),
+                    )
+                    .await?;
+                    if results.is_some() {
+                        return Ok(results);
+                    }
+                }
+
+                if !join_through_values.is_empty() {
+                    return ServiceResolver::execute_join_through(
+                        input_document,
+                        entity,
+                        operation_type,
+                        join_through_values,
+                        &JoinThroughRequest {
+                            data_sources: &data_sources,
+                            subgraph_config: &subgraph_config,
+                            token_data: &token_data,
+                            headers: &headers,
+                            service_guards,
+                        },
+                    )
+                    .await;
+                }
 
                 let results = DataSources::execute(
                     &data_sources,
@@ -132,7 +195,8 @@ impl ServiceResolver {
                     operation_type,
                     &subgraph_config,
                     &token_data,
-                    has_selection_set.clone(),
+                    &headers,
+                    has_selection_set,
                 )
                 .await?;
 
@@ -0,0 +1,203 @@
+use async_graphql::{
+    dynamic::{Field, FieldFuture, FieldValue, InputValue, TypeRef},
+    Value,
+};
+use bson::Document;
+use http::HeaderMap;
+use log::{debug, error, trace};
+
+use crate::{
+    configuration::subgraph::{
+        data_sources::ServiceDataSourceConfig, guard::Guard, mutations::ServiceMutationConfig,
+    },
+    data_sources::{DataSource, DataSources},
+    graphql::{
+        entity::create_return_types::{ResolverResponse, ResolverResponseMeta},
+        resolver::ServiceResolver,
+    },
+    resolver_type::ResolverType,
+    scalar_option::ScalarOption,
+};
+
+use super::ServiceSchema;
+
+impl ServiceSchema {
+    /// Registers the custom mutations of the service on the `Mutation` object.
+    pub fn create_custom_mutations(mut self) -> Result<Self, async_graphql::Error> {
+        debug!("Creating Custom Mutations");
+
+        let mutations = self.subgraph_config.service.mutations.clone();
+        for mutation in mutations.unwrap_or(vec![]) {
+            let resolver = self.create_custom_mutation(mutation)?;
+            self.mutation = self.mutation.field(resolver);
+        }
+
+        Ok(self)
+    }
+
+    fn create_custom_mutation(
+        &self,
+        mutation: ServiceMutationConfig,
+    ) -> Result<Field, async_graphql::Error> {
+        debug!("Creating Custom Mutation: {}", mutation.name);
+
+        let is_sql = self
+            .subgraph_config
+            .service
+            .data_sources
+            .iter()
+            .any(|data_source| match data_source {
+                ServiceDataSourceConfig::SQL(ds) => ds.name == mutation.data_source,
+                _ => false,
+            });
+        if !is_sql {
+            error!(
+                "Custom mutation {} must use a SQL data source.",
+                mutation.name
+            );
+            return Err(async_graphql::Error::new(format!(
+                "Custom mutation {} must use a SQL data source.",
+                mutation.name
+            )));
+        }
+
+        // Mutations returning an entity resolve to the find many response of the entity, which
+        // exists for read only entities too.
+        let type_ref = match &mutation.returns {
+            Some(entity_name) => {
+                let entity = match self.subgraph_config.clone().get_entity(entity_name) {
+                    Some(entity) => entity,
+                    None => {
+                        error!(
+                            "Custom mutation {} returns unknown entity {}.",
+                            mutation.name, entity_name
+                        );
+                        return Err(async_graphql::Error::new(format!(
+                            "Custom mutation {} returns unknown entity {}.",
+                            mutation.name, entity_name
+                        )));
+                    }
+                };
+                match DataSources::get_entity_data_soruce(&self.data_sources, &entity) {
+                    DataSource::SQL(_) => (),
+                    _ => {
+                        error!(
+                            "Custom mutation {} must return an entity of a SQL data source.",
+                            mutation.name
+                        );
+                        return Err(async_graphql::Error::new(format!(
+                            "Custom mutation {} must return an entity of a SQL data source.",
+                            mutation.name
+                        )));
+                    }
+                };
+                TypeRef::named_nn(format!(
+                    "{}_{}_response",
+                    ResolverType::FindMany.to_string().to_lowercase(),
+                    entity_name
+                ))
+            }
+            None => TypeRef::named_nn(TypeRef::INT),
+        };
+
+        let args = mutation.args.clone().unwrap_or(vec![]);
+        let service_guards = self.subgraph_config.service.guards.clone();
+        let is_auth = self.subgraph_config.service.auth.is_some();
+        let subgraph_config = self.subgraph_config.clone();
+
+        let mut resolver = Field::new(mutation.name.clone(), type_ref, move |ctx| {
+            let mutation = mutation.clone();
+            let service_guards = service_guards.clone();
+            let subgraph_config = subgraph_config.clone();
+
+            FieldFuture::new(async move {
+                debug!("Resolving Custom Mutation: {}", mutation.name);
+                let data_sources = ctx.data_unchecked::<DataSources>().clone();
+                let headers = ctx.data_unchecked::<HeaderMap>().clone();
+                let token_data = if is_auth {
+                    ServiceResolver::get_token_data(&ctx, headers.clone())?
+                } else {
+                    None
+                };
+
+                let mut args = serde_json::Map::new();
+                for (name, value) in ctx.args.iter() {
+                    args.insert(name.to_string(), value.as_value().clone().into_json()?);
+                }
+                let args = bson::to_document(&args)?;
+                trace!("Custom Mutation Args: {:?}", args);
+
+                // Guards read the arguments as the `values` of the input.
+                let mut input_document = Document::new();
+                input_document.insert("values", args.clone());
+                let mut guard_context = Guard::create_guard_context(
+                    headers,
+                    token_data.clone(),
+                    input_document,
+                    "CustomMutation".to_string(),
+                    None,
+                    None,
+                    subgraph_config.clone(),
+                )?;
+                if let Some(guards) = service_guards {
+                    Guard::check(&guards, &mut guard_context)?;
+                }
+                if let Some(guards) = &mutation.guards {
+                    Guard::check(guards, &mut guard_context)?;
+                }
+
+                let data_source = match DataSources::get_data_source_by_name(
+                    &data_sources,
+                    &mutation.data_source,
+                ) {
+                    DataSource::SQL(ds) => ds,
+                    _ => unreachable!(),
+                };
+                let (rows, count) = data_source.execute_mutation(&mutation, &args).await?;
+
+                if mutation.returns.is_none() {
+                    return Ok(Some(FieldValue::value(Value::from(count))));
+                }
+
+                let res = ResolverResponse {
+                    data: rows.into_iter().map(FieldValue::owned_any).collect(),
+                    meta: ResolverResponseMeta {
+                        request_id: uuid::Uuid::new_v4().to_string(),
+                        service_name: subgraph_config.service.name.clone(),
+                        service_version: subgraph_config.service.version.clone(),
+                        executed_at: chrono::Utc::now()
+                            .to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+                        count: count as i64,
+                        total_count: count as i64,
+                        page: 1,
+                        total_pages: 1,
+                        user_uuid: token_data.map(|token_data| token_data.user_uuid.to_string()),
+                    },
+                };
+                Ok(Some(FieldValue::owned_any(res)))
+            })
+        });
+
+        for arg in args {
+            let is_scalar = !matches!(arg.scalar, ScalarOption::Object | ScalarOption::Enum);
+            let type_ref = match arg.scalar.to_input_type_ref(
+                arg.list.unwrap_or(false),
+                arg.required.unwrap_or(false),
+                &ResolverType::CreateOne,
+                None,
+            ) {
+                Ok(type_ref) if is_scalar => type_ref,
+                _ => {
+                    error!("Custom mutation argument {} must be a scalar.", arg.name);
+                    return Err(async_graphql::Error::new(format!(
+                        "Custom mutation argument {} must be a scalar.",
+                        arg.name
+                    )));
+                }
+            };
+            resolver = resolver.argument(InputValue::new(arg.name.clone(), type_ref));
+        }
+
+        Ok(resolver)
+    }
+}
@@ -0,0 +1,97 @@
+use async_graphql::{
+    dynamic::{Field, FieldFuture, FieldValue, Object, TypeRef},
+    Value,
+};
+use bson::doc;
+use http::HeaderMap;
+use log::{debug, error};
+
+use crate::{
+    configuration::subgraph::guard::Guard,
+    data_sources::{DataSourceHealth, DataSources},
+    graphql::resolver::ServiceResolver,
+};
+
+use super::ServiceSchema;
+
+impl ServiceSchema {
+    /// Registers the `data_source_health` query, reporting whether each SQL data source and
+    /// replica can be reached. Requests are checked against the service guards.
+    pub fn create_data_source_health(mut self) -> Self {
+        debug!("Creating Data Source Health");
+        let subgraph_config = self.subgraph_config.clone();
+        let is_auth = self.subgraph_config.service.auth.is_some();
+
+        let data_source_health = Object::new("data_source_health")
+            .field(Field::new(
+                "name",
+                TypeRef::named_nn(TypeRef::STRING),
+                move |ctx| {
+                    FieldFuture::new(async move {
+                        let health = ctx
+                            .parent_value
+                            .try_downcast_ref::<DataSourceHealth>()
+                            .map_err(|e| {
+                                error!("Failed to downcast: {:?}", e);
+                                async_graphql::Error::new(format!("Failed to downcast: {:?}", e))
+                            })?;
+                        Ok(Some(Value::from(health.name.clone())))
+                    })
+                },
+            ))
+            .field(Field::new(
+                "healthy",
+                TypeRef::named_nn(TypeRef::BOOLEAN),
+                move |ctx| {
+                    FieldFuture::new(async move {
+                        let health = ctx
+                            .parent_value
+                            .try_downcast_ref::<DataSourceHealth>()
+                            .map_err(|e| {
+                                error!("Failed to downcast: {:?}", e);
+                                async_graphql::Error::new(format!("Failed to downcast: {:?}", e))
+                            })?;
+                        Ok(Some(Value::from(health.healthy)))
+                    })
+                },
+            ));
+
+        let resolver = Field::new(
+            "data_source_health",
+            TypeRef::named_nn_list_nn("data_source_health"),
+            move |ctx| {
+                let subgraph_config = subgraph_config.clone();
+                FieldFuture::new(async move {
+                    if let Some(service_guards) = &subgraph_config.service.guards {
+                        let headers = ctx.data_unchecked::<HeaderMap>().clone();
+                        let token_data = if is_auth {
+                            ServiceResolver::get_token_data(&ctx, headers.clone())?
+                        } else {
+                            None
+                        };
+                        let mut guard_context = Guard::create_guard_context(
+                            headers,
+                            token_data,
+                            doc! {},
+                            "DataSourceHealth".to_string(),
+                            None,
+                            None,
+                            subgraph_config.clone(),
+                        )?;
+                        Guard::check(service_guards, &mut guard_context)?;
+                    }
+
+                    let data_sources = ctx.data_unchecked::<DataSources>();
+                    let health = DataSources::get_health(data_sources).await;
+                    Ok(Some(FieldValue::list(
+                        health.into_iter().map(FieldValue::owned_any),
+                    )))
+                })
+            },
+        );
+
+        self = self.register_types(vec![data_source_health]);
+        self.query = self.query.field(resolver);
+        self
+    }
+}
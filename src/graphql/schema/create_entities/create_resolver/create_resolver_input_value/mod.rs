@@ -18,7 +18,7 @@ impl ServiceSchema {
         entity: &ServiceEntityConfig,
         mut resolver: Field,
         resolver_type: &ResolverType,
-    ) -> Self {
+    ) -> Result<Self, async_graphql::Error> {
         debug!("Creating Resolver Input");
 
         let mut inputs = Vec::new();
@@ -27,6 +27,7 @@ impl ServiceSchema {
         let entity_data_source = DataSources::get_entity_data_soruce(&self.data_sources, entity);
 
         let mut root_input = InputObject::new(&input_name);
+        let mut has_root_fields = false;
 
         let include_query_input = resolver_type == &ResolverType::UpdateOne
             || resolver_type == &ResolverType::UpdateMany
@@ -66,28 +67,57 @@ impl ServiceSchema {
                 _ => unreachable!(),
             };
 
-            let rest_inputs = ServiceInput::new(
-                query_input_name.clone(),
-                entity.fields.clone(),
-                resolver_type.clone(),
-                exclude_from_input,
-                entity_data_source.clone(),
-            )
-            .build(Some(true));
+            let query_params = match &entity_data_source {
+                DataSource::SQL(ds) => match ServiceEntityConfig::get_sql_query_name(entity) {
+                    Some(query_name) => {
+                        let named_query = ds.get_named_query(&query_name)?;
+                        Some(named_query.params.clone().unwrap_or(vec![]))
+                    }
+                    None => None,
+                },
+                _ => None,
+            };
 
-            root_input = root_input.field(InputValue::new(
-                "query",
-                TypeRef::named_nn(query_input_name.clone()),
-            ));
-            let is_http_ds = match entity_data_source {
-                DataSource::HTTP(_) => true,
-                _ => false,
+            let rest_inputs = match query_params {
+                // The query input of entities backed by a named query holds its parameters,
+                // which are required when the parameter is required.
+                Some(query_params) => ServiceInput::new(
+                    query_input_name.clone(),
+                    query_params,
+                    ResolverType::CreateOne,
+                    None,
+                    entity_data_source.clone(),
+                )
+                .build(None),
+                None => ServiceInput::new(
+                    query_input_name.clone(),
+                    entity.fields.clone(),
+                    *resolver_type,
+                    exclude_from_input,
+                    entity_data_source.clone(),
+                )
+                .build(Some(true)),
             };
-            if resolver_type == &ResolverType::FindMany && !is_http_ds {
+
+            if !rest_inputs.is_empty() {
+                root_input = root_input.field(InputValue::new(
+                    "query",
+                    TypeRef::named_nn(query_input_name.clone()),
+                ));
+                has_root_fields = true;
+            }
+            // HTTP entities only accept options when they are mapped onto the upstream API.
+            let has_options = match entity_data_source {
+                DataSource::HTTP(_) => ServiceEntityConfig::get_resolver(entity, *resolver_type)
+                    .is_some_and(|resolver| resolver.pagination.is_some()),
+                _ => true,
+            };
+            if resolver_type == &ResolverType::FindMany && has_options {
                 root_input = root_input.field(InputValue::new(
                     "opts",
                     TypeRef::named("options_input".to_string()),
                 ));
+                has_root_fields = true;
             }
 
             inputs.extend(rest_inputs);
@@ -128,7 +158,7 @@ impl ServiceSchema {
                 values_input_name.clone(),
                 entity.fields.clone(),
                 resolver_type.clone(),
-                exclude_from_input,
+                exclude_from_input.clone(),
                 entity_data_source.clone(),
             )
             .build(None);
@@ -137,11 +167,33 @@ impl ServiceSchema {
                 "values",
                 TypeRef::named_nn(values_input_name.clone()),
             ));
+            has_root_fields = true;
 
             inputs.extend(rest_inputs);
+
+            // Updates may also increment, unset or change the lists of fields.
+            if resolver_type != &ResolverType::CreateOne {
+                let operator_inputs = ServiceInput::create_update_operators_input(
+                    &entity.fields,
+                    resolver_type,
+                    exclude_from_input,
+                    &values_input_name.replace("_values_input", ""),
+                    entity_data_source,
+                );
+                if let Some(operators_input) = operator_inputs.last() {
+                    root_input = root_input.field(InputValue::new(
+                        "operators",
+                        TypeRef::named(operators_input.type_name()),
+                    ));
+                }
+                inputs.extend(operator_inputs);
+            }
         }
 
-        inputs.push(root_input);
+        // Named queries without parameters take no input when finding one.
+        if has_root_fields {
+            inputs.push(root_input);
+        }
 
         if !inputs.is_empty() {
             resolver = resolver.argument(InputValue::new(
@@ -160,6 +212,6 @@ impl ServiceSchema {
             _ => panic!("Invalid resolver type: {:?}", resolver_type),
         }
         self = self.register_inputs(inputs);
-        self
+        Ok(self)
     }
 }
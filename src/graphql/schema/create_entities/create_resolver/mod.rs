@@ -13,7 +13,7 @@ impl ServiceSchema {
         mut self,
         entity: &ServiceEntityConfig,
         resolver_type: ResolverType,
-    ) -> Self {
+    ) -> Result<Self, async_graphql::Error> {
         debug!("Adding Resolver");
 
         let resolver = ServiceResolver::new(
@@ -24,7 +24,7 @@ impl ServiceSchema {
         )
         .build();
 
-        self = self.create_resolver_input_value(&entity, resolver, &resolver_type);
-        self
+        self = self.create_resolver_input_value(entity, resolver, &resolver_type)?;
+        Ok(self)
     }
 }
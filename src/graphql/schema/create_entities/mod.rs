@@ -1,5 +1,5 @@
 use crate::{
-    configuration::subgraph::data_sources::sql::DialectEnum,
+    configuration::subgraph::{data_sources::sql::DialectEnum, entities::ServiceEntityConfig},
     data_sources::{DataSource, DataSources},
     resolver_type::ResolverType,
 };
@@ -11,7 +11,9 @@ mod create_entity_type_defs;
 mod create_resolver;
 
 impl ServiceSchema {
-    pub fn create_entities(mut self) -> Self {
+    /// Creates the types and resolvers of each entity. Fails when an entity references config
+    /// which does not exist, such as a missing named query.
+    pub fn create_entities(mut self) -> Result<Self, async_graphql::Error> {
         debug!("Creating Entities Config");
         debug!("Entities: {:?}", self.subgraph_config.service.entities);
         let entities = self.subgraph_config.service.entities.clone();
@@ -22,37 +24,49 @@ impl ServiceSchema {
             let data_source = DataSources::get_entity_data_soruce(&self.data_sources, entity);
 
             match data_source {
+                // Entities backed by a named query are read only.
+                DataSource::SQL(_) if ServiceEntityConfig::get_sql_query_name(entity).is_some() => {
+                    self = self.create_resolver(entity, ResolverType::FindOne)?;
+                    self = self.create_resolver(entity, ResolverType::FindMany)?;
+                }
+                // Entities backed by an aggregation pipeline are read only.
+                DataSource::Mongo(_)
+                    if ServiceEntityConfig::get_mongo_pipeline(entity).is_some() =>
+                {
+                    self = self.create_resolver(entity, ResolverType::FindOne)?;
+                    self = self.create_resolver(entity, ResolverType::FindMany)?;
+                }
                 DataSource::SQL(ds) => match ds.config.dialect {
                     DialectEnum::POSTGRES => {
-                        self = self.create_resolver(entity, ResolverType::FindOne);
-                        self = self.create_resolver(entity, ResolverType::FindMany);
-                        self = self.create_resolver(entity, ResolverType::CreateOne);
-                        self = self.create_resolver(entity, ResolverType::UpdateMany);
+                        self = self.create_resolver(entity, ResolverType::FindOne)?;
+                        self = self.create_resolver(entity, ResolverType::FindMany)?;
+                        self = self.create_resolver(entity, ResolverType::CreateOne)?;
+                        self = self.create_resolver(entity, ResolverType::UpdateMany)?;
                     }
                     DialectEnum::MYSQL => {
-                        self = self.create_resolver(entity, ResolverType::FindOne);
-                        self = self.create_resolver(entity, ResolverType::FindMany);
-                        self = self.create_resolver(entity, ResolverType::CreateOne);
-                        self = self.create_resolver(entity, ResolverType::UpdateMany);
-                        self = self.create_resolver(entity, ResolverType::UpdateOne);
+                        self = self.create_resolver(entity, ResolverType::FindOne)?;
+                        self = self.create_resolver(entity, ResolverType::FindMany)?;
+                        self = self.create_resolver(entity, ResolverType::CreateOne)?;
+                        self = self.create_resolver(entity, ResolverType::UpdateMany)?;
+                        self = self.create_resolver(entity, ResolverType::UpdateOne)?;
                     }
                     DialectEnum::SQLITE => {
-                        self = self.create_resolver(entity, ResolverType::FindOne);
-                        self = self.create_resolver(entity, ResolverType::FindMany);
-                        self = self.create_resolver(entity, ResolverType::CreateOne);
-                        self = self.create_resolver(entity, ResolverType::UpdateMany);
+                        self = self.create_resolver(entity, ResolverType::FindOne)?;
+                        self = self.create_resolver(entity, ResolverType::FindMany)?;
+                        self = self.create_resolver(entity, ResolverType::CreateOne)?;
+                        self = self.create_resolver(entity, ResolverType::UpdateMany)?;
                     }
                 },
                 DataSource::Mongo(_) | DataSource::HTTP(_) => {
-                    self = self.create_resolver(entity, ResolverType::FindOne);
-                    self = self.create_resolver(entity, ResolverType::FindMany);
-                    self = self.create_resolver(entity, ResolverType::CreateOne);
-                    self = self.create_resolver(entity, ResolverType::UpdateOne);
-                    self = self.create_resolver(entity, ResolverType::UpdateMany);
+                    self = self.create_resolver(entity, ResolverType::FindOne)?;
+                    self = self.create_resolver(entity, ResolverType::FindMany)?;
+                    self = self.create_resolver(entity, ResolverType::CreateOne)?;
+                    self = self.create_resolver(entity, ResolverType::UpdateOne)?;
+                    self = self.create_resolver(entity, ResolverType::UpdateMany)?;
                 }
             }
         }
 
-        self
+        Ok(self)
     }
 }
@@ -0,0 +1,153 @@
+use async_graphql::dynamic::{FieldFuture, FieldValue};
+use bson::{doc, Document};
+use http::HeaderMap;
+use json::JsonValue;
+use log::{debug, error, trace};
+
+use crate::{
+    configuration::subgraph::entities::ServiceEntityConfig,
+    data_sources::{sql::services::ResponseRow, DataSources},
+    graphql::{entity::create_return_types::ResolverResponse, resolver::ServiceResolver},
+    resolver_type::ResolverType,
+};
+
+use super::ServiceSchema;
+
+impl ServiceSchema {
+    /// Registers the federation entity resolver, loading each representation of the `_entities`
+    /// query by the primary key fields of its entity.
+    pub fn create_entity_resolver(mut self) -> Self {
+        debug!("Creating Entity Resolver");
+        let subgraph_config = self.subgraph_config.clone();
+        let service_guards = self.subgraph_config.service.guards.clone();
+        let is_auth = self.subgraph_config.service.auth.is_some();
+
+        self.schema_builder = self.schema_builder.entity_resolver(move |ctx| {
+            let subgraph_config = subgraph_config.clone();
+            let service_guards = service_guards.clone();
+
+            FieldFuture::new(async move {
+                let data_sources = ctx.data_unchecked::<DataSources>().clone();
+                let headers = ctx.data_unchecked::<HeaderMap>().clone();
+                let token_data = if is_auth {
+                    ServiceResolver::get_token_data(&ctx, headers.clone())?
+                } else {
+                    None
+                };
+
+                let mut entities = vec![];
+                for representation in ctx.args.try_get("representations")?.list()?.iter() {
+                    let representation = representation.deserialize::<Document>()?;
+                    trace!("Representation: {:?}", representation);
+
+                    let type_name = representation.get_str("__typename").map_err(|_| {
+                        error!("Representation is missing `__typename`");
+                        async_graphql::Error::new("Representation is missing `__typename`")
+                    })?;
+                    let entity = match subgraph_config
+                        .service
+                        .entities
+                        .iter()
+                        .find(|entity| entity.name == type_name)
+                    {
+                        Some(entity) => entity.clone(),
+                        None => {
+                            error!("Entity not found: {}", type_name);
+                            return Err(async_graphql::Error::new(format!(
+                                "Entity not found: {}",
+                                type_name
+                            )));
+                        }
+                    };
+
+                    // Load the entity by every primary key field, which may be composite.
+                    let mut query = Document::new();
+                    for field in ServiceEntityConfig::get_primary_key_fields(&entity)? {
+                        match representation.get(&field.name) {
+                            Some(value) => {
+                                query.insert(field.name.clone(), value.clone());
+                            }
+                            None => {
+                                error!("Representation is missing key field: {}", field.name);
+                                return Err(async_graphql::Error::new(format!(
+                                    "Representation is missing key field: {}",
+                                    field.name
+                                )));
+                            }
+                        }
+                    }
+                    let input_document = doc! { "query": query };
+
+                    ServiceResolver::guard_resolver_function(
+                        vec![],
+                        &input_document,
+                        &entity,
+                        service_guards.clone(),
+                        &ResolverType::FindOne,
+                        headers.clone(),
+                        &token_data,
+                        &data_sources,
+                        &subgraph_config,
+                    )
+                    .await?;
+
+                    let results = DataSources::execute(
+                        &data_sources,
+                        input_document,
+                        entity.clone(),
+                        ResolverType::FindOne,
+                        &subgraph_config,
+                        &token_data,
+                        &headers,
+                        true,
+                    )
+                    .await?;
+
+                    entities.push(ServiceSchema::get_entity_value(results, &entity.name)?);
+                }
+
+                Ok(Some(FieldValue::list(entities)))
+            })
+        });
+        self
+    }
+
+    /// Takes the loaded record out of a Find One response, typed as the entity.
+    /// Entities can not resolve as null, so a missing record is an error.
+    fn get_entity_value<'a>(
+        results: Option<FieldValue>,
+        type_name: &str,
+    ) -> Result<FieldValue<'a>, async_graphql::Error> {
+        let value = match &results {
+            Some(results) => results
+                .try_downcast_ref::<ResolverResponse>()?
+                .data
+                .first()
+                .and_then(|value| {
+                    if let Ok(row) = value.try_downcast_ref::<Option<ResponseRow>>() {
+                        row.clone().map(|row| FieldValue::owned_any(Some(row)))
+                    } else if let Ok(document) = value.try_downcast_ref::<Option<Document>>() {
+                        document
+                            .clone()
+                            .map(|document| FieldValue::owned_any(Some(document)))
+                    } else if let Ok(json) = value.try_downcast_ref::<JsonValue>() {
+                        (!json.is_null()).then(|| FieldValue::owned_any(json.clone()))
+                    } else {
+                        None
+                    }
+                }),
+            None => None,
+        };
+
+        match value {
+            Some(value) => Ok(value.with_type(type_name.to_string())),
+            None => {
+                error!("Entity not found: {}", type_name);
+                Err(async_graphql::Error::new(format!(
+                    "Entity not found: {}",
+                    type_name
+                )))
+            }
+        }
+    }
+}
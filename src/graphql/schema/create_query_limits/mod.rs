@@ -0,0 +1,254 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use async_graphql::{
+    extensions::{Extension, ExtensionContext, ExtensionFactory, NextParseQuery},
+    parser::types::{ExecutableDocument, OperationType, Selection, SelectionSet},
+    registry::{MetaTypeName, Registry},
+    Name, ServerError, ServerResult, Value, Variables,
+};
+use log::{debug, error, trace};
+
+use crate::resolver_type::ResolverType;
+
+use super::ServiceSchema;
+
+/// The number of results assumed for lists without a `per_page` option.
+const DEFAULT_PER_PAGE: usize = 10;
+
+impl ServiceSchema {
+    /// Applies the `max_depth` and `max_complexity` limits of the service.
+    pub fn create_query_limits(mut self) -> Self {
+        debug!("Creating Query Limits");
+
+        if let Some(max_depth) = self.subgraph_config.service.max_depth {
+            trace!("Max Depth: {}", max_depth);
+            self.schema_builder = self.schema_builder.limit_depth(max_depth);
+        }
+
+        if let Some(max_complexity) = self.subgraph_config.service.max_complexity {
+            trace!("Max Complexity: {}", max_complexity);
+            let mut costs = HashMap::new();
+            for entity in self.subgraph_config.service.entities.iter() {
+                for field in entity.fields.iter() {
+                    if let Some(cost) = field.cost {
+                        costs.insert((entity.name.clone(), field.name.clone()), cost);
+                    }
+                }
+            }
+            self.schema_builder = self.schema_builder.extension(ComplexityLimit {
+                max_complexity,
+                costs: Arc::new(costs),
+            });
+        }
+
+        self
+    }
+}
+
+/// Rejects queries whose complexity exceeds `max_complexity` before they are executed. Each
+/// field costs 1 unless a `cost` is configured for it, and the fields selected from the
+/// results of a list are multiplied by its `per_page` option.
+#[derive(Clone)]
+pub struct ComplexityLimit {
+    max_complexity: usize,
+    costs: Arc<HashMap<(String, String), usize>>,
+}
+
+impl ExtensionFactory for ComplexityLimit {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(self.clone())
+    }
+}
+
+#[async_graphql::async_trait::async_trait]
+impl Extension for ComplexityLimit {
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        let document = next.run(ctx, query, variables).await?;
+
+        for (_name, operation) in document.operations.iter() {
+            let root_type = match operation.node.ty {
+                OperationType::Query => "Query",
+                OperationType::Mutation => "Mutation",
+                OperationType::Subscription => "Subscription",
+            };
+            let complexity = self.get_complexity(
+                &ComplexityQuery {
+                    registry: &ctx.schema_env.registry,
+                    document: &document,
+                    variables,
+                },
+                root_type,
+                &operation.node.selection_set.node,
+                1,
+                &mut HashSet::new(),
+            );
+            trace!("Query Complexity: {}", complexity);
+
+            if complexity > self.max_complexity {
+                error!(
+                    "Query complexity of {} exceeds the maximum of {}.",
+                    complexity, self.max_complexity
+                );
+                return Err(ServerError::new(
+                    format!(
+                        "Query complexity of {} exceeds the maximum of {}.",
+                        complexity, self.max_complexity
+                    ),
+                    Some(operation.pos),
+                ));
+            }
+        }
+
+        Ok(document)
+    }
+}
+
+/// The query of which the complexity is calculated.
+struct ComplexityQuery<'a> {
+    registry: &'a Registry,
+    document: &'a ExecutableDocument,
+    variables: &'a Variables,
+}
+
+impl ComplexityLimit {
+    /// Calculates the complexity of a selection set of the provided type. `list_size` is the
+    /// number of results of the list the selection set is resolved for.
+    fn get_complexity(
+        &self,
+        query: &ComplexityQuery,
+        type_name: &str,
+        selection_set: &SelectionSet,
+        list_size: usize,
+        fragments: &mut HashSet<Name>,
+    ) -> usize {
+        let mut complexity: usize = 0;
+
+        for selection in selection_set.items.iter() {
+            let selection_complexity = match &selection.node {
+                Selection::Field(field) => {
+                    let field = &field.node;
+                    let field_name = field.name.node.as_str();
+                    let cost = self
+                        .costs
+                        .get(&(type_name.to_string(), field_name.to_string()))
+                        .copied()
+                        .unwrap_or(1);
+
+                    let field_type = query
+                        .registry
+                        .concrete_type_by_name(type_name)
+                        .and_then(|meta_type| meta_type.field_by_name(field_name))
+                        .map(|meta_field| MetaTypeName::concrete_typename(&meta_field.ty));
+                    let field_type = match field_type {
+                        Some(field_type) => field_type,
+                        None => {
+                            complexity = complexity.saturating_add(cost);
+                            continue;
+                        }
+                    };
+
+                    let is_list_response = type_name.starts_with(&format!(
+                        "{}_",
+                        ResolverType::FindMany.to_string().to_lowercase()
+                    ));
+                    let returns_list_response = field_type.starts_with(&format!(
+                        "{}_",
+                        ResolverType::FindMany.to_string().to_lowercase()
+                    ));
+
+                    let (multiplier, child_list_size) = if is_list_response && field_name == "data"
+                    {
+                        (list_size, 1)
+                    } else if returns_list_response {
+                        (1, self.get_per_page(field, query.variables))
+                    } else {
+                        (1, 1)
+                    };
+
+                    let children = self.get_complexity(
+                        query,
+                        field_type,
+                        &field.selection_set.node,
+                        child_list_size,
+                        fragments,
+                    );
+                    cost.saturating_add(children.saturating_mul(multiplier))
+                }
+                Selection::FragmentSpread(fragment_spread) => {
+                    let fragment_name = &fragment_spread.node.fragment_name.node;
+                    // Cyclic fragments are rejected during validation.
+                    if !fragments.insert(fragment_name.clone()) {
+                        continue;
+                    }
+                    let selection_complexity = match query.document.fragments.get(fragment_name) {
+                        Some(fragment) => self.get_complexity(
+                            query,
+                            fragment.node.type_condition.node.on.node.as_str(),
+                            &fragment.node.selection_set.node,
+                            list_size,
+                            fragments,
+                        ),
+                        None => 0,
+                    };
+                    fragments.remove(fragment_name);
+                    selection_complexity
+                }
+                Selection::InlineFragment(inline_fragment) => {
+                    let fragment_type = match &inline_fragment.node.type_condition {
+                        Some(type_condition) => type_condition.node.on.node.as_str(),
+                        None => type_name,
+                    };
+                    self.get_complexity(
+                        query,
+                        fragment_type,
+                        &inline_fragment.node.selection_set.node,
+                        list_size,
+                        fragments,
+                    )
+                }
+            };
+            complexity = complexity.saturating_add(selection_complexity);
+        }
+
+        complexity
+    }
+
+    /// Gets the `per_page` option from the input of a field. Lists without a limit, `-1`, are
+    /// counted as unbounded.
+    fn get_per_page(
+        &self,
+        field: &async_graphql::parser::types::Field,
+        variables: &Variables,
+    ) -> usize {
+        for (_name, argument) in field.arguments.iter() {
+            let argument = argument
+                .node
+                .clone()
+                .into_const_with(|name| variables.get(&name).cloned().ok_or(()));
+            let per_page = match argument {
+                Ok(Value::Object(input)) => match input.get("opts") {
+                    Some(Value::Object(opts)) => opts.get("per_page").cloned(),
+                    _ => None,
+                },
+                _ => None,
+            };
+            if let Some(Value::Number(per_page)) = per_page {
+                return match per_page.as_i64() {
+                    Some(per_page) if per_page < 0 => usize::MAX,
+                    Some(per_page) => per_page as usize,
+                    None => DEFAULT_PER_PAGE,
+                };
+            }
+        }
+        DEFAULT_PER_PAGE
+    }
+}
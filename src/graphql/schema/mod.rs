@@ -4,11 +4,18 @@ use async_graphql::dynamic::{
 use biscuit_auth::KeyPair;
 use log::{debug, error, trace};
 
-use crate::{configuration::subgraph::SubGraphConfig, data_sources::DataSources};
+use crate::{
+    configuration::subgraph::SubGraphConfig, data_sources::DataSources,
+    graphql::join_loader::JoinLoaderExtension,
+};
 
 pub mod create_auth_service;
+pub mod create_custom_mutations;
+pub mod create_data_source_health;
 pub mod create_entities;
+pub mod create_entity_resolver;
 pub mod create_options_input;
+pub mod create_query_limits;
 
 pub struct ServiceSchema {
     pub subgraph_config: SubGraphConfig,
@@ -50,7 +57,7 @@ impl ServiceSchema {
         self
     }
 
-    pub fn build(mut self) -> Schema {
+    pub fn build(mut self) -> Result<Schema, async_graphql::Error> {
         debug!("Building Schema");
 
         // Check for key pair and create if needed
@@ -60,16 +67,33 @@ impl ServiceSchema {
         self = self.create_options_input();
 
         // Create entities
-        self = self.create_entities();
+        self = self.create_entities()?;
+
+        // Resolve federation entity references by their keys
+        self = self.create_entity_resolver();
+
+        // Create custom mutations
+        self = self.create_custom_mutations()?;
 
         // Create Health Check
         self = self.register_health_check();
+        if self
+            .subgraph_config
+            .service
+            .data_source_health
+            .unwrap_or(false)
+        {
+            self = self.create_data_source_health();
+        }
 
         // Create auth service
         if self.subgraph_config.service.auth.is_some() {
             self = self.create_auth_service();
         }
 
+        // Limit query depth and complexity
+        self = self.create_query_limits();
+
         // List scalars
         let object_id = Scalar::new("ObjectID");
 
@@ -78,6 +102,7 @@ impl ServiceSchema {
             .schema_builder
             .data(self.data_sources.clone())
             .data(self.key_pair)
+            .extension(JoinLoaderExtension)
             .enable_federation()
             .register(object_id)
             .register(self.query)
@@ -87,10 +112,13 @@ impl ServiceSchema {
         trace!("{:?}", schema);
 
         match schema {
-            Ok(sch) => sch,
+            Ok(sch) => Ok(sch),
             Err(err) => {
-                error!("{}", err);
-                panic!("Failed to build schema.")
+                error!("Failed to build schema: {}", err);
+                Err(async_graphql::Error::new(format!(
+                    "Failed to build schema: {}",
+                    err
+                )))
             }
         }
     }
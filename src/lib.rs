@@ -19,8 +19,29 @@ pub mod resolver_type;
 pub mod scalar_option;
 pub mod sql_value;
 pub mod traits;
+pub mod update_operator;
 pub mod utils;
 
+/// Runs the `--migrate` command against the SQL Data Sources without starting the service.
+pub async fn migrate(
+    args: cli_args::CliArgs,
+    subgraph_config: SubGraphConfig,
+) -> Result<(), std::io::Error> {
+    info!("⛵ Running Subgraph Migrations");
+    trace!("Service Arguments: {:?}", args);
+
+    data_sources::DataSources::migrate(
+        subgraph_config.service.data_sources.clone(),
+        &args,
+        &subgraph_config,
+    )
+    .await
+    .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.message))?;
+
+    info!("❇️  Subgraph Migrations Complete");
+    Ok(())
+}
+
 /// Starts the Subgraph Service. Initializes the DataSources and builds the GraphQL Schema.
 pub async fn run(
     args: cli_args::CliArgs,
@@ -42,10 +63,13 @@ pub async fn run(
         &args,
         &subgraph_config,
     )
-    .await;
+    .await
+    .map_err(|e| std::io::Error::other(e.message))?;
 
     // Build GraphQL Schema
-    let schema = graphql::schema::ServiceSchema::new(subgraph_config.clone(), data_sources).build();
+    let schema = graphql::schema::ServiceSchema::new(subgraph_config.clone(), data_sources)
+        .build()
+        .map_err(|e| std::io::Error::other(e.message))?;
 
     // GraphQL Endpoint at /graphql
     let graphql_post = warp::path("graphql")
@@ -43,6 +43,17 @@ async fn main() -> Result<(), std::io::Error> {
     // Initialize the logger
     utils::logger::Logger::init(&args, &subgraph_config);
 
+    // If migrate only, handle the migrate command and exit without starting the server.
+    if args.migrate_only {
+        match subgraph::migrate(args.clone(), subgraph_config).await {
+            Ok(_) => return Ok(()),
+            Err(e) => {
+                error!("Migration Error: {}", e);
+                exit(1);
+            }
+        }
+    }
+
     // Verify the license key
     let license_key = subgraph_config.service.license_key.clone();
 
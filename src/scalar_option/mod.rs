@@ -9,8 +9,9 @@ pub mod to_input_type_ref;
 pub mod to_mongo;
 pub mod to_nn_type_ref;
 pub mod to_nullable_type_ref;
+pub mod to_sql_type;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum ScalarOption {
     String,
     Int,
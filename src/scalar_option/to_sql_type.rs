@@ -0,0 +1,54 @@
+use log::debug;
+
+use crate::configuration::subgraph::data_sources::sql::DialectEnum;
+
+use super::ScalarOption;
+
+impl ScalarOption {
+    /// Get the column type used to store the scalar in the provided SQL dialect.
+    pub fn to_sql_type(&self, dialect: &DialectEnum, is_list: bool) -> String {
+        debug!("Converting {:?} to SQL Type", self);
+
+        let sql_type = match dialect {
+            DialectEnum::POSTGRES => match self {
+                ScalarOption::String | ScalarOption::Enum => "TEXT",
+                ScalarOption::Int => "INTEGER",
+                ScalarOption::Boolean => "BOOLEAN",
+                ScalarOption::ObjectID => "VARCHAR(24)",
+                ScalarOption::Object => "JSONB",
+                ScalarOption::UUID => "UUID",
+                ScalarOption::DateTime => "TIMESTAMP WITH TIME ZONE",
+            },
+            DialectEnum::MYSQL => match self {
+                ScalarOption::String | ScalarOption::Enum => "VARCHAR(255)",
+                ScalarOption::Int => "INT",
+                ScalarOption::Boolean => "BOOLEAN",
+                ScalarOption::ObjectID => "CHAR(24)",
+                ScalarOption::Object => "JSON",
+                ScalarOption::UUID => "CHAR(36)",
+                ScalarOption::DateTime => "DATETIME",
+            },
+            DialectEnum::SQLITE => match self {
+                ScalarOption::String
+                | ScalarOption::Enum
+                | ScalarOption::ObjectID
+                | ScalarOption::Object
+                | ScalarOption::UUID => "TEXT",
+                ScalarOption::Int => "INTEGER",
+                ScalarOption::Boolean => "BOOLEAN",
+                ScalarOption::DateTime => "TIMESTAMP",
+            },
+        };
+
+        // Lists are stored as native arrays in postgres and as JSON elsewhere.
+        if is_list {
+            return match dialect {
+                DialectEnum::POSTGRES => format!("{}[]", sql_type),
+                DialectEnum::MYSQL => "JSON".to_string(),
+                DialectEnum::SQLITE => "TEXT".to_string(),
+            };
+        }
+
+        sql_type.to_string()
+    }
+}
@@ -1,4 +1,4 @@
-use crate::configuration::subgraph::data_sources::sql::DialectEnum;
+use crate::{configuration::subgraph::data_sources::sql::DialectEnum, scalar_option::ScalarOption};
 
 mod to_sql_value;
 
@@ -6,6 +6,7 @@ mod to_sql_value;
 pub enum SqlValue {
     String(String),
     Int(i32),
+    BigInt(i64),
     Bool(bool),
     StringList(Vec<String>),
     IntList(Vec<i32>),
@@ -16,6 +17,13 @@ pub enum SqlValue {
     DateTimeList(Vec<chrono::DateTime<chrono::Utc>>),
     ObjectID(String),
     ObjectIDList(Vec<String>),
+    /// A list stored in a single column. Wraps one of the list values.
+    /// Postgres binds it as a native array, MySQL and SQLite bind each element.
+    Array(Box<SqlValue>),
+    /// A JSON document stored in a text or JSON column, bound as is.
+    Json(String),
+    /// A null value, bound with the type of the scalar it stands in for.
+    Null(ScalarOption),
 }
 
 pub trait FromBson {
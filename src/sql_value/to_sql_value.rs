@@ -34,7 +34,10 @@ impl FromBson for Bson {
                 value
             }
             Bson::Int32(i) => SqlValue::Int(*i),
-            Bson::Int64(i) => SqlValue::Int(*i as i32),
+            Bson::Int64(i) => match i32::try_from(*i) {
+                Ok(i) => SqlValue::Int(i),
+                Err(_) => SqlValue::BigInt(*i),
+            },
             Bson::Boolean(b) => SqlValue::Bool(*b),
             Bson::ObjectId(o) => SqlValue::ObjectID(o.to_string()),
             Bson::DateTime(d) => SqlValue::DateTime(chrono::DateTime::<chrono::Utc>::from(*d)),
@@ -13,10 +13,13 @@ impl FromJson for json::JsonValue {
             json::JsonValue::String(s) => async_graphql::Value::String(s.to_string()),
             json::JsonValue::Null => async_graphql::Value::Null,
             json::JsonValue::Short(s) => async_graphql::Value::String(s.to_string()),
-            json::JsonValue::Number(n) => {
-                let num: f64 = n.clone().into();
-                async_graphql::Value::from(num)
-            }
+            json::JsonValue::Number(n) => match n.as_fixed_point_i64(0) {
+                Some(num) => async_graphql::Value::from(num),
+                None => {
+                    let num: f64 = (*n).into();
+                    async_graphql::Value::from(num)
+                }
+            },
             json::JsonValue::Boolean(b) => async_graphql::Value::Boolean(*b),
             json::JsonValue::Array(a) => async_graphql::Value::List(
                 a.iter()
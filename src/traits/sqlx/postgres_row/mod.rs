@@ -61,6 +61,31 @@ impl FromPostgresRow for PgRow {
                     let value: Option<bool> = self.try_get(column_name)?;
                     document.insert(column_name, value);
                 }
+                "TEXT[]" | "VARCHAR[]" => {
+                    let value: Option<Vec<String>> = self.try_get(column_name)?;
+                    document.insert(column_name, value);
+                }
+                "INT4[]" => {
+                    let value: Option<Vec<i32>> = self.try_get(column_name)?;
+                    document.insert(column_name, value);
+                }
+                "INT8[]" => {
+                    let value: Option<Vec<i64>> = self.try_get(column_name)?;
+                    document.insert(column_name, value);
+                }
+                "BOOL[]" => {
+                    let value: Option<Vec<bool>> = self.try_get(column_name)?;
+                    document.insert(column_name, value);
+                }
+                "UUID[]" => {
+                    let value: Option<Vec<uuid::Uuid>> = self.try_get(column_name)?;
+                    if let Some(value) = value {
+                        document.insert(
+                            column_name,
+                            value.iter().map(|v| v.to_string()).collect::<Vec<String>>(),
+                        );
+                    }
+                }
 
                 _ => {
                     error!("Unsupported Column Type: {}", column_type);
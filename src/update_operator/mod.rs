@@ -0,0 +1,94 @@
+use log::{debug, trace};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    configuration::subgraph::entities::service_entity_field::ServiceEntityFieldConfig,
+    data_sources::DataSource, scalar_option::ScalarOption,
+};
+
+/// Operators of update resolvers, applied by the data source in the same write as the values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum UpdateOperator {
+    #[serde(rename = "inc")]
+    Inc,
+    #[serde(rename = "push")]
+    Push,
+    #[serde(rename = "pull")]
+    Pull,
+    #[serde(rename = "add_to_set")]
+    AddToSet,
+    #[serde(rename = "unset")]
+    Unset,
+}
+
+impl UpdateOperator {
+    pub fn as_str(&self) -> &str {
+        match self {
+            UpdateOperator::Inc => "inc",
+            UpdateOperator::Push => "push",
+            UpdateOperator::Pull => "pull",
+            UpdateOperator::AddToSet => "add_to_set",
+            UpdateOperator::Unset => "unset",
+        }
+    }
+
+    /// Returns a list of all available update operators.
+    pub fn list() -> Vec<UpdateOperator> {
+        debug!("Listing Available Update Operators");
+        let list = vec![
+            UpdateOperator::Inc,
+            UpdateOperator::Push,
+            UpdateOperator::Pull,
+            UpdateOperator::AddToSet,
+            UpdateOperator::Unset,
+        ];
+        trace!("{:?}", list);
+        list
+    }
+
+    pub fn get_mongo_operator(&self) -> &str {
+        match self {
+            UpdateOperator::Inc => "$inc",
+            UpdateOperator::Push => "$push",
+            UpdateOperator::Pull => "$pull",
+            UpdateOperator::AddToSet => "$addToSet",
+            UpdateOperator::Unset => "$unset",
+        }
+    }
+
+    /// Whether the operator can be applied to the field by the data source.
+    /// SQL data sources increment and unset columns, list operators are only applied by Mongo.
+    pub fn supports_field(
+        &self,
+        field: &ServiceEntityFieldConfig,
+        entity_data_source: &DataSource,
+    ) -> bool {
+        let is_stored = !field.is_virtual.unwrap_or(false)
+            && field.as_type.is_none()
+            && field.join_through.is_none()
+            && !field.primary_key.unwrap_or(false);
+        if !is_stored {
+            return false;
+        }
+
+        let is_list = field.list.unwrap_or(false);
+        match (self, entity_data_source) {
+            (_, DataSource::HTTP(_)) => false,
+            (UpdateOperator::Inc, _) => !is_list && matches!(field.scalar, ScalarOption::Int),
+            (UpdateOperator::Push | UpdateOperator::Pull | UpdateOperator::AddToSet, ds) => {
+                matches!(ds, DataSource::Mongo(_))
+                    && is_list
+                    && matches!(
+                        field.scalar,
+                        ScalarOption::String
+                            | ScalarOption::Int
+                            | ScalarOption::Boolean
+                            | ScalarOption::ObjectID
+                            | ScalarOption::UUID
+                            | ScalarOption::DateTime
+                    )
+            }
+            (UpdateOperator::Unset, _) => !field.required.unwrap_or(false),
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use std::net::SocketAddr;
+
+use async_graphql::dynamic::Schema;
+use http::{HeaderMap, HeaderValue};
+use serde_json::{json, Value};
+use subgraph::{cli_args::CliArgs, configuration::subgraph::SubGraphConfig};
+use warp::Filter;
+
+/// Serves an item echoing the headers of the request.
+fn spawn_mock_server() -> SocketAddr {
+    let echo = warp::path!("echo" / "items")
+        .and(warp::get())
+        .and(warp::header::headers_cloned())
+        .map(|headers: HeaderMap| {
+            let get_header = |name: &str| match headers.get(name) {
+                Some(value) => json!(value.to_str().unwrap()),
+                None => Value::Null,
+            };
+            warp::reply::json(&json!([{
+                "id": 1,
+                "authorization": get_header("authorization"),
+                "locale": get_header("x-locale"),
+                "request_id": get_header("x-request-id"),
+                "service": get_header("x-service"),
+                "user": get_header("x-user-id"),
+                "cookie": get_header("cookie"),
+            }]))
+        });
+
+    let (addr, server) = warp::serve(echo).bind_ephemeral(([127, 0, 0, 1], 0));
+    tokio::spawn(server);
+    addr
+}
+
+async fn spawn_forward_headers_app(addr: SocketAddr) -> Schema {
+    let config = format!(
+        r#"
+        [service]
+        name = "http_forward_headers"
+
+        [[service.data_sources]]
+        [service.data_sources.HTTP]
+        name = "api"
+        url = "http://{addr}"
+        forward_headers = [
+          {{ name = "authorization" }},
+          {{ name = "accept-language", rename = "x-locale" }},
+          {{ name = "x-request-id", value_expr = "headers(\"x-request-id\") + \"-upstream\"" }},
+          {{ name = "x-service", value = "subgraph" }},
+          {{ name = "x-user-id", value_expr = "token_data(\"user_uuid\")" }},
+        ]
+
+        [[service.entities]]
+        name = "echo_item"
+        fields = [
+          {{ name = "id", scalar = "Int", required = true }},
+          {{ name = "authorization", scalar = "String" }},
+          {{ name = "locale", scalar = "String" }},
+          {{ name = "request_id", scalar = "String" }},
+          {{ name = "service", scalar = "String" }},
+          {{ name = "user", scalar = "String" }},
+          {{ name = "cookie", scalar = "String" }},
+        ]
+        data_source = {{ from = "api", path = "/echo/items" }}
+        "#
+    );
+    let subgraph_config = toml::from_str::<SubGraphConfig>(&config).unwrap();
+
+    let args = CliArgs {
+        config: None,
+        port: Some(0),
+        log_level: None,
+        generate_keypair: false,
+        migrate: None,
+        steps: None,
+        migration_name: None,
+        migrate_only: false,
+        watch: false,
+        host: false,
+    };
+    let (server, schema, _shutdown) = subgraph::run(args, subgraph_config)
+        .await
+        .expect("Failed to run server.");
+    let _ = tokio::spawn(server);
+    schema
+}
+
+#[tokio::test]
+async fn forwards_allowed_headers() {
+    let addr = spawn_mock_server();
+    let schema = spawn_forward_headers_app(addr).await;
+
+    let mut headers = HeaderMap::new();
+    headers.insert("authorization", HeaderValue::from_static("Bearer abc"));
+    headers.insert("accept-language", HeaderValue::from_static("de-CH"));
+    headers.insert("x-request-id", HeaderValue::from_static("req-1"));
+    headers.insert("cookie", HeaderValue::from_static("session=secret"));
+
+    let response = schema
+        .execute(
+            async_graphql::Request::new(
+                "{ get_echo_items(get_echo_items_input: { query: {} }) { data { authorization locale request_id service user cookie } } }",
+            )
+            .data(headers),
+        )
+        .await;
+    assert!(response.is_ok(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(
+        data["get_echo_items"]["data"][0],
+        json!({
+            "authorization": "Bearer abc",
+            "locale": "de-CH",
+            "request_id": "req-1-upstream",
+            "service": "subgraph",
+            "user": null,
+            "cookie": null,
+        })
+    );
+}
@@ -1,5 +1,10 @@
 mod create_one;
 mod find_many;
 mod find_one;
+mod forward_headers;
+mod pagination;
+mod resilience;
+mod response_cache;
+mod response_mapping;
 mod update_many;
 mod update_one;
@@ -0,0 +1,369 @@
+use std::{collections::HashMap, net::SocketAddr};
+
+use async_graphql::dynamic::Schema;
+use http::HeaderMap;
+use serde_json::{json, Value};
+use subgraph::{cli_args::CliArgs, configuration::subgraph::SubGraphConfig};
+use warp::Filter;
+
+const TOTAL_ITEMS: i64 = 25;
+
+/// The items from `start` with at most `size` items, sorted by descending id with `-id`.
+fn get_items(start: i64, size: i64, sort: Option<&String>) -> Vec<Value> {
+    let mut ids = (1..=TOTAL_ITEMS).collect::<Vec<i64>>();
+    if sort.is_some_and(|sort| sort == "-id") {
+        ids.reverse();
+    }
+    ids.into_iter()
+        .skip(start as usize)
+        .take(size as usize)
+        .map(|id| json!({ "id": id, "name": format!("Item {}", id) }))
+        .collect()
+}
+
+fn get_param(params: &HashMap<String, String>, name: &str, default: i64) -> i64 {
+    params
+        .get(name)
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Serves 25 items paginated by page, offset, cursor and `Link` headers.
+fn spawn_mock_server() -> SocketAddr {
+    let paged = warp::path!("paged" / "items")
+        .and(warp::get())
+        .and(warp::query::<HashMap<String, String>>())
+        .map(|params: HashMap<String, String>| {
+            let per_page = get_param(&params, "per_page", 10);
+            let page = get_param(&params, "page", 1);
+            let items = get_items((page - 1) * per_page, per_page, params.get("sort"));
+            warp::reply::with_header(
+                warp::reply::json(&items),
+                "X-Total-Count",
+                TOTAL_ITEMS.to_string(),
+            )
+        });
+    let offset = warp::path!("offset" / "items")
+        .and(warp::get())
+        .and(warp::query::<HashMap<String, String>>())
+        .map(|params: HashMap<String, String>| {
+            let limit = get_param(&params, "limit", 10);
+            let offset = get_param(&params, "offset", 0);
+            warp::reply::json(&json!({
+                "items": get_items(offset, limit, None),
+                "meta": { "total": TOTAL_ITEMS }
+            }))
+        });
+    let cursor = warp::path!("cursor" / "items")
+        .and(warp::get())
+        .and(warp::query::<HashMap<String, String>>())
+        .map(|params: HashMap<String, String>| {
+            let start = get_param(&params, "after", 0);
+            let items = get_items(start, 5, None);
+            let next = start + 5;
+            let next = if next < TOTAL_ITEMS {
+                json!(next.to_string())
+            } else {
+                Value::Null
+            };
+            warp::reply::json(&json!({ "items": items, "next": next }))
+        });
+    let linked = warp::path!("linked" / "items")
+        .and(warp::get())
+        .and(warp::query::<HashMap<String, String>>())
+        .and(warp::host::optional())
+        .map(
+            |params: HashMap<String, String>, host: Option<warp::host::Authority>| {
+                let per_page = get_param(&params, "per_page", 10);
+                let page = get_param(&params, "page", 1);
+                let items = get_items((page - 1) * per_page, per_page, None);
+                let link = format!(
+                    "<http://{}/linked/items?page={}&per_page={}>; rel=\"next\"",
+                    host.unwrap(),
+                    page + 1,
+                    per_page
+                );
+                warp::reply::with_header(warp::reply::json(&items), "Link", link)
+            },
+        );
+
+    // Links to the same server under another host name, a different origin.
+    let foreign = warp::path!("foreign" / "items")
+        .and(warp::get())
+        .and(warp::query::<HashMap<String, String>>())
+        .and(warp::host::optional())
+        .map(
+            |params: HashMap<String, String>, host: Option<warp::host::Authority>| {
+                let per_page = get_param(&params, "per_page", 10);
+                let page = get_param(&params, "page", 1);
+                let items = get_items((page - 1) * per_page, per_page, None);
+                let link = format!(
+                    "<http://localhost:{}/foreign/items?page={}&per_page={}>; rel=\"next\"",
+                    host.unwrap().port_u16().unwrap(),
+                    page + 1,
+                    per_page
+                );
+                warp::reply::with_header(warp::reply::json(&items), "Link", link)
+            },
+        );
+
+    let routes = paged.or(offset).or(cursor).or(linked).or(foreign);
+    let (addr, server) = warp::serve(routes).bind_ephemeral(([127, 0, 0, 1], 0));
+    tokio::spawn(server);
+    addr
+}
+
+async fn spawn_pagination_app(addr: SocketAddr) -> Schema {
+    let fields = r#"fields = [
+          { name = "id", scalar = "Int", required = true, exclude_from_input = ["CreateOne", "UpdateOne", "UpdateMany"] },
+          { name = "name", scalar = "String", required = true },
+        ]"#;
+    let config = format!(
+        r#"
+        [service]
+        name = "http_pagination"
+
+        [[service.data_sources]]
+        [service.data_sources.HTTP]
+        name = "api"
+        url = "http://{addr}"
+
+        [[service.entities]]
+        name = "paged_item"
+        {fields}
+        [service.entities.data_source]
+        from = "api"
+        path = "/paged/items"
+        [service.entities.data_source.resolvers.find_many]
+        total_count_header = "X-Total-Count"
+        pagination = {{ sort_param = "sort", max_per_page = 5, max_follow_pages = 4 }}
+
+        [[service.entities]]
+        name = "offset_item"
+        {fields}
+        [service.entities.data_source]
+        from = "api"
+        path = "/offset/items"
+        [service.entities.data_source.resolvers.find_many]
+        records_path = "items"
+        total_count_path = "meta.total"
+        pagination = {{ style = "Offset", per_page_param = "limit" }}
+
+        [[service.entities]]
+        name = "cursor_item"
+        {fields}
+        [service.entities.data_source]
+        from = "api"
+        path = "/cursor/items"
+        [service.entities.data_source.resolvers.find_many]
+        records_path = "items"
+        pagination = {{ style = "Cursor", cursor_param = "after", next_cursor_path = "next", max_follow_pages = 3 }}
+
+        [[service.entities]]
+        name = "default_cursor_item"
+        {fields}
+        [service.entities.data_source]
+        from = "api"
+        path = "/cursor/items"
+        [service.entities.data_source.resolvers.find_many]
+        records_path = "items"
+        pagination = {{ style = "Cursor", cursor_param = "after", next_cursor_path = "next" }}
+
+        [[service.entities]]
+        name = "linked_item"
+        {fields}
+        [service.entities.data_source]
+        from = "api"
+        path = "/linked/items"
+        [service.entities.data_source.resolvers.find_many]
+        pagination = {{ style = "LinkHeader", max_per_page = 3, max_follow_pages = 2 }}
+
+        [[service.entities]]
+        name = "foreign_linked_item"
+        {fields}
+        [service.entities.data_source]
+        from = "api"
+        path = "/foreign/items"
+        [service.entities.data_source.resolvers.find_many]
+        pagination = {{ style = "LinkHeader", max_per_page = 3, max_follow_pages = 2 }}
+        "#
+    );
+    let subgraph_config = toml::from_str::<SubGraphConfig>(&config).unwrap();
+
+    let args = CliArgs {
+        config: None,
+        port: Some(0),
+        log_level: None,
+        generate_keypair: false,
+        migrate: None,
+        steps: None,
+        migration_name: None,
+        migrate_only: false,
+        watch: false,
+        host: false,
+    };
+    let (server, schema, _shutdown) = subgraph::run(args, subgraph_config)
+        .await
+        .expect("Failed to run server.");
+    let _ = tokio::spawn(server);
+    schema
+}
+
+async fn execute(schema: &Schema, request: &str) -> Value {
+    let response = schema
+        .execute(async_graphql::Request::new(request).data(HeaderMap::new()))
+        .await;
+    assert!(response.is_ok(), "{:?}", response.errors);
+    response.data.into_json().unwrap()
+}
+
+fn get_ids(data: &Value) -> Vec<i64> {
+    data["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["id"].as_i64().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn page_style_follows_pages() {
+    let addr = spawn_mock_server();
+    let schema = spawn_pagination_app(addr).await;
+
+    let data = execute(
+        &schema,
+        "{ get_paged_items(get_paged_items_input: { query: {}, opts: { page: 2, per_page: 10 } }) { data { id } meta { page total_count total_pages } } }",
+    )
+    .await;
+    let items = &data["get_paged_items"];
+    assert_eq!(get_ids(items), (11..=20).collect::<Vec<i64>>());
+    assert_eq!(items["meta"]["page"], 2);
+    assert_eq!(items["meta"]["total_count"], 25);
+    assert_eq!(items["meta"]["total_pages"], 3);
+}
+
+#[tokio::test]
+async fn page_style_passes_sort() {
+    let addr = spawn_mock_server();
+    let schema = spawn_pagination_app(addr).await;
+
+    let data = execute(
+        &schema,
+        "{ get_paged_items(get_paged_items_input: { query: {}, opts: { per_page: 3, sort: [{ field: \"id\", direction: DESC }] } }) { data { id } } }",
+    )
+    .await;
+    assert_eq!(get_ids(&data["get_paged_items"]), vec![25, 24, 23]);
+
+    let response = schema
+        .execute(
+            async_graphql::Request::new(
+                "{ get_paged_items(get_paged_items_input: { query: {}, opts: { sort: [{ field: \"missing\", direction: ASC }] } }) { data { id } } }",
+            )
+            .data(HeaderMap::new()),
+        )
+        .await;
+    assert_eq!(response.errors[0].message, "Invalid sort field: missing");
+}
+
+#[tokio::test]
+async fn offset_style() {
+    let addr = spawn_mock_server();
+    let schema = spawn_pagination_app(addr).await;
+
+    let data = execute(
+        &schema,
+        "{ get_offset_items(get_offset_items_input: { query: {}, opts: { page: 3, per_page: 5 } }) { data { id } meta { total_count total_pages } } }",
+    )
+    .await;
+    let items = &data["get_offset_items"];
+    assert_eq!(get_ids(items), (11..=15).collect::<Vec<i64>>());
+    assert_eq!(items["meta"]["total_count"], 25);
+    assert_eq!(items["meta"]["total_pages"], 5);
+}
+
+#[tokio::test]
+async fn cursor_style_walks_to_page() {
+    let addr = spawn_mock_server();
+    let schema = spawn_pagination_app(addr).await;
+
+    let data = execute(
+        &schema,
+        "{ get_cursor_items(get_cursor_items_input: { query: {}, opts: { page: 2, per_page: 5 } }) { data { id } } }",
+    )
+    .await;
+    assert_eq!(
+        get_ids(&data["get_cursor_items"]),
+        (6..=10).collect::<Vec<i64>>()
+    );
+}
+
+#[tokio::test]
+async fn cursor_style_walks_past_max_follow_pages() {
+    let addr = spawn_mock_server();
+    let schema = spawn_pagination_app(addr).await;
+
+    // Earlier cursor pages are walked regardless of the default of one followed page.
+    let data = execute(
+        &schema,
+        "{ get_default_cursor_items(get_default_cursor_items_input: { query: {}, opts: { page: 3, per_page: 5 } }) { data { id } } }",
+    )
+    .await;
+    assert_eq!(
+        get_ids(&data["get_default_cursor_items"]),
+        (11..=15).collect::<Vec<i64>>()
+    );
+}
+
+#[tokio::test]
+async fn cursor_style_rejects_pages_past_max_walk_pages() {
+    let addr = spawn_mock_server();
+    let schema = spawn_pagination_app(addr).await;
+
+    let response = schema
+        .execute(
+            async_graphql::Request::new(
+                "{ get_default_cursor_items(get_default_cursor_items_input: { query: {}, opts: { page: 100000, per_page: 5 } }) { data { id } } }",
+            )
+            .data(HeaderMap::new()),
+        )
+        .await;
+    assert_eq!(
+        response.errors[0].message,
+        "Page 100000 is past the 10 upstream pages which can be walked"
+    );
+}
+
+#[tokio::test]
+async fn link_header_style_follows_next() {
+    let addr = spawn_mock_server();
+    let schema = spawn_pagination_app(addr).await;
+
+    let data = execute(
+        &schema,
+        "{ get_linked_items(get_linked_items_input: { query: {}, opts: { per_page: 10 } }) { data { id } } }",
+    )
+    .await;
+    // Two upstream pages of three items are followed at most.
+    assert_eq!(
+        get_ids(&data["get_linked_items"]),
+        (1..=6).collect::<Vec<i64>>()
+    );
+}
+
+#[tokio::test]
+async fn link_header_style_only_follows_same_origin() {
+    let addr = spawn_mock_server();
+    let schema = spawn_pagination_app(addr).await;
+
+    let data = execute(
+        &schema,
+        "{ get_foreign_linked_items(get_foreign_linked_items_input: { query: {}, opts: { per_page: 10 } }) { data { id } } }",
+    )
+    .await;
+    // The `next` link points to another host, so only the first page is returned.
+    assert_eq!(
+        get_ids(&data["get_foreign_linked_items"]),
+        (1..=3).collect::<Vec<i64>>()
+    );
+}
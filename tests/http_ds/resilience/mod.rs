@@ -0,0 +1,270 @@
+use std::{
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use async_graphql::dynamic::Schema;
+use http::HeaderMap;
+use serde_json::json;
+use subgraph::{
+    cli_args::CliArgs,
+    configuration::subgraph::{data_sources::http::HttpCircuitBreakerConfig, SubGraphConfig},
+    data_sources::http::circuit_breaker::CircuitBreaker,
+};
+use warp::{http::StatusCode, Filter};
+
+#[derive(Default)]
+struct MockCounters {
+    flaky_gets: AtomicUsize,
+    flaky_posts: AtomicUsize,
+    down_gets: AtomicUsize,
+}
+
+/// Serves a slow endpoint, an endpoint failing twice before succeeding and an endpoint which
+/// always fails.
+fn spawn_mock_server(counters: Arc<MockCounters>) -> SocketAddr {
+    let slow_many = warp::path!("slow" / "items")
+        .and(warp::get())
+        .then(|| async {
+            tokio::time::sleep(Duration::from_millis(1000)).await;
+            warp::reply::with_status(warp::reply::json(&json!([])), StatusCode::OK)
+        });
+    let slow_one =
+        warp::path!("slow" / "items" / i32)
+            .and(warp::get())
+            .then(|id: i32| async move {
+                tokio::time::sleep(Duration::from_millis(500)).await;
+                warp::reply::with_status(
+                    warp::reply::json(&json!({ "id": id, "title": "Slow" })),
+                    StatusCode::OK,
+                )
+            });
+
+    let flaky_counters = counters.clone();
+    let flaky_many = warp::path!("flaky" / "items")
+        .and(warp::get())
+        .then(move || {
+            let counters = flaky_counters.clone();
+            async move {
+                if counters.flaky_gets.fetch_add(1, Ordering::SeqCst) < 2 {
+                    return warp::reply::with_status(
+                        warp::reply::json(&json!({})),
+                        StatusCode::SERVICE_UNAVAILABLE,
+                    );
+                }
+                warp::reply::with_status(
+                    warp::reply::json(&json!([{ "id": 1, "title": "Flaky" }])),
+                    StatusCode::OK,
+                )
+            }
+        });
+    let flaky_counters = counters.clone();
+    let flaky_create = warp::path!("flaky" / "items")
+        .and(warp::post())
+        .then(move || {
+            let counters = flaky_counters.clone();
+            async move {
+                counters.flaky_posts.fetch_add(1, Ordering::SeqCst);
+                warp::reply::with_status(
+                    warp::reply::json(&json!({})),
+                    StatusCode::SERVICE_UNAVAILABLE,
+                )
+            }
+        });
+
+    let down_counters = counters.clone();
+    let down_many = warp::path!("down" / "items")
+        .and(warp::get())
+        .then(move || {
+            let counters = down_counters.clone();
+            async move {
+                counters.down_gets.fetch_add(1, Ordering::SeqCst);
+                warp::reply::with_status(
+                    warp::reply::json(&json!({})),
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                )
+            }
+        });
+
+    let routes = slow_many
+        .or(slow_one)
+        .or(flaky_many)
+        .or(flaky_create)
+        .or(down_many);
+    let (addr, server) = warp::serve(routes).bind_ephemeral(([127, 0, 0, 1], 0));
+    tokio::spawn(server);
+    addr
+}
+
+async fn spawn_resilience_app(addr: SocketAddr) -> Schema {
+    let config = format!(
+        r#"
+        [service]
+        name = "http_resilience"
+
+        [[service.data_sources]]
+        [service.data_sources.HTTP]
+        name = "slow_api"
+        url = "http://{addr}"
+        request_timeout = 200
+
+        [[service.data_sources]]
+        [service.data_sources.HTTP]
+        name = "flaky_api"
+        url = "http://{addr}"
+        retry = {{ max_retries = 3, backoff = 10 }}
+
+        [[service.data_sources]]
+        [service.data_sources.HTTP]
+        name = "down_api"
+        url = "http://{addr}"
+        circuit_breaker = {{ failure_threshold = 2, reset_timeout = 60000 }}
+
+        [[service.entities]]
+        name = "slow_item"
+        fields = [
+          {{ name = "id", scalar = "Int", required = true, exclude_from_input = ["CreateOne", "UpdateOne", "UpdateMany"] }},
+          {{ name = "title", scalar = "String", required = true }},
+        ]
+        [service.entities.data_source]
+        from = "slow_api"
+        path = "/slow/items"
+        [service.entities.data_source.resolvers.find_one]
+        path = "/:id"
+        request_timeout = 5000
+
+        [[service.entities]]
+        name = "flaky_item"
+        data_source = {{ from = "flaky_api", path = "/flaky/items" }}
+        fields = [
+          {{ name = "id", scalar = "Int", required = true, exclude_from_input = ["CreateOne", "UpdateOne", "UpdateMany"] }},
+          {{ name = "title", scalar = "String", required = true }},
+        ]
+
+        [[service.entities]]
+        name = "down_item"
+        data_source = {{ from = "down_api", path = "/down/items" }}
+        fields = [
+          {{ name = "id", scalar = "Int", required = true, exclude_from_input = ["CreateOne", "UpdateOne", "UpdateMany"] }},
+          {{ name = "title", scalar = "String", required = true }},
+        ]
+        "#
+    );
+    let subgraph_config = toml::from_str::<SubGraphConfig>(&config).unwrap();
+
+    let args = CliArgs {
+        config: None,
+        port: Some(0),
+        log_level: None,
+        generate_keypair: false,
+        migrate: None,
+        steps: None,
+        migration_name: None,
+        migrate_only: false,
+        watch: false,
+        host: false,
+    };
+    let (server, schema, _shutdown) = subgraph::run(args, subgraph_config)
+        .await
+        .expect("Failed to run server.");
+    let _ = tokio::spawn(server);
+    schema
+}
+
+async fn execute(schema: &Schema, request: &str) -> async_graphql::Response {
+    schema
+        .execute(async_graphql::Request::new(request).data(HeaderMap::new()))
+        .await
+}
+
+#[tokio::test]
+async fn request_timeout_per_resolver() {
+    let addr = spawn_mock_server(Arc::new(MockCounters::default()));
+    let schema = spawn_resilience_app(addr).await;
+
+    let response = execute(
+        &schema,
+        "{ get_slow_items(get_slow_items_input: { query: {} }) { data { id } } }",
+    )
+    .await;
+    assert_eq!(response.errors[0].message, "HTTP Request Timed Out");
+
+    // The find one resolver waits longer than the data source.
+    let response = execute(
+        &schema,
+        "{ get_slow_item(get_slow_item_input: { query: { id: 1 } }) { data { title } } }",
+    )
+    .await;
+    assert!(response.is_ok(), "{:?}", response.errors);
+}
+
+#[tokio::test]
+async fn retries_idempotent_requests() {
+    let counters = Arc::new(MockCounters::default());
+    let addr = spawn_mock_server(counters.clone());
+    let schema = spawn_resilience_app(addr).await;
+
+    let response = execute(
+        &schema,
+        "{ get_flaky_items(get_flaky_items_input: { query: {} }) { data { title } } }",
+    )
+    .await;
+    assert!(response.is_ok(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["get_flaky_items"]["data"].as_array().unwrap().len(), 1);
+    assert_eq!(counters.flaky_gets.load(Ordering::SeqCst), 3);
+
+    // Creating is not idempotent, so it is sent once.
+    let response = execute(
+        &schema,
+        r#"mutation { create_flaky_item(create_flaky_item_input: { values: { title: "New" } }) { data { id } } }"#,
+    )
+    .await;
+    assert_eq!(response.errors[0].message, "HTTP Request Failed");
+    assert_eq!(counters.flaky_posts.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn circuit_breaker_fails_fast() {
+    let counters = Arc::new(MockCounters::default());
+    let addr = spawn_mock_server(counters.clone());
+    let schema = spawn_resilience_app(addr).await;
+
+    let request = "{ get_down_items(get_down_items_input: { query: {} }) { data { id } } }";
+    for _ in 0..2 {
+        let response = execute(&schema, request).await;
+        assert_eq!(response.errors[0].message, "HTTP Request Failed");
+    }
+
+    let response = execute(&schema, request).await;
+    assert_eq!(
+        response.errors[0].message,
+        "Circuit open for HTTP data source down_api"
+    );
+    assert_eq!(counters.down_gets.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn circuit_breaker_trial_expires() {
+    let circuit_breaker = CircuitBreaker::new(Some(&HttpCircuitBreakerConfig {
+        failure_threshold: Some(1),
+        reset_timeout: Some(50),
+    }));
+    circuit_breaker.record_failure("trial_api");
+    assert!(circuit_breaker.check("trial_api").is_err());
+
+    // A single trial is allowed after the reset timeout.
+    tokio::time::sleep(Duration::from_millis(60)).await;
+    assert!(circuit_breaker.check("trial_api").is_ok());
+    assert!(circuit_breaker.check("trial_api").is_err());
+
+    // The trial never reported back, so another is allowed once it expires.
+    tokio::time::sleep(Duration::from_millis(60)).await;
+    assert!(circuit_breaker.check("trial_api").is_ok());
+    circuit_breaker.record_success();
+    assert!(!circuit_breaker.is_open());
+}
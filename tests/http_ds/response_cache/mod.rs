@@ -0,0 +1,263 @@
+use std::{
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use async_graphql::dynamic::Schema;
+use http::{HeaderMap, HeaderValue};
+use serde_json::json;
+use subgraph::{cli_args::CliArgs, configuration::subgraph::SubGraphConfig};
+use warp::{http::StatusCode, Filter, Reply};
+
+#[derive(Default)]
+struct MockCounters {
+    cached_gets: AtomicUsize,
+    revalidated_gets: AtomicUsize,
+    not_modified: AtomicUsize,
+    uncached_gets: AtomicUsize,
+    not_modified_gets: AtomicUsize,
+}
+
+/// Serves a cacheable endpoint, an endpoint revalidated with an `ETag`, an endpoint which
+/// must not be stored and an endpoint which always replies `304 Not Modified`.
+fn spawn_mock_server(counters: Arc<MockCounters>) -> SocketAddr {
+    let cached_counters = counters.clone();
+    let cached_many = warp::path!("cached" / "items")
+        .and(warp::get())
+        .map(move || {
+            cached_counters.cached_gets.fetch_add(1, Ordering::SeqCst);
+            warp::reply::json(&json!([{ "id": 1, "title": "Cached" }]))
+        });
+    let cached_create = warp::path!("cached" / "items")
+        .and(warp::post())
+        .map(|| warp::reply::json(&json!({ "id": 2, "title": "New" })));
+
+    let revalidated_counters = counters.clone();
+    let revalidated_many = warp::path!("revalidated" / "items")
+        .and(warp::get())
+        .and(warp::header::optional::<String>("if-none-match"))
+        .map(move |if_none_match: Option<String>| {
+            revalidated_counters
+                .revalidated_gets
+                .fetch_add(1, Ordering::SeqCst);
+            if if_none_match.as_deref() == Some("\"v1\"") {
+                revalidated_counters
+                    .not_modified
+                    .fetch_add(1, Ordering::SeqCst);
+                return StatusCode::NOT_MODIFIED.into_response();
+            }
+            let reply = warp::reply::json(&json!([{ "id": 1, "title": "Revalidated" }]));
+            let reply = warp::reply::with_header(reply, "ETag", "\"v1\"");
+            warp::reply::with_header(reply, "Cache-Control", "no-cache").into_response()
+        });
+
+    let uncached_counters = counters.clone();
+    let uncached_many = warp::path!("uncached" / "items")
+        .and(warp::get())
+        .map(move || {
+            uncached_counters
+                .uncached_gets
+                .fetch_add(1, Ordering::SeqCst);
+            warp::reply::with_header(
+                warp::reply::json(&json!([{ "id": 1, "title": "Uncached" }])),
+                "Cache-Control",
+                "no-store",
+            )
+        });
+
+    let not_modified_counters = counters.clone();
+    let not_modified_many = warp::path!("not_modified" / "items")
+        .and(warp::get())
+        .map(move || {
+            not_modified_counters
+                .not_modified_gets
+                .fetch_add(1, Ordering::SeqCst);
+            StatusCode::NOT_MODIFIED
+        });
+
+    let routes = cached_many
+        .or(cached_create)
+        .or(revalidated_many)
+        .or(uncached_many)
+        .or(not_modified_many);
+    let (addr, server) = warp::serve(routes).bind_ephemeral(([127, 0, 0, 1], 0));
+    tokio::spawn(server);
+    addr
+}
+
+async fn spawn_response_cache_app(addr: SocketAddr) -> Schema {
+    let fields = r#"fields = [
+          { name = "id", scalar = "Int", required = true, exclude_from_input = ["CreateOne", "UpdateOne", "UpdateMany"] },
+          { name = "title", scalar = "String", required = true },
+        ]"#;
+    let config = format!(
+        r#"
+        [service]
+        name = "http_response_cache"
+
+        [[service.data_sources]]
+        [service.data_sources.HTTP]
+        name = "api"
+        url = "http://{addr}"
+        forward_headers = [{{ name = "authorization" }}, {{ name = "traceparent" }}]
+        cache = {{ ttl = 60000, max_entries = 10 }}
+
+        [[service.entities]]
+        name = "cached_item"
+        {fields}
+        data_source = {{ from = "api", path = "/cached/items" }}
+
+        [[service.entities]]
+        name = "revalidated_item"
+        {fields}
+        data_source = {{ from = "api", path = "/revalidated/items" }}
+
+        [[service.entities]]
+        name = "uncached_item"
+        {fields}
+        data_source = {{ from = "api", path = "/uncached/items" }}
+
+        [[service.entities]]
+        name = "not_modified_item"
+        {fields}
+        data_source = {{ from = "api", path = "/not_modified/items" }}
+        "#
+    );
+    let subgraph_config = toml::from_str::<SubGraphConfig>(&config).unwrap();
+
+    let args = CliArgs {
+        config: None,
+        port: Some(0),
+        log_level: None,
+        generate_keypair: false,
+        migrate: None,
+        steps: None,
+        migration_name: None,
+        migrate_only: false,
+        watch: false,
+        host: false,
+    };
+    let (server, schema, _shutdown) = subgraph::run(args, subgraph_config)
+        .await
+        .expect("Failed to run server.");
+    let _ = tokio::spawn(server);
+    schema
+}
+
+async fn execute(schema: &Schema, request: &str, authorization: Option<&'static str>) {
+    let mut headers = HeaderMap::new();
+    if let Some(authorization) = authorization {
+        headers.insert("authorization", HeaderValue::from_static(authorization));
+    }
+    let response = schema
+        .execute(async_graphql::Request::new(request).data(headers))
+        .await;
+    assert!(response.is_ok(), "{:?}", response.errors);
+}
+
+const FIND_CACHED_ITEMS: &str =
+    "{ get_cached_items(get_cached_items_input: { query: {} }) { data { title } } }";
+
+#[tokio::test]
+async fn caches_until_mutation() {
+    let counters = Arc::new(MockCounters::default());
+    let addr = spawn_mock_server(counters.clone());
+    let schema = spawn_response_cache_app(addr).await;
+
+    execute(&schema, FIND_CACHED_ITEMS, None).await;
+    execute(&schema, FIND_CACHED_ITEMS, None).await;
+    assert_eq!(counters.cached_gets.load(Ordering::SeqCst), 1);
+
+    execute(
+        &schema,
+        r#"mutation { create_cached_item(create_cached_item_input: { values: { title: "New" } }) { data { id } } }"#,
+        None,
+    )
+    .await;
+    execute(&schema, FIND_CACHED_ITEMS, None).await;
+    assert_eq!(counters.cached_gets.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn keyed_by_forwarded_auth() {
+    let counters = Arc::new(MockCounters::default());
+    let addr = spawn_mock_server(counters.clone());
+    let schema = spawn_response_cache_app(addr).await;
+
+    execute(&schema, FIND_CACHED_ITEMS, Some("Bearer a")).await;
+    execute(&schema, FIND_CACHED_ITEMS, Some("Bearer b")).await;
+    execute(&schema, FIND_CACHED_ITEMS, Some("Bearer a")).await;
+    assert_eq!(counters.cached_gets.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn ignores_headers_not_varied_by() {
+    let counters = Arc::new(MockCounters::default());
+    let addr = spawn_mock_server(counters.clone());
+    let schema = spawn_response_cache_app(addr).await;
+
+    for traceparent in [
+        "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+        "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01",
+    ] {
+        let mut headers = HeaderMap::new();
+        headers.insert("traceparent", HeaderValue::from_static(traceparent));
+        let response = schema
+            .execute(async_graphql::Request::new(FIND_CACHED_ITEMS).data(headers))
+            .await;
+        assert!(response.is_ok(), "{:?}", response.errors);
+    }
+    assert_eq!(counters.cached_gets.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn revalidates_with_etag() {
+    let counters = Arc::new(MockCounters::default());
+    let addr = spawn_mock_server(counters.clone());
+    let schema = spawn_response_cache_app(addr).await;
+
+    let request =
+        "{ get_revalidated_items(get_revalidated_items_input: { query: {} }) { data { title } } }";
+    execute(&schema, request, None).await;
+    let response = schema
+        .execute(async_graphql::Request::new(request).data(HeaderMap::new()))
+        .await;
+    let data = response.data.into_json().unwrap();
+    assert_eq!(
+        data["get_revalidated_items"]["data"],
+        json!([{ "title": "Revalidated" }])
+    );
+    assert_eq!(counters.revalidated_gets.load(Ordering::SeqCst), 2);
+    assert_eq!(counters.not_modified.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn no_store_is_not_cached() {
+    let counters = Arc::new(MockCounters::default());
+    let addr = spawn_mock_server(counters.clone());
+    let schema = spawn_response_cache_app(addr).await;
+
+    let request =
+        "{ get_uncached_items(get_uncached_items_input: { query: {} }) { data { title } } }";
+    execute(&schema, request, None).await;
+    execute(&schema, request, None).await;
+    assert_eq!(counters.uncached_gets.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn unexpected_not_modified_fails() {
+    let counters = Arc::new(MockCounters::default());
+    let addr = spawn_mock_server(counters.clone());
+    let schema = spawn_response_cache_app(addr).await;
+
+    let request =
+        "{ get_not_modified_items(get_not_modified_items_input: { query: {} }) { data { title } } }";
+    let response = schema
+        .execute(async_graphql::Request::new(request).data(HeaderMap::new()))
+        .await;
+    assert_eq!(response.errors[0].message, "HTTP Request Failed");
+    assert_eq!(counters.not_modified_gets.load(Ordering::SeqCst), 1);
+}
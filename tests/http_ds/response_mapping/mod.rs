@@ -0,0 +1,171 @@
+use std::net::SocketAddr;
+
+use async_graphql::dynamic::Schema;
+use http::HeaderMap;
+use serde_json::json;
+use subgraph::{cli_args::CliArgs, configuration::subgraph::SubGraphConfig};
+use warp::{http::StatusCode, Filter};
+
+/// Serves records wrapped in an envelope and records counted by a response header.
+fn spawn_mock_server() -> SocketAddr {
+    let envelope_many = warp::path!("envelope" / "items").and(warp::get()).map(|| {
+        warp::reply::json(&json!({
+            "data": {
+                "items": [
+                    { "id": 1, "attributes": { "name": "First" } },
+                    { "id": 2, "attributes": { "name": "Second" } },
+                ]
+            },
+            "total": 5
+        }))
+    });
+    let envelope_one = warp::path!("envelope" / "items" / i32)
+        .and(warp::get())
+        .map(|id: i32| {
+            warp::reply::json(&json!({
+                "data": { "id": id, "attributes": { "name": "First" } }
+            }))
+        });
+    let counted_many = warp::path!("counted" / "items").and(warp::get()).map(|| {
+        warp::reply::with_header(
+            warp::reply::with_status(
+                warp::reply::json(&json!([{ "id": 1, "name": "Counted" }])),
+                StatusCode::OK,
+            ),
+            "X-Total-Count",
+            "3",
+        )
+    });
+
+    let routes = envelope_many.or(envelope_one).or(counted_many);
+    let (addr, server) = warp::serve(routes).bind_ephemeral(([127, 0, 0, 1], 0));
+    tokio::spawn(server);
+    addr
+}
+
+async fn spawn_response_mapping_app(addr: SocketAddr) -> Schema {
+    let config = format!(
+        r#"
+        [service]
+        name = "http_response_mapping"
+
+        [[service.data_sources]]
+        [service.data_sources.HTTP]
+        name = "api"
+        url = "http://{addr}"
+
+        [[service.entities]]
+        name = "envelope_item"
+        fields = [
+          {{ name = "id", scalar = "Int", required = true, exclude_from_input = ["CreateOne", "UpdateOne", "UpdateMany"] }},
+          {{ name = "name", scalar = "String", required = true }},
+        ]
+        [service.entities.data_source]
+        from = "api"
+        path = "/envelope/items"
+        [service.entities.data_source.resolvers.find_one]
+        path = "/:id"
+        records_path = "data"
+        field_paths = [["name", "attributes.name"]]
+        [service.entities.data_source.resolvers.find_many]
+        records_path = "data.items"
+        total_count_path = "total"
+        field_paths = [["name", "attributes.name"]]
+
+        [[service.entities]]
+        name = "counted_item"
+        fields = [
+          {{ name = "id", scalar = "Int", required = true, exclude_from_input = ["CreateOne", "UpdateOne", "UpdateMany"] }},
+          {{ name = "name", scalar = "String", required = true }},
+        ]
+        [service.entities.data_source]
+        from = "api"
+        path = "/counted/items"
+        [service.entities.data_source.resolvers.find_many]
+        total_count_header = "x-total-count"
+        "#
+    );
+    let subgraph_config = toml::from_str::<SubGraphConfig>(&config).unwrap();
+
+    let args = CliArgs {
+        config: None,
+        port: Some(0),
+        log_level: None,
+        generate_keypair: false,
+        migrate: None,
+        steps: None,
+        migration_name: None,
+        migrate_only: false,
+        watch: false,
+        host: false,
+    };
+    let (server, schema, _shutdown) = subgraph::run(args, subgraph_config)
+        .await
+        .expect("Failed to run server.");
+    let _ = tokio::spawn(server);
+    schema
+}
+
+async fn execute(schema: &Schema, request: &str) -> async_graphql::Response {
+    schema
+        .execute(async_graphql::Request::new(request).data(HeaderMap::new()))
+        .await
+}
+
+#[tokio::test]
+async fn find_many_from_envelope() {
+    let addr = spawn_mock_server();
+    let schema = spawn_response_mapping_app(addr).await;
+
+    let response = execute(
+        &schema,
+        "{ get_envelope_items(get_envelope_items_input: { query: {} }) { data { id name } meta { count total_count total_pages } } }",
+    )
+    .await;
+    assert!(response.is_ok(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let items = &data["get_envelope_items"];
+    assert_eq!(
+        items["data"],
+        json!([{ "id": 1, "name": "First" }, { "id": 2, "name": "Second" }])
+    );
+    assert_eq!(items["meta"]["count"], 2);
+    assert_eq!(items["meta"]["total_count"], 5);
+    assert_eq!(items["meta"]["total_pages"], 3);
+}
+
+#[tokio::test]
+async fn find_one_from_envelope() {
+    let addr = spawn_mock_server();
+    let schema = spawn_response_mapping_app(addr).await;
+
+    let response = execute(
+        &schema,
+        "{ get_envelope_item(get_envelope_item_input: { query: { id: 1 } }) { data { id name } } }",
+    )
+    .await;
+    assert!(response.is_ok(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(
+        data["get_envelope_item"]["data"],
+        json!({ "id": 1, "name": "First" })
+    );
+}
+
+#[tokio::test]
+async fn total_count_from_header() {
+    let addr = spawn_mock_server();
+    let schema = spawn_response_mapping_app(addr).await;
+
+    let response = execute(
+        &schema,
+        "{ get_counted_items(get_counted_items_input: { query: {} }) { data { name } meta { total_count total_pages } } }",
+    )
+    .await;
+    assert!(response.is_ok(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let items = &data["get_counted_items"];
+    assert_eq!(items["data"], json!([{ "name": "Counted" }]));
+    assert_eq!(items["meta"]["total_count"], 3);
+    assert_eq!(items["meta"]["total_pages"], 3);
+}
@@ -1,5 +1,10 @@
+mod client_options;
 mod create_one;
+mod eager_loading;
 mod find_many;
 mod find_one;
+mod indexes;
+mod pipeline;
 mod update_many;
 mod update_one;
+mod update_operators;
@@ -48,3 +48,30 @@ async fn create_one_with_default_value() {
         .unwrap();
     assert_eq!(car_id, 1);
 }
+
+#[tokio::test]
+async fn create_one_with_uuid_primary_key() {
+    let registration_id = uuid::Uuid::new_v4().to_string();
+    let request = async_graphql::Request::new(format!(
+        r#"
+            mutation {{
+                create_car_registration(create_car_registration_input: {{ values: {{ registration_id: "{}", car_id: 1, plate: "ABC-123" }} }}) {{
+                    data {{
+                        registration_id
+                        plate
+                    }}
+                }}
+            }}
+            "#,
+        registration_id
+    ));
+
+    let response = execute(request, None).await;
+    assert!(response.is_ok());
+    let data = response.data.into_json().unwrap();
+    assert_eq!(
+        data["create_car_registration"]["data"]["registration_id"],
+        registration_id
+    );
+    assert_eq!(data["create_car_registration"]["data"]["plate"], "ABC-123");
+}
@@ -0,0 +1,7 @@
+-- Add up migration script here
+CREATE TABLE IF NOT EXISTS car_registrations (
+    registration_id CHAR(36) PRIMARY KEY,
+    car_id INT NOT NULL,
+    plate VARCHAR(255) NOT NULL,
+    FOREIGN KEY (car_id) REFERENCES cars (id) ON DELETE CASCADE
+);
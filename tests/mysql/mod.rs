@@ -1,5 +1,6 @@
 mod create_one;
 mod find_many;
 mod find_one;
+mod list_column;
 mod update_many;
 mod update_one;
@@ -1,4 +1,5 @@
 mod create_one;
 mod find_many;
 mod find_one;
+mod list_column;
 mod update_many;
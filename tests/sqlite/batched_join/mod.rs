@@ -0,0 +1,298 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use async_graphql::{async_trait::async_trait, dynamic::Schema, futures_util::future::join_all};
+use bson::Bson;
+use http::HeaderMap;
+use subgraph::{
+    cli_args::CliArgs,
+    configuration::subgraph::{
+        data_sources::sql::{DialectEnum, SqlDataSourceConfig},
+        SubGraphConfig,
+    },
+    data_sources::sql::{PoolEnum, SqlDataSource},
+    graphql::join_loader::{JoinBatch, JoinFetch, JoinLoader, JoinedRow},
+};
+
+fn batched_join_args() -> CliArgs {
+    CliArgs {
+        config: None,
+        port: Some(0),
+        log_level: None,
+        generate_keypair: false,
+        migrate: None,
+        steps: None,
+        migration_name: None,
+        migrate_only: false,
+        watch: false,
+        host: false,
+    }
+}
+
+/// Creates a database with three authors, each with a different number of books.
+async fn spawn_batched_join_app(db_name: &str) -> Schema {
+    let path = std::env::temp_dir().join(db_name);
+    let _ = std::fs::remove_file(&path);
+    let uri = format!("sqlite:{}?mode=rwc", path.display());
+
+    let pool = SqlDataSource::create_pool(&SqlDataSourceConfig {
+        name: "library_data_source".to_string(),
+        uri: uri.clone(),
+        dialect: DialectEnum::SQLITE,
+        sqlite_extensions: None,
+        migrations_path: None,
+        pool: None,
+        replicas: None,
+        queries: None,
+        schema: None,
+    })
+    .await
+    .unwrap();
+    if let PoolEnum::SqLite(pool) = pool {
+        sqlx::query("CREATE TABLE authors (id INTEGER PRIMARY KEY, name TEXT NOT NULL);")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "CREATE TABLE books (id INTEGER PRIMARY KEY, author_id INTEGER NOT NULL, title TEXT NOT NULL);",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO authors (id, name) VALUES (1, 'ann'), (2, 'bob'), (3, 'cat');")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO books (id, author_id, title) VALUES (1, 1, 'a1'), (2, 2, 'b1'), (3, 1, 'a2'), (4, 1, 'a3'), (5, 2, 'b2');",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let config = format!(
+        r#"
+        [service]
+        name = "batched_joins"
+
+        [[service.data_sources]]
+        [service.data_sources.SQL]
+        name = "library_data_source"
+        uri = "{uri}"
+        dialect = "SQLITE"
+
+        [[service.entities]]
+        name = "author"
+        data_source = {{ from = "library_data_source", table = "authors" }}
+        fields = [
+          {{ name = "id", scalar = "Int", required = true }},
+          {{ name = "name", scalar = "String", required = true }},
+          {{ name = "books", scalar = "Int", list = true, as_type = "book", join_on = "author_id", join_from = "id" }},
+        ]
+
+        [[service.entities]]
+        name = "book"
+        data_source = {{ from = "library_data_source", table = "books" }}
+        fields = [
+          {{ name = "id", scalar = "Int", required = true }},
+          {{ name = "author_id", scalar = "Int", required = true }},
+          {{ name = "title", scalar = "String", required = true }},
+          {{ name = "author", scalar = "Int", as_type = "author", join_on = "id", join_from = "author_id" }},
+          {{ name = "on_loan", scalar = "Boolean", is_virtual = true }},
+        ]
+        "#
+    );
+    let subgraph_config = toml::from_str::<SubGraphConfig>(&config).unwrap();
+    let (server, schema, _shutdown) = subgraph::run(batched_join_args(), subgraph_config)
+        .await
+        .expect("Failed to run server.");
+    let _ = tokio::spawn(server);
+    schema
+}
+
+#[tokio::test]
+async fn resolves_batched_joins_for_each_parent() {
+    let schema = spawn_batched_join_app("subgraph_batched_join_test.db").await;
+
+    let request = async_graphql::Request::new(
+        r#"
+        query {
+            get_books(get_books_input: { query: {}, opts: { sort: [{ field: "id", direction: "ASC" }] } }) {
+                data {
+                    title
+                    author(author: { query: {} }) {
+                        data {
+                            name
+                        }
+                    }
+                }
+            }
+            get_authors(get_authors_input: { query: {}, opts: { sort: [{ field: "id", direction: "ASC" }] } }) {
+                data {
+                    name
+                    books(books: { query: {}, opts: { per_page: 2, sort: [{ field: "title", direction: "DESC" }] } }) {
+                        data {
+                            title
+                        }
+                        meta {
+                            count
+                            total_count
+                        }
+                    }
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let json = response.data.into_json().unwrap();
+
+    let authors = json["get_books"]["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|book| book["author"]["data"]["name"].as_str().unwrap())
+        .collect::<Vec<&str>>();
+    assert_eq!(authors, vec!["ann", "bob", "ann", "ann", "bob"]);
+
+    let books = json["get_authors"]["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|author| {
+            let titles = author["books"]["data"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|book| book["title"].as_str().unwrap())
+                .collect::<Vec<&str>>();
+            (
+                titles,
+                author["books"]["meta"]["total_count"].as_i64().unwrap(),
+            )
+        })
+        .collect::<Vec<(Vec<&str>, i64)>>();
+    assert_eq!(
+        books,
+        vec![(vec!["a3", "a2"], 3), (vec!["b2", "b1"], 2), (vec![], 0)]
+    );
+}
+
+#[tokio::test]
+async fn batched_joins_apply_the_prepared_input() {
+    let schema = spawn_batched_join_app("subgraph_batched_join_input_test.db").await;
+
+    let request = async_graphql::Request::new(
+        r#"
+        query {
+            get_books(get_books_input: { query: {}, opts: { sort: [{ field: "id", direction: "ASC" }] } }) {
+                data {
+                    author(author: { query: { name: "ann" } }) {
+                        data {
+                            name
+                        }
+                    }
+                }
+            }
+            get_authors(get_authors_input: { query: {}, opts: { sort: [{ field: "id", direction: "ASC" }] } }) {
+                data {
+                    books(books: { query: { title: "a2", on_loan: true } }) {
+                        data {
+                            title
+                        }
+                    }
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let json = response.data.into_json().unwrap();
+
+    let authors = json["get_books"]["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|book| book["author"]["data"]["name"].as_str())
+        .collect::<Vec<Option<&str>>>();
+    assert_eq!(
+        authors,
+        vec![Some("ann"), None, Some("ann"), Some("ann"), None]
+    );
+
+    // The virtual field is removed from the query of the batch.
+    let books = json["get_authors"]["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|author| author["books"]["data"].as_array().unwrap().len())
+        .collect::<Vec<usize>>();
+    assert_eq!(books, vec![1, 0, 0]);
+}
+
+/// Counts the fetches of a join, returning a row for each value.
+struct CountingJoinFetch {
+    fetch_count: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl JoinFetch for CountingJoinFetch {
+    async fn fetch(
+        &self,
+        values: Vec<Bson>,
+    ) -> Result<Vec<(Vec<String>, JoinedRow)>, async_graphql::Error> {
+        self.fetch_count.fetch_add(1, Ordering::SeqCst);
+        Ok(values
+            .iter()
+            .map(|value| {
+                let key = JoinLoader::get_join_key(value).unwrap();
+                let row = bson::doc! { "id": value.clone() };
+                (vec![key], JoinedRow::Document(row))
+            })
+            .collect())
+    }
+}
+
+#[tokio::test]
+async fn join_loader_fetches_once_per_batch_and_caches() {
+    let data_loader = JoinLoader::create_data_loader();
+    let fetch_count = Arc::new(AtomicUsize::new(0));
+    let batch = Arc::new(JoinBatch {
+        key: "author.id".to_string(),
+        fetch: Arc::new(CountingJoinFetch {
+            fetch_count: fetch_count.clone(),
+        }),
+    });
+
+    let loads = (1..=5)
+        .map(|id| JoinLoader::load_rows(&data_loader, batch.clone(), vec![Bson::Int64(id)]))
+        .collect::<Vec<_>>();
+    let results = join_all(loads).await;
+    assert_eq!(fetch_count.load(Ordering::SeqCst), 1);
+    for (i, result) in results.into_iter().enumerate() {
+        let rows = result.unwrap();
+        assert_eq!(rows.len(), 1);
+        match &rows[0] {
+            JoinedRow::Document(row) => assert_eq!(row.get_i64("id").unwrap(), i as i64 + 1),
+            JoinedRow::Row(_) => panic!("Expected a document."),
+        }
+    }
+
+    // Loaded values are served from the cache for the rest of the request.
+    let rows = JoinLoader::load_rows(
+        &data_loader,
+        batch.clone(),
+        vec![Bson::Int64(2), Bson::Int64(4)],
+    )
+    .await
+    .unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(fetch_count.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,247 @@
+use async_graphql::dynamic::Schema;
+use http::HeaderMap;
+use subgraph::{
+    cli_args::CliArgs,
+    configuration::subgraph::{
+        data_sources::sql::{DialectEnum, SqlDataSourceConfig},
+        SubGraphConfig,
+    },
+    data_sources::sql::{PoolEnum, SqlDataSource},
+};
+
+fn composite_key_args() -> CliArgs {
+    CliArgs {
+        config: None,
+        port: Some(0),
+        log_level: None,
+        generate_keypair: false,
+        migrate: None,
+        steps: None,
+        migration_name: None,
+        migrate_only: false,
+        watch: false,
+        host: false,
+    }
+}
+
+/// Creates a database with a `memberships` table keyed by `(tenant_id, user_id)`.
+async fn composite_key_config(db_name: &str) -> SubGraphConfig {
+    let path = std::env::temp_dir().join(db_name);
+    let _ = std::fs::remove_file(&path);
+    let uri = format!("sqlite:{}?mode=rwc", path.display());
+
+    let pool = SqlDataSource::create_pool(&SqlDataSourceConfig {
+        name: "composite_data_source".to_string(),
+        uri: uri.clone(),
+        dialect: DialectEnum::SQLITE,
+        sqlite_extensions: None,
+        migrations_path: None,
+        pool: None,
+        replicas: None,
+        queries: None,
+        schema: None,
+    })
+    .await
+    .unwrap();
+    if let PoolEnum::SqLite(pool) = pool {
+        sqlx::query(
+            "CREATE TABLE memberships (tenant_id INTEGER NOT NULL, user_id INTEGER NOT NULL, role TEXT NOT NULL, PRIMARY KEY (tenant_id, user_id));",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "CREATE TABLE notes (id INTEGER PRIMARY KEY AUTOINCREMENT, tenant_id INTEGER NOT NULL, user_id INTEGER NOT NULL, body TEXT NOT NULL);",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO memberships (tenant_id, user_id, role) VALUES (1, 1, 'owner'), (1, 2, 'member'), (2, 1, 'member');",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO notes (tenant_id, user_id, body) VALUES (1, 2, 'hello');")
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+
+    let config = format!(
+        r#"
+        [service]
+        name = "composite_keys"
+
+        [[service.data_sources]]
+        [service.data_sources.SQL]
+        name = "composite_data_source"
+        uri = "{uri}"
+        dialect = "SQLITE"
+
+        [[service.entities]]
+        name = "membership"
+        data_source = {{ from = "composite_data_source", table = "memberships" }}
+        fields = [
+          {{ name = "tenant_id", scalar = "Int", required = true, primary_key = true }},
+          {{ name = "user_id", scalar = "Int", required = true, primary_key = true }},
+          {{ name = "role", scalar = "String", required = true }},
+        ]
+
+        [[service.entities]]
+        name = "note"
+        data_source = {{ from = "composite_data_source", table = "notes" }}
+        fields = [
+          {{ name = "id", scalar = "Int", required = true, exclude_from_input = ["CreateOne"] }},
+          {{ name = "tenant_id", scalar = "Int", required = true }},
+          {{ name = "user_id", scalar = "Int", required = true }},
+          {{ name = "body", scalar = "String", required = true }},
+          {{ name = "membership", scalar = "Int", as_type = "membership", join_on = "tenant_id", join_from = "tenant_id", join_keys = [{{ join_on = "user_id", join_from = "user_id" }}] }},
+        ]
+        "#
+    );
+    toml::from_str::<SubGraphConfig>(&config).unwrap()
+}
+
+async fn spawn_composite_key_app(db_name: &str) -> Schema {
+    let subgraph_config = composite_key_config(db_name).await;
+    let (server, schema, _shutdown) = subgraph::run(composite_key_args(), subgraph_config)
+        .await
+        .expect("Failed to run server.");
+    let _ = tokio::spawn(server);
+    schema
+}
+
+#[tokio::test]
+async fn update_many_refetches_by_composite_key() {
+    let schema = spawn_composite_key_app("subgraph_composite_update_test.db").await;
+
+    let request = async_graphql::Request::new(
+        r#"
+        mutation {
+            update_memberships(update_memberships_input: { values: { role: "admin" }, query: { tenant_id: 1 } }) {
+                data {
+                    tenant_id
+                    user_id
+                    role
+                }
+            }
+        }
+        "#,
+    );
+    let response = schema.execute(request.data(HeaderMap::new())).await;
+    assert!(response.is_ok(), "{:?}", response.errors);
+
+    let data = response.data.into_json().unwrap();
+    let data = data["update_memberships"]["data"]
+        .as_array()
+        .unwrap()
+        .clone();
+    assert_eq!(data.len(), 2);
+    for membership in data {
+        assert_eq!(membership["tenant_id"], 1);
+        assert_eq!(membership["role"], "admin");
+    }
+
+    let request = async_graphql::Request::new(
+        r#"
+        query {
+            get_membership(get_membership_input: { query: { tenant_id: 2, user_id: 1 } }) {
+                data {
+                    role
+                }
+            }
+        }
+        "#,
+    );
+    let response = schema.execute(request.data(HeaderMap::new())).await;
+    assert!(response.is_ok(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["get_membership"]["data"]["role"], "member");
+}
+
+#[tokio::test]
+async fn join_on_composite_key() {
+    let schema = spawn_composite_key_app("subgraph_composite_join_test.db").await;
+
+    let request = async_graphql::Request::new(
+        r#"
+        query {
+            get_note(get_note_input: { query: { id: 1 } }) {
+                data {
+                    body
+                    membership(membership: { query: {} }) {
+                        data {
+                            tenant_id
+                            user_id
+                            role
+                        }
+                    }
+                }
+            }
+        }
+        "#,
+    );
+    let response = schema.execute(request.data(HeaderMap::new())).await;
+    assert!(response.is_ok(), "{:?}", response.errors);
+
+    let data = response.data.into_json().unwrap();
+    let membership = &data["get_note"]["data"]["membership"]["data"];
+    assert_eq!(membership["tenant_id"], 1);
+    assert_eq!(membership["user_id"], 2);
+    assert_eq!(membership["role"], "member");
+}
+
+#[tokio::test]
+async fn federation_key_includes_every_primary_key() {
+    let schema = spawn_composite_key_app("subgraph_composite_federation_test.db").await;
+    let sdl = schema.sdl_with_options(async_graphql::SDLExportOptions::new().federation());
+    assert!(sdl.contains("type membership @key(fields: \"tenant_id user_id\")"));
+}
+
+#[tokio::test]
+async fn resolve_entities_by_composite_key() {
+    let schema = spawn_composite_key_app("subgraph_composite_entities_test.db").await;
+
+    let request = async_graphql::Request::new(
+        r#"
+        query {
+            _entities(representations: [
+                { __typename: "membership", tenant_id: 1, user_id: 2 },
+                { __typename: "membership", tenant_id: 2, user_id: 1 }
+            ]) {
+                ... on membership {
+                    tenant_id
+                    user_id
+                    role
+                }
+            }
+        }
+        "#,
+    );
+    let response = schema.execute(request.data(HeaderMap::new())).await;
+    assert!(response.is_ok(), "{:?}", response.errors);
+
+    let data = response.data.into_json().unwrap();
+    let entities = data["_entities"].as_array().unwrap();
+    assert_eq!(entities.len(), 2);
+    assert_eq!(entities[0]["tenant_id"], 1);
+    assert_eq!(entities[0]["user_id"], 2);
+    assert_eq!(entities[0]["role"], "member");
+    assert_eq!(entities[1]["tenant_id"], 2);
+    assert_eq!(entities[1]["role"], "member");
+
+    let request = async_graphql::Request::new(
+        r#"
+        query {
+            _entities(representations: [{ __typename: "membership", tenant_id: 3, user_id: 3 }]) {
+                ... on membership {
+                    role
+                }
+            }
+        }
+        "#,
+    );
+    let response = schema.execute(request.data(HeaderMap::new())).await;
+    assert_eq!(response.errors[0].message, "Entity not found: membership");
+}
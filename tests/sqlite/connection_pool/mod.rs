@@ -0,0 +1,274 @@
+use http::HeaderMap;
+use subgraph::{
+    cli_args::CliArgs,
+    configuration::subgraph::{
+        data_sources::sql::{DialectEnum, SqlDataSourceConfig, SqlPoolConfig},
+        SubGraphConfig,
+    },
+    data_sources::sql::{PoolEnum, SqlDataSource},
+};
+
+/// A database file in a directory that does not exist can never be opened.
+const UNREACHABLE_URI: &str = "sqlite:/subgraph/does/not/exist.db";
+
+fn connection_pool_args() -> CliArgs {
+    CliArgs {
+        config: None,
+        port: Some(0),
+        log_level: None,
+        generate_keypair: false,
+        migrate: None,
+        steps: None,
+        migration_name: None,
+        migrate_only: false,
+        watch: false,
+        host: false,
+    }
+}
+
+fn pool_config(uri: &str, pool: SqlPoolConfig) -> SqlDataSourceConfig {
+    SqlDataSourceConfig {
+        name: "pool_data_source".to_string(),
+        uri: uri.to_string(),
+        dialect: DialectEnum::SQLITE,
+        sqlite_extensions: None,
+        migrations_path: None,
+        pool: Some(pool),
+        replicas: None,
+        queries: None,
+        schema: None,
+    }
+}
+
+#[tokio::test]
+async fn applies_pool_options() {
+    let path = std::env::temp_dir().join("subgraph_pool_options_test.db");
+    let uri = format!("sqlite:{}?mode=rwc", path.display());
+    let config = pool_config(
+        &uri,
+        SqlPoolConfig {
+            min_connections: Some(1),
+            max_connections: Some(2),
+            acquire_timeout: Some(1000),
+            idle_timeout: Some(1000),
+            ..Default::default()
+        },
+    );
+
+    let pool = SqlDataSource::create_pool(&config).await.unwrap();
+    match pool {
+        PoolEnum::SqLite(pool) => {
+            assert_eq!(pool.options().get_max_connections(), 2);
+            assert_eq!(pool.options().get_min_connections(), 1);
+        }
+        _ => panic!("Expected SQLite pool"),
+    }
+}
+
+#[tokio::test]
+async fn retries_connect_with_backoff() {
+    let config = pool_config(
+        UNREACHABLE_URI,
+        SqlPoolConfig {
+            connect_retries: Some(2),
+            connect_backoff: Some(20),
+            ..Default::default()
+        },
+    );
+
+    let started = std::time::Instant::now();
+    let pool = SqlDataSource::create_pool(&config).await;
+    assert!(pool.is_err());
+    // Backs off 20ms, then 40ms.
+    assert!(started.elapsed() >= std::time::Duration::from_millis(60));
+}
+
+#[tokio::test]
+async fn lazy_connect_reports_unhealthy_data_source() {
+    let config = format!(
+        r#"
+        [service]
+        name = "lazy_connect"
+        data_source_health = true
+
+        [[service.data_sources]]
+        [service.data_sources.SQL]
+        name = "pool_data_source"
+        uri = "{UNREACHABLE_URI}"
+        dialect = "SQLITE"
+        pool = {{ lazy_connect = true, acquire_timeout = 500 }}
+        "#
+    );
+    let subgraph_config = toml::from_str::<SubGraphConfig>(&config).unwrap();
+    let (server, schema, _shutdown) = subgraph::run(connection_pool_args(), subgraph_config)
+        .await
+        .expect("Failed to run server.");
+    let _ = tokio::spawn(server);
+
+    let request = async_graphql::Request::new(
+        r#"
+        query {
+            data_source_health {
+                name
+                healthy
+            }
+        }
+        "#,
+    );
+    let response = schema.execute(request.data(HeaderMap::new())).await;
+    assert!(response.is_ok(), "{:?}", response.errors);
+
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["data_source_health"][0]["name"], "pool_data_source");
+    assert_eq!(data["data_source_health"][0]["healthy"], false);
+}
+
+#[tokio::test]
+async fn unreachable_data_source_fails_startup() {
+    let config = format!(
+        r#"
+        [service]
+        name = "unreachable"
+        data_source_health = true
+
+        [[service.data_sources]]
+        [service.data_sources.SQL]
+        name = "pool_data_source"
+        uri = "{UNREACHABLE_URI}"
+        dialect = "SQLITE"
+        pool = {{ connect_retries = 0 }}
+        "#
+    );
+    let subgraph_config = toml::from_str::<SubGraphConfig>(&config).unwrap();
+
+    let error = match subgraph::run(connection_pool_args(), subgraph_config).await {
+        Ok(_) => panic!("Expected the service to fail to start."),
+        Err(error) => error,
+    };
+    assert!(error
+        .to_string()
+        .starts_with("Failed to connect to data source pool_data_source"));
+}
+
+#[tokio::test]
+async fn unreachable_replica_starts_unhealthy() {
+    let path = std::env::temp_dir().join("subgraph_unreachable_replica_test.db");
+    let uri = format!("sqlite:{}?mode=rwc", path.display());
+    let config = format!(
+        r#"
+        [service]
+        name = "unreachable_replica"
+        data_source_health = true
+
+        [[service.data_sources]]
+        [service.data_sources.SQL]
+        name = "pool_data_source"
+        uri = "{uri}"
+        dialect = "SQLITE"
+        replicas = ["{UNREACHABLE_URI}"]
+        pool = {{ connect_retries = 0, acquire_timeout = 500 }}
+        "#
+    );
+    let subgraph_config = toml::from_str::<SubGraphConfig>(&config).unwrap();
+    let (server, schema, _shutdown) = subgraph::run(connection_pool_args(), subgraph_config)
+        .await
+        .expect("Failed to run server.");
+    let _ = tokio::spawn(server);
+
+    let request = async_graphql::Request::new(
+        r#"
+        query {
+            data_source_health {
+                name
+                healthy
+            }
+        }
+        "#,
+    );
+    let response = schema.execute(request.data(HeaderMap::new())).await;
+    assert!(response.is_ok(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(
+        data["data_source_health"],
+        serde_json::json!([
+            { "name": "pool_data_source", "healthy": true },
+            { "name": "pool_data_source_replica_0", "healthy": false },
+        ])
+    );
+}
+
+#[tokio::test]
+async fn sqlite_statement_timeout_is_rejected() {
+    let path = std::env::temp_dir().join("subgraph_statement_timeout_test.db");
+    let uri = format!("sqlite:{}?mode=rwc", path.display());
+    let config = format!(
+        r#"
+        [service]
+        name = "statement_timeout"
+
+        [[service.data_sources]]
+        [service.data_sources.SQL]
+        name = "pool_data_source"
+        uri = "{uri}"
+        dialect = "SQLITE"
+        pool = {{ statement_timeout = 1000 }}
+        "#
+    );
+    let subgraph_config = toml::from_str::<SubGraphConfig>(&config).unwrap();
+
+    let error = match subgraph::run(connection_pool_args(), subgraph_config).await {
+        Ok(_) => panic!("Expected the service to fail to start."),
+        Err(error) => error,
+    };
+    assert_eq!(
+        error.to_string(),
+        "Statement timeout is not supported for SQLite data source: pool_data_source"
+    );
+}
+
+#[tokio::test]
+async fn data_source_health_is_opt_in_and_guarded() {
+    let path = std::env::temp_dir().join("subgraph_health_guard_test.db");
+    let uri = format!("sqlite:{}?mode=rwc", path.display());
+    let request = r#"
+        query {
+            data_source_health {
+                name
+                healthy
+            }
+        }
+        "#;
+
+    for (service, expected_error) in [
+        ("", r#"Unknown field "data_source_health" on type "Query"."#),
+        (
+            r#"data_source_health = true
+        guards = [{ name = "internal_only", if_expr = 'headers("x-internal") != "true"', then_msg = "Internal requests only" }]"#,
+            "Internal requests only",
+        ),
+    ] {
+        let config = format!(
+            r#"
+        [service]
+        name = "health_guard"
+        {service}
+
+        [[service.data_sources]]
+        [service.data_sources.SQL]
+        name = "pool_data_source"
+        uri = "{uri}"
+        dialect = "SQLITE"
+        "#
+        );
+        let subgraph_config = toml::from_str::<SubGraphConfig>(&config).unwrap();
+        let (server, schema, _shutdown) = subgraph::run(connection_pool_args(), subgraph_config)
+            .await
+            .expect("Failed to run server.");
+        let _ = tokio::spawn(server);
+
+        let response = schema
+            .execute(async_graphql::Request::new(request).data(HeaderMap::new()))
+            .await;
+        assert_eq!(response.errors[0].message, expected_error);
+    }
+}
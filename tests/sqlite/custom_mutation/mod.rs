@@ -0,0 +1,305 @@
+use async_graphql::dynamic::Schema;
+use http::HeaderMap;
+use subgraph::{
+    cli_args::CliArgs,
+    configuration::subgraph::{
+        data_sources::sql::{DialectEnum, SqlDataSourceConfig},
+        SubGraphConfig,
+    },
+    data_sources::sql::{PoolEnum, SqlDataSource},
+};
+
+fn custom_mutation_args() -> CliArgs {
+    CliArgs {
+        config: None,
+        port: Some(0),
+        log_level: None,
+        generate_keypair: false,
+        migrate: None,
+        steps: None,
+        migration_name: None,
+        migrate_only: false,
+        watch: false,
+        host: false,
+    }
+}
+
+/// Creates a database of `accounts` and their `sessions`.
+async fn custom_mutation_config(db_name: &str) -> SubGraphConfig {
+    let path = std::env::temp_dir().join(db_name);
+    let _ = std::fs::remove_file(&path);
+    let uri = format!("sqlite:{}?mode=rwc", path.display());
+
+    let pool = SqlDataSource::create_pool(&SqlDataSourceConfig {
+        name: "accounts_data_source".to_string(),
+        uri: uri.clone(),
+        dialect: DialectEnum::SQLITE,
+        sqlite_extensions: None,
+        migrations_path: None,
+        pool: None,
+        replicas: None,
+        queries: None,
+        schema: None,
+    })
+    .await
+    .unwrap();
+    if let PoolEnum::SqLite(pool) = pool {
+        sqlx::query(
+            "CREATE TABLE accounts (id INTEGER PRIMARY KEY AUTOINCREMENT, status TEXT NOT NULL);",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "CREATE TABLE sessions (id INTEGER PRIMARY KEY AUTOINCREMENT, account_id INTEGER NOT NULL);",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO accounts (status) VALUES ('open'), ('open');")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO sessions (account_id) VALUES (1), (1), (2);")
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+
+    let config = format!(
+        r#"
+        [service]
+        name = "custom_mutations"
+
+        [[service.data_sources]]
+        [service.data_sources.SQL]
+        name = "accounts_data_source"
+        uri = "{uri}"
+        dialect = "SQLITE"
+
+        [[service.data_sources.SQL.queries]]
+        name = "open_accounts"
+        sql = "SELECT id, status FROM accounts WHERE status = 'open'"
+
+        [[service.entities]]
+        name = "open_account"
+        data_source = {{ from = "accounts_data_source", query = "open_accounts" }}
+        fields = [
+          {{ name = "id", scalar = "Int", required = true, primary_key = true }},
+          {{ name = "status", scalar = "String", required = true }},
+        ]
+
+        [[service.entities]]
+        name = "account"
+        data_source = {{ from = "accounts_data_source", table = "accounts" }}
+        fields = [
+          {{ name = "id", scalar = "Int", required = true }},
+          {{ name = "status", scalar = "String", required = true }},
+        ]
+
+        [[service.mutations]]
+        name = "close_account"
+        data_source = "accounts_data_source"
+        sql = "UPDATE accounts SET status = 'closed' WHERE id = :account_id RETURNING *"
+        args = [{{ name = "account_id", scalar = "Int", required = true }}]
+        returns = "account"
+        guards = [{{ name = "protected_account", if_expr = "contains(input(\"values\", \"account_id\"), \"2\")", then_msg = "Account can not be closed" }}]
+
+        [[service.mutations]]
+        name = "reopen_account"
+        data_source = "accounts_data_source"
+        sql = "UPDATE accounts SET status = 'open' WHERE id = :account_id RETURNING id, status"
+        args = [{{ name = "account_id", scalar = "Int", required = true }}]
+        returns = "open_account"
+
+        [[service.mutations]]
+        name = "end_sessions"
+        data_source = "accounts_data_source"
+        sql = "DELETE FROM sessions WHERE account_id = :account_id"
+        args = [{{ name = "account_id", scalar = "Int", required = true }}]
+
+        [[service.mutations]]
+        name = "set_account_status"
+        data_source = "accounts_data_source"
+        sql = "UPDATE accounts SET status = :status WHERE id = :account_id RETURNING *"
+        args = [
+          {{ name = "account_id", scalar = "Int", required = true }},
+          {{ name = "status", scalar = "String", default_value = "\"frozen\"" }},
+        ]
+        returns = "account"
+        "#
+    );
+    toml::from_str::<SubGraphConfig>(&config).unwrap()
+}
+
+async fn spawn_custom_mutation_app(db_name: &str) -> Schema {
+    let subgraph_config = custom_mutation_config(db_name).await;
+    let (server, schema, _shutdown) = subgraph::run(custom_mutation_args(), subgraph_config)
+        .await
+        .expect("Failed to run server.");
+    let _ = tokio::spawn(server);
+    schema
+}
+
+#[tokio::test]
+async fn custom_mutation_returns_entity() {
+    let schema = spawn_custom_mutation_app("subgraph_custom_mutation_entity_test.db").await;
+
+    let request = async_graphql::Request::new(
+        r#"
+        mutation {
+            close_account(account_id: 1) {
+                data {
+                    id
+                    status
+                }
+                meta {
+                    count
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let json = response.data.into_json().unwrap();
+    assert_eq!(json["close_account"]["data"][0]["id"], 1);
+    assert_eq!(json["close_account"]["data"][0]["status"], "closed");
+    assert_eq!(json["close_account"]["meta"]["count"], 1);
+}
+
+#[tokio::test]
+async fn custom_mutation_returns_affected_rows() {
+    let schema = spawn_custom_mutation_app("subgraph_custom_mutation_count_test.db").await;
+
+    let request = async_graphql::Request::new(
+        r#"
+        mutation {
+            end_sessions(account_id: 1)
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let json = response.data.into_json().unwrap();
+    assert_eq!(json["end_sessions"], 2);
+}
+
+#[tokio::test]
+async fn custom_mutation_binds_defaults_of_optional_args() {
+    let schema = spawn_custom_mutation_app("subgraph_custom_mutation_default_test.db").await;
+
+    let request = async_graphql::Request::new(
+        r#"
+        mutation {
+            set_account_status(account_id: 1) {
+                data {
+                    status
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let json = response.data.into_json().unwrap();
+    assert_eq!(json["set_account_status"]["data"][0]["status"], "frozen");
+
+    let request = async_graphql::Request::new(
+        r#"
+        mutation {
+            set_account_status(account_id: 1, status: "closed") {
+                data {
+                    status
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let json = response.data.into_json().unwrap();
+    assert_eq!(json["set_account_status"]["data"][0]["status"], "closed");
+}
+
+#[tokio::test]
+async fn custom_mutation_applies_guards() {
+    let schema = spawn_custom_mutation_app("subgraph_custom_mutation_guard_test.db").await;
+
+    let request = async_graphql::Request::new(
+        r#"
+        mutation {
+            close_account(account_id: 2) {
+                data {
+                    status
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert_eq!(response.errors[0].message, "Account can not be closed");
+
+    let request = async_graphql::Request::new(
+        r#"
+        query {
+            get_account(get_account_input: { query: { id: 2 } }) {
+                data {
+                    status
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    let json = response.data.into_json().unwrap();
+    assert_eq!(json["get_account"]["data"]["status"], "open");
+}
+
+#[tokio::test]
+async fn custom_mutation_returns_read_only_entity() {
+    let schema = spawn_custom_mutation_app("subgraph_custom_mutation_read_only_test.db").await;
+
+    let request = async_graphql::Request::new(
+        r#"
+        mutation {
+            reopen_account(account_id: 1) {
+                data {
+                    id
+                    status
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let json = response.data.into_json().unwrap();
+    assert_eq!(json["reopen_account"]["data"][0]["id"], 1);
+    assert_eq!(json["reopen_account"]["data"][0]["status"], "open");
+}
+
+#[tokio::test]
+async fn custom_mutation_config_errors_fail_startup() {
+    let mut subgraph_config =
+        custom_mutation_config("subgraph_custom_mutation_startup_test.db").await;
+    if let Some(mutations) = subgraph_config.service.mutations.as_mut() {
+        mutations[0].returns = Some("missing".to_string());
+    }
+
+    let error = match subgraph::run(custom_mutation_args(), subgraph_config).await {
+        Ok(_) => panic!("Expected the service to fail to start."),
+        Err(error) => error,
+    };
+    assert_eq!(
+        error.to_string(),
+        "Custom mutation close_account returns unknown entity missing."
+    );
+}
@@ -0,0 +1,209 @@
+use async_graphql::dynamic::Schema;
+use http::HeaderMap;
+use subgraph::{
+    cli_args::CliArgs,
+    configuration::subgraph::{
+        data_sources::sql::{DialectEnum, SqlDataSourceConfig},
+        SubGraphConfig,
+    },
+    data_sources::sql::{PoolEnum, SqlDataSource},
+};
+
+fn identifier_safety_args() -> CliArgs {
+    CliArgs {
+        config: None,
+        port: Some(0),
+        log_level: None,
+        generate_keypair: false,
+        migrate: None,
+        steps: None,
+        migration_name: None,
+        migrate_only: false,
+        watch: false,
+        host: false,
+    }
+}
+
+/// Creates a database with an `order` table, whose name and `group` column are reserved words.
+async fn identifier_safety_config(db_name: &str) -> SubGraphConfig {
+    let path = std::env::temp_dir().join(db_name);
+    let _ = std::fs::remove_file(&path);
+    let uri = format!("sqlite:{}?mode=rwc", path.display());
+
+    let pool = SqlDataSource::create_pool(&SqlDataSourceConfig {
+        name: "identifier_data_source".to_string(),
+        uri: uri.clone(),
+        dialect: DialectEnum::SQLITE,
+        sqlite_extensions: None,
+        migrations_path: None,
+        pool: None,
+        replicas: None,
+        queries: None,
+        schema: None,
+    })
+    .await
+    .unwrap();
+    if let PoolEnum::SqLite(pool) = pool {
+        sqlx::query(
+            "CREATE TABLE `order` (id INTEGER PRIMARY KEY AUTOINCREMENT, `group` TEXT NOT NULL, total INTEGER NOT NULL);",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO `order` (`group`, total) VALUES ('a', 3), ('b', 1), ('c', 2);")
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+
+    let config = format!(
+        r#"
+        [service]
+        name = "identifier_safety"
+
+        [[service.data_sources]]
+        [service.data_sources.SQL]
+        name = "identifier_data_source"
+        uri = "{uri}"
+        dialect = "SQLITE"
+
+        [[service.entities]]
+        name = "order"
+        data_source = {{ from = "identifier_data_source", table = "order" }}
+        fields = [
+          {{ name = "id", scalar = "Int", required = true, exclude_from_input = ["CreateOne"] }},
+          {{ name = "group", scalar = "String", required = true }},
+          {{ name = "total", scalar = "Int", required = true }},
+        ]
+        "#
+    );
+    toml::from_str::<SubGraphConfig>(&config).unwrap()
+}
+
+async fn spawn_identifier_safety_app(db_name: &str) -> Schema {
+    let subgraph_config = identifier_safety_config(db_name).await;
+    let (server, schema, _shutdown) = subgraph::run(identifier_safety_args(), subgraph_config)
+        .await
+        .expect("Failed to run server.");
+    let _ = tokio::spawn(server);
+    schema
+}
+
+async fn get_order_totals(schema: &Schema) -> Vec<i64> {
+    let request = async_graphql::Request::new(
+        r#"
+        query {
+            get_orders(get_orders_input: { query: {}, opts: { sort: [{ field: "total", direction: "ASC" }] } }) {
+                data {
+                    total
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let json = response.data.into_json().unwrap();
+    json["get_orders"]["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|order| order["total"].as_i64().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn quotes_reserved_word_identifiers() {
+    let schema = spawn_identifier_safety_app("subgraph_identifier_reserved_test.db").await;
+
+    let request = async_graphql::Request::new(
+        r#"
+        mutation {
+            create_order(create_order_input: { values: { group: "d", total: 4 } }) {
+                data {
+                    id
+                    group
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+    let request = async_graphql::Request::new(
+        r#"
+        mutation {
+            update_orders(update_orders_input: { values: { group: "e" }, query: { group: "d" } }) {
+                data {
+                    group
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+    let request = async_graphql::Request::new(
+        r#"
+        query {
+            get_order(get_order_input: { query: { group: "e" } }) {
+                data {
+                    total
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let json = response.data.into_json().unwrap();
+    assert_eq!(json["get_order"]["data"]["total"], 4);
+
+    assert_eq!(get_order_totals(&schema).await, vec![1, 2, 3, 4]);
+}
+
+#[tokio::test]
+async fn rejects_malicious_sort_field() {
+    let schema = spawn_identifier_safety_app("subgraph_identifier_sort_test.db").await;
+
+    let request = async_graphql::Request::new(
+        r#"
+        query {
+            get_orders(get_orders_input: { query: {}, opts: { sort: [{ field: "total; DROP TABLE `order`; --", direction: "ASC" }] } }) {
+                data {
+                    id
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert_eq!(response.errors.len(), 1);
+    assert!(response.errors[0].message.contains("Invalid sort field"));
+
+    // Unknown columns are rejected as well, rather than passed to the database.
+    let request = async_graphql::Request::new(
+        r#"
+        query {
+            get_orders(get_orders_input: { query: {}, opts: { sort: [{ field: "(SELECT 1)", direction: "ASC" }] } }) {
+                data {
+                    id
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert!(response.errors[0].message.contains("Invalid sort field"));
+
+    // The table is untouched.
+    assert_eq!(get_order_totals(&schema).await, vec![1, 2, 3]);
+}
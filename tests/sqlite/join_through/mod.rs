@@ -0,0 +1,399 @@
+use async_graphql::dynamic::Schema;
+use http::HeaderMap;
+use subgraph::{
+    cli_args::CliArgs,
+    configuration::subgraph::{
+        data_sources::sql::{DialectEnum, SqlDataSourceConfig},
+        SubGraphConfig,
+    },
+    data_sources::sql::{PoolEnum, SqlDataSource},
+};
+
+fn join_through_args() -> CliArgs {
+    CliArgs {
+        config: None,
+        port: Some(0),
+        log_level: None,
+        generate_keypair: false,
+        migrate: None,
+        steps: None,
+        migration_name: None,
+        migrate_only: false,
+        watch: false,
+        host: false,
+    }
+}
+
+/// Creates a database of posts and tags joined through a `post_tags` junction table.
+async fn spawn_join_through_app(db_name: &str) -> Schema {
+    spawn_guarded_join_through_app(db_name, "{}").await
+}
+
+/// Creates the app with the provided resolvers of the `post_tag` junction entity.
+async fn spawn_guarded_join_through_app(db_name: &str, junction_resolvers: &str) -> Schema {
+    let path = std::env::temp_dir().join(db_name);
+    let _ = std::fs::remove_file(&path);
+    let uri = format!("sqlite:{}?mode=rwc", path.display());
+
+    let pool = SqlDataSource::create_pool(&SqlDataSourceConfig {
+        name: "blog_data_source".to_string(),
+        uri: uri.clone(),
+        dialect: DialectEnum::SQLITE,
+        sqlite_extensions: None,
+        migrations_path: None,
+        pool: None,
+        replicas: None,
+        queries: None,
+        schema: None,
+    })
+    .await
+    .unwrap();
+    if let PoolEnum::SqLite(pool) = pool {
+        for statement in [
+            "CREATE TABLE posts (id INTEGER PRIMARY KEY, title TEXT NOT NULL);",
+            "CREATE TABLE tags (id INTEGER PRIMARY KEY, name TEXT NOT NULL);",
+            "CREATE TABLE post_tags (post_id INTEGER NOT NULL, tag_id INTEGER NOT NULL CHECK (tag_id > 0));",
+            "INSERT INTO posts (id, title) VALUES (1, 'first'), (2, 'second'), (3, 'third');",
+            "INSERT INTO tags (id, name) VALUES (1, 'rust'), (2, 'graphql'), (3, 'sql');",
+            "INSERT INTO post_tags (post_id, tag_id) VALUES (1, 1), (1, 2), (2, 2), (2, 3), (1, 3);",
+        ] {
+            sqlx::query(statement).execute(&pool).await.unwrap();
+        }
+    }
+
+    let config = format!(
+        r#"
+        [service]
+        name = "join_through"
+
+        [[service.data_sources]]
+        [service.data_sources.SQL]
+        name = "blog_data_source"
+        uri = "{uri}"
+        dialect = "SQLITE"
+
+        [[service.entities]]
+        name = "post"
+        data_source = {{ from = "blog_data_source", table = "posts" }}
+        fields = [
+          {{ name = "id", scalar = "Int", required = true, exclude_from_input = ["CreateOne", "UpdateOne"] }},
+          {{ name = "title", scalar = "String", required = true, exclude_from_input = ["UpdateOne"] }},
+          {{ name = "tags", scalar = "Int", list = true, as_type = "tag", join_on = "id", join_from = "id", join_through = {{ entity = "post_tag", join_from = "post_id", join_on = "tag_id" }} }},
+        ]
+
+        [[service.entities]]
+        name = "tag"
+        data_source = {{ from = "blog_data_source", table = "tags" }}
+        fields = [
+          {{ name = "id", scalar = "Int", required = true }},
+          {{ name = "name", scalar = "String", required = true }},
+        ]
+
+        [[service.entities]]
+        name = "post_tag"
+        data_source = {{ from = "blog_data_source", table = "post_tags", resolvers = {junction_resolvers} }}
+        fields = [
+          {{ name = "post_id", scalar = "Int", required = true }},
+          {{ name = "tag_id", scalar = "Int", required = true }},
+        ]
+        "#
+    );
+    let subgraph_config = toml::from_str::<SubGraphConfig>(&config).unwrap();
+    let (server, schema, _shutdown) = subgraph::run(join_through_args(), subgraph_config)
+        .await
+        .expect("Failed to run server.");
+    let _ = tokio::spawn(server);
+    schema
+}
+
+/// Gets the names of the tags of each post, ordered by post id.
+async fn get_post_tags(schema: &Schema) -> Vec<Vec<String>> {
+    let request = async_graphql::Request::new(
+        r#"
+        query {
+            get_posts(get_posts_input: { query: {}, opts: { sort: [{ field: "id", direction: "ASC" }] } }) {
+                data {
+                    tags(tags: { query: {}, opts: { sort: [{ field: "name", direction: "ASC" }] } }) {
+                        data {
+                            name
+                        }
+                    }
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let json = response.data.into_json().unwrap();
+
+    json["get_posts"]["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|post| {
+            post["tags"]["data"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|tag| tag["name"].as_str().unwrap().to_string())
+                .collect()
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn resolves_joins_through_junction_entities() {
+    let schema = spawn_join_through_app("subgraph_join_through_resolve_test.db").await;
+
+    assert_eq!(
+        get_post_tags(&schema).await,
+        vec![
+            vec!["graphql", "rust", "sql"],
+            vec!["graphql", "sql"],
+            vec![],
+        ]
+    );
+
+    // Filters and pagination apply to the tags of each post.
+    let request = async_graphql::Request::new(
+        r#"
+        query {
+            get_posts(get_posts_input: { query: {}, opts: { sort: [{ field: "id", direction: "ASC" }] } }) {
+                data {
+                    tags(tags: { query: { OR: [{ name: "rust" }, { name: "sql" }] }, opts: { per_page: 1, sort: [{ field: "name", direction: "DESC" }] } }) {
+                        data {
+                            name
+                        }
+                        meta {
+                            total_count
+                        }
+                    }
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let json = response.data.into_json().unwrap();
+    let posts = json["get_posts"]["data"].as_array().unwrap();
+    assert_eq!(posts[0]["tags"]["data"][0]["name"], "sql");
+    assert_eq!(posts[0]["tags"]["meta"]["total_count"], 2);
+    assert_eq!(posts[1]["tags"]["data"][0]["name"], "sql");
+    assert_eq!(posts[1]["tags"]["meta"]["total_count"], 1);
+    assert_eq!(posts[2]["tags"]["meta"]["total_count"], 0);
+}
+
+#[tokio::test]
+async fn connects_and_disconnects_joins_through_junction_entities() {
+    let schema = spawn_join_through_app("subgraph_join_through_connect_test.db").await;
+
+    let request = async_graphql::Request::new(
+        r#"
+        mutation {
+            create_post(create_post_input: { values: { title: "fourth", tags: { connect: [1, 3] } } }) {
+                data {
+                    id
+                    tags(tags: { query: {}, opts: { sort: [{ field: "name", direction: "ASC" }] } }) {
+                        data {
+                            name
+                        }
+                    }
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let json = response.data.into_json().unwrap();
+    assert_eq!(json["create_post"]["data"]["id"], 4);
+    assert_eq!(
+        json["create_post"]["data"]["tags"]["data"],
+        serde_json::json!([{ "name": "rust" }, { "name": "sql" }])
+    );
+
+    // Connecting an already connected tag does not duplicate it.
+    let request = async_graphql::Request::new(
+        r#"
+        mutation {
+            update_posts(update_posts_input: { values: { tags: { connect: [2, 3], disconnect: [1] } }, query: { OR: [{ id: 2 }, { id: 4 }] } }) {
+                data {
+                    id
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let json = response.data.into_json().unwrap();
+    assert_eq!(
+        json["update_posts"]["data"],
+        serde_json::json!([{ "id": 2 }, { "id": 4 }])
+    );
+
+    assert_eq!(
+        get_post_tags(&schema).await,
+        vec![
+            vec!["graphql", "rust", "sql"],
+            vec!["graphql", "sql"],
+            vec![],
+            vec!["graphql", "sql"],
+        ]
+    );
+}
+
+#[tokio::test]
+async fn failed_connect_keeps_existing_joins() {
+    let schema = spawn_join_through_app("subgraph_join_through_rollback_test.db").await;
+
+    // The junction rejects the second tag, so the connected tag must not be removed.
+    let request = async_graphql::Request::new(
+        r#"
+        mutation {
+            update_posts(update_posts_input: { values: { tags: { connect: [1, -1] } }, query: { id: 1 } }) {
+                data {
+                    id
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert_eq!(response.errors[0].message, "Error executing transaction.");
+
+    assert_eq!(
+        get_post_tags(&schema).await[0],
+        vec!["graphql", "rust", "sql"]
+    );
+}
+
+#[tokio::test]
+async fn failed_connect_rolls_back_the_mutation() {
+    let schema = spawn_join_through_app("subgraph_join_through_mutation_rollback_test.db").await;
+
+    let request = async_graphql::Request::new(
+        r#"
+        mutation {
+            create_post(create_post_input: { values: { title: "fourth", tags: { connect: [1, -1] } } }) {
+                data {
+                    id
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert_eq!(response.errors[0].message, "Error executing transaction.");
+
+    let request = async_graphql::Request::new(
+        r#"
+        mutation {
+            update_posts(update_posts_input: { values: { title: "updated", tags: { connect: [-1] } }, query: { id: 1 } }) {
+                data {
+                    id
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert_eq!(response.errors[0].message, "Error executing transaction.");
+
+    // Neither the created post nor the updated title is kept.
+    let request = async_graphql::Request::new(
+        r#"
+        query {
+            get_posts(get_posts_input: { query: {}, opts: { sort: [{ field: "id", direction: "ASC" }] } }) {
+                data {
+                    title
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let json = response.data.into_json().unwrap();
+    assert_eq!(
+        json["get_posts"]["data"],
+        serde_json::json!([{ "title": "first" }, { "title": "second" }, { "title": "third" }])
+    );
+}
+
+#[tokio::test]
+async fn connecting_duplicate_keys_connects_once() {
+    let schema = spawn_join_through_app("subgraph_join_through_duplicate_test.db").await;
+
+    let request = async_graphql::Request::new(
+        r#"
+        mutation {
+            update_posts(update_posts_input: { values: { tags: { connect: [1, 1] } }, query: { id: 3 } }) {
+                data {
+                    id
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+    assert_eq!(get_post_tags(&schema).await[2], vec!["rust"]);
+}
+
+#[tokio::test]
+async fn junction_guards_are_checked() {
+    let junction_resolvers = r#"{ create_one = { guards = [{ name = "locked_tag", if_expr = "contains(input(\"values\", \"tag_id\"), \"3\")", then_msg = "Tag can not be connected" }] }, update_many = { guards = [{ name = "pinned_tag", if_expr = "contains(input(\"query\", \"tag_id\"), \"2\")", then_msg = "Tag can not be disconnected" }] } }"#;
+    let schema =
+        spawn_guarded_join_through_app("subgraph_join_through_guard_test.db", junction_resolvers)
+            .await;
+
+    for (tags, message) in [
+        ("{ connect: [3] }", "Tag can not be connected"),
+        ("{ disconnect: [2] }", "Tag can not be disconnected"),
+    ] {
+        let request = async_graphql::Request::new(format!(
+            r#"
+            mutation {{
+                update_posts(update_posts_input: {{ values: {{ title: "guarded", tags: {tags} }}, query: {{ id: 2 }} }}) {{
+                    data {{
+                        id
+                    }}
+                }}
+            }}
+            "#
+        ))
+        .data(HeaderMap::new());
+        let response = schema.execute(request).await;
+        assert_eq!(response.errors[0].message, message);
+    }
+
+    let request = async_graphql::Request::new(
+        r#"
+        mutation {
+            update_posts(update_posts_input: { values: { tags: { connect: [1], disconnect: [3] } }, query: { id: 2 } }) {
+                data {
+                    id
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+    assert_eq!(get_post_tags(&schema).await[1], vec!["graphql", "rust"]);
+}
@@ -0,0 +1,181 @@
+use async_graphql::dynamic::Schema;
+use http::HeaderMap;
+use subgraph::{
+    cli_args::CliArgs,
+    configuration::subgraph::{
+        data_sources::sql::{DialectEnum, SqlDataSourceConfig},
+        SubGraphConfig,
+    },
+    data_sources::sql::{PoolEnum, SqlDataSource},
+};
+
+/// Creates a database with an `articles` table, storing `tags` and `scores` as JSON arrays.
+async fn spawn_list_column_app(db_name: &str) -> Schema {
+    let path = std::env::temp_dir().join(db_name);
+    let _ = std::fs::remove_file(&path);
+    let uri = format!("sqlite:{}?mode=rwc", path.display());
+
+    let pool = SqlDataSource::create_pool(&SqlDataSourceConfig {
+        name: "list_data_source".to_string(),
+        uri: uri.clone(),
+        dialect: DialectEnum::SQLITE,
+        sqlite_extensions: None,
+        migrations_path: None,
+        pool: None,
+        replicas: None,
+        queries: None,
+        schema: None,
+    })
+    .await
+    .unwrap();
+    if let PoolEnum::SqLite(pool) = pool {
+        sqlx::query(
+            "CREATE TABLE articles (id INTEGER PRIMARY KEY AUTOINCREMENT, title TEXT NOT NULL, tags TEXT NOT NULL, scores TEXT);",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let config = format!(
+        r#"
+        [service]
+        name = "list_columns"
+
+        [[service.data_sources]]
+        [service.data_sources.SQL]
+        name = "list_data_source"
+        uri = "{uri}"
+        dialect = "SQLITE"
+
+        [[service.entities]]
+        name = "article"
+        data_source = {{ from = "list_data_source", table = "articles" }}
+        fields = [
+          {{ name = "id", scalar = "Int", required = true, exclude_from_input = ["CreateOne", "UpdateMany"] }},
+          {{ name = "title", scalar = "String", required = true }},
+          {{ name = "tags", scalar = "String", required = true, list = true }},
+          {{ name = "scores", scalar = "Int", list = true }},
+        ]
+        "#
+    );
+    let subgraph_config = toml::from_str::<SubGraphConfig>(&config).unwrap();
+
+    let args = CliArgs {
+        config: None,
+        port: Some(0),
+        log_level: None,
+        generate_keypair: false,
+        migrate: None,
+        steps: None,
+        migration_name: None,
+        migrate_only: false,
+        watch: false,
+        host: false,
+    };
+    let (server, schema, _shutdown) = subgraph::run(args, subgraph_config)
+        .await
+        .expect("Failed to run server.");
+    let _ = tokio::spawn(server);
+    schema
+}
+
+#[tokio::test]
+async fn list_fields_round_trip() {
+    let schema = spawn_list_column_app("subgraph_list_column_test.db").await;
+
+    for (title, tags, scores) in [
+        ("Rust", r#"["code", "systems"]"#, "[1, 2, 3]"),
+        ("Bread", r#"["food"]"#, "[]"),
+    ] {
+        let request = async_graphql::Request::new(format!(
+            r#"
+            mutation {{
+                create_article(create_article_input: {{ values: {{ title: "{}", tags: {}, scores: {} }} }}) {{
+                    data {{
+                        title
+                        tags
+                        scores
+                    }}
+                }}
+            }}
+            "#,
+            title, tags, scores
+        ));
+        let response = schema.execute(request.data(HeaderMap::new())).await;
+        assert!(response.is_ok(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(
+            data["create_article"]["data"]["tags"],
+            serde_json::from_str::<serde_json::Value>(tags).unwrap()
+        );
+        assert_eq!(
+            data["create_article"]["data"]["scores"],
+            serde_json::from_str::<serde_json::Value>(scores).unwrap()
+        );
+    }
+
+    // List filters match rows sharing at least one value.
+    let request = async_graphql::Request::new(
+        r#"
+        {
+            get_articles(get_articles_input: { query: { tags: ["systems", "science"] } }) {
+                data {
+                    title
+                    tags
+                }
+                meta {
+                    total_count
+                }
+            }
+        }
+        "#,
+    );
+    let response = schema.execute(request.data(HeaderMap::new())).await;
+    assert!(response.is_ok(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["get_articles"]["meta"]["total_count"], 1);
+    assert_eq!(data["get_articles"]["data"][0]["title"], "Rust");
+
+    let request = async_graphql::Request::new(
+        r#"
+        mutation {
+            update_articles(update_articles_input: { values: { tags: ["food", "baking"] }, query: { title: "Bread" } }) {
+                data {
+                    tags
+                }
+            }
+        }
+        "#,
+    );
+    let response = schema.execute(request.data(HeaderMap::new())).await;
+    assert!(response.is_ok(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(
+        data["update_articles"]["data"][0]["tags"],
+        serde_json::json!(["food", "baking"])
+    );
+}
+
+#[tokio::test]
+async fn list_fields_reject_comparison_operators() {
+    let schema = spawn_list_column_app("subgraph_list_column_operator_test.db").await;
+
+    let request = async_graphql::Request::new(
+        r#"
+        {
+            get_articles(get_articles_input: { query: { LIKE: { tags: ["sys%"] } } }) {
+                data {
+                    title
+                }
+            }
+        }
+        "#,
+    );
+    let response = schema.execute(request.data(HeaderMap::new())).await;
+    assert!(response.is_err());
+    assert_eq!(
+        response.errors[0].message,
+        "Filter operator LIKE is not supported on list field `articles`.`tags`"
+    );
+}
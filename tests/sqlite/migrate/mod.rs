@@ -0,0 +1,294 @@
+use subgraph::{
+    cli_args::CliArgs,
+    configuration::subgraph::{
+        data_sources::sql::{DialectEnum, SqlDataSourceConfig},
+        SubGraphConfig,
+    },
+    data_sources::sql::{PoolEnum, SqlDataSource},
+};
+
+fn migrate_config(db_name: &str) -> SqlDataSourceConfig {
+    let path = std::env::temp_dir().join(db_name);
+    let _ = std::fs::remove_file(&path);
+    SqlDataSourceConfig {
+        name: "migrate_data_source".to_string(),
+        uri: format!("sqlite:{}?mode=rwc", path.display()),
+        dialect: DialectEnum::SQLITE,
+        sqlite_extensions: None,
+        migrations_path: Some("./tests/sqlite/migrations".to_string()),
+        pool: None,
+        replicas: None,
+        queries: None,
+        schema: None,
+    }
+}
+
+fn migrate_args(migrate: &str, steps: Option<u32>) -> CliArgs {
+    CliArgs {
+        config: None,
+        port: None,
+        log_level: None,
+        generate_keypair: false,
+        migrate: Some(migrate.to_string()),
+        steps,
+        migration_name: None,
+        migrate_only: true,
+        watch: false,
+        host: false,
+    }
+}
+
+#[tokio::test]
+async fn run_status_and_revert_migrations() {
+    let config = migrate_config("subgraph_migrate_test.db");
+    let subgraph_config = generate_subgraph_config("./tests/sqlite/migrations", "");
+    let pool = SqlDataSource::create_pool(&config).await.unwrap();
+    let migrator = sqlx::migrate::Migrator::new(std::path::Path::new(
+        config.migrations_path.as_ref().unwrap(),
+    ))
+    .await
+    .unwrap();
+
+    let status = SqlDataSource::get_migration_status(&pool, &migrator)
+        .await
+        .unwrap();
+    assert_eq!(status.len(), 1);
+    assert!(!status[0].applied);
+    assert!(status[0].reversible);
+
+    SqlDataSource::migrate(&config, &migrate_args("run", None), &subgraph_config)
+        .await
+        .unwrap();
+    let status = SqlDataSource::get_migration_status(&pool, &migrator)
+        .await
+        .unwrap();
+    assert!(status[0].applied);
+
+    SqlDataSource::migrate(&config, &migrate_args("status", None), &subgraph_config)
+        .await
+        .unwrap();
+
+    SqlDataSource::migrate(&config, &migrate_args("revert", Some(1)), &subgraph_config)
+        .await
+        .unwrap();
+    let status = SqlDataSource::get_migration_status(&pool, &migrator)
+        .await
+        .unwrap();
+    assert!(!status[0].applied);
+}
+
+fn generate_subgraph_config(migrations_path: &str, extra_field: &str) -> SubGraphConfig {
+    let config = format!(
+        r#"
+        [service]
+        name = "migrate_generate"
+
+        [[service.data_sources]]
+        [service.data_sources.SQL]
+        name = "migrate_data_source"
+        uri = "sqlite::memory:"
+        dialect = "SQLITE"
+        migrations_path = "{migrations_path}"
+
+        [[service.entities]]
+        name = "author"
+        data_source = {{ from = "migrate_data_source", table = "authors" }}
+        fields = [
+          {{ name = "id", scalar = "Int", required = true, exclude_from_input = ["CreateOne"] }},
+          {{ name = "name", scalar = "String", required = true }},
+          {{ name = "books", scalar = "Int", list = true, as_type = "book", join_on = "author_id", join_from = "id" }},
+        ]
+
+        [[service.entities]]
+        name = "book"
+        data_source = {{ from = "migrate_data_source", table = "books" }}
+        fields = [
+          {{ name = "id", scalar = "Int", required = true, exclude_from_input = ["CreateOne"] }},
+          {{ name = "title", scalar = "String", required = true }},
+          {{ name = "author_id", scalar = "Int", required = true, as_type = "author", join_on = "id" }},
+          {extra_field}
+        ]
+        "#
+    );
+    toml::from_str::<SubGraphConfig>(&config).unwrap()
+}
+
+#[tokio::test]
+async fn generate_migrations_from_entities() {
+    let migrations_dir = std::env::temp_dir().join("subgraph_generate_migrations");
+    let _ = std::fs::remove_dir_all(&migrations_dir);
+    let migrations_path = migrations_dir.display().to_string();
+
+    let mut config = migrate_config("subgraph_generate_test.db");
+    config.migrations_path = Some(migrations_path.clone());
+    let pool = SqlDataSource::create_pool(&config).await.unwrap();
+
+    // Initial generation creates both tables, with the foreign key on books.
+    let subgraph_config = generate_subgraph_config(&migrations_path, "");
+    let version = SqlDataSource::generate_migration(&pool, &config, &subgraph_config, None)
+        .await
+        .unwrap()
+        .unwrap();
+    let up = std::fs::read_to_string(
+        migrations_dir.join(format!("{}_subgraph_generated.up.sql", version)),
+    )
+    .unwrap();
+    assert!(up.contains("CREATE TABLE `authors`"));
+    assert!(up.contains("`id` INTEGER PRIMARY KEY AUTOINCREMENT"));
+    assert!(up.contains("`title` TEXT NOT NULL"));
+    assert!(up.contains("FOREIGN KEY (`author_id`) REFERENCES `authors` (`id`)"));
+    assert!(!up.contains("`books` INTEGER"));
+
+    // Without changes, no migration is generated.
+    let unchanged = SqlDataSource::generate_migration(&pool, &config, &subgraph_config, None)
+        .await
+        .unwrap();
+    assert!(unchanged.is_none());
+
+    SqlDataSource::migrate(&config, &migrate_args("run", None), &subgraph_config)
+        .await
+        .unwrap();
+
+    // Adding a field generates an add column migration.
+    std::thread::sleep(std::time::Duration::from_secs(1));
+    let subgraph_config =
+        generate_subgraph_config(&migrations_path, r#"{ name = "pages", scalar = "Int" },"#);
+    let version = SqlDataSource::generate_migration(
+        &pool,
+        &config,
+        &subgraph_config,
+        Some("add pages".to_string()),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    let up = std::fs::read_to_string(migrations_dir.join(format!("{}_add_pages.up.sql", version)))
+        .unwrap();
+    assert!(up.contains("ALTER TABLE `books` ADD COLUMN `pages` INTEGER;"));
+    let down =
+        std::fs::read_to_string(migrations_dir.join(format!("{}_add_pages.down.sql", version)))
+            .unwrap();
+    assert!(down.contains("ALTER TABLE `books` DROP COLUMN `pages`;"));
+
+    SqlDataSource::migrate(&config, &migrate_args("run", None), &subgraph_config)
+        .await
+        .unwrap();
+    SqlDataSource::migrate(&config, &migrate_args("revert", Some(2)), &subgraph_config)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn generate_migrations_from_live_schema() {
+    let migrations_dir = std::env::temp_dir().join("subgraph_live_schema_migrations");
+    let _ = std::fs::remove_dir_all(&migrations_dir);
+    let migrations_path = migrations_dir.display().to_string();
+
+    let mut config = migrate_config("subgraph_live_schema_test.db");
+    config.migrations_path = Some(migrations_path.clone());
+    let pool = SqlDataSource::create_pool(&config).await.unwrap();
+    if let PoolEnum::SqLite(sqlite_pool) = &pool {
+        sqlx::query(
+            "CREATE TABLE authors (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL)",
+        )
+        .execute(sqlite_pool)
+        .await
+        .unwrap();
+    }
+
+    // Without a snapshot, existing tables are not created again.
+    let subgraph_config = generate_subgraph_config(&migrations_path, "");
+    let version = SqlDataSource::generate_migration(&pool, &config, &subgraph_config, None)
+        .await
+        .unwrap()
+        .unwrap();
+    let up = std::fs::read_to_string(
+        migrations_dir.join(format!("{}_subgraph_generated.up.sql", version)),
+    )
+    .unwrap();
+    assert!(!up.contains("CREATE TABLE `authors`"));
+    assert!(!up.contains("ALTER TABLE `authors`"));
+    assert!(up.contains("CREATE TABLE `books`"));
+}
+
+#[tokio::test]
+async fn generate_migrations_from_live_schema_drift() {
+    let migrations_dir = std::env::temp_dir().join("subgraph_live_schema_drift_migrations");
+    let _ = std::fs::remove_dir_all(&migrations_dir);
+    let migrations_path = migrations_dir.display().to_string();
+
+    let mut config = migrate_config("subgraph_live_schema_drift_test.db");
+    config.migrations_path = Some(migrations_path.clone());
+    let pool = SqlDataSource::create_pool(&config).await.unwrap();
+    if let PoolEnum::SqLite(sqlite_pool) = &pool {
+        for statement in [
+            "CREATE TABLE authors (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT, legacy TEXT)",
+            "CREATE TABLE books (id INTEGER PRIMARY KEY AUTOINCREMENT, title TEXT NOT NULL, author_id INTEGER NOT NULL REFERENCES authors (id))",
+            "CREATE TABLE archived_books (id INTEGER PRIMARY KEY AUTOINCREMENT, title TEXT NOT NULL)",
+        ] {
+            sqlx::query(statement).execute(sqlite_pool).await.unwrap();
+        }
+    }
+
+    // Nullability drift, and columns and tables no longer configured, are migrated.
+    let subgraph_config = generate_subgraph_config(&migrations_path, "");
+    let version = SqlDataSource::generate_migration(&pool, &config, &subgraph_config, None)
+        .await
+        .unwrap()
+        .unwrap();
+    let up = std::fs::read_to_string(
+        migrations_dir.join(format!("{}_subgraph_generated.up.sql", version)),
+    )
+    .unwrap();
+    assert!(up.contains("-- SQLite can not alter column name on authors."));
+    assert!(up.contains("ALTER TABLE `authors` DROP COLUMN `legacy`;"));
+    assert!(up.contains("DROP TABLE `archived_books`;"));
+    assert!(!up.contains("`books` ("));
+    let down = std::fs::read_to_string(
+        migrations_dir.join(format!("{}_subgraph_generated.down.sql", version)),
+    )
+    .unwrap();
+    assert!(down.contains("ALTER TABLE `authors` ADD COLUMN `legacy` TEXT;"));
+    assert!(down.contains("CREATE TABLE `archived_books`"));
+}
+
+#[tokio::test]
+async fn baseline_writes_snapshot_without_migration() {
+    let migrations_dir = std::env::temp_dir().join("subgraph_baseline_migrations");
+    let _ = std::fs::remove_dir_all(&migrations_dir);
+    let migrations_path = migrations_dir.display().to_string();
+
+    let mut config = migrate_config("subgraph_baseline_test.db");
+    config.migrations_path = Some(migrations_path.clone());
+    let subgraph_config = generate_subgraph_config(&migrations_path, "");
+
+    SqlDataSource::migrate(&config, &migrate_args("baseline", None), &subgraph_config)
+        .await
+        .unwrap();
+    let files = std::fs::read_dir(&migrations_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<String>>();
+    assert_eq!(files, vec!["subgraph_snapshot.json".to_string()]);
+
+    let pool = SqlDataSource::create_pool(&config).await.unwrap();
+    let unchanged = SqlDataSource::generate_migration(&pool, &config, &subgraph_config, None)
+        .await
+        .unwrap();
+    assert!(unchanged.is_none());
+}
+
+#[tokio::test]
+async fn generate_requires_migrations_path() {
+    let mut config = migrate_config("subgraph_generate_no_path_test.db");
+    config.migrations_path = None;
+    let subgraph_config = generate_subgraph_config("./tests/sqlite/migrations", "");
+
+    let error = SqlDataSource::migrate(&config, &migrate_args("generate", None), &subgraph_config)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error.message,
+        "Migrations path required to generate migrations: migrate_data_source"
+    );
+}
@@ -1,4 +1,19 @@
+mod batched_join;
+mod composite_key;
+mod connection_pool;
 mod create_one;
+mod custom_mutation;
 mod find_many;
 mod find_one;
+mod identifier_safety;
+mod join_through;
+mod list_column;
+mod migrate;
+mod named_query;
+mod query_limits;
+mod query_plan_cache;
+mod read_replica;
+mod table_schema;
 mod update_many;
+mod update_operators;
+mod uuid_key;
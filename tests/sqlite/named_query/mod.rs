@@ -0,0 +1,275 @@
+use async_graphql::dynamic::Schema;
+use http::HeaderMap;
+use subgraph::{
+    cli_args::CliArgs,
+    configuration::subgraph::{
+        data_sources::sql::{DialectEnum, SqlDataSourceConfig},
+        SubGraphConfig,
+    },
+    data_sources::sql::{PoolEnum, SqlDataSource},
+};
+
+fn named_query_args() -> CliArgs {
+    CliArgs {
+        config: None,
+        port: Some(0),
+        log_level: None,
+        generate_keypair: false,
+        migrate: None,
+        steps: None,
+        migration_name: None,
+        migrate_only: false,
+        watch: false,
+        host: false,
+    }
+}
+
+/// Creates a database of `sales`, reported per region by the `sales_by_region` named query.
+async fn named_query_config(db_name: &str) -> SubGraphConfig {
+    let path = std::env::temp_dir().join(db_name);
+    let _ = std::fs::remove_file(&path);
+    let uri = format!("sqlite:{}?mode=rwc", path.display());
+
+    let pool = SqlDataSource::create_pool(&SqlDataSourceConfig {
+        name: "reports_data_source".to_string(),
+        uri: uri.clone(),
+        dialect: DialectEnum::SQLITE,
+        sqlite_extensions: None,
+        migrations_path: None,
+        pool: None,
+        replicas: None,
+        queries: None,
+        schema: None,
+    })
+    .await
+    .unwrap();
+    if let PoolEnum::SqLite(pool) = pool {
+        sqlx::query(
+            "CREATE TABLE sales (id INTEGER PRIMARY KEY AUTOINCREMENT, region TEXT NOT NULL, amount INTEGER NOT NULL);",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO sales (region, amount) VALUES ('north', 10), ('north', 30), ('south', 50), ('east', 5), ('east', 15), ('west', 1);",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let config = format!(
+        r#"
+        [service]
+        name = "named_queries"
+
+        [[service.data_sources]]
+        [service.data_sources.SQL]
+        name = "reports_data_source"
+        uri = "{uri}"
+        dialect = "SQLITE"
+
+        [[service.data_sources.SQL.queries]]
+        name = "sales_by_region"
+        sql = """
+          SELECT region, SUM(amount) AS total, RANK() OVER (ORDER BY SUM(amount) DESC) AS rank
+          FROM sales
+          WHERE amount >= :min_amount AND region <> 'skip:me'
+          GROUP BY region;
+        """
+        params = [{{ name = "min_amount", scalar = "Int", required = true }}]
+
+        [[service.data_sources.SQL.queries]]
+        name = "sales_in_region"
+        sql = "SELECT id, region, amount FROM sales WHERE region = :region AND (:max_amount IS NULL OR amount <= :max_amount)"
+        params = [
+          {{ name = "region", scalar = "String", default_value = "\"north\"" }},
+          {{ name = "max_amount", scalar = "Int" }},
+        ]
+
+        [[service.entities]]
+        name = "region_sales"
+        data_source = {{ from = "reports_data_source", query = "sales_by_region" }}
+        fields = [
+          {{ name = "region", scalar = "String", required = true, primary_key = true }},
+          {{ name = "total", scalar = "Int", required = true }},
+          {{ name = "rank", scalar = "Int", required = true }},
+        ]
+
+        [[service.entities.guards]]
+        name = "positive_amount"
+        if_expr = "contains(input(\"query\", \"min_amount\"), \"0\")"
+        then_msg = "min_amount must be positive"
+
+        [[service.entities]]
+        name = "sale"
+        data_source = {{ from = "reports_data_source", query = "sales_in_region" }}
+        fields = [
+          {{ name = "id", scalar = "Int", required = true, primary_key = true }},
+          {{ name = "region", scalar = "String", required = true }},
+          {{ name = "amount", scalar = "Int", required = true }},
+        ]
+        "#
+    );
+    toml::from_str::<SubGraphConfig>(&config).unwrap()
+}
+
+async fn spawn_named_query_app(db_name: &str) -> Schema {
+    let subgraph_config = named_query_config(db_name).await;
+    let (server, schema, _shutdown) = subgraph::run(named_query_args(), subgraph_config)
+        .await
+        .expect("Failed to run server.");
+    let _ = tokio::spawn(server);
+    schema
+}
+
+#[tokio::test]
+async fn find_many_runs_named_query_with_pagination() {
+    let schema = spawn_named_query_app("subgraph_named_query_many_test.db").await;
+
+    let request = async_graphql::Request::new(
+        r#"
+        query {
+            get_region_saless(get_region_saless_input: { query: { min_amount: 5 }, opts: { per_page: 2, page: 1, sort: [{ field: "rank", direction: "ASC" }] } }) {
+                data {
+                    region
+                    total
+                    rank
+                }
+                meta {
+                    total_count
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let json = response.data.into_json().unwrap();
+    let data = json["get_region_saless"]["data"].as_array().unwrap();
+    assert_eq!(data.len(), 2);
+    assert_eq!(data[0]["region"], "south");
+    assert_eq!(data[0]["total"], 50);
+    assert_eq!(data[0]["rank"], 1);
+    assert_eq!(data[1]["region"], "north");
+    assert_eq!(json["get_region_saless"]["meta"]["total_count"], 3);
+}
+
+#[tokio::test]
+async fn find_one_binds_named_query_params() {
+    let schema = spawn_named_query_app("subgraph_named_query_one_test.db").await;
+
+    let request = async_graphql::Request::new(
+        r#"
+        query {
+            get_region_sales(get_region_sales_input: { query: { min_amount: 20 } }) {
+                data {
+                    region
+                    total
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let json = response.data.into_json().unwrap();
+    assert_eq!(json["get_region_sales"]["data"]["region"], "south");
+
+    // Parameters are required, matching the named query definition.
+    let request = async_graphql::Request::new(
+        r#"
+        query {
+            get_region_sales(get_region_sales_input: { query: {} }) {
+                data {
+                    region
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert!(!response.errors.is_empty());
+}
+
+#[tokio::test]
+async fn optional_named_query_params_bind_defaults_or_null() {
+    let schema = spawn_named_query_app("subgraph_named_query_optional_test.db").await;
+
+    let get_amounts = |query: &str| {
+        let request = async_graphql::Request::new(format!(
+            r#"
+            query {{
+                get_sales(get_sales_input: {{ query: {}, opts: {{ sort: [{{ field: "amount", direction: "ASC" }}] }} }}) {{
+                    data {{
+                        amount
+                    }}
+                }}
+            }}
+            "#,
+            query
+        ))
+        .data(HeaderMap::new());
+        let schema = schema.clone();
+        async move {
+            let response = schema.execute(request).await;
+            assert!(response.errors.is_empty(), "{:?}", response.errors);
+            let json = response.data.into_json().unwrap();
+            json["get_sales"]["data"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|row| row["amount"].as_i64().unwrap())
+                .collect::<Vec<i64>>()
+        }
+    };
+
+    // The region defaults to `north` and a missing `max_amount` binds null.
+    assert_eq!(get_amounts("{}").await, vec![10, 30]);
+    assert_eq!(get_amounts("{ max_amount: 20 }").await, vec![10]);
+    assert_eq!(get_amounts(r#"{ region: "east" }"#).await, vec![5, 15]);
+}
+
+#[tokio::test]
+async fn named_query_entities_apply_guards_and_are_read_only() {
+    let schema = spawn_named_query_app("subgraph_named_query_guard_test.db").await;
+
+    let request = async_graphql::Request::new(
+        r#"
+        query {
+            get_region_saless(get_region_saless_input: { query: { min_amount: 0 } }) {
+                data {
+                    region
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert_eq!(response.errors[0].message, "min_amount must be positive");
+
+    let sdl = schema.sdl();
+    assert!(!sdl.contains("create_region_sales"));
+    assert!(!sdl.contains("update_region_saless"));
+}
+
+#[tokio::test]
+async fn missing_named_query_fails_schema_creation() {
+    let mut subgraph_config = named_query_config("subgraph_named_query_missing_test.db").await;
+    if let Some(data_source) = subgraph_config.service.entities[0].data_source.as_mut() {
+        data_source.query = Some("missing_query".to_string());
+    }
+
+    let error = subgraph::run(named_query_args(), subgraph_config)
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(
+        error.to_string(),
+        "Query missing_query not found in data source reports_data_source"
+    );
+}
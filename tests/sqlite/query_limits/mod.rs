@@ -0,0 +1,170 @@
+use async_graphql::{dynamic::Schema, Value, Variables};
+use http::HeaderMap;
+use subgraph::{
+    cli_args::CliArgs,
+    configuration::subgraph::{
+        data_sources::sql::{DialectEnum, SqlDataSourceConfig},
+        SubGraphConfig,
+    },
+    data_sources::sql::{PoolEnum, SqlDataSource},
+};
+
+fn query_limits_args() -> CliArgs {
+    CliArgs {
+        config: None,
+        port: Some(0),
+        log_level: None,
+        generate_keypair: false,
+        migrate: None,
+        steps: None,
+        migration_name: None,
+        migrate_only: false,
+        watch: false,
+        host: false,
+    }
+}
+
+/// Creates a `users` table where each user may be the friend of another.
+async fn spawn_query_limits_app(db_name: &str) -> Schema {
+    let path = std::env::temp_dir().join(db_name);
+    let _ = std::fs::remove_file(&path);
+    let uri = format!("sqlite:{}?mode=rwc", path.display());
+
+    let pool = SqlDataSource::create_pool(&SqlDataSourceConfig {
+        name: "users_data_source".to_string(),
+        uri: uri.clone(),
+        dialect: DialectEnum::SQLITE,
+        sqlite_extensions: None,
+        migrations_path: None,
+        pool: None,
+        replicas: None,
+        queries: None,
+        schema: None,
+    })
+    .await
+    .unwrap();
+    if let PoolEnum::SqLite(pool) = pool {
+        sqlx::query(
+            "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL, friend_id INTEGER);",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO users (id, name, friend_id) VALUES (1, 'ann', NULL), (2, 'bob', 1);",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let config = format!(
+        r#"
+        [service]
+        name = "query_limits"
+        max_depth = 6
+        max_complexity = 50
+
+        [[service.data_sources]]
+        [service.data_sources.SQL]
+        name = "users_data_source"
+        uri = "{uri}"
+        dialect = "SQLITE"
+
+        [[service.entities]]
+        name = "user"
+        data_source = {{ from = "users_data_source", table = "users" }}
+        fields = [
+          {{ name = "id", scalar = "Int", required = true }},
+          {{ name = "name", scalar = "String", required = true }},
+          {{ name = "friend_id", scalar = "Int" }},
+          {{ name = "friends", scalar = "Int", list = true, as_type = "user", join_on = "friend_id", join_from = "id", cost = 5 }},
+        ]
+        "#
+    );
+    let subgraph_config = toml::from_str::<SubGraphConfig>(&config).unwrap();
+    let (server, schema, _shutdown) = subgraph::run(query_limits_args(), subgraph_config)
+        .await
+        .expect("Failed to run server.");
+    let _ = tokio::spawn(server);
+    schema
+}
+
+const FRIENDS_QUERY: &str = r#"
+    query ($per_page: Int) {
+        get_users(get_users_input: { query: {}, opts: { per_page: $per_page } }) {
+            data {
+                name
+                friends(friends: { query: {} }) {
+                    data {
+                        name
+                    }
+                }
+            }
+        }
+    }
+"#;
+
+#[tokio::test]
+async fn allows_queries_within_limits() {
+    let schema = spawn_query_limits_app("subgraph_query_limits_allow_test.db").await;
+
+    // 1 + (1 + 2 * (1 + (5 + (1 + 10 * 1)))) = 36
+    let request = async_graphql::Request::new(FRIENDS_QUERY)
+        .variables(Variables::from_json(serde_json::json!({ "per_page": 2 })))
+        .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+    let json = response.data.into_json().unwrap();
+    let users = json["get_users"]["data"].as_array().unwrap();
+    assert_eq!(users.len(), 2);
+    assert_eq!(users[0]["friends"]["data"][0]["name"], "bob");
+}
+
+#[tokio::test]
+async fn rejects_queries_exceeding_max_complexity() {
+    let schema = spawn_query_limits_app("subgraph_query_limits_complexity_test.db").await;
+
+    // 1 + (1 + 10 * (1 + (5 + (1 + 10 * 1)))) = 172
+    let request = async_graphql::Request::new(FRIENDS_QUERY)
+        .variables(Variables::from_json(serde_json::json!({ "per_page": 10 })))
+        .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+
+    assert_eq!(response.data, Value::Null);
+    assert_eq!(
+        response.errors[0].message,
+        "Query complexity of 172 exceeds the maximum of 50."
+    );
+}
+
+#[tokio::test]
+async fn rejects_queries_exceeding_max_depth() {
+    let schema = spawn_query_limits_app("subgraph_query_limits_depth_test.db").await;
+
+    let request = async_graphql::Request::new(
+        r#"
+        query {
+            get_users(get_users_input: { query: {}, opts: { per_page: 1 } }) {
+                data {
+                    friends(friends: { query: {}, opts: { per_page: 1 } }) {
+                        data {
+                            friends(friends: { query: {}, opts: { per_page: 1 } }) {
+                                data {
+                                    name
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+
+    assert_eq!(response.data, Value::Null);
+    assert_eq!(response.errors[0].message, "Query is nested too deep.");
+}
@@ -0,0 +1,263 @@
+use async_graphql::dynamic::Schema;
+use bson::doc;
+use http::HeaderMap;
+use subgraph::{
+    cli_args::CliArgs,
+    configuration::subgraph::{
+        data_sources::{
+            sql::{DialectEnum, SqlDataSourceConfig},
+            ServiceDataSourceConfig,
+        },
+        SubGraphConfig,
+    },
+    data_sources::{
+        sql::{
+            query_plan_cache::{QueryPlan, QueryPlanCache},
+            PoolEnum, SqlDataSource,
+        },
+        DataSource,
+    },
+    resolver_type::ResolverType,
+};
+
+fn cli_args() -> CliArgs {
+    CliArgs {
+        config: None,
+        port: Some(0),
+        log_level: None,
+        generate_keypair: false,
+        migrate: None,
+        steps: None,
+        migration_name: None,
+        migrate_only: false,
+        watch: false,
+        host: false,
+    }
+}
+
+fn query_plan_config(uri: &str) -> SubGraphConfig {
+    let config = format!(
+        r#"
+        [service]
+        name = "query_plans"
+
+        [[service.data_sources]]
+        [service.data_sources.SQL]
+        name = "plan_data_source"
+        uri = "{uri}"
+        dialect = "SQLITE"
+
+        [[service.entities]]
+        name = "note"
+        data_source = {{ from = "plan_data_source", table = "notes" }}
+        fields = [
+          {{ name = "id", scalar = "Int", required = true, exclude_from_input = ["CreateOne", "UpdateMany"] }},
+          {{ name = "title", scalar = "String", required = true }},
+          {{ name = "priority", scalar = "Int", required = true }},
+        ]
+        "#
+    );
+    toml::from_str::<SubGraphConfig>(&config).unwrap()
+}
+
+/// Creates a database with a `notes` table holding three notes.
+async fn create_notes_database(db_name: &str) -> String {
+    let path = std::env::temp_dir().join(db_name);
+    let _ = std::fs::remove_file(&path);
+    let uri = format!("sqlite:{}?mode=rwc", path.display());
+
+    let pool = SqlDataSource::create_pool(&SqlDataSourceConfig {
+        name: "plan_data_source".to_string(),
+        uri: uri.clone(),
+        dialect: DialectEnum::SQLITE,
+        sqlite_extensions: None,
+        migrations_path: None,
+        pool: None,
+        replicas: None,
+        queries: None,
+        schema: None,
+    })
+    .await
+    .unwrap();
+    if let PoolEnum::SqLite(pool) = pool {
+        for statement in [
+            "CREATE TABLE notes (id INTEGER PRIMARY KEY AUTOINCREMENT, title TEXT NOT NULL, priority INTEGER NOT NULL);",
+            "INSERT INTO notes (title, priority) VALUES ('First', 1), ('Second', 2), ('Third', 3);",
+        ] {
+            sqlx::query(statement).execute(&pool).await.unwrap();
+        }
+    }
+    uri
+}
+
+async fn spawn_query_plan_app(db_name: &str) -> Schema {
+    let uri = create_notes_database(db_name).await;
+    let (server, schema, _shutdown) = subgraph::run(cli_args(), query_plan_config(&uri))
+        .await
+        .expect("Failed to run server.");
+    let _ = tokio::spawn(server);
+    schema
+}
+
+async fn get_note_titles(schema: &Schema, query: &str) -> Vec<String> {
+    let request = async_graphql::Request::new(format!(
+        r#"
+        {{
+            get_notes(get_notes_input: {{ query: {} }}) {{
+                data {{
+                    title
+                }}
+            }}
+        }}
+        "#,
+        query
+    ));
+    let response = schema.execute(request.data(HeaderMap::new())).await;
+    assert!(response.is_ok(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    data["get_notes"]["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|note| note["title"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn same_shape_queries_bind_their_own_values() {
+    let schema = spawn_query_plan_app("subgraph_query_plan_test.db").await;
+
+    assert_eq!(
+        get_note_titles(&schema, r#"{ title: "First" }"#).await,
+        vec!["First"]
+    );
+    assert_eq!(
+        get_note_titles(&schema, r#"{ title: "Second" }"#).await,
+        vec!["Second"]
+    );
+    assert_eq!(
+        get_note_titles(&schema, r#"{ OR: [{ title: "First" }, { priority: 3 }] }"#).await,
+        vec!["First", "Third"]
+    );
+    assert_eq!(
+        get_note_titles(&schema, r#"{ OR: [{ title: "Second" }, { priority: 1 }] }"#).await,
+        vec!["First", "Second"]
+    );
+    assert_eq!(
+        get_note_titles(&schema, r#"{ GT: { priority: 1 } }"#).await,
+        vec!["Second", "Third"]
+    );
+    assert_eq!(
+        get_note_titles(&schema, r#"{ GT: { priority: 2 } }"#).await,
+        vec!["Third"]
+    );
+
+    let request = async_graphql::Request::new(
+        r#"
+        mutation {
+            update_notes(update_notes_input: { values: { priority: 5 }, query: { title: "Third" } }) {
+                data {
+                    title
+                    priority
+                }
+            }
+        }
+        "#,
+    );
+    let response = schema.execute(request.data(HeaderMap::new())).await;
+    assert!(response.is_ok(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["update_notes"]["data"][0]["priority"], 5);
+}
+
+#[tokio::test]
+async fn query_plans_are_cached_per_shape() {
+    let uri = create_notes_database("subgraph_query_plan_cache_test.db").await;
+    let subgraph_config = query_plan_config(&uri);
+    let sql_config = match &subgraph_config.service.data_sources[0] {
+        ServiceDataSourceConfig::SQL(config) => config.clone(),
+        _ => panic!("Expected SQL data source"),
+    };
+    let data_source = match SqlDataSource::init(&sql_config, &cli_args(), subgraph_config.clone())
+        .await
+        .unwrap()
+    {
+        DataSource::SQL(data_source) => data_source,
+        _ => panic!("Expected SQL data source"),
+    };
+    let entity = &subgraph_config.service.entities[0];
+
+    let mut queries = vec![];
+    for input in [
+        doc! { "query": { "title": "First" } },
+        doc! { "query": { "title": "Second" } },
+        doc! { "query": { "priority": 2 } },
+        doc! { "query": { "priority": 2 }, "opts": { "page": 2, "per_page": 1 } },
+        doc! { "query": { "priority": 2 }, "opts": { "page": 3, "per_page": 1 } },
+    ] {
+        let sql_query = data_source
+            .create_cached_query(
+                input,
+                ResolverType::FindMany,
+                "notes",
+                entity,
+                &subgraph_config,
+            )
+            .unwrap();
+        queries.push(format!("{:?}", sql_query));
+    }
+    // Pages share the plan of their filter, with their own pagination.
+    assert_eq!(data_source.query_plan_cache.len(), 3);
+    assert!(queries[3].contains("LIMIT 1 OFFSET 1;"));
+    assert!(queries[4].contains("LIMIT 1 OFFSET 2;"));
+}
+
+#[tokio::test]
+async fn paginated_queries_reuse_the_plan_of_their_shape() {
+    let schema = spawn_query_plan_app("subgraph_query_plan_page_test.db").await;
+
+    let mut titles = vec![];
+    for page in 1..=3 {
+        let request = async_graphql::Request::new(format!(
+            r#"
+            {{
+                get_notes(get_notes_input: {{ query: {{}}, opts: {{ page: {}, per_page: 1, sort: [{{ field: "priority", direction: "ASC" }}] }} }}) {{
+                    data {{
+                        title
+                    }}
+                }}
+            }}
+            "#,
+            page
+        ));
+        let response = schema.execute(request.data(HeaderMap::new())).await;
+        assert!(response.is_ok(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        titles.push(data["get_notes"]["data"][0]["title"].clone());
+    }
+    assert_eq!(titles, vec!["First", "Second", "Third"]);
+}
+
+#[test]
+fn least_recently_used_plans_are_evicted() {
+    let cache = QueryPlanCache::new(2);
+    let plan = |query: &str| QueryPlan {
+        query: query.to_string(),
+        count_query: None,
+        identifier_query: None,
+        schema: None,
+    };
+
+    cache.insert("first".to_string(), plan("SELECT 1"));
+    std::thread::sleep(std::time::Duration::from_millis(2));
+    cache.insert("second".to_string(), plan("SELECT 2"));
+    std::thread::sleep(std::time::Duration::from_millis(2));
+    assert!(cache.get("first").is_some());
+    std::thread::sleep(std::time::Duration::from_millis(2));
+    cache.insert("third".to_string(), plan("SELECT 3"));
+
+    assert_eq!(cache.len(), 2);
+    assert!(cache.get("first").is_some());
+    assert!(cache.get("second").is_none());
+    assert!(cache.get("third").is_some());
+}
@@ -0,0 +1,306 @@
+use async_graphql::dynamic::Schema;
+use http::HeaderMap;
+use subgraph::{
+    cli_args::CliArgs,
+    configuration::subgraph::{
+        data_sources::sql::{DialectEnum, SqlDataSourceConfig},
+        SubGraphConfig,
+    },
+    data_sources::{
+        sql::{PoolEnum, SqlDataSource},
+        DataSource,
+    },
+    resolver_type::ResolverType,
+};
+
+fn read_replica_args() -> CliArgs {
+    CliArgs {
+        config: None,
+        port: Some(0),
+        log_level: None,
+        generate_keypair: false,
+        migrate: None,
+        steps: None,
+        migration_name: None,
+        migrate_only: false,
+        watch: false,
+        host: false,
+    }
+}
+
+/// Creates a `books` database holding a single book with the provided title.
+async fn create_database(db_name: &str, title: &str) -> String {
+    let path = std::env::temp_dir().join(db_name);
+    let _ = std::fs::remove_file(&path);
+    let uri = format!("sqlite:{}?mode=rwc", path.display());
+
+    let pool = SqlDataSource::create_pool(&SqlDataSourceConfig {
+        name: db_name.to_string(),
+        uri: uri.clone(),
+        dialect: DialectEnum::SQLITE,
+        sqlite_extensions: None,
+        migrations_path: None,
+        pool: None,
+        replicas: None,
+        queries: None,
+        schema: None,
+    })
+    .await
+    .unwrap();
+    if let PoolEnum::SqLite(pool) = pool {
+        sqlx::query(
+            "CREATE TABLE books (id INTEGER PRIMARY KEY AUTOINCREMENT, title TEXT NOT NULL);",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO books (title) VALUES (?);")
+            .bind(title)
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+    uri
+}
+
+/// Each database holds a different title, revealing which one served a query.
+async fn spawn_read_replica_app(prefix: &str) -> Schema {
+    let primary = create_database(&format!("{}_primary.db", prefix), "primary").await;
+    let replica_a = create_database(&format!("{}_replica_a.db", prefix), "replica_a").await;
+    let replica_b = create_database(&format!("{}_replica_b.db", prefix), "replica_b").await;
+    spawn_replicas_app(&primary, &replica_a, &replica_b).await
+}
+
+async fn spawn_replicas_app(primary: &str, replica_a: &str, replica_b: &str) -> Schema {
+    let config = format!(
+        r#"
+        [service]
+        name = "read_replicas"
+        data_source_health = true
+
+        [[service.data_sources]]
+        [service.data_sources.SQL]
+        name = "books_data_source"
+        uri = "{primary}"
+        dialect = "SQLITE"
+        replicas = ["{replica_a}", "{replica_b}"]
+        pool = {{ lazy_connect = true, acquire_timeout = 500, replica_probe_interval = 50 }}
+
+        [[service.entities]]
+        name = "book"
+        data_source = {{ from = "books_data_source", table = "books" }}
+        fields = [
+          {{ name = "id", scalar = "Int", required = true, exclude_from_input = ["CreateOne"] }},
+          {{ name = "title", scalar = "String", required = true }},
+        ]
+        "#
+    );
+    let subgraph_config = toml::from_str::<SubGraphConfig>(&config).unwrap();
+    let (server, schema, _shutdown) = subgraph::run(read_replica_args(), subgraph_config)
+        .await
+        .expect("Failed to run server.");
+    let _ = tokio::spawn(server);
+    schema
+}
+
+async fn get_titles(schema: &Schema) -> Vec<String> {
+    let request = async_graphql::Request::new(
+        r#"
+        query {
+            get_books(get_books_input: { query: {} }) {
+                data {
+                    title
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let json = response.data.into_json().unwrap();
+    json["get_books"]["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|book| book["title"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn reads_rotate_through_replicas() {
+    let schema = spawn_read_replica_app("subgraph_replica_reads").await;
+
+    let first = get_titles(&schema).await;
+    let second = get_titles(&schema).await;
+    let third = get_titles(&schema).await;
+
+    assert_eq!(first, vec!["replica_a"]);
+    assert_eq!(second, vec!["replica_b"]);
+    assert_eq!(third, vec!["replica_a"]);
+}
+
+#[tokio::test]
+async fn writes_and_refetches_use_primary() {
+    let schema = spawn_read_replica_app("subgraph_replica_writes").await;
+
+    let request = async_graphql::Request::new(
+        r#"
+        mutation {
+            create_book(create_book_input: { values: { title: "written" } }) {
+                data {
+                    id
+                    title
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let json = response.data.into_json().unwrap();
+    assert_eq!(json["create_book"]["data"]["id"], 2);
+    assert_eq!(json["create_book"]["data"]["title"], "written");
+
+    let request = async_graphql::Request::new(
+        r#"
+        mutation {
+            update_books(update_books_input: { values: { title: "updated" }, query: { id: 1 } }) {
+                data {
+                    title
+                }
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let json = response.data.into_json().unwrap();
+    assert_eq!(json["update_books"]["data"][0]["title"], "updated");
+
+    // The replicas are not written to.
+    assert_eq!(get_titles(&schema).await, vec!["replica_a"]);
+}
+
+#[tokio::test]
+async fn unhealthy_replicas_are_skipped() {
+    let primary = create_database("subgraph_replica_health_primary.db", "primary").await;
+    let replica_a = create_database("subgraph_replica_health_replica_a.db", "replica_a").await;
+    let schema =
+        spawn_replicas_app(&primary, &replica_a, "sqlite:/subgraph/does/not/exist.db").await;
+
+    let request = async_graphql::Request::new(
+        r#"
+        query {
+            data_source_health {
+                name
+                healthy
+            }
+        }
+        "#,
+    )
+    .data(HeaderMap::new());
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let json = response.data.into_json().unwrap();
+    assert_eq!(
+        json["data_source_health"],
+        serde_json::json!([
+            { "name": "books_data_source", "healthy": true },
+            { "name": "books_data_source_replica_0", "healthy": true },
+            { "name": "books_data_source_replica_1", "healthy": false },
+        ])
+    );
+
+    assert_eq!(get_titles(&schema).await, vec!["replica_a"]);
+    assert_eq!(get_titles(&schema).await, vec!["replica_a"]);
+}
+
+#[tokio::test]
+async fn recovered_replicas_are_read_again() {
+    let primary = create_database("subgraph_replica_recover_primary.db", "primary").await;
+    let replica_a = create_database("subgraph_replica_recover_replica_a.db", "replica_a").await;
+    let path = std::env::temp_dir().join("subgraph_replica_recover_replica_b.db");
+    let _ = std::fs::remove_file(&path);
+    let schema =
+        spawn_replicas_app(&primary, &replica_a, &format!("sqlite:{}", path.display())).await;
+
+    assert_eq!(get_titles(&schema).await, vec!["replica_a"]);
+    assert_eq!(get_titles(&schema).await, vec!["replica_a"]);
+
+    // Once the replica can be reached the probe returns it to the rotation.
+    create_database("subgraph_replica_recover_replica_b.db", "replica_b").await;
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+
+    let mut titles = get_titles(&schema).await;
+    titles.extend(get_titles(&schema).await);
+    titles.sort();
+    assert_eq!(titles, vec!["replica_a", "replica_b"]);
+}
+
+#[tokio::test]
+async fn failing_replicas_are_marked_unhealthy() {
+    let primary = create_database("subgraph_replica_failing_primary.db", "primary").await;
+    let replica_a = create_database("subgraph_replica_failing_replica_a.db", "replica_a").await;
+    let replica_b = create_database("subgraph_replica_failing_replica_b.db", "replica_b").await;
+    let config = format!(
+        r#"
+        [service]
+        name = "failing_replicas"
+
+        [[service.data_sources]]
+        [service.data_sources.SQL]
+        name = "books_data_source"
+        uri = "{primary}"
+        dialect = "SQLITE"
+        replicas = ["{replica_a}", "{replica_b}"]
+        pool = {{ replica_probe_interval = 60000 }}
+
+        [[service.entities]]
+        name = "book"
+        data_source = {{ from = "books_data_source", table = "books" }}
+        fields = [
+          {{ name = "id", scalar = "Int", required = true }},
+          {{ name = "title", scalar = "String", required = true }},
+        ]
+        "#
+    );
+    let subgraph_config = toml::from_str::<SubGraphConfig>(&config).unwrap();
+    let sql_config = match &subgraph_config.service.data_sources[0] {
+        subgraph::configuration::subgraph::data_sources::ServiceDataSourceConfig::SQL(config) => {
+            config.clone()
+        }
+        _ => panic!("Expected SQL data source"),
+    };
+    let data_source =
+        SqlDataSource::init(&sql_config, &read_replica_args(), subgraph_config.clone())
+            .await
+            .unwrap();
+    let data_source = match data_source {
+        DataSource::SQL(data_source) => data_source,
+        _ => panic!("Expected SQL data source"),
+    };
+    let entity = subgraph_config.service.entities[0].clone();
+    let query = data_source
+        .create_cached_query(
+            bson::doc! { "query": {} },
+            ResolverType::FindMany,
+            "books",
+            &entity,
+            &subgraph_config,
+        )
+        .unwrap();
+
+    // The first read goes to replica_a, which has lost its connections.
+    if let PoolEnum::SqLite(pool) = &data_source.replica_pools[0] {
+        pool.close().await;
+    }
+    assert!(data_source.read_many(&query, &true).await.is_err());
+
+    for _ in 0..3 {
+        let (replica, _pool) = data_source.get_read_pool();
+        assert_eq!(replica, Some(1));
+    }
+}
@@ -0,0 +1,178 @@
+use async_graphql::dynamic::Schema;
+use http::HeaderMap;
+use subgraph::{
+    cli_args::CliArgs,
+    configuration::subgraph::{
+        data_sources::sql::{DialectEnum, SqlDataSourceConfig},
+        SubGraphConfig,
+    },
+    data_sources::sql::{PoolEnum, SqlDataSource},
+};
+
+fn table_schema_config(uri: &str) -> SubGraphConfig {
+    let config = format!(
+        r#"
+        [service]
+        name = "table_schema"
+
+        [[service.data_sources]]
+        [service.data_sources.SQL]
+        name = "schema_data_source"
+        uri = "{uri}"
+        dialect = "SQLITE"
+        schema = "main"
+
+        [[service.entities]]
+        name = "author"
+        data_source = {{ from = "schema_data_source", table = "authors" }}
+        fields = [
+          {{ name = "id", scalar = "Int", required = true, exclude_from_input = ["CreateOne"] }},
+          {{ name = "name", scalar = "String", required = true }},
+          {{ name = "books", scalar = "Int", list = true, as_type = "book", join_on = "author_id", join_from = "id" }},
+        ]
+
+        [[service.entities]]
+        name = "book"
+        data_source = {{ from = "schema_data_source", table = "books", schema = "main" }}
+        fields = [
+          {{ name = "id", scalar = "Int", required = true, exclude_from_input = ["CreateOne"] }},
+          {{ name = "title", scalar = "String", required = true }},
+          {{ name = "author_id", scalar = "Int", required = true, as_type = "author", join_on = "id" }},
+        ]
+        "#
+    );
+    toml::from_str::<SubGraphConfig>(&config).unwrap()
+}
+
+/// Creates a database with `authors` and `books` tables, queried through the `main` schema.
+async fn spawn_table_schema_app(db_name: &str) -> Schema {
+    let path = std::env::temp_dir().join(db_name);
+    let _ = std::fs::remove_file(&path);
+    let uri = format!("sqlite:{}?mode=rwc", path.display());
+
+    let pool = SqlDataSource::create_pool(&SqlDataSourceConfig {
+        name: "schema_data_source".to_string(),
+        uri: uri.clone(),
+        dialect: DialectEnum::SQLITE,
+        sqlite_extensions: None,
+        migrations_path: None,
+        pool: None,
+        replicas: None,
+        queries: None,
+        schema: None,
+    })
+    .await
+    .unwrap();
+    if let PoolEnum::SqLite(pool) = pool {
+        for statement in [
+            "CREATE TABLE authors (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL);",
+            "CREATE TABLE books (id INTEGER PRIMARY KEY AUTOINCREMENT, title TEXT NOT NULL, author_id INTEGER NOT NULL);",
+            "INSERT INTO authors (name) VALUES ('Ursula');",
+        ] {
+            sqlx::query(statement).execute(&pool).await.unwrap();
+        }
+    }
+
+    let args = CliArgs {
+        config: None,
+        port: Some(0),
+        log_level: None,
+        generate_keypair: false,
+        migrate: None,
+        steps: None,
+        migration_name: None,
+        migrate_only: false,
+        watch: false,
+        host: false,
+    };
+    let (server, schema, _shutdown) = subgraph::run(args, table_schema_config(&uri))
+        .await
+        .expect("Failed to run server.");
+    let _ = tokio::spawn(server);
+    schema
+}
+
+#[tokio::test]
+async fn create_and_find_with_table_schema() {
+    let schema = spawn_table_schema_app("subgraph_table_schema_test.db").await;
+
+    let request = async_graphql::Request::new(
+        r#"
+        mutation {
+            create_book(create_book_input: { values: { title: "Earthsea", author_id: 1 } }) {
+                data {
+                    id
+                    title
+                }
+            }
+        }
+        "#,
+    );
+    let response = schema.execute(request.data(HeaderMap::new())).await;
+    assert!(response.is_ok(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["create_book"]["data"]["title"], "Earthsea");
+
+    let request = async_graphql::Request::new(
+        r#"
+        {
+            get_authors(get_authors_input: { query: { name: "Ursula" } }) {
+                data {
+                    name
+                    books(books: { query: {} }) {
+                        data {
+                            title
+                        }
+                    }
+                }
+                meta {
+                    total_count
+                }
+            }
+        }
+        "#,
+    );
+    let response = schema.execute(request.data(HeaderMap::new())).await;
+    assert!(response.is_ok(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["get_authors"]["meta"]["total_count"], 1);
+    assert_eq!(
+        data["get_authors"]["data"][0]["books"]["data"][0]["title"],
+        "Earthsea"
+    );
+}
+
+#[tokio::test]
+async fn generate_migration_with_table_schema() {
+    let migrations_dir = std::env::temp_dir().join("subgraph_table_schema_migrations");
+    let _ = std::fs::remove_dir_all(&migrations_dir);
+
+    let config = SqlDataSourceConfig {
+        name: "schema_data_source".to_string(),
+        uri: "sqlite::memory:".to_string(),
+        dialect: DialectEnum::SQLITE,
+        sqlite_extensions: None,
+        migrations_path: Some(migrations_dir.display().to_string()),
+        pool: None,
+        replicas: None,
+        queries: None,
+        schema: Some("main".to_string()),
+    };
+    let pool = SqlDataSource::create_pool(&config).await.unwrap();
+    let version = SqlDataSource::generate_migration(
+        &pool,
+        &config,
+        &table_schema_config("sqlite::memory:"),
+        None,
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    let up = std::fs::read_to_string(
+        migrations_dir.join(format!("{}_subgraph_generated.up.sql", version)),
+    )
+    .unwrap();
+    assert!(up.contains("CREATE TABLE `main`.`authors`"));
+    assert!(up.contains("CREATE TABLE `main`.`books`"));
+    assert!(up.contains("FOREIGN KEY (`author_id`) REFERENCES `authors` (`id`)"));
+}
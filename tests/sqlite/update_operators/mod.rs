@@ -0,0 +1,201 @@
+use async_graphql::dynamic::Schema;
+use http::HeaderMap;
+use subgraph::{
+    cli_args::CliArgs,
+    configuration::subgraph::{
+        data_sources::sql::{DialectEnum, SqlDataSourceConfig},
+        SubGraphConfig,
+    },
+    data_sources::sql::{PoolEnum, SqlDataSource},
+};
+
+/// Creates a database with a `counters` table, with counted `views` and `likes` columns and a
+/// `note`. The likes of each counter are null.
+async fn spawn_update_operators_app(db_name: &str) -> Schema {
+    let path = std::env::temp_dir().join(db_name);
+    let _ = std::fs::remove_file(&path);
+    let uri = format!("sqlite:{}?mode=rwc", path.display());
+
+    let pool = SqlDataSource::create_pool(&SqlDataSourceConfig {
+        name: "operators_data_source".to_string(),
+        uri: uri.clone(),
+        dialect: DialectEnum::SQLITE,
+        sqlite_extensions: None,
+        migrations_path: None,
+        pool: None,
+        replicas: None,
+        queries: None,
+        schema: None,
+    })
+    .await
+    .unwrap();
+    if let PoolEnum::SqLite(pool) = pool {
+        for statement in [
+            "CREATE TABLE counters (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, views INTEGER NOT NULL, likes INTEGER, note TEXT);",
+            "INSERT INTO counters (name, views, note) VALUES ('home', 1, 'draft'), ('about', 5, 'draft');",
+        ] {
+            sqlx::query(statement).execute(&pool).await.unwrap();
+        }
+    }
+
+    let config = format!(
+        r#"
+        [service]
+        name = "update_operators"
+
+        [[service.data_sources]]
+        [service.data_sources.SQL]
+        name = "operators_data_source"
+        uri = "{uri}"
+        dialect = "SQLITE"
+
+        [[service.entities]]
+        name = "counter"
+        data_source = {{ from = "operators_data_source", table = "counters", resolvers = {{ update_many = {{ guards = [{{ name = "viral_views", if_expr = "contains(input(\"values\", \"views\"), \"100\")", then_msg = "Views can not be set to 100" }}] }} }} }}
+        fields = [
+          {{ name = "id", scalar = "Int", required = true, exclude_from_input = ["CreateOne", "UpdateMany"] }},
+          {{ name = "name", scalar = "String", required = true }},
+          {{ name = "views", scalar = "Int", required = true }},
+          {{ name = "likes", scalar = "Int" }},
+          {{ name = "note", scalar = "String" }},
+        ]
+        "#
+    );
+    let subgraph_config = toml::from_str::<SubGraphConfig>(&config).unwrap();
+
+    let args = CliArgs {
+        config: None,
+        port: Some(0),
+        log_level: None,
+        generate_keypair: false,
+        migrate: None,
+        steps: None,
+        migration_name: None,
+        migrate_only: false,
+        watch: false,
+        host: false,
+    };
+    let (server, schema, _shutdown) = subgraph::run(args, subgraph_config)
+        .await
+        .expect("Failed to run server.");
+    let _ = tokio::spawn(server);
+    schema
+}
+
+#[tokio::test]
+async fn increment_and_unset_columns() {
+    let schema = spawn_update_operators_app("subgraph_update_operators_test.db").await;
+
+    let request = async_graphql::Request::new(
+        r#"
+        mutation {
+            update_counters(update_counters_input: { values: {}, operators: { inc: { views: 2 }, unset: { note: true } }, query: { name: "home" } }) {
+                data {
+                    views
+                    note
+                }
+            }
+        }
+        "#,
+    );
+    let response = schema.execute(request.data(HeaderMap::new())).await;
+    assert!(response.is_ok(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["update_counters"]["data"][0]["views"], 3);
+    assert_eq!(
+        data["update_counters"]["data"][0]["note"],
+        serde_json::Value::Null
+    );
+
+    // Values are set in the same statement as the increments, which reuses its cached plan.
+    for views in [15, 25] {
+        let request = async_graphql::Request::new(
+            r#"
+            mutation {
+                update_counters(update_counters_input: { values: { note: "published" }, operators: { inc: { views: 10 } }, query: { name: "about" } }) {
+                    data {
+                        views
+                        note
+                    }
+                }
+            }
+            "#,
+        );
+        let response = schema.execute(request.data(HeaderMap::new())).await;
+        assert!(response.is_ok(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["update_counters"]["data"][0]["views"], views);
+        assert_eq!(data["update_counters"]["data"][0]["note"], "published");
+    }
+}
+
+#[tokio::test]
+async fn update_without_values_or_operators_fails() {
+    let schema = spawn_update_operators_app("subgraph_update_operators_empty_test.db").await;
+
+    let request = async_graphql::Request::new(
+        r#"
+        mutation {
+            update_counters(update_counters_input: { values: {}, query: { name: "home" } }) {
+                data {
+                    views
+                }
+            }
+        }
+        "#,
+    );
+    let response = schema.execute(request.data(HeaderMap::new())).await;
+    assert_eq!(
+        response.errors[0].message,
+        "No values or operators provided to update."
+    );
+}
+
+#[tokio::test]
+async fn increment_null_columns_from_zero() {
+    let schema = spawn_update_operators_app("subgraph_update_operators_null_test.db").await;
+
+    let request = async_graphql::Request::new(
+        r#"
+        mutation {
+            update_counters(update_counters_input: { values: {}, operators: { inc: { likes: 2 } }, query: { name: "home" } }) {
+                data {
+                    likes
+                }
+            }
+        }
+        "#,
+    );
+    let response = schema.execute(request.data(HeaderMap::new())).await;
+    assert!(response.is_ok(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["update_counters"]["data"][0]["likes"], 2);
+}
+
+#[tokio::test]
+async fn guards_on_values_apply_to_operators() {
+    let schema = spawn_update_operators_app("subgraph_update_operators_guard_test.db").await;
+
+    for update in [
+        r#"values: { views: 100 }"#,
+        r#"values: {}, operators: { inc: { views: 100 } }"#,
+    ] {
+        let request = async_graphql::Request::new(format!(
+            r#"
+            mutation {{
+                update_counters(update_counters_input: {{ {update}, query: {{ name: "home" }} }}) {{
+                    data {{
+                        views
+                    }}
+                }}
+            }}
+            "#
+        ));
+        let response = schema.execute(request.data(HeaderMap::new())).await;
+        assert_eq!(
+            response.errors[0].message, "Views can not be set to 100",
+            "{}",
+            update
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use async_graphql::dynamic::Schema;
+use http::HeaderMap;
+use subgraph::{
+    cli_args::CliArgs,
+    configuration::subgraph::{
+        data_sources::sql::{DialectEnum, SqlDataSourceConfig},
+        SubGraphConfig,
+    },
+    data_sources::sql::{PoolEnum, SqlDataSource},
+};
+
+/// Creates a database with a `tickets` table keyed by a client provided UUID.
+async fn spawn_uuid_key_app(db_name: &str) -> Schema {
+    let path = std::env::temp_dir().join(db_name);
+    let _ = std::fs::remove_file(&path);
+    let uri = format!("sqlite:{}?mode=rwc", path.display());
+
+    let pool = SqlDataSource::create_pool(&SqlDataSourceConfig {
+        name: "uuid_data_source".to_string(),
+        uri: uri.clone(),
+        dialect: DialectEnum::SQLITE,
+        sqlite_extensions: None,
+        migrations_path: None,
+        pool: None,
+        replicas: None,
+        queries: None,
+        schema: None,
+    })
+    .await
+    .unwrap();
+    if let PoolEnum::SqLite(pool) = pool {
+        sqlx::query(
+            "CREATE TABLE tickets (ticket_id TEXT PRIMARY KEY NOT NULL, title TEXT NOT NULL);",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let config = format!(
+        r#"
+        [service]
+        name = "uuid_keys"
+
+        [[service.data_sources]]
+        [service.data_sources.SQL]
+        name = "uuid_data_source"
+        uri = "{uri}"
+        dialect = "SQLITE"
+
+        [[service.entities]]
+        name = "ticket"
+        data_source = {{ from = "uuid_data_source", table = "tickets" }}
+        fields = [
+          {{ name = "ticket_id", scalar = "UUID", required = true, primary_key = true }},
+          {{ name = "title", scalar = "String", required = true }},
+        ]
+        "#
+    );
+    let subgraph_config = toml::from_str::<SubGraphConfig>(&config).unwrap();
+
+    let args = CliArgs {
+        config: None,
+        port: Some(0),
+        log_level: None,
+        generate_keypair: false,
+        migrate: None,
+        steps: None,
+        migration_name: None,
+        migrate_only: false,
+        watch: false,
+        host: false,
+    };
+    let (server, schema, _shutdown) = subgraph::run(args, subgraph_config)
+        .await
+        .expect("Failed to run server.");
+    let _ = tokio::spawn(server);
+    schema
+}
+
+#[tokio::test]
+async fn create_one_with_uuid_primary_key() {
+    let schema = spawn_uuid_key_app("subgraph_uuid_key_test.db").await;
+
+    for title in ["First", "Second"] {
+        let ticket_id = uuid::Uuid::new_v4().to_string();
+        let request = async_graphql::Request::new(format!(
+            r#"
+            mutation {{
+                create_ticket(create_ticket_input: {{ values: {{ ticket_id: "{}", title: "{}" }} }}) {{
+                    data {{
+                        ticket_id
+                        title
+                    }}
+                }}
+            }}
+            "#,
+            ticket_id, title
+        ));
+        let response = schema.execute(request.data(HeaderMap::new())).await;
+        assert!(response.is_ok(), "{:?}", response.errors);
+
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["create_ticket"]["data"]["ticket_id"], ticket_id);
+        assert_eq!(data["create_ticket"]["data"]["title"], title);
+    }
+}
@@ -34,6 +34,9 @@ async fn execute(
         log_level: None,
         generate_keypair: false,
         migrate: None,
+        steps: None,
+        migration_name: None,
+        migrate_only: false,
         watch: false,
         host: false,
     });